@@ -0,0 +1,35 @@
+''' Time rebuilding the CPU after a change to the writeback stage only, with and without
+incremental elaboration.
+'''
+import time
+
+from assassyn.frontend import *
+
+import writeback
+from main import build_cpu
+
+
+@module.combinational
+def traced_build(self, reg_file: Array):
+    '''The writeback stage, with one more log line.'''
+    rd, mdata = self.pop_all_ports(False)
+    log("writeback traced | x{:02}", rd)
+    with Condition((rd != Bits(5)(0))):
+        reg_file[rd] = mdata
+    return rd
+
+
+def timed_build(**kwargs):
+    start = time.time()
+    build_cpu(depth_log=16, enable_cache=False, verbose=False, **kwargs)
+    return time.time() - start
+
+
+if __name__ == '__main__':
+    full = timed_build()
+    # Leave the fingerprints of the unchanged CPU behind
+    timed_build(incremental=True)
+    writeback.WriteBack.build = traced_build
+    incremental = timed_build(incremental=True)
+    print(f'Full rebuild: {full:.1f}s, incremental rebuild: {incremental:.1f}s, '
+          f'speedup: {full / incremental:.2f}x')
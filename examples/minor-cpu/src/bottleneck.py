@@ -0,0 +1,28 @@
+''' Run a workload on the CPU built with `sim_stats`, and check that the pipeline analysis
+reports the execution stage as the bottleneck: it is the only stage that stalls, waiting in
+its RAW hazard check until the registers it reads are no longer pending in `reg_onwrite`.
+'''
+import sys as py_sys
+
+from assassyn import utils
+from assassyn.analysis.pipeline import SimReport, analyze
+
+from main import build_cpu, init_workspace, prepare_workload
+
+
+if __name__ == '__main__':
+    workload = py_sys.argv[1] if len(py_sys.argv) > 1 else 'vvadd'
+    cpu, binary, _ = build_cpu(depth_log=16, sim_stats=True, verilog=False, verbose=False)
+    init_workspace(f'{utils.repo_path()}/examples/minor-cpu/workloads', workload)
+    prepare_workload(cpu)
+    raw = utils.run_simulator(binary_path=binary)
+    report = analyze(cpu, SimReport.parse(raw))
+    print(f'{workload}: bottleneck {report.bottleneck_stage} at {report.bottleneck_fifo}, '
+          f'{report.cycles} cycles')
+    for key, stage in report.stages.items():
+        if stage.stalled:
+            print(f'{key} stalled {stage.stalled} cycles over {stage.executed} executions')
+    assert report.bottleneck_stage == 'E', report
+    assert [key for key, stage in report.stages.items() if stage.stalled] == ['E'], report
+    # The stalls are the RAW checks of the operands, logged by E when it backs up
+    assert '[E]' in raw and 'backlogged' in raw
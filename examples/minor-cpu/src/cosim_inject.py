@@ -0,0 +1,42 @@
+''' Inject a program into the CPU through a co-simulation handle, and check the registers it
+writes. The instructions are fetched from the `icache` SRAM rather than from a FIFO, so the
+program is written into the contents of the SRAM before the first cycle. The workspace only
+holds a CPU spinning at address 0 until then.
+'''
+import os
+
+from assassyn.cosim import SimHandle
+
+from main import build_cpu, workspace
+
+PROGRAM = [
+    0x00500093, # addi x1, x0, 5
+    0x00700113, # addi x2, x0, 7
+    0x002081b3, # add  x3, x1, x2
+    0x40118233, # sub  x4, x3, x1
+    0x00000063, # beq  x0, x0, 0
+]
+
+EXPECTED = {1: 5, 2: 7, 3: 12, 4: 7}
+
+
+def init_spin_workspace():
+    os.makedirs(workspace, exist_ok=True)
+    open(f'{workspace}/workload.exe', 'w').write('00000063\n')
+    open(f'{workspace}/workload.data', 'w').write('')
+    open(f'{workspace}/workload.init', 'w').write('0\n')
+
+
+if __name__ == '__main__':
+    cpu, binary, _ = build_cpu(depth_log=10, cosim=True, verilog=False, verbose=False)
+    init_spin_workspace()
+    icache = next(m for m in cpu.downstreams if m.name == 'icache')
+    reg_file = next(a for a in cpu.arrays if a.name == 'reg_file')
+    with SimHandle(cpu, binary) as handle:
+        for i, inst in enumerate(PROGRAM):
+            handle.write_array(icache, i, inst)
+        # The CPU never idles, as the driver fetches every cycle
+        handle.run_until_idle(100)
+        regs = {i: handle.read_array(reg_file, i) for i in EXPECTED}
+    print(f'registers after {handle.cycle} cycles: {regs}')
+    assert regs == EXPECTED, regs
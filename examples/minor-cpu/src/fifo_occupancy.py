@@ -0,0 +1,24 @@
+''' Run a workload on the CPU built with `sim_stats`, and report the FIFOs holding more than
+one entry, i.e. the stages whose input queue actually backs up.
+'''
+import sys as py_sys
+
+from assassyn import utils
+from assassyn.analysis.pipeline import SimReport
+
+from main import build_cpu, init_workspace, prepare_workload
+
+
+if __name__ == '__main__':
+    workload = py_sys.argv[1] if len(py_sys.argv) > 1 else '0to100'
+    cpu, binary, _ = build_cpu(depth_log=16, sim_stats=True, verilog=False, verbose=False)
+    init_workspace(f'{utils.repo_path()}/examples/minor-cpu/workloads', workload)
+    prepare_workload(cpu)
+    report = SimReport.parse(utils.run_simulator(binary_path=binary))
+    deep = {key: report.occupancy_summary(key) for key in report.occupancy}
+    deep = {key: summary for key, summary in deep.items() if summary['max'] > 1}
+    for key, summary in deep.items():
+        print(f'{key} holds up to {summary["max"]} entries, '
+              f'mean {summary["mean"]:.2f}, p99 {summary["p99"]}')
+    if not deep:
+        print(f'No FIFO holds more than one entry in {report.cycles} cycles of {workload}')
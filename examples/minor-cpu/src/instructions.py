@@ -41,7 +41,7 @@ class InstSignal:
         if cond is not None:
             pred, flip = cond
             self.cond = Bits(RV32I_ALU.CNT)(1 << pred)
-            self.flip = Bits(1)(flip)
+            self.flip = Bits(1)(int(flip))
 
 class RInst(InstType):
 
@@ -394,7 +394,7 @@ class Driver(Module):
             d_call = fetcher.async_called()
         return init_cache
 
-def build_cpu(depth_log):
+def build_cpu(depth_log, **kwargs):
     sys = SysBuilder('minor_cpu')
 
     with sys:
@@ -511,6 +511,7 @@ def build_cpu(depth_log):
         resource_base='',
         fifo_depth=1,
     )
+    conf.update(kwargs)
 
     simulator_path, verilog_path = elaborate(sys, **conf)
 
@@ -523,7 +524,7 @@ def build_cpu(depth_log):
     return sys, simulator_binary, verilog_path
 
 
-def run_cpu(sys, simulator_binary, verilog_path, workload='default'):
+def prepare_workload(sys):
     with sys:
         with open(f'{workspace}/workload.config') as f:
             raw = f.readline()
@@ -534,6 +535,10 @@ def run_cpu(sys, simulator_binary, verilog_path, workload='default'):
             value = value[2:]
             open(f'{workspace}/workload.init', 'w').write(value)
 
+
+def run_cpu(sys, simulator_binary, verilog_path, workload='default'):
+    prepare_workload(sys)
+
     report = False
 
     if report:
@@ -4,4 +4,15 @@ from .external_usage import (
     build_external_usage_index,
     expr_externally_used,
 )
-from .topo import topo_downstream_modules, get_upstreams
+from .topo import topo_downstream_modules, get_upstreams, registered_inputs
+from .pipeline import SimReport, PipelineReport, analyze as analyze_pipeline
+from .typecheck import TypeCheckError, validate_types, check_types
+from .infer_downstream import infer_downstream, is_downstream_candidate
+from .reachability import check_reachability, unreachable_modules
+from .merge_calls import check_merged_calls, shared_merged_ports
+from .clock_domain import check_clock_domains, crosses_domain, module_domain
+from .ir_stats import IrStats, ModuleStats, collect_stats
+from .fifo_depth import infer as infer_fifo_depths
+from .mermaid import mermaid_call_graph, mermaid_dataflow
+from .subgraph import callee_subgraph, callees, reachable_from
+from .coverage import cover_key, cover_points, uncovered_blocks
@@ -0,0 +1,110 @@
+"""Check the clock domains of the modules and find the ports crossing them."""
+
+from __future__ import annotations
+
+from fractions import Fraction
+from typing import Dict, Optional
+
+from ..ir.expr import AsyncCall, FIFOPush
+from ..ir.module import Module
+
+
+def module_domain(module) -> Optional[str]:
+    '''The clock domain of `module`, None for the default clock. Downstreams are
+    combinational, so they always belong to the default clock.'''
+    return module.clock_domain if isinstance(module, Module) else None
+
+
+def domain_periods(clock_domains) -> Dict[str, Fraction]:
+    '''The period of each declared clock domain, in cycles of the default clock.
+
+    Args:
+        clock_domains: The `clock_domains` option, mapping a domain name to its period as
+            an int, a `Fraction` or a string like `'3/2'`
+    '''
+    res = {}
+    for name, period in (clock_domains or {}).items():
+        if not isinstance(name, str) or not name.isidentifier():
+            raise ValueError(f'A clock domain is named by an identifier, got {name!r}')
+        try:
+            period = Fraction(period)
+        except (TypeError, ValueError) as e:
+            raise ValueError(f'The period of clock domain {name} is not rational: '
+                             f'{period!r}') from e
+        if period < 1:
+            raise ValueError(f'Clock domain {name} has period {period}, but no domain can be '
+                             'faster than the default clock')
+        res[name] = period
+    return res
+
+
+def source_domains(sys) -> Dict[Module, set]:
+    '''The clock domains of the modules pushing the ports of, or calling, each module.'''
+    res = {}
+    for caller in sys.modules + sys.downstreams:
+        for expr in caller.body or []:
+            if isinstance(expr, FIFOPush):
+                callee = expr.fifo.module
+            elif isinstance(expr, AsyncCall):
+                callee = expr.bind.callee
+            else:
+                continue
+            res.setdefault(callee, set()).add(module_domain(caller))
+    return res
+
+
+def crosses_domain(module, sources: Dict[Module, set]) -> bool:
+    '''Whether the ports and the calls of `module` come from another clock domain.'''
+    return any(i != module_domain(module) for i in sources.get(module, ()))
+
+
+def array_domain(array) -> Optional[str]:
+    '''The clock domain of the modules writing `array`, which clocks its registers.'''
+    domains = {module_domain(i) for i in array.get_write_ports()}
+    return domains.pop() if len(domains) == 1 else None
+
+
+def check_clock_domains(sys, clock_domains) -> Dict[str, Fraction]:
+    '''Check the clock domains of `sys` against the declared ones, and return the periods of
+    the domains its modules use.
+
+    A module is driven by a single domain: its ports and calls come from one domain, and an
+    array is written from one domain. A module called across domains gets an asynchronous
+    FIFO per port and for its credits, which carry one value and one credit per cycle, so
+    its ports can neither be peeked ahead nor flow through, nor its calls be replicated.
+    '''
+    periods = domain_periods(clock_domains)
+    used = {}
+    for module in sys.modules:
+        domain = module_domain(module)
+        if domain is None:
+            continue
+        if domain not in periods:
+            raise ValueError(f'{module.name} is in clock domain {domain}, which is not declared '
+                             'in the clock_domains option')
+        used[domain] = periods[domain]
+
+    sources = source_domains(sys)
+    for module, domains in sources.items():
+        if len(domains) > 1:
+            names = ', '.join(sorted(i or 'default' for i in domains))
+            raise ValueError(f'{module.name} is pushed or called from clock domains {names}, '
+                             'but a module is driven by a single domain')
+        if not crosses_domain(module, sources):
+            continue
+        if module.flow_through or any(port.lookahead_depth() for port in module.ports):
+            raise ValueError(f'{module.name} is called across clock domains, so its ports can '
+                             'neither be peeked ahead nor flow through')
+        calls = [i for i in module.users if isinstance(i, AsyncCall)]
+        if any(i.replication > 1 for i in calls) or (len(calls) > 1 and not module.merge_calls):
+            raise ValueError(f'{module.name} is called across clock domains, which carries '
+                             'one credit per cycle, but it can be called more than once')
+
+    for array in sys.arrays:
+        domains = {module_domain(i) for i in array.get_write_ports()}
+        if len(domains) > 1:
+            names = ', '.join(sorted(i or 'default' for i in domains))
+            raise ValueError(f'{array.name} is written from clock domains {names}, but an '
+                             'array is clocked by a single domain')
+
+    return dict(sorted(used.items()))
@@ -0,0 +1,44 @@
+"""Coverage points of the condition blocks, counting the cycles each block is entered."""
+
+from __future__ import annotations
+
+from typing import List, Tuple
+
+from ..ir.expr.intrinsic import Intrinsic
+from ..utils import namify
+
+# The prefix of the line each backend prints for a block never entered
+UNCOVERED_PREFIX = '[cover] untaken'
+
+
+def cover_points(module) -> List[Tuple[str, Intrinsic]]:
+    '''The (field, intrinsic) of each condition and else block of `module`, in program order,
+    the field being `cover_<index>`.'''
+    res = []
+    for expr in module.body or []:
+        if isinstance(expr, Intrinsic) and \
+                expr.opcode in (Intrinsic.PUSH_CONDITION, Intrinsic.PUSH_ELSE):
+            res.append((f'cover_{len(res)}', expr))
+    return res
+
+
+def cover_key(module, field: str) -> str:
+    '''The key of the block `field` of `module` in the `[stats] cover` lines.'''
+    return f'{namify(module.name)}_{field}'
+
+
+def cover_location(block: Intrinsic) -> str:
+    '''Where the block was opened in the user code, as printed for an untaken block.'''
+    return getattr(block, 'loc', None) or 'unknown'
+
+
+def uncovered_blocks(sys, report) -> List[Tuple[str, Intrinsic]]:
+    '''The (key, intrinsic) of the blocks of `sys` that `report`, a `SimReport` of a run with
+    `cover=True`, counts no entry of, in declaration order.'''
+    res = []
+    for module in sys.modules + sys.downstreams:
+        for field, block in cover_points(module):
+            key = cover_key(module, field)
+            if not report.covered.get(key, 0):
+                res.append((key, block))
+    return res
@@ -0,0 +1,99 @@
+"""Static FIFO depth inference from the call graph of a system."""
+
+from __future__ import annotations
+
+from typing import Dict, List, Optional
+
+from ..ir.expr import FIFOPush
+from ..ir.module import Downstream, Port
+from .topo import get_upstreams
+
+
+def _call_graph(sys):
+    '''Map each module to its `(successor, latency)` edges, and each port to its pushers.
+
+    An async call pushing a port of another module takes a cycle, while a downstream runs in the
+    same cycle as its upstreams.'''
+    modules = sys.modules + sys.downstreams
+    graph = {m: [] for m in modules}
+    pushers = {}
+    for module in modules:
+        for expr in module.body or []:
+            if isinstance(expr, FIFOPush):
+                graph[module].append((expr.fifo.module, 1))
+                pushers.setdefault(expr.fifo, []).append(module)
+        if isinstance(module, Downstream):
+            for upstream in sorted(get_upstreams(module), key=lambda m: m.name):
+                graph.setdefault(upstream, []).append((module, 0))
+    return graph, pushers
+
+
+def _find_cycle(graph) -> Optional[List[object]]:
+    '''Get the modules of a cycle of `graph`, starting and ending with the same one.'''
+    state = {}  # absent: unvisited, True: on the DFS stack, False: done
+    for root in graph:
+        if root in state:
+            continue
+        state[root] = True
+        stack = [(root, iter(graph[root]))]
+        while stack:
+            node, edges = stack[-1]
+            succ = next(edges, None)
+            if succ is None:
+                state[node] = False
+                stack.pop()
+                continue
+            succ = succ[0]
+            if state.get(succ) is True:
+                path = [n for n, _ in stack]
+                return path[path.index(succ):] + [succ]
+            if succ not in state:
+                state[succ] = True
+                stack.append((succ, iter(graph.get(succ, []))))
+    return None
+
+
+def _levels(graph) -> Dict[object, int]:
+    '''The cycle of each module along the longest call path from a module nobody calls.'''
+    in_degree = {m: 0 for m in graph}
+    for edges in graph.values():
+        for succ, _ in edges:
+            in_degree[succ] = in_degree.get(succ, 0) + 1
+    res = {m: 0 for m in in_degree}
+    worklist = [m for m, degree in in_degree.items() if degree == 0]
+    while worklist:
+        module = worklist.pop()
+        for succ, latency in graph.get(module, []):
+            res[succ] = max(res[succ], res[module] + latency)
+            in_degree[succ] -= 1
+            if in_degree[succ] == 0:
+                worklist.append(succ)
+    return res
+
+
+def infer(sys) -> Dict[Port, int]:
+    '''Suggest the number of entries of each port FIFO of a feed-forward system.
+
+    A module fires once the value of its longest incoming call path arrives, so a value pushed
+    over a shorter path waits in its FIFO one cycle per stage of latency difference, and the
+    FIFO needs one entry per waiting cycle, with at least one entry. Ports peeking ahead need
+    room for the peeked entries. Ports nobody pushes get no suggestion.
+
+    When the call graph has a cycle, the latencies are unbounded: a warning lists the cycle,
+    and no depth is suggested, so that every FIFO keeps the default depth.'''
+    graph, pushers = _call_graph(sys)
+    cycle = _find_cycle(graph)
+    if cycle is not None:
+        path = ' -> '.join(m.name for m in cycle)
+        print(f'[WARN] Cannot infer FIFO depths, the call graph has a cycle: {path}. '
+              'The default depth is used.')
+        return {}
+    levels = _levels(graph)
+    res = {}
+    for module in sys.modules:
+        for port in module.ports:
+            if port not in pushers:
+                continue
+            waiting = max(levels[module] - levels[pusher] - 1 for pusher in pushers[port])
+            res[port] = max(waiting, port.lookahead_depth() + 1, 1)
+    return res
@@ -0,0 +1,54 @@
+"""Heuristic inference of modules that should have been declared as downstreams."""
+
+from __future__ import annotations
+
+from typing import List
+
+from ..ir.const import Const
+from ..ir.expr import ArrayWrite, AsyncCall
+from ..ir.expr.intrinsic import is_wait_until
+from ..ir.module import Module
+
+
+def _written_arrays(module) -> List:
+    return [expr.array for expr in module.body or [] if isinstance(expr, ArrayWrite)]
+
+
+def _unconditional(expr) -> bool:
+    cond = expr.meta_cond
+    return cond is None or (isinstance(cond, Const) and cond.value == 1)
+
+
+def is_downstream_candidate(module: Module) -> bool:
+    '''Check if `module` behaves as a combinational extension of its callers.
+
+    That is, it (1) has no `wait_until`, (2) writes arrays, but only ones that its callers
+    also write, and (3) is called, and only unconditionally.'''
+    if module.inferred_downstream:
+        return True
+    if module.body is None or any(is_wait_until(expr) for expr in module.body):
+        return False
+    calls = [user for user in module.users if isinstance(user, AsyncCall)]
+    if not calls or not all(_unconditional(call) for call in calls):
+        return False
+    # A module writing no array at all is a plain pipeline stage, not an extension.
+    writes = _written_arrays(module)
+    caller_writes = {id(array) for call in calls for array in _written_arrays(call.parent)}
+    return bool(writes) and all(id(array) in caller_writes for array in writes)
+
+
+def infer_downstream(sys) -> List[Module]:
+    '''Mark every downstream candidate of `sys`, and warn about the newly marked ones.
+
+    Returns all the marked modules, in declaration order.'''
+    res = []
+    for module in sys.modules:
+        if not isinstance(module, Module) or not is_downstream_candidate(module):
+            continue
+        if not module.inferred_downstream:
+            module.inferred_downstream = True
+            print(f'[WARN] Module {module.name} looks like a downstream: it never waits, '
+                  'is always called unconditionally, and only writes arrays of its callers. '
+                  'Consider declaring it as a Downstream.')
+        res.append(module)
+    return res
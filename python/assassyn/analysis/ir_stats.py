@@ -0,0 +1,100 @@
+"""Static statistics of the IR of a system, reported before elaboration."""
+
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass, field, asdict
+from typing import Dict, List, Optional
+
+from ..ir.expr import Bind
+from ..ir.expr.intrinsic import Intrinsic
+from ..ir.module import Downstream
+
+# The intrinsics that delimit condition blocks, rather than compute anything
+_BLOCK_MARKERS = (Intrinsic.PUSH_CONDITION, Intrinsic.PUSH_ELSE, Intrinsic.POP_CONDITION)
+
+
+@dataclass
+class ModuleStats:
+    '''The size of one module or downstream.'''
+
+    name: str
+    downstream: bool
+    body_length: int  # expressions in the body, condition block delimiters excluded
+    ports: int
+    externals: int  # values defined in other modules and used here
+
+
+@dataclass
+class IrStats:
+    '''The result of `collect_stats`.'''
+
+    system: str
+    modules: int
+    downstreams: int
+    arrays: int
+    fifos: int
+    expressions: int
+    avg_body_length: float
+    # '<module>.<port>' -> the depth set with `set_fifo_depth`, or None for the default
+    fifo_depths: Dict[str, Optional[int]] = field(default_factory=dict)
+    per_module: List[ModuleStats] = field(default_factory=list)
+
+    def to_dict(self) -> dict:
+        '''Convert the statistics into plain dicts and lists.'''
+        return asdict(self)
+
+    def to_json(self, **kwargs) -> str:
+        '''Serialize the statistics into JSON; `kwargs` are forwarded to `json.dumps`.'''
+        kwargs.setdefault('indent', 2)
+        return json.dumps(self.to_dict(), **kwargs)
+
+    def format(self) -> str:
+        '''The human-readable summary printed by `SysBuilder.print_stats`.'''
+        lines = [f'[ir-stats] system {self.system}: {self.modules} modules, '
+                 f'{self.downstreams} downstreams, {self.arrays} arrays, {self.fifos} FIFOs, '
+                 f'{self.expressions} expressions ({self.avg_body_length:.1f} per module)']
+        for m in self.per_module:
+            kind = 'downstream' if m.downstream else 'module'
+            lines.append(f'[ir-stats] {kind} {m.name}: body {m.body_length}, '
+                         f'ports {m.ports}, externals {m.externals}')
+        for name, depth in self.fifo_depths.items():
+            lines.append(f'[ir-stats] fifo {name}: depth {"default" if depth is None else depth}')
+        return '\n'.join(lines)
+
+
+def _body_length(module) -> int:
+    return sum(1 for expr in module.body or []
+               if not (isinstance(expr, Intrinsic) and expr.opcode in _BLOCK_MARKERS))
+
+
+def collect_stats(sys) -> IrStats:
+    '''Count the modules, arrays, FIFOs and expressions of `sys`.'''
+    all_modules = sys.modules + sys.downstreams
+    per_module = [ModuleStats(m.name, isinstance(m, Downstream), _body_length(m),
+                              len(getattr(m, 'ports', [])), len(m.externals))
+                  for m in all_modules]
+    fifo_depths = {}
+    for m in sys.modules:
+        for port in m.ports:
+            fifo_depths[f'{m.name}.{port.name}'] = None
+    # The largest depth any call asks for wins, as in the Verilog backend
+    for m in all_modules:
+        for expr in m.body or []:
+            if not isinstance(expr, Bind):
+                continue
+            for push in expr.pushes:
+                key = f'{push.fifo.module.name}.{push.fifo.name}'
+                if push.fifo_depth is not None:
+                    fifo_depths[key] = max(fifo_depths.get(key) or 0, push.fifo_depth)
+    expressions = sum(m.body_length for m in per_module)
+    return IrStats(
+        system=sys.name,
+        modules=len(sys.modules),
+        downstreams=len(sys.downstreams),
+        arrays=len(sys.arrays),
+        fifos=len(fifo_depths),
+        expressions=expressions,
+        avg_body_length=expressions / len(per_module) if per_module else 0.0,
+        fifo_depths=fifo_depths,
+        per_module=per_module)
@@ -0,0 +1,42 @@
+"""Check the ports of the modules merging the calls of a cycle."""
+
+from __future__ import annotations
+
+from typing import List
+
+from ..ir.expr import AsyncCall
+from ..ir.module import Port
+
+
+def shared_merged_ports(sys) -> List[Port]:
+    '''Get the ports of `merge_calls` modules pushed by more than one call site.
+
+    All the calls of a cycle activate such a module once, so it pops each port at most once
+    per cycle: the values pushed by two callers in the same cycle are served one cycle apart,
+    without an activation of their own.'''
+    counts = {}
+    ports = []
+    for module in sys.modules + sys.downstreams:
+        for expr in module.body or []:
+            if not isinstance(expr, AsyncCall) or not expr.bind.callee.merge_calls:
+                continue
+            for push in expr.bind.pushes:
+                port = push.fifo
+                if id(port) not in counts:
+                    counts[id(port)] = 0
+                    ports.append(port)
+                counts[id(port)] += 1
+    return [p for p in ports if counts[id(p)] > 1]
+
+
+def check_merged_calls(sys, strict: bool = False) -> List[Port]:
+    '''Warn about the shared ports of `merge_calls` modules, or raise a ValueError with
+    `strict`. Returns the shared ports.'''
+    res = shared_merged_ports(sys)
+    if res and strict:
+        names = ', '.join(f'{p.module.name}.{p.name}' for p in res)
+        raise ValueError(f'Ports of merge_calls modules pushed by several calls: {names}')
+    for port in res:
+        print(f'[WARN] Port {port.module.name}.{port.name} is pushed by several calls, but '
+              f'{port.module.name} merges the calls of a cycle and pops it once per cycle.')
+    return res
@@ -0,0 +1,75 @@
+"""Mermaid flowcharts of the call graph of a system and of the data flow of a module."""
+
+from __future__ import annotations
+
+from typing import Dict, List, Tuple
+
+from ..ir.expr import AsyncCall, Expr, FIFOPush, Operand
+from ..ir.module.base import ModuleBase
+from ..utils import namify
+from .topo import get_upstreams
+
+
+def _edge(src: str, dst: str, labels: List[str], arrow: str = '-->') -> str:
+    label = f'|{", ".join(labels)}|' if labels else ''
+    return f'    {src} {arrow}{label} {dst}'
+
+
+def call_graph_edges(sys) -> Dict[Tuple[str, str], List[str]]:
+    '''The (caller, callee) pairs of the modules of `sys`, in the order of the calls, mapped to
+    the FIFOs pushed along each of them.'''
+    edges: Dict[Tuple[str, str], List[str]] = {}
+    for module in sys.modules + sys.downstreams:
+        for expr in module.body or []:
+            if isinstance(expr, FIFOPush):
+                fifos = edges.setdefault((module.name, expr.fifo.module.name), [])
+                if expr.fifo.name not in fifos:
+                    fifos.append(expr.fifo.name)
+            elif isinstance(expr, AsyncCall):
+                # A call of a module without ports pushes nothing
+                edges.setdefault((module.name, expr.bind.callee.name), [])
+    return edges
+
+
+def mermaid_call_graph(sys) -> str:
+    '''A flowchart of the modules of `sys` with an edge from each caller to its callee,
+    labelled with the FIFOs it pushes. A downstream gets a dotted edge from each module whose
+    values it reads.'''
+    lines = ['flowchart TD']
+    for module in sys.modules + sys.downstreams:
+        lines.append(f'    {namify(module.name)}["{module.name}"]')
+    for (caller, callee), fifos in call_graph_edges(sys).items():
+        lines.append(_edge(namify(caller), namify(callee), fifos))
+    for downstream in sys.downstreams:
+        upstreams = sorted(get_upstreams(downstream), key=lambda m: m.name)
+        for upstream in upstreams:
+            lines.append(_edge(namify(upstream.name), namify(downstream.name), [], '-.->'))
+    return '\n'.join(lines)
+
+
+def mermaid_dataflow(module) -> str:
+    '''A flowchart of the expressions of `module`, with an edge from each expression to the
+    ones using it as an operand. The values of other modules it uses are nodes named
+    `<module>.<value>`.'''
+    lines = ['flowchart TD']
+    ids: Dict[int, str] = {}
+    edges = []
+    body = list(module.body or [])
+    for expr in body:
+        ids[id(expr)] = namify(expr.as_operand())
+        lines.append(f'    {ids[id(expr)]}["{expr.as_operand()}"]')
+    for expr in body:
+        for operand in expr.operands:
+            value = operand.value if isinstance(operand, Operand) else operand
+            if not isinstance(value, Expr):
+                continue
+            if id(value) not in ids:
+                owner = value.parent
+                if not isinstance(owner, ModuleBase):
+                    owner = owner.module
+                ids[id(value)] = namify(f'{owner.name}_{value.as_operand()}')
+                lines.append(f'    {ids[id(value)]}["{owner.name}.{value.as_operand()}"]')
+            edge = _edge(ids[id(value)], ids[id(expr)], [])
+            if edge not in edges:
+                edges.append(edge)
+    return '\n'.join(lines + edges)
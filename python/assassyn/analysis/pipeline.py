@@ -0,0 +1,204 @@
+"""Pipeline performance analysis built on the async-call graph and simulation statistics."""
+
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass, field, asdict
+from typing import Dict, List, Optional
+
+from ..ir.expr import AsyncCall
+from ..ir.module import Module, Downstream
+from ..ir.module.base import ModuleBase
+from ..utils import namify
+from .topo import get_upstreams
+
+STATS_PREFIX = '[stats]'
+
+
+@dataclass
+class SimReport:
+    '''Per-module and per-FIFO counters printed by a simulator built with `sim_stats=True`,
+    and the per-block counters printed by both backends with `cover=True`.'''
+
+    cycles: int = 0
+    # Module key -> number of cycles the module executed successfully
+    executed: Dict[str, int] = field(default_factory=dict)
+    # Module key -> number of cycles the module had a valid event but failed its wait_until
+    stalled: Dict[str, int] = field(default_factory=dict)
+    # FIFO key -> number of stalled cycles of its owner during which the FIFO held data
+    backlog: Dict[str, int] = field(default_factory=dict)
+    # FIFO key -> key of the module owning the FIFO
+    fifo_owner: Dict[str, str] = field(default_factory=dict)
+    # FIFO key -> number of cycles ending with each number of entries, up to the deepest one
+    occupancy: Dict[str, List[int]] = field(default_factory=dict)
+    # Block key -> number of cycles the block was entered, with `cover=True`
+    covered: Dict[str, int] = field(default_factory=dict)
+
+    @classmethod
+    def parse(cls, raw: str) -> SimReport:
+        '''Collect the `[stats]` lines of a simulator output.'''
+        report = cls()
+        for line in raw.splitlines():
+            tokens = line.split()
+            if not tokens or tokens[0] != STATS_PREFIX:
+                continue
+            kind = tokens[1]
+            if kind == 'cycles':
+                report.cycles = int(tokens[2])
+            elif kind == 'module':
+                report.executed[tokens[2]] = int(tokens[3])
+                report.stalled[tokens[2]] = int(tokens[4])
+            elif kind == 'fifo':
+                report.fifo_owner[tokens[2]] = tokens[3]
+                report.backlog[tokens[2]] = int(tokens[4])
+            elif kind == 'hist':
+                report.occupancy[tokens[2]] = [int(i) for i in tokens[3:]]
+            elif kind == 'cover':
+                report.covered[tokens[2]] = int(tokens[3])
+            else:
+                raise ValueError(f'Unknown statistics line: {line}')
+        return report
+
+    def occupancy_summary(self, key: str) -> Dict[str, float]:
+        '''The max, mean and p99 occupancy of a FIFO, as in the table of the simulator.'''
+        hist = self.occupancy[key]
+        cycles = sum(hist)
+        if not cycles:
+            return {'max': 0, 'mean': 0.0, 'p99': 0}
+        mean = sum(depth * n for depth, n in enumerate(hist)) / cycles
+        seen = 0
+        p99 = 0
+        for depth, n in enumerate(hist):
+            seen += n
+            if seen and seen >= cycles * 99.0 / 100.0:
+                p99 = depth
+                break
+        return {'max': len(hist) - 1, 'mean': mean, 'p99': p99}
+
+
+@dataclass
+class StageStats:
+    '''Achieved behavior of one module in the pipeline.'''
+
+    key: str
+    name: str
+    executed: int
+    stalled: int
+    avg_stall: float  # stalled cycles per successful execution
+
+
+@dataclass
+class ChainStats:
+    '''One chain of modules connected by async calls, from a root to a sink.'''
+
+    stages: List[str]
+    ipc: float  # executions of the last stage per simulated cycle
+
+
+@dataclass
+class PipelineReport:
+    '''The result of `analyze`.'''
+
+    cycles: int
+    modules: Dict[str, str]  # module key -> module name
+    stages: Dict[str, StageStats]
+    chains: List[ChainStats]
+    bottleneck_fifo: Optional[str] = None
+    bottleneck_stage: Optional[str] = None
+
+    def to_dict(self) -> dict:
+        '''Convert the report into plain dicts and lists.'''
+        return asdict(self)
+
+    def to_json(self, **kwargs) -> str:
+        '''Serialize the report into JSON; `kwargs` are forwarded to `json.dumps`.'''
+        kwargs.setdefault('indent', 2)
+        return json.dumps(self.to_dict(), **kwargs)
+
+
+def module_keys(sys) -> Dict[str, ModuleBase]:
+    '''The stable key of every module, in declaration order.
+
+    The key is the namified module name, which is also the identifier used by the generated
+    simulator, so keys read from a `SimReport` can be looked up here.'''
+    res = {}
+    for module in sys.modules[:] + sys.downstreams[:]:
+        key = namify(module.name)
+        if key in res:
+            raise ValueError(f'Duplicated module key {key}')
+        res[key] = module
+    return res
+
+
+def call_graph(sys) -> Dict[ModuleBase, List[ModuleBase]]:
+    '''The static graph of async calls, plus the upstream-to-downstream triggers.'''
+    graph = {module: [] for module in sys.modules[:] + sys.downstreams[:]}
+    for module in sys.modules[:] + sys.downstreams[:]:
+        if isinstance(module, Module):
+            for user in module.users:
+                caller = getattr(user, 'parent', None)
+                if isinstance(user, AsyncCall) and isinstance(caller, ModuleBase):
+                    succs = graph.setdefault(caller, [])
+                    if not any(i is module for i in succs):
+                        succs.append(module)
+        elif isinstance(module, Downstream):
+            for upstream in sorted(get_upstreams(module), key=lambda m: m.name):
+                succs = graph.setdefault(upstream, [])
+                if not any(i is module for i in succs):
+                    succs.append(module)
+    return graph
+
+
+def _chains(graph: Dict[ModuleBase, List[ModuleBase]]) -> List[List[ModuleBase]]:
+    '''All maximal simple paths starting from modules that nobody triggers.'''
+    targets = {id(succ) for succs in graph.values() for succ in succs}
+    roots = [m for m in graph if id(m) not in targets]
+    res = []
+
+    def dfs(path):
+        succs = [i for i in graph.get(path[-1], []) if all(i is not j for j in path)]
+        if not succs:
+            res.append(list(path))
+            return
+        for succ in succs:
+            path.append(succ)
+            dfs(path)
+            path.pop()
+
+    for root in roots:
+        dfs([root])
+    return res
+
+
+def analyze(sys, report: SimReport) -> PipelineReport:
+    '''Combine the static call graph of `sys` with the statistics of a simulation run.'''
+    keys = module_keys(sys)
+    key_of = {id(module): key for key, module in keys.items()}
+    cycles = max(report.cycles, 1)
+
+    stages = {}
+    for key in keys:
+        executed = report.executed.get(key, 0)
+        stalled = report.stalled.get(key, 0)
+        stages[key] = StageStats(key, keys[key].name, executed, stalled,
+                                 stalled / executed if executed else float(stalled))
+
+    chains = []
+    for path in _chains(call_graph(sys)):
+        path_keys = [key_of[id(m)] for m in path]
+        chains.append(ChainStats(path_keys, stages[path_keys[-1]].executed / cycles))
+
+    bottleneck_fifo = None
+    for fifo, count in sorted(report.backlog.items()):
+        if count and (bottleneck_fifo is None or count > report.backlog[bottleneck_fifo]):
+            bottleneck_fifo = fifo
+    bottleneck_stage = report.fifo_owner.get(bottleneck_fifo) if bottleneck_fifo else None
+
+    return PipelineReport(
+        cycles=report.cycles,
+        modules={key: module.name for key, module in keys.items()},
+        stages=stages,
+        chains=chains,
+        bottleneck_fifo=bottleneck_fifo,
+        bottleneck_stage=bottleneck_stage,
+    )
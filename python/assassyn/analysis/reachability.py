@@ -0,0 +1,47 @@
+"""Check that every module can be activated from the driver or the testbench."""
+
+from __future__ import annotations
+
+from typing import List
+
+from ..ir.expr import AsyncCall
+from ..ir.module import Module
+
+# The modules the simulator activates in every cycle
+ROOTS = ('Driver', 'Testbench')
+
+
+def unreachable_modules(sys) -> List[Module]:
+    '''Get the modules of `sys` that no chain of async calls from the roots reaches.
+
+    Downstream modules are not checked, and their calls count as reachable, since they run
+    combinationally with their upstreams. A system without any root is not checked.'''
+    roots = [m for m in sys.modules if m.name in ROOTS]
+    if not roots:
+        return []
+    reached = {id(m) for m in roots}
+    worklist = roots + list(sys.downstreams)
+    while worklist:
+        module = worklist.pop()
+        for expr in module.body or []:
+            if not isinstance(expr, AsyncCall):
+                continue
+            callee = expr.bind.callee
+            if id(callee) not in reached:
+                reached.add(id(callee))
+                worklist.append(callee)
+    return [m for m in sys.modules if id(m) not in reached]
+
+
+def check_reachability(sys, strict: bool = False) -> List[Module]:
+    '''Warn about the unreachable modules of `sys`, which generate hardware but never run.
+
+    With `strict`, raise a ValueError instead. Returns the unreachable modules.'''
+    res = unreachable_modules(sys)
+    if res and strict:
+        names = ', '.join(m.name for m in res)
+        raise ValueError(f'Modules never called from {" or ".join(ROOTS)}: {names}')
+    for module in res:
+        print(f'[WARN] Module {module.name} is never async-called from the driver or the '
+              'testbench, so it can never fire.')
+    return res
@@ -0,0 +1,69 @@
+"""The modules reachable from a root through the calls, and the system restricted to them."""
+
+from __future__ import annotations
+
+from typing import List
+
+from ..ir.expr import ArrayRead, ArrayWrite, AsyncCall, Expr, FIFOPush
+from ..ir.module.base import ModuleBase
+from .topo import get_upstreams
+
+
+def callees(sys, module) -> List[ModuleBase]:
+    '''The modules `module` calls, in the order of the calls, then the downstreams of `sys`
+    reading its values.'''
+    res = []
+    for expr in module.body or []:
+        if isinstance(expr, FIFOPush):
+            res.append(expr.fifo.module)
+        elif isinstance(expr, AsyncCall):
+            res.append(expr.bind.callee)
+    res += [d for d in sys.downstreams if any(u is module for u in get_upstreams(d))]
+    unique = {}
+    for callee in res:
+        unique.setdefault(id(callee), callee)
+    return list(unique.values())
+
+
+def reachable_from(sys, root) -> List[ModuleBase]:
+    '''The modules and downstreams of `sys` reachable from `root` by a chain of `callees`,
+    `root` first, in breadth-first order.'''
+    reached = {id(root): root}
+    queue = [root]
+    for module in queue:
+        for callee in callees(sys, module):
+            if id(callee) not in reached:
+                reached[id(callee)] = callee
+                queue.append(callee)
+    return queue
+
+
+def callee_subgraph(sys, root):
+    '''A new system named `<system>_<root>` with only the modules, downstreams and arrays of
+    `sys` that `root` reaches, to elaborate them without the rest of the design.
+
+    The IR is shared, not copied: the modules, their ports and bodies are those of `sys`.
+    The arrays are the ones the reached modules read or write, and the exposed nodes and
+    probes are kept if they belong to them.'''
+    # pylint: disable=import-outside-toplevel,protected-access
+    from ..builder import SysBuilder
+    reached = {id(m) for m in reachable_from(sys, root)}
+    res = SysBuilder(f'{sys.name}_{root.name}')
+    res.modules = [m for m in sys.modules if id(m) in reached]
+    res.downstreams = [m for m in sys.downstreams if id(m) in reached]
+    accessed = set()
+    for module in res.modules + res.downstreams:
+        for expr in module.body or []:
+            if isinstance(expr, (ArrayRead, ArrayWrite)):
+                accessed.add(id(expr.array))
+    res.arrays = [a for a in sys.arrays if id(a) in accessed]
+
+    def kept(node):
+        if isinstance(node, Expr):
+            return id(node.parent) in reached
+        return id(node) in accessed or id(node) in reached
+
+    res._exposes = {node: kind for node, kind in sys.exposed_nodes.items() if kept(node)}
+    res._probes = [(signal, label) for signal, label in sys.probes if kept(signal)]
+    res._registered = [value for value in sys.registered_values if kept(value)]
+    return res
@@ -60,6 +60,9 @@ def get_upstreams(module):
 
         if isinstance(elem, (FIFOPush, Bind)):
             continue
+        # A registered value is read a cycle late, so its producer need not run first
+        if elem.registered:
+            continue
 
         parent_block = getattr(elem, 'parent', None)
         if isinstance(parent_block, ModuleBase):
@@ -70,3 +73,10 @@ def get_upstreams(module):
             res.add(upstream_module)
 
     return res
+
+
+def registered_inputs(module):
+    """The registered values of other modules that `module` uses, in the order of its
+    externals. A downstream executes when the registered valid of one of them is set."""
+    return [elem for elem in getattr(module, 'externals', {})
+            if isinstance(elem, Expr) and elem.registered and elem.parent is not module]
@@ -0,0 +1,120 @@
+"""Pre-elaboration type checking of a whole system."""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from typing import List, Optional
+
+from ..ir.array import Slice
+from ..ir.dtype import DType
+from ..ir.expr import BinaryOp, Cast, Concat, Expr, FIFOPush, Select
+from ..ir.visitor import Visitor
+from ..utils import unwrap_operand
+
+
+@dataclass
+class TypeCheckError:
+    '''One type inconsistency found by `validate_types`.'''
+
+    node: Expr
+    expected: Optional[DType]
+    actual: Optional[DType]
+    message: str
+
+    def __str__(self):
+        module = getattr(getattr(self.node, 'parent', None), 'name', '<unknown>')
+        loc = getattr(self.node, 'loc', None)
+        where = f' ({loc})' if loc else ''
+        return f'[{module}] {self.message}: {self.node!r}{where}'
+
+
+# Binary operations whose operands must be equally wide. Arithmetic ones define their
+# result width from both operands, and shifts take an amount of any width.
+_SAME_WIDTH = (
+    BinaryOp.BITWISE_AND, BinaryOp.BITWISE_OR, BinaryOp.BITWISE_XOR,
+    BinaryOp.ILT, BinaryOp.IGT, BinaryOp.ILE, BinaryOp.IGE, BinaryOp.EQ, BinaryOp.NEQ,
+)
+
+
+class _TypeChecker(Visitor):
+    '''Collects every type error of the visited expressions.'''
+
+    def __init__(self):
+        super().__init__()
+        self.errors: List[TypeCheckError] = []
+
+    def _error(self, node, expected, actual, message):
+        self.errors.append(TypeCheckError(node, expected, actual, message))
+
+    def visit_expr(self, node: Expr):
+        if isinstance(node, BinaryOp):
+            self._check_binary(node)
+        elif isinstance(node, Select):
+            self._check_select(node)
+        elif isinstance(node, Slice):
+            self._check_slice(node)
+        elif isinstance(node, Concat):
+            self._check_concat(node)
+        elif isinstance(node, Cast):
+            self._check_cast(node)
+        elif isinstance(node, FIFOPush):
+            self._check_fifo_push(node)
+
+    def _check_binary(self, node: BinaryOp):
+        lhs, rhs = node.lhs.dtype, node.rhs.dtype
+        if node.is_saturating():
+            # The clamp bounds come from one type, so both operands must agree on it.
+            if lhs.bits != rhs.bits or lhs.is_signed() != rhs.is_signed():
+                op = BinaryOp.OPERATORS[node.opcode]
+                self._error(node, lhs, rhs, f'Operands of "{op}" differ in width or signedness')
+            return
+        if node.opcode not in _SAME_WIDTH:
+            return
+        if lhs.bits != rhs.bits:
+            op = BinaryOp.OPERATORS[node.opcode]
+            self._error(node, lhs, rhs, f'Operands of "{op}" differ in width')
+
+    def _check_select(self, node: Select):
+        if node.cond.dtype.bits != 1:
+            self._error(node, None, node.cond.dtype, 'Select condition is not 1-bit')
+        true_ty, false_ty = node.true_value.dtype, node.false_value.dtype
+        if not true_ty.type_eq(false_ty):
+            self._error(node, true_ty, false_ty, 'Select branches differ in type')
+
+    def _check_slice(self, node: Slice):
+        l, r = unwrap_operand(node.l).value, unwrap_operand(node.r).value
+        bits = node.x.dtype.bits
+        if not 0 <= l <= r < bits:
+            self._error(node, node.x.dtype, None, f'Slice [{l}:{r}] is out of {bits} bits')
+
+    def _check_concat(self, node: Concat):
+        bits = node.msb.dtype.bits + node.lsb.dtype.bits
+        if node.dtype.bits != bits:
+            self._error(node, None, node.dtype, f'Concat result is not {bits} bits')
+
+    def _check_cast(self, node: Cast):
+        src, dst = node.x.dtype, node.dtype
+        # Bitcasts may truncate or pad, so only the extensions are constrained.
+        if node.opcode in (Cast.ZEXT, Cast.SEXT) and dst.bits < src.bits:
+            method = Cast.SUBCODES[node.opcode]
+            self._error(node, dst, src, f'{method.capitalize()} narrows the value')
+
+    def _check_fifo_push(self, node: FIFOPush):
+        expected, actual = node.fifo.dtype, node.val.dtype
+        if expected.bits != actual.bits:
+            self._error(node, expected, actual, f'Push to FIFO {node.fifo.name} mismatches')
+
+
+def validate_types(sys) -> List[TypeCheckError]:
+    '''Check the operand types of every expression in `sys`, and return all the errors.'''
+    checker = _TypeChecker()
+    checker.visit_system(sys)
+    return checker.errors
+
+
+def check_types(sys) -> None:
+    '''Raise a `ValueError` listing all the type errors of `sys`, if any.'''
+    errors = validate_types(sys)
+    if errors:
+        listed = '\n'.join(f'  {error}' for error in errors)
+        raise ValueError(f'{len(errors)} type error(s) in system {sys.name}:\n{listed}')
@@ -6,11 +6,20 @@ import os
 import inspect
 import hashlib
 import json
+from dataclasses import asdict
 from pathlib import Path
 
 from .builder import SysBuilder
+from . import analysis
+from . import xform
 from . import codegen
 from . import utils
+from .ir.block import verify_blocks
+from .ir.array import verify_array_names, verify_write_ports
+from .ir.module.module import verify_responses
+from .codegen.simulator.run import run_crate
+from .codegen.simulator.fuzz import FuzzConfig
+from .codegen.verilog.reset import check_reset_style
 
 def config( # pylint: disable=too-many-arguments
         path='./workspace',
@@ -23,7 +32,39 @@ def config( # pylint: disable=too-many-arguments
         idle_threshold=100,
         fifo_depth=4,
         random=False,
-        enable_cache=True):
+        enable_cache=True,
+        sim_stats=False,
+        cosim=False,
+        infer_downstream=False,
+        licm=False,
+        cse=False,
+        simplify_casts=False,
+        infer_fifo_depth=False,
+        strict=False,
+        snapshot_at=None,
+        restore_from=None,
+        fifo_occupancy_csv=False,
+        incremental=False,
+        print_stats=False,
+        emit_mermaid=False,
+        synthesis=False,
+        firrtl=False,
+        trace=False,
+        verilog_defines=None,
+        include_dirs=None,
+        verilator_flags=None,
+        clock_domains=None,
+        cdc_sync_stages=2,
+        axi_lite=False,
+        axil_transactions=None,
+        log_dpi=False,
+        formal=False,
+        formal_depth=20,
+        cover=False,
+        reset_style='async_low',
+        fuzz=None,
+        build=False,
+        force_regenerate=False):
     '''The helper function to dump the default configuration of elaboration.'''
     res = {
         'path': path,
@@ -36,7 +77,39 @@ def config( # pylint: disable=too-many-arguments
         'idle_threshold': idle_threshold,
         'fifo_depth': fifo_depth,
         'random': random,
-        'enable_cache': enable_cache
+        'enable_cache': enable_cache,
+        'sim_stats': sim_stats,
+        'cosim': cosim,
+        'infer_downstream': infer_downstream,
+        'licm': licm,
+        'cse': cse,
+        'simplify_casts': simplify_casts,
+        'infer_fifo_depth': infer_fifo_depth,
+        'strict': strict,
+        'snapshot_at': snapshot_at,
+        'restore_from': restore_from,
+        'fifo_occupancy_csv': fifo_occupancy_csv,
+        'incremental': incremental,
+        'print_stats': print_stats,
+        'emit_mermaid': emit_mermaid,
+        'synthesis': synthesis,
+        'firrtl': firrtl,
+        'trace': trace,
+        'verilog_defines': verilog_defines,
+        'include_dirs': include_dirs,
+        'verilator_flags': verilator_flags,
+        'clock_domains': clock_domains,
+        'cdc_sync_stages': cdc_sync_stages,
+        'axi_lite': axi_lite,
+        'axil_transactions': axil_transactions,
+        'log_dpi': log_dpi,
+        'formal': formal,
+        'formal_depth': formal_depth,
+        'cover': cover,
+        'reset_style': reset_style,
+        'fuzz': fuzz,
+        'build': build,
+        'force_regenerate': force_regenerate
     }
     return res.copy()
 
@@ -72,6 +145,27 @@ def _generate_cache_key(sys_name: str, config_dict: dict) -> str:
         'idle_threshold': config_dict.get('idle_threshold'),
         'fifo_depth': config_dict.get('fifo_depth'),
         'random': config_dict.get('random', False),
+        'sim_stats': config_dict.get('sim_stats', False),
+        'cosim': config_dict.get('cosim', False),
+        'snapshot_at': config_dict.get('snapshot_at'),
+        'restore_from': config_dict.get('restore_from'),
+        'fifo_occupancy_csv': config_dict.get('fifo_occupancy_csv', False),
+        'synthesis': config_dict.get('synthesis', False),
+        'firrtl': config_dict.get('firrtl', False),
+        'trace': config_dict.get('trace', False),
+        'verilog_defines': config_dict.get('verilog_defines'),
+        'include_dirs': [str(i) for i in config_dict.get('include_dirs') or []],
+        'verilator_flags': config_dict.get('verilator_flags'),
+        'clock_domains': {k: str(v) for k, v in (config_dict.get('clock_domains') or {}).items()},
+        'cdc_sync_stages': config_dict.get('cdc_sync_stages'),
+        'axi_lite': config_dict.get('axi_lite', False),
+        'axil_transactions': [list(i) for i in config_dict.get('axil_transactions') or []],
+        'log_dpi': config_dict.get('log_dpi', False),
+        'formal': config_dict.get('formal', False),
+        'formal_depth': config_dict.get('formal_depth'),
+        'cover': config_dict.get('cover', False),
+        'reset_style': config_dict.get('reset_style', 'async_low'),
+        'fuzz': asdict(config_dict['fuzz']) if config_dict.get('fuzz') else None,
     }
 
     # Create a stable string representation and hash it
@@ -92,7 +186,46 @@ def elaborate(# pylint: disable=too-many-locals
         simulator (bool): Whether to generate the Rust code for the simulator.
         verilog (bool): Whether to generate the SystemVerilog code.
         idle_threshold (int): The threshold for the idle state to terminate the simulation.
-        sim_threshold (int): The threshold for the simulation to terminate.
+        sim_threshold (int): The number of cycles to simulate, numbered from 1 in both backends.
+        infer_downstream (bool): Whether to mark and warn about modules that look like downstreams.
+        licm (bool): Whether to hoist invariant array reads out of condition blocks.
+        cse (bool): Whether to merge equal one-hot selects, concatenations and slices.
+        simplify_casts (bool): Whether to remove the casts that cancel out.
+        infer_fifo_depth (bool): Whether to size the FIFOs without a user-given depth by the
+            latencies of the call graph.
+        strict (bool): Whether unreachable modules and shared ports of merge_calls modules are
+            errors instead of warnings.
+        incremental (bool): Whether to keep the simulator files of unchanged modules.
+        print_stats (bool): Whether to print the IR statistics to stderr before code generation.
+        emit_mermaid (bool): Whether to write the call graph as a Mermaid flowchart to
+            call_graph.mmd, next to the generated code.
+        synthesis (bool): Whether to strip the logs from the generated Verilog.
+        firrtl (bool): Whether to also emit the system as a FIRRTL circuit.
+        trace (bool): Whether both backends print a `@trace` line per array write and FIFO push.
+        verilog_defines (dict): The `+define+` macros of the Verilator build, by name.
+        include_dirs (list): The `+incdir+` directories of the Verilator build.
+        verilator_flags (list): The extra arguments of the Verilator build.
+        clock_domains (dict): The period of each clock domain named by a module, in cycles of
+            the default clock, e.g. `{'mem': 2}` or `{'mem': Fraction(3, 2)}`.
+        cdc_sync_stages (int): The synchronizer flops of the FIFOs between clock domains.
+        axi_lite (bool): Whether the Verilog Top has an AXI4-Lite slave mapping the exposed
+            arrays, one element per word, read-write for the `Input` and `Inout` kinds.
+        axil_transactions (list): The `('write', addr, data)`, `('read', addr)` and
+            `('wait', cycles)` transactions the testbench runs on the AXI4-Lite slave.
+        log_dpi (bool): Whether the VCS testbench prints the logs, through the DPI-C function
+            `assassyn_log` whose C side writes them as binary records.
+        formal (bool): Whether the Verilog backend also writes the SymbiYosys harness of the
+            assertions, run by run_formal.sh.
+        formal_depth (int): The cycles the formal harness checks.
+        cover (bool): Whether both backends count the cycles each condition and else block is
+            entered, and print the counts and the blocks never entered at the end of the run.
+        reset_style (str): The reset of the Verilog FIFOs and trigger counters, 'async_low' on
+            `rst_n` or 'sync_high' on `rst` like the registers of the arrays.
+        fuzz (FuzzConfig): The seed and the probabilities of the random activation delays and
+            reorderings the simulator injects, or None for the nominal timing.
+        build (bool): Whether to compile the simulator once it is generated.
+        force_regenerate (bool): Whether to regenerate the simulator even if its IR hash
+            matches the existing crate.
         **kwargs: The optional arguments that will be passed to the code generator.
     '''
 
@@ -103,6 +236,39 @@ def elaborate(# pylint: disable=too-many-locals
             raise ValueError(f'Invalid config key: {k}')
         real_config[k] = v
 
+    if real_config['infer_downstream']:
+        analysis.infer_downstream(sys)
+    if real_config['licm']:
+        xform.licm(sys)
+    if real_config['cse']:
+        xform.cse(sys)
+    if real_config['simplify_casts']:
+        xform.simplify_casts(sys)
+    if real_config['infer_fifo_depth']:
+        xform.apply_fifo_depths(sys)
+    for module in sys.modules + sys.downstreams:
+        verify_blocks(module)
+    verify_array_names(sys)
+    verify_write_ports(sys)
+    verify_responses(sys)
+    analysis.check_reachability(sys, real_config['strict'])
+    analysis.check_merged_calls(sys, real_config['strict'])
+    analysis.check_clock_domains(sys, real_config['clock_domains'])
+    if real_config['axil_transactions'] is not None and not real_config['axi_lite']:
+        raise ValueError('axil_transactions run on the slave of the axi_lite option, which '
+                         'is not set')
+    if real_config['log_dpi'] and str(real_config['verilog']).lower() != 'vcs':
+        raise ValueError('log_dpi lowers the logs of the VCS testbench, but verilog is '
+                         f"{real_config['verilog']!r}")
+    check_reset_style(real_config['reset_style'])
+    if real_config['fuzz'] is not None and not isinstance(real_config['fuzz'], FuzzConfig):
+        raise ValueError(f"fuzz takes a FuzzConfig, not {real_config['fuzz']!r}")
+    if real_config['formal'] and not real_config['verilog']:
+        raise ValueError('formal writes its harness with the Verilog design, but verilog is '
+                         'not set')
+    if real_config['print_stats']:
+        sys.print_stats()
+
     frame = inspect.stack()[1]
     caller_file = frame.filename
     source_dir = os.path.dirname(os.path.abspath(caller_file))
@@ -130,6 +296,9 @@ def elaborate(# pylint: disable=too-many-locals
     sys_dir = proj_root / sys.name
 
     make_existing_dir(sys_dir)
+    if real_config['emit_mermaid']:
+        with open(sys_dir / 'call_graph.mmd', 'w', encoding='utf-8') as f:
+            f.write(sys.mermaid_call_graph() + '\n')
 
     # Update the path in config to point to the system directory
     real_config['path'] = str(sys_dir)
@@ -142,3 +311,24 @@ def elaborate(# pylint: disable=too-many-locals
         utils.CACHE_PENDING = (source_dir, cache_key, verilog_path)
 
     return [simulator_manifest, verilog_path]
+
+def elaborate_and_run(sys: SysBuilder, target_dir=None, stream=False, **kwargs):
+    '''
+    Elaborate the simulator of the given system, build it, and run it.
+    Args:
+        sys (SysBuilder): The assassyn system to be simulated.
+        target_dir (Path): The cargo target directory, `target` in the crate by default.
+        stream (bool): Whether to echo the output of cargo and of the simulator while it runs.
+        **kwargs: The options of `elaborate`, which must generate the simulator.
+    Returns:
+        SimOutput: The log of the simulator and its `[stats]` report.
+    Raises:
+        SimulatorBuildError: If the crate does not compile, with the first rustc error.
+        subprocess.CalledProcessError: If the simulator fails, e.g. on a panic.
+    '''
+    if not kwargs.get('simulator', True):
+        raise ValueError('elaborate_and_run needs the simulator')
+    # The crate is built here, so the binary cache of the caller's directory is not involved
+    kwargs['enable_cache'] = False
+    simulator_manifest, _ = elaborate(sys, **kwargs)
+    return run_crate(simulator_manifest, target_dir, stream)
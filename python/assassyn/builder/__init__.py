@@ -4,15 +4,19 @@
 
 from __future__ import annotations
 
+import contextlib
 import functools
+import hashlib
 import inspect
 import os
 import site
+import sys as py_sys
 import typing
 from .naming_manager import (
     NamingManager,
 )
 from .rewrite_assign import rewrite_assign
+from .snapshot import Snapshot, take_snapshot, restore_snapshot
 from .type_oriented_namer import TypeOrientedNamer
 from .unique_name import UniqueNameCache
 
@@ -20,6 +24,7 @@ if typing.TYPE_CHECKING:
     from ..ir.array import Array, ArrayRead
     from ..ir.dtype import DType
     from ..ir.module import Module
+    from ..ir.module.stage import StagePipeline
     from ..ir.value import Value
 
 __all__ = [
@@ -31,9 +36,31 @@ __all__ = [
     # Decorators
     'rewrite_assign',
 
+    # Transactions
+    'Snapshot',
+
 ]
 
 
+def user_location() -> typing.Optional[str]:
+    '''The `file:line` of the innermost frame of the call stack outside this package, i.e. the
+    user code being built, or None if there is none.'''
+    #pylint: disable=import-outside-toplevel
+    from ..utils import package_path
+    package_dir = os.path.abspath(package_path())
+    Singleton.initialize_dirs_to_exclude()
+    for i in inspect.stack()[1:]:
+        fname, lineno = i.filename, i.lineno
+        fname_abs = os.path.abspath(fname)
+        if not fname_abs.startswith(package_dir) \
+            and not any(
+                fname_abs.startswith(exclude_dir)
+                for exclude_dir in Singleton.all_dirs_to_exclude
+            ):
+            return f'{fname}:{lineno}'
+    return None
+
+
 def ir_builder(func=None):
     '''Decorator that records builder metadata and injects IR nodes into the AST.'''
 
@@ -48,7 +75,6 @@ def ir_builder(func=None):
 
             #pylint: disable=cyclic-import,import-outside-toplevel
             from ..ir.const import Const
-            from ..utils import package_path
             from ..ir.expr import Expr
 
             builder = Singleton.peek_builder()
@@ -68,21 +94,9 @@ def ir_builder(func=None):
                 if not already_materialized:
                     builder.insert_point.append(res)
 
-            package_dir = os.path.abspath(package_path())
-
-            Singleton.initialize_dirs_to_exclude()
-            for i in inspect.stack()[1:]:  # pylint: disable=too-many-nested-blocks
-                fname, lineno = i.filename, i.lineno
-                fname_abs = os.path.abspath(fname)
-
-                if not fname_abs.startswith(package_dir) \
-                    and not any(
-                        fname_abs.startswith(exclude_dir)
-                        for exclude_dir in Singleton.all_dirs_to_exclude
-                    ):
-                    res.loc = f'{fname}:{lineno}'
-
-                    break
+            loc = user_location()
+            if loc is not None:
+                res.loc = loc
             assert hasattr(res, 'loc')
             return res
 
@@ -138,10 +152,12 @@ class ModuleContext:  # pylint: disable=too-few-public-methods
 
     module: Module
     cond_stack: list[PredicateFrame]
+    pipeline: StagePipeline | None  # Stages split from the module being built, if any
 
     def __init__(self, module: Module):
         self.module = module
         self.cond_stack = []
+        self.pipeline = None
 
 
 class SysBuilder:
@@ -157,12 +173,17 @@ class SysBuilder:
     naming_manager: NamingManager  # Naming manager
 
     @property
-    def current_module(self):
-        '''Get the current module being built.'''
+    def current_context(self) -> ModuleContext:
+        '''Get the context record of the current module being built.'''
         module_stack = self._module_stack
         if not module_stack:
             raise RuntimeError('Module context stack is empty')
-        return module_stack[-1].module
+        return module_stack[-1]
+
+    @property
+    def current_module(self):
+        '''Get the current module being built.'''
+        return self.current_context.module
 
     @property
     def current_body(self):
@@ -189,8 +210,8 @@ class SysBuilder:
         stack = self.get_predicate_stack()
         if not stack:
             # pylint: disable=import-outside-toplevel
-            from ..ir.dtype import Bits
-            return Bits(1)(1)
+            from ..ir.const import bool_imm
+            return bool_imm(True)
         return stack[-1].carry
 
     def reuse_array_read(self, array, index, factory):
@@ -265,11 +286,38 @@ class SysBuilder:
         self.downstreams = []
         self.arrays = []
         self._module_stack = []
+        self._namespaces = []
         self._exposes = {}
+        self._probes = []
+        self._registered = []
         self.line_expression_tracker = {}
         self.naming_manager = NamingManager()
         self._reset_caches()
 
+    @contextlib.contextmanager
+    def with_namespace(self, name: str):
+        '''Prefix the names of the modules and arrays created in this scope with `name`.'''
+        self._namespaces.append(name)
+        try:
+            yield self
+        finally:
+            self._namespaces.pop()
+
+    @property
+    def namespace(self) -> str:
+        '''The dotted path of the active namespaces, or an empty string.'''
+        return '.'.join(self._namespaces)
+
+    def scoped_name(self, name: str) -> str:
+        '''Qualify `name` with the active namespace, as an identifier.'''
+        if not self._namespaces:
+            return name
+        # pylint: disable=import-outside-toplevel
+        from ..utils import namify
+        prefix = namify(f'{self.namespace}.')
+        # Names derived from an already qualified one, e.g. module-local arrays, keep theirs.
+        return name if name.startswith(prefix) else namify(prefix + name)
+
     def expose_on_top(self, node, kind=None):
         '''Expose the given node in the top function with the given kind.'''
         self._exposes[node] = kind
@@ -279,6 +327,153 @@ class SysBuilder:
         '''Get the exposed nodes.'''
         return self._exposes
 
+    def add_probe(self, signal, label: str):
+        '''Capture `signal` in the waveform of the Verilog simulation, as `probe_<label>`, and
+        print it in the cycles where it is computed, as `@probe <cycle> <label> <hex>`.'''
+        # pylint: disable=import-outside-toplevel
+        from ..ir.expr import Expr
+        from ..utils import namify
+        if not isinstance(signal, Expr) or signal.parent is None or not signal.is_valued():
+            raise ValueError(f'Only a value computed in a module can be probed, not {signal!r}')
+        if not label or namify(label) != label:
+            raise ValueError(f'Probe label {label!r} is not an identifier')
+        if any(other == label for _, other in self._probes):
+            raise ValueError(f'Probe label {label} is already taken')
+        self._probes.append((signal, label))
+        return signal
+
+    def probe(self, signal, label: str = None):
+        '''Probe `signal` like `add_probe`, labelled `<module>_<signal>` unless `label` is
+        given, so that a value of a module body is probed where it is computed.'''
+        if label is None and getattr(signal, 'parent', None) is not None:
+            # pylint: disable=import-outside-toplevel
+            from ..utils import namify
+            label = namify(f'{signal.parent.name}_{signal.as_operand()}')
+        return self.add_probe(signal, label)
+
+    @property
+    def probes(self):
+        '''The (signal, label) of the probes, in the order they were added.'''
+        return self._probes
+
+    def register_value(self, value):
+        '''Register `value` in the module computing it, so that the other modules using it
+        read the value and the valid of the previous cycle instead of combinational ones.'''
+        # pylint: disable=import-outside-toplevel
+        from ..ir.expr import Expr
+        if not isinstance(value, Expr) or value.parent is None or not value.is_valued():
+            raise ValueError(
+                f'Only a value computed in a module can be registered, not {value!r}')
+        if not value.registered:
+            value.registered = True
+            self._registered.append(value)
+        return value
+
+    @property
+    def registered_values(self):
+        '''The values registered by `register_value`, in the order they were registered.'''
+        return self._registered
+
+    def create_enabled_reg(self, ty: DType, name: str = None, init: int = None) -> Array:
+        '''Create a single-element register that holds its value unless written by
+        `Array.write_if`.'''
+        # pylint: disable=import-outside-toplevel
+        from ..ir.array import RegArray
+        assert Singleton.peek_builder() is self, 'create_enabled_reg needs an active builder'
+        initializer = None if init is None else [init]
+        return RegArray(ty, 1, initializer=initializer, name=name)
+
+    def insert_before(self, anchor, build):
+        '''Run `build()` with the insert point in front of `anchor`, so that the expressions it
+        creates are put before `anchor`, in order, under the predicate of `anchor`. Returns
+        what `build` returns.'''
+        return self._build_at(anchor, build, 0)
+
+    def insert_after(self, anchor, build):
+        '''Run `build()` with the insert point right after `anchor`, like `insert_before`.'''
+        return self._build_at(anchor, build, 1)
+
+    def _build_at(self, anchor, build, offset: int):
+        '''Run `build()` in the module of `anchor`, and move what it appends to the body to
+        the position `offset` after `anchor`.'''
+        # pylint: disable=import-outside-toplevel
+        from ..ir.const import Const
+        module = getattr(anchor, 'parent', None)
+        if module is None or not isinstance(getattr(module, 'body', None), list):
+            raise ValueError(f'{anchor!r} is not in the body of a module')
+        with self._activated():
+            body = module.body
+            start = len(body)
+            self.enter_context_of(module)
+            cond_stack = self.current_context.cond_stack
+            if not isinstance(anchor.meta_cond, Const):
+                # The expressions built take the predicate of the anchor as theirs
+                cond_stack.append(PredicateFrame(anchor.meta_cond, anchor.meta_cond))
+            try:
+                res = build()
+            finally:
+                cond_stack.clear()
+                self.exit_context_of()
+                built = body[start:]
+                del body[start:]
+                idx = next(i for i, expr in enumerate(body) if expr is anchor) + offset
+                body[idx:idx] = built
+        return res
+
+    @contextlib.contextmanager
+    def _activated(self):
+        '''Make this builder the active one, unless it already is.'''
+        try:
+            active = Singleton.peek_builder() is self
+        except RuntimeError:
+            active = False
+        with contextlib.ExitStack() as stack:
+            if not active:
+                stack.enter_context(self)
+            yield
+
+    def clone_module(self, module, new_name: str, substitutions: dict = None):
+        '''A copy of the built `module` named `new_name`, with fresh ports and a copy of its body
+        where the nodes keyed in `substitutions` are replaced, e.g. an array by another one.'''
+        # pylint: disable=import-outside-toplevel
+        from ..ir.module.clone import clone_module
+        with self._activated():
+            return clone_module(self, module, new_name, substitutions)
+
+    def rename_port(self, module, old: str, new: str):
+        '''Rename the port `old` of `module` to `new`, keeping its FIFO and binds.
+
+        The binds and pops refer to the port itself, so they follow the rename, and the
+        FIFO, named after the module and the port, is renamed with it. Returns the port.'''
+        assert any(m is module for m in self.modules), \
+            f'{module.name} is not a module of {self.name}'
+        port = next((p for p in module.ports if p.name == old), None)
+        if port is None:
+            raise ValueError(f'{module.name} has no port named {old}, its ports are '
+                             f'{[p.name for p in module.ports]}')
+        if not new.isidentifier():
+            raise ValueError(f'Invalid port name: {new!r}')
+        if hasattr(module, new):
+            raise ValueError(f'Cannot rename {module.name}.{old} to {new}: the name is taken')
+        delattr(module, old)
+        setattr(module, new, port)
+        port.name = new
+        return port
+
+    def validate_types(self) -> list:
+        '''Type check every expression, and return all the errors found.'''
+        # pylint: disable=import-outside-toplevel
+        from ..analysis.typecheck import validate_types
+        return validate_types(self)
+
+    def snapshot(self) -> Snapshot:
+        '''Record the current IR state so a speculative rewrite can be undone.'''
+        return take_snapshot(self)
+
+    def rollback(self, snap: Snapshot) -> None:
+        '''Restore the IR state recorded by `snapshot`.'''
+        restore_snapshot(self, snap)
+
     def _reset_caches(self):
         '''Initialise or clear per-builder caches.'''
         self.const_cache = {}
@@ -299,11 +494,90 @@ class SysBuilder:
         Singleton.naming_manager = None
         self._reset_caches()
 
-    def __repr__(self):
-        body = '\n\n'.join(map(repr, self.modules))
-        body = body + '\n\n' + '\n\n'.join(map(repr, self.downstreams))
+    def to_string_filtered(self, module_names=None):
+        '''Print the system with only the modules and downstreams named in `module_names`,
+        or all of them if it is None. Modules are sorted by name, so that the output does not
+        depend on the order they were created in.'''
+        def select(modules):
+            modules = sorted(modules, key=lambda m: m.name)
+            if module_names is None:
+                return modules
+            return [m for m in modules if m.name in module_names]
+        body = '\n\n'.join(map(repr, select(self.modules)))
+        body = body + '\n\n' + '\n\n'.join(map(repr, select(self.downstreams)))
         array = '  ' + '\n  '.join(repr(elem) for elem in self.arrays)
-        return f'system {self.name} {{\n{array}\n\n{body}\n}}'
+        probes = ''.join(f'\n  probe {label} = {signal.parent.name}.{signal.as_operand()}'
+                         for signal, label in self._probes)
+        probes += ''.join(f'\n  registered {value.parent.name}.{value.as_operand()}'
+                          for value in self._registered)
+        return f'system {self.name} {{\n{array}{probes}\n\n{body}\n}}'
+
+    def compute_fingerprint(self, module) -> int:
+        '''A stable 64-bit hash of the IR of `module`, for incremental code generation.
+
+        Besides the dump of the module, which has its ports, attributes and body, it covers the
+        arrays the module accesses and the modules writing them, which decide the array write
+        ports the module is given.'''
+        # pylint: disable=import-outside-toplevel
+        from ..ir.expr import ArrayRead, ArrayWrite
+        accessed = {}
+        for expr in module.body or []:
+            if isinstance(expr, (ArrayRead, ArrayWrite)):
+                accessed.setdefault(id(expr.array), expr.array)
+        text = [repr(module)]
+        for array in accessed.values():
+            writers = [m.name for m in self.modules + self.downstreams
+                       if any(isinstance(e, ArrayWrite) and e.array is array
+                              for e in m.body or [])]
+            text.append(f'{array!r} written by {", ".join(writers)}')
+        digest = hashlib.sha256('\n'.join(text).encode()).hexdigest()
+        return int(digest[:16], 16)
+
+    def stats(self):
+        '''Count the modules, arrays, FIFOs and expressions of this system. See
+        `analysis.ir_stats.IrStats`.'''
+        # pylint: disable=import-outside-toplevel
+        from ..analysis.ir_stats import collect_stats
+        return collect_stats(self)
+
+    def print_stats(self):
+        '''Print the summary of `stats()` to stderr.'''
+        print(self.stats().format(), file=py_sys.stderr)
+
+    def mermaid_call_graph(self) -> str:
+        '''The call graph of this system as a Mermaid flowchart. See `analysis.mermaid`.'''
+        # pylint: disable=import-outside-toplevel
+        from ..analysis.mermaid import mermaid_call_graph
+        return mermaid_call_graph(self)
+
+    def mermaid_dataflow(self, module) -> str:
+        '''The data flow between the expressions of `module` as a Mermaid flowchart.'''
+        # pylint: disable=import-outside-toplevel
+        from ..analysis.mermaid import mermaid_dataflow
+        return mermaid_dataflow(module)
+
+    def set_all_fifo_depths(self, depths: dict) -> list:
+        '''Override the depth of each FIFO named `<module>.<port>` in `depths`, the log2 of its
+        entries, and return the ports set. See `xform.fifo_depth`.'''
+        # pylint: disable=import-outside-toplevel
+        from ..xform.fifo_depth import set_fifo_depths
+        return set_fifo_depths(self, depths)
+
+    def reachable_from(self, root) -> list:
+        '''The modules and downstreams reachable from `root` through the calls, in
+        breadth-first order. See `analysis.subgraph`.'''
+        # pylint: disable=import-outside-toplevel
+        from ..analysis.subgraph import reachable_from
+        return reachable_from(self, root)
+
+    def callee_subgraph(self, root) -> SysBuilder:
+        '''A system with only the modules and arrays `root` reaches, sharing their IR.'''
+        # pylint: disable=import-outside-toplevel
+        from ..analysis.subgraph import callee_subgraph
+        return callee_subgraph(self, root)
+
+    def __repr__(self):
+        return self.to_string_filtered()
 
 class Singleton(type):
     '''The class maintains the global singleton instance of the system builder.'''
@@ -338,3 +612,8 @@ class Singleton(type):
             site_package_dirs = site.getsitepackages()
             user_site_package_dir = site.getusersitepackages()
             mcs.all_dirs_to_exclude = site_package_dirs + [user_site_package_dir]
+
+
+def probe(signal, label: str = None):
+    '''Probe `signal` in the system being built, see `SysBuilder.probe`.'''
+    return Singleton.peek_builder().probe(signal, label)
@@ -57,17 +57,35 @@ class NamingManager:
             # Some Python builtins cannot be annotated - ignore silently
             pass
 
-    def assign_name(self, value: Any, hint: Optional[str] = None) -> str:
+    def assign_name(self, value: Any, hint: Optional[str] = None, scoped: bool = True) -> str:
         """
         Public helper to assign a semantic name to any value-like object.
 
         Useful for non-expression objects (arrays, modules, etc.) that still
-        participate in textual IR dumps.
+        participate in textual IR dumps. Unless `scoped` is False, the name is
+        qualified by the namespace of the active builder.
         """
+        if scoped:
+            hint = self._scoped_hint(value, hint)
         name = self._namer.name_value(value, hint)
         self._apply_name(value, name)
         return name
 
+    def _scoped_hint(self, value: Any, hint: Optional[str]) -> Optional[str]:
+        """Qualify the naming hint of `value` with the active builder namespace."""
+        # pylint: disable=import-outside-toplevel,cyclic-import
+        from . import Singleton
+
+        try:
+            builder = Singleton.peek_builder()
+        except RuntimeError:
+            return hint
+        if not builder.namespace:
+            return hint
+        if hint is None:
+            hint = self._namer.get_prefix_for_type(value)
+        return builder.scoped_name(hint)
+
     def get_module_name(self, base_name: str) -> str:
         """
         Get a unique module name based on the given base name.
@@ -0,0 +1,103 @@
+'''Transactional snapshots of a system builder for speculative rewrites.'''
+
+from __future__ import annotations
+
+import copy
+import typing
+from dataclasses import dataclass, field
+
+if typing.TYPE_CHECKING:
+    from . import SysBuilder
+
+
+# Per-class attributes that hold mutable IR state. Only these containers are copied,
+# the IR nodes themselves are shared between the live system and the snapshot.
+_EXPR_FIELDS = ('_operands', 'users', 'parent', 'name', 'registered')
+_MODULE_FIELDS = ('body', '_externals', '_users', '_attrs', 'name')
+_PORT_FIELDS = ('_users',)
+_ARRAY_FIELDS = ('_users', '_write_ports', '_name')
+
+
+def _copy_field(value):
+    '''Copy one level deep; dicts of lists (e.g. externals) get their lists copied too.'''
+    if isinstance(value, dict):
+        return {k: list(v) if isinstance(v, list) else v for k, v in value.items()}
+    if isinstance(value, list):
+        return list(value)
+    return value
+
+
+def _capture(node, fields) -> dict:
+    return {f: _copy_field(getattr(node, f)) for f in fields if hasattr(node, f)}
+
+
+@dataclass
+class Snapshot:
+    '''The saved state of a `SysBuilder`, restored by `SysBuilder.rollback`.'''
+
+    owner: SysBuilder
+    system: dict = field(default_factory=dict)
+    module_stack: list = field(default_factory=list)
+    nodes: list = field(default_factory=list)  # [(node, {field: saved value})]
+
+
+def take_snapshot(sys: SysBuilder) -> Snapshot:
+    '''Record every container of the system that IR construction or rewrites mutate.'''
+    # pylint: disable=import-outside-toplevel
+    from ..ir.expr import Expr
+
+    snap = Snapshot(owner=sys)
+    snap.system = {
+        'modules': list(sys.modules),
+        'downstreams': list(sys.downstreams),
+        'arrays': list(sys.arrays),
+        '_exposes': dict(sys._exposes),  # pylint: disable=protected-access
+        '_probes': list(sys._probes),  # pylint: disable=protected-access
+        '_registered': list(sys._registered),  # pylint: disable=protected-access
+        'const_cache': dict(sys.const_cache),
+        'naming_manager': copy.deepcopy(sys.naming_manager),
+    }
+    # Predicate frames are immutable once pushed, so copying the stacks suffices.
+    snap.module_stack = [(ctx, list(ctx.cond_stack)) for ctx in sys._module_stack]  # pylint: disable=protected-access
+
+    for module in sys.modules + sys.downstreams:
+        snap.nodes.append((module, _capture(module, _MODULE_FIELDS)))
+        for port in getattr(module, 'ports', []):
+            snap.nodes.append((port, _capture(port, _PORT_FIELDS)))
+        for expr in module.body or []:
+            if isinstance(expr, Expr):
+                snap.nodes.append((expr, _capture(expr, _EXPR_FIELDS)))
+    for array in sys.arrays:
+        snap.nodes.append((array, _capture(array, _ARRAY_FIELDS)))
+    return snap
+
+
+def restore_snapshot(sys: SysBuilder, snap: Snapshot) -> None:
+    '''Restore the state recorded by `take_snapshot` in place.'''
+    # pylint: disable=import-outside-toplevel
+    from . import Singleton
+
+    if snap.owner is not sys:
+        raise ValueError(f'Snapshot was taken from system {snap.owner.name}, not {sys.name}')
+
+    sys.modules[:] = snap.system['modules']
+    sys.downstreams[:] = snap.system['downstreams']
+    sys.arrays[:] = snap.system['arrays']
+    sys._exposes = dict(snap.system['_exposes'])  # pylint: disable=protected-access
+    sys._probes = list(snap.system['_probes'])  # pylint: disable=protected-access
+    sys._registered = list(snap.system['_registered'])  # pylint: disable=protected-access
+    sys.const_cache = dict(snap.system['const_cache'])
+    sys.naming_manager = copy.deepcopy(snap.system['naming_manager'])
+    if Singleton._builder is sys:  # pylint: disable=protected-access
+        Singleton.naming_manager = sys.naming_manager
+
+    stack = []
+    for ctx, cond_stack in snap.module_stack:
+        ctx.cond_stack[:] = cond_stack
+        stack.append(ctx)
+    sys._module_stack[:] = stack  # pylint: disable=protected-access
+
+    # Copy again on the way out, so one snapshot can be rolled back to multiple times.
+    for node, fields in snap.nodes:
+        for name, value in fields.items():
+            setattr(node, name, _copy_field(value))
@@ -24,7 +24,7 @@ class TypeOrientedNamer:
         from ..ir.expr.array import ArrayRead, ArrayWrite
         from ..ir.expr.call import FIFOPush, Bind, AsyncCall
         from ..ir.expr.expr import FIFOPop, Cast, Concat, Select, Select1Hot
-        from ..ir.expr.intrinsic import PureIntrinsic
+        from ..ir.expr.intrinsic import PureIntrinsic, Intrinsic
         from ..ir.array import Slice
 
         # Unified naming strategies dictionary
@@ -32,6 +32,7 @@ class TypeOrientedNamer:
             BinaryOp: self._binary_op_strategy,
             UnaryOp: self._unary_op_strategy,
             PureIntrinsic: self._pure_intrinsic_strategy,
+            Intrinsic: self._intrinsic_strategy,
             ArrayRead: lambda n: self._combine_parts(self._entity_name(n.array), 'rd') or 'rd',
             ArrayWrite: lambda n: self._combine_parts(self._entity_name(n.array), 'wt') or 'wt',
             FIFOPop: lambda n: self._entity_name(n.fifo) or 'pop',
@@ -62,6 +63,7 @@ class TypeOrientedNamer:
             '&': 'and_', '|': 'or_', '^': 'xor_',
             '<': 'lt', '>': 'gt', '<=': 'le', '>=': 'ge', '==': 'eq', '!=': 'neq',
             '<<': 'shl', '>>': 'shr',
+            '+|': 'add_sat', '-|': 'sub_sat',
             '!': 'not_',
         }
 
@@ -170,6 +172,15 @@ class TypeOrientedNamer:
 
         return 'intrinsic'
 
+    def _intrinsic_strategy(self, node: Any) -> str:
+        """Strategy for valued intrinsics, naming an else predicate after its condition."""
+        # pylint: disable=import-outside-toplevel
+        from ..ir.expr.intrinsic import Intrinsic
+
+        if node.opcode == Intrinsic.PUSH_ELSE:
+            return self._combine_parts(self._describe_operand(node.args[0]), 'else') or 'else'
+        return 'val'
+
     def get_prefix_for_type(self, node: Any) -> str:
         """Get the naming prefix for a given node type using strategy pattern."""
         # Check ModuleBase via MRO
@@ -0,0 +1,6 @@
+"""FIRRTL backend for Assassyn."""
+
+from .elaborate import elaborate
+from .emit import generate_firrtl
+
+__all__ = ['elaborate', 'generate_firrtl']
@@ -0,0 +1,26 @@
+"""Elaborate Assassyn IR to FIRRTL."""
+
+import os
+from pathlib import Path
+
+from ...builder import SysBuilder
+from ...utils import create_dir
+from .emit import generate_firrtl
+
+
+def elaborate(sys: SysBuilder, **kwargs) -> str:
+    """Write the FIRRTL circuit of the system to `<path>/firrtl/<system>.fir`.
+
+    Args:
+        sys: The system to elaborate
+        **kwargs: Configuration options including:
+            - path: The directory of the generated files
+
+    Returns:
+        Path to the generated FIRRTL file
+    """
+    path = Path(kwargs.get('path', os.getcwd())) / 'firrtl'
+    create_dir(path)
+    fir = path / f'{sys.name}.fir'
+    fir.write_text(generate_firrtl(sys), encoding='utf-8')
+    return str(fir)
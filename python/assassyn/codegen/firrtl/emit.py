@@ -0,0 +1,412 @@
+"""Emit a system as a FIRRTL circuit."""
+
+from __future__ import annotations
+
+from typing import Dict, List, Optional, Tuple
+
+from ...analysis import check_types
+from ...ir.array import Array, Slice
+from ...ir.const import Const
+from ...ir.dtype import DType, Int, UInt
+from ...ir.expr import (
+    ArrayRead,
+    ArrayWrite,
+    AsyncCall,
+    BinaryOp,
+    Bind,
+    Cast,
+    Concat,
+    Expr,
+    FIFOPop,
+    FIFOPush,
+    Intrinsic,
+    Log,
+    PureIntrinsic,
+    Select,
+    UnaryOp,
+)
+from ...ir.module import Downstream, Module, Port
+from ...ir.visitor import Visitor
+from ...utils import namify, unwrap_operand
+from ..verilog.utils import addr_width
+
+FIRRTL_VERSION = '3.3.0'
+
+_BINARY_OPS = {
+    BinaryOp.ADD: 'add',
+    BinaryOp.SUB: 'sub',
+    BinaryOp.MUL: 'mul',
+    BinaryOp.DIV: 'div',
+    BinaryOp.MOD: 'rem',
+    BinaryOp.BITWISE_AND: 'and',
+    BinaryOp.BITWISE_OR: 'or',
+    BinaryOp.BITWISE_XOR: 'xor',
+    BinaryOp.ILT: 'lt',
+    BinaryOp.IGT: 'gt',
+    BinaryOp.ILE: 'leq',
+    BinaryOp.IGE: 'geq',
+    BinaryOp.EQ: 'eq',
+    BinaryOp.NEQ: 'neq',
+}
+
+# The FIRRTL result of these operations is exactly the type of the Assassyn expression. The
+# bitwise ones are fitted instead, as FIRRTL types them from their operands, always as a UInt
+_EXACT_OPS = (BinaryOp.ILT, BinaryOp.IGT, BinaryOp.ILE, BinaryOp.IGE, BinaryOp.EQ, BinaryOp.NEQ)
+
+_TRUE = 'UInt<1>(1)'
+
+
+def dump_type(dtype: DType) -> str:
+    """The FIRRTL ground type of `dtype`: signed integers are SInt, everything else UInt."""
+    kind = 'SInt' if isinstance(dtype, Int) else 'UInt'
+    return f'{kind}<{dtype.bits}>'
+
+
+def fit(code: str, dtype: DType) -> str:
+    """Convert `code`, of any ground type, to `dtype`, truncating or extending it.
+
+    `pad` sign-extends signed values, so a narrower signed value keeps its value."""
+    bits = dtype.bits
+    res = f'bits(pad({code}, {bits}), {bits - 1}, 0)'
+    return f'asSInt({res})' if isinstance(dtype, Int) else res
+
+
+def _and(terms: List[str]) -> str:
+    terms = [i for i in terms if i != _TRUE]
+    if not terms:
+        return _TRUE
+    res = terms[0]
+    for term in terms[1:]:
+        res = f'and({res}, {term})'
+    return res
+
+
+class FIRRTLDumper(Visitor):
+    """Dump each module as a FIRRTL module with the interface of its Verilog counterpart.
+
+    The body of a module is visited in order: valued expressions become nodes, and the side
+    effects (pops, pushes, calls and array writes) are collected with their predicates, to
+    drive the output ports once the body is done."""
+
+    # pylint: disable=too-many-instance-attributes
+
+    body: List[str]  # The statements of the module being dumped
+    ports: Dict[str, Tuple[str, str]]  # Port name -> (direction, type), in declaration order
+    waits: List[str]  # The wait_until conditions visited so far
+    pops: Dict[Port, List[str]]  # FIFO port -> predicates of its pops
+    pushes: Dict[Port, List[Tuple[str, str]]]  # Callee port -> (predicate, data) of its pushes
+    calls: Dict[Module, List[str]]  # Callee -> predicates of its async calls
+    writes: Dict[Array, List[Tuple[str, str, str]]]  # Array -> (predicate, index, data)
+    reads: Dict[Array, None]  # The arrays read by the module, in order
+
+    def __init__(self, sys):
+        super().__init__()
+        self.sys = sys
+        self.modules: List[Tuple[Module, Dict[str, Tuple[str, str]]]] = []
+        self.code: List[str] = []
+
+    def _indent(self, level: int, line: str):
+        self.body.append('  ' * level + line)
+
+    def _port(self, name: str, direction: str, ty: str):
+        self.ports.setdefault(name, (direction, ty))
+
+    def _node(self, expr: Expr, code: str):
+        self._indent(2, f'node {self.name_of(expr)} = {code}')
+
+    def name_of(self, expr: Expr) -> str:
+        """The node name of `expr`. The underscore keeps it apart from the IO ports."""
+        return f'_{namify(expr.as_operand())}'
+
+    def ref(self, value) -> str:
+        """Refer to a value of the module being dumped."""
+        value = unwrap_operand(value)
+        if isinstance(value, Const):
+            if isinstance(value.dtype, Int):
+                return f'SInt<{value.dtype.bits}>({value.value})'
+            return f'UInt<{value.dtype.bits}>({value.value})'
+        if isinstance(value, Expr):
+            if value.parent is not self.current_module:
+                raise NotImplementedError(
+                    f'FIRRTL backend: {self.current_module.name} uses {value.as_operand()} '
+                    f'of {value.parent.name}, cross-module values are not supported')
+            return self.name_of(value)
+        raise NotImplementedError(f'FIRRTL backend: cannot refer to {value!r}')
+
+    def predicate(self, expr: Expr) -> str:
+        """The condition under which a side effect of `expr` takes place."""
+        meta = unwrap_operand(expr.meta_cond)
+        terms = ['_executed'] + list(self.waits)
+        if meta is not None and not (isinstance(meta, Const) and meta.value):
+            terms.append(self.ref(meta))
+        return _and(terms)
+
+    def visit_module(self, node: Module):
+        if isinstance(node, Downstream):
+            raise NotImplementedError(
+                f'FIRRTL backend: downstream module {node.name} is not supported yet')
+        self.body = []
+        self.ports = {}
+        self.waits = []
+        self.pops = {p: [] for p in node.ports}
+        self.pushes = {}
+        self.calls = {}
+        self.writes = {}
+        self.reads = {}
+        self._port('clock', 'input', 'Clock')
+        self._port('reset', 'input', 'UInt<1>')
+        self._port('trigger_counter_pop_valid', 'input', 'UInt<1>')
+        self._port('executed', 'output', 'UInt<1>')
+        for port in node.ports:
+            name = namify(port.name)
+            self._port(name, 'input', dump_type(port.dtype))
+            self._port(f'{name}_valid', 'input', 'UInt<1>')
+            self._port(f'{name}_pop_ready', 'output', 'UInt<1>')
+        # The waits gate everything after them, so executed is declared before the body
+        # and driven once all the waits are known
+        self._indent(2, 'wire _executed : UInt<1>')
+        super().visit_module(node)
+        self._indent(2, f"connect _executed, {_and(['trigger_counter_pop_valid'] + self.waits)}")
+        self._indent(2, 'connect executed, _executed')
+        self._drive_outputs()
+        self._emit_module(node)
+
+    # pylint: disable=too-many-branches,too-many-return-statements
+    def visit_expr(self, node: Expr):
+        if isinstance(node, (Log, Bind)):
+            # Logs are not synthesizable, and binds are resolved by their pushes and calls
+            return
+        if isinstance(node, BinaryOp):
+            self._node(node, self._binary(node))
+        elif isinstance(node, UnaryOp):
+            x = self.ref(node.x)
+            if node.opcode == UnaryOp.FLIP:
+                self._node(node, f'not({x})')
+            else:
+                self._node(node, fit(f'neg({x})', node.dtype))
+        elif isinstance(node, Select):
+            cond = self.ref(node.cond)
+            true_value, false_value = self.ref(node.true_value), self.ref(node.false_value)
+            self._node(node, f'mux({cond}, {true_value}, {false_value})')
+        elif isinstance(node, Slice):
+            low = unwrap_operand(node.l).value
+            high = unwrap_operand(node.r).value
+            self._node(node, f'bits({self.ref(node.x)}, {high}, {low})')
+        elif isinstance(node, Concat):
+            self._node(node, f'cat({self.ref(node.msb)}, {self.ref(node.lsb)})')
+        elif isinstance(node, Cast):
+            self._node(node, self._cast(node))
+        elif isinstance(node, ArrayRead):
+            array = unwrap_operand(node.array)
+            name = namify(array.name)
+            self.reads[array] = None
+            self._port(f'{name}_q', 'input', f'{dump_type(array.scalar_ty)}[{array.size}]')
+            self._node(node, f'{name}_q[{self._uint(node.idx)}]')
+        elif isinstance(node, ArrayWrite):
+            array = unwrap_operand(node.array)
+            index = fit(self._uint(node.idx), UInt(addr_width(array.size)))
+            data = self._converted(node.val, array.scalar_ty)
+            self.writes.setdefault(array, []).append((self.predicate(node), index, data))
+        elif isinstance(node, FIFOPop):
+            port = unwrap_operand(node.fifo)
+            self.pops[port].append(self.predicate(node))
+            self._node(node, namify(port.name))
+        elif isinstance(node, FIFOPush):
+            port = unwrap_operand(node.fifo)
+            data = self._converted(node.val, port.dtype)
+            self.pushes.setdefault(port, []).append((self.predicate(node), data))
+        elif isinstance(node, AsyncCall):
+            callee = unwrap_operand(node.bind).callee
+            self.calls.setdefault(callee, []).append(self.predicate(node))
+        elif isinstance(node, PureIntrinsic):
+            self._pure_intrinsic(node)
+        elif isinstance(node, Intrinsic):
+            self._intrinsic(node)
+        else:
+            raise NotImplementedError(f'FIRRTL backend: {node!r} is not supported yet')
+
+    def _binary(self, node: BinaryOp) -> str:
+        lhs, rhs = unwrap_operand(node.lhs), unwrap_operand(node.rhs)
+        lhs_code = self.ref(lhs)
+        if node.opcode in (BinaryOp.SHL, BinaryOp.SHR):
+            return fit(self._shift(node, lhs_code, rhs), node.dtype)
+        if node.opcode not in _BINARY_OPS:
+            raise NotImplementedError(f'FIRRTL backend: {node!r} is not supported yet')
+        rhs_code = self.ref(rhs)
+        # FIRRTL operands share a signedness: the rhs follows the lhs
+        lhs_signed = isinstance(lhs.dtype, Int)
+        if lhs_signed != isinstance(rhs.dtype, Int):
+            rhs_code = f"{'asSInt' if lhs_signed else 'asUInt'}({rhs_code})"
+        code = f'{_BINARY_OPS[node.opcode]}({lhs_code}, {rhs_code})'
+        return code if node.opcode in _EXACT_OPS else fit(code, node.dtype)
+
+    def _shift(self, node: BinaryOp, lhs_code: str, rhs) -> str:
+        if isinstance(rhs, Const):
+            op = 'shl' if node.opcode == BinaryOp.SHL else 'shr'
+            return f'{op}({lhs_code}, {rhs.value})'
+        amount = self._uint(rhs)
+        if node.opcode == BinaryOp.SHR:
+            return f'dshr({lhs_code}, {amount})'
+        # dshl widens by 2^(amount bits) - 1: shift by the bits that can keep a one, and
+        # clear the result for larger amounts, so that the width stays bounded
+        bits = node.lhs.dtype.bits
+        amount_bits = max((bits - 1).bit_length(), 1)
+        if rhs.dtype.bits <= amount_bits:
+            return f'dshl({lhs_code}, {amount})'
+        narrow = f'bits({amount}, {amount_bits - 1}, 0)'
+        return f'mux(lt({amount}, UInt({bits})), dshl({lhs_code}, {narrow}), UInt<1>(0))'
+
+    def _cast(self, node: Cast) -> str:
+        x = self.ref(node.x)
+        if node.opcode == Cast.ZEXT:
+            return fit(f'asUInt({x})', node.dtype)
+        if node.opcode == Cast.SEXT:
+            return fit(f'asSInt({x})', node.dtype)
+        if node.dtype.bits == node.x.dtype.bits:
+            return f"{'asSInt' if isinstance(node.dtype, Int) else 'asUInt'}({x})"
+        return fit(f'asUInt({x})', node.dtype)
+
+    def _uint(self, value) -> str:
+        code = self.ref(value)
+        return f'asUInt({code})' if isinstance(unwrap_operand(value).dtype, Int) else code
+
+    def _converted(self, value, dtype: DType) -> str:
+        value = unwrap_operand(value)
+        code = self.ref(value)
+        if dump_type(value.dtype) == dump_type(dtype):
+            return code
+        return fit(code, dtype)
+
+    def _pure_intrinsic(self, node: PureIntrinsic):
+        port = unwrap_operand(node.args[0]) if node.args else None
+        if node.opcode == PureIntrinsic.FIFO_VALID:
+            self._node(node, f'{namify(port.name)}_valid')
+        elif node.opcode == PureIntrinsic.FIFO_PEEK:
+            self._node(node, namify(port.name))
+        else:
+            raise NotImplementedError(f'FIRRTL backend: {node!r} is not supported yet')
+
+    def _intrinsic(self, node: Intrinsic):
+        if node.opcode in (Intrinsic.PUSH_CONDITION, Intrinsic.POP_CONDITION):
+            # The predicates are carried by the meta conditions of the side effects
+            return
+        if node.opcode == Intrinsic.PUSH_ELSE:
+            self._node(node, f'not({self.ref(node.args[0])})')
+            return
+        if node.opcode == Intrinsic.WAIT_UNTIL:
+            cond = self.ref(node.args[0])
+            meta = unwrap_operand(node.meta_cond)
+            if meta is not None and not (isinstance(meta, Const) and meta.value):
+                # Inside a condition block, the module only waits when the block is taken
+                cond = f'or(not({self.ref(meta)}), {cond})'
+            self.waits.append(cond)
+            return
+        raise NotImplementedError(f'FIRRTL backend: {node!r} is not supported yet')
+
+    def _drive(self, name: str, default: str, cases: List[Tuple[str, str]]):
+        """Connect `name` to the value of the last case whose predicate holds."""
+        self._indent(2, f'connect {name}, {default}')
+        for pred, value in cases:
+            self._indent(2, f'when {pred} :')
+            self._indent(3, f'connect {name}, {value}')
+
+    def _drive_outputs(self):
+        for port, preds in self.pops.items():
+            self._drive(f'{namify(port.name)}_pop_ready', 'UInt<1>(0)',
+                        [(p, _TRUE) for p in preds])
+        for port, cases in self.pushes.items():
+            prefix = f'{namify(port.module.name)}_{namify(port.name)}'
+            self._port(f'{prefix}_push_valid', 'output', 'UInt<1>')
+            self._port(f'{prefix}_push_data', 'output', dump_type(port.dtype))
+            self._drive(f'{prefix}_push_valid', 'UInt<1>(0)', [(p, _TRUE) for p, _ in cases])
+            self._drive(f'{prefix}_push_data', f'{dump_type(port.dtype)}(0)', cases)
+        for callee, preds in self.calls.items():
+            name = f'{namify(callee.name)}_trigger'
+            self._port(name, 'output', 'UInt<1>')
+            self._drive(name, 'UInt<1>(0)', [(p, _TRUE) for p in preds])
+        for array, cases in self.writes.items():
+            name = namify(array.name)
+            idx_type = dump_type(UInt(addr_width(array.size)))
+            data_type = dump_type(array.scalar_ty)
+            self._port(f'{name}_w', 'output', 'UInt<1>')
+            self._port(f'{name}_widx', 'output', idx_type)
+            self._port(f'{name}_wdata', 'output', data_type)
+            self._drive(f'{name}_w', 'UInt<1>(0)', [(p, _TRUE) for p, _, _ in cases])
+            self._drive(f'{name}_widx', f'{idx_type}(0)', [(p, i) for p, i, _ in cases])
+            self._drive(f'{name}_wdata', f'{data_type}(0)', [(p, d) for p, _, d in cases])
+
+    def _emit_module(self, node: Module):
+        self.code.append(f'  module {namify(node.name)} :')
+        for name, (direction, ty) in self.ports.items():
+            self.code.append(f'    {direction} {name} : {ty}')
+        self.code.append('')
+        self.code.extend(self.body)
+        self.code.append('')
+        self.modules.append((node, dict(self.ports)))
+
+    def emit_top(self):
+        """Instantiate the modules and hold the arrays in registers.
+
+        The FIFOs and the trigger counters are not emitted yet: their handshakes are lifted
+        to the ports of the top module, named after the instance, for the harness to drive."""
+        ports: Dict[str, Tuple[str, str]] = {'clock': ('input', 'Clock'),
+                                             'reset': ('input', 'UInt<1>')}
+        body: List[str] = []
+        arrays = {}
+        for module, module_ports in self.modules:
+            inst = namify(module.name)
+            body.append(f'    inst {inst} of {inst}')
+            for name, (direction, ty) in module_ports.items():
+                array = self._array_of(name)
+                if name in ('clock', 'reset'):
+                    body.append(f'    connect {inst}.{name}, {name}')
+                elif array is not None:
+                    arrays.setdefault(array, [])
+                    if name.endswith('_w'):
+                        arrays[array].append(inst)
+                    if name.endswith('_q'):
+                        body.append(f'    connect {inst}.{name}, {namify(array.name)}')
+                elif direction == 'input':
+                    ports[f'{inst}_{name}'] = (direction, ty)
+                    body.append(f'    connect {inst}.{name}, {inst}_{name}')
+                else:
+                    ports[f'{inst}_{name}'] = (direction, ty)
+                    body.append(f'    connect {inst}_{name}, {inst}.{name}')
+        regs = []
+        for array, writers in arrays.items():
+            name = namify(array.name)
+            ty = f'{dump_type(array.scalar_ty)}[{array.size}]'
+            init = array.initializer or [0] * array.size
+            regs.append(f'    wire {name}_init : {ty}')
+            for i, value in enumerate(init):
+                regs.append(f'    connect {name}_init[{i}], {dump_type(array.scalar_ty)}({value})')
+            regs.append(f'    regreset {name} : {ty}, clock, reset, {name}_init')
+            for inst in writers:
+                body.append(f'    when {inst}.{name}_w :')
+                body.append(f'      connect {name}[{inst}.{name}_widx], {inst}.{name}_wdata')
+        self.code.append('  public module Top :')
+        for name, (direction, ty) in ports.items():
+            self.code.append(f'    {direction} {name} : {ty}')
+        self.code.append('')
+        self.code.extend(regs)
+        self.code.extend(body)
+
+    def _array_of(self, port_name: str) -> Optional[Array]:
+        for array in self.sys.arrays:
+            name = namify(array.name)
+            if port_name in (f'{name}_q', f'{name}_w', f'{name}_widx', f'{name}_wdata'):
+                return array
+        return None
+
+
+def generate_firrtl(sys) -> str:
+    """Return the FIRRTL circuit of `sys`, whose main module is `Top`."""
+    check_types(sys)
+    dumper = FIRRTLDumper(sys)
+    for module in sys.modules + sys.downstreams:
+        dumper.current_module = module
+        dumper.visit_module(module)
+    dumper.current_module = None
+    dumper.emit_top()
+    header = [f'FIRRTL version {FIRRTL_VERSION}', 'circuit Top :']
+    return '\n'.join(header + dumper.code) + '\n'
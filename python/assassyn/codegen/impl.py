@@ -2,7 +2,9 @@
 
 from . import simulator
 from . import verilog
+from . import firrtl
 from ..builder import SysBuilder
+from ..xform import strip_logs
 
 def codegen(sys: SysBuilder, **kwargs):
     '''
@@ -17,6 +19,11 @@ def codegen(sys: SysBuilder, **kwargs):
         random: Whether to randomize module execution order
         resource_base: Path to resource files
         fifo_depth: Default FIFO depth
+        synthesis: Whether the Verilog is generated without the logs
+        firrtl: Whether to also emit a FIRRTL circuit
+        trace: Whether both backends trace the array writes and FIFO pushes
+        build: Whether the simulator is compiled right after it is generated
+        force_regenerate: Whether the simulator is regenerated even if its IR hash matches
     '''
     # Create a CodeGen object but exclude simulator generation flag
     # We'll handle simulator generation separately using the Python implementation
@@ -25,11 +32,24 @@ def codegen(sys: SysBuilder, **kwargs):
     # If simulator flag is set, use the Python implementation to generate it
     if kwargs['simulator']:
         print('Start simulator in-python elaboration')
-        simulator_manifest = simulator.elaborate(sys, **kwargs)
+        simulator_manifest, _ = simulator.elaborate(sys, **kwargs)
 
     verilog_path = None
     if kwargs.get('verilog'):
         print('Start verilog elaboration')
-        verilog_path = verilog.elaborate(sys, **kwargs)
+        if kwargs.get('synthesis', False):
+            # Only the Verilog loses the logs: the system is rolled back afterwards
+            snap = sys.snapshot()
+            strip_logs(sys)
+            try:
+                verilog_path = verilog.elaborate(sys, **kwargs)
+            finally:
+                sys.rollback(snap)
+        else:
+            verilog_path = verilog.elaborate(sys, **kwargs)
+
+    if kwargs.get('firrtl'):
+        print('Start FIRRTL elaboration')
+        print(f'FIRRTL: {firrtl.elaborate(sys, **kwargs)}')
 
     return simulator_manifest, verilog_path
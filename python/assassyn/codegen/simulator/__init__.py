@@ -3,3 +3,4 @@
 from .elaborate import elaborate
 from .utils import camelize, dtype_to_rust_type
 from .modules import ElaborateModule
+from .run import SimOutput, SimulatorBuildError, run_crate
@@ -22,7 +22,8 @@ from ....ir.expr import (
 from ....ir.expr.intrinsic import PureIntrinsic, Intrinsic
 from ....ir.expr.call import Bind
 from ....ir.array import Slice
-from ..utils import dtype_to_rust_type
+from ....utils import namify
+from ..utils import dtype_to_rust_type, fit_to_width
 from ..node_dumper import dump_rval_ref
 from .array import codegen_array_read, codegen_array_write
 from .arith import codegen_binary_op, codegen_unary_op
@@ -37,10 +38,15 @@ def codegen_log(node: Log, module_ctx):
     result.append("println!(")
     result.append(f"{dump_rval_ref(module_ctx, node.operands[0])}, ")
 
-    for elem in node.operands[1:]:
+    for idx, elem in enumerate(node.operands[1:]):
         dump = dump_rval_ref(module_ctx, elem)
         dtype = elem.dtype
-        if dtype.bits == 1:
+        if idx in node.fixed:
+            # The payload scaled back, exact within the 53 bits of an f64 mantissa
+            frac = node.fixed[idx].frac_bits
+            value = f"sim_runtime::num_traits::ToPrimitive::to_f64(&{dump}).unwrap()"
+            dump = f'format!("{{:.{frac}}}", {value} / {1 << frac}f64)'
+        elif dtype.bits == 1:
             dump = f"if {dump} {{ 1 }} else {{ 0 }}"
         result.append(f"{dump}, ")
 
@@ -71,16 +77,32 @@ def codegen_slice(node: Slice, module_ctx):
             }}"""
 
 
+def _bit_pattern(value, module_ctx):
+    """Generate the two's-complement bits of `value` as a `BigUint` of its width.
+
+    Signed operands are not cast to `BigUint` directly, which wraps negative values to whole
+    64-bit words; they are reinterpreted as unsigned of their width, as `{a, b}` does in Verilog."""
+    ref = dump_rval_ref(module_ctx, value)
+    dtype = value.dtype
+    if not dtype.is_signed():
+        return f"ValueCastTo::<BigUint>::cast(&{ref})"
+    bits = dtype.bits
+    if bits <= 64:
+        mask = (1 << bits) - 1
+        return f"ValueCastTo::<BigUint>::cast(&(ValueCastTo::<u64>::cast(&{ref}) & {mask}u64))"
+    return f"UnsignedValueCastTo::<BigUint>::unsigned_cast(&{ref}, {bits})"
+
+
 def codegen_concat(node: Concat, module_ctx):
     """Generate code for concatenation operations."""
     dtype = node.dtype
-    a = dump_rval_ref(module_ctx, node.msb)
-    b = dump_rval_ref(module_ctx, node.lsb)
+    a = _bit_pattern(node.msb, module_ctx)
+    b = _bit_pattern(node.lsb, module_ctx)
     b_bits = node.lsb.dtype.bits
 
     return f"""{{
-                let a = ValueCastTo::<BigUint>::cast(&{a});
-                let b = ValueCastTo::<BigUint>::cast(&{b});
+                let a = {a};
+                let b = {b};
                 let c = (a << {b_bits}) | b;
                 ValueCastTo::<{dtype_to_rust_type(dtype)}>::cast(&c)
             }}"""
@@ -117,11 +139,55 @@ def codegen_cast(node: Cast, module_ctx):
     dest_dtype = node.dtype
     a = dump_rval_ref(module_ctx, node.x)
 
+    src_dtype = node.x.dtype
+    dest_ty = dtype_to_rust_type(dest_dtype)
+    # A wide bitcast cannot mask the `BigUint` cast of a negative value to the width
+    wide_bitcast = node.opcode == Cast.BITCAST and src_dtype.bits > 64 \
+        and not dest_dtype.is_signed()
+    if src_dtype.is_signed() and (node.opcode == Cast.ZEXT or wide_bitcast):
+        # A negative source is sign-extended in its Rust integer, so take its raw bits
+        return f"ValueCastTo::<{dest_ty}>::cast(&{_bit_pattern(node.x, module_ctx)})"
+    if node.opcode == Cast.SEXT and not src_dtype.is_signed() and src_dtype.bits <= 64 \
+            and dest_dtype.bits <= 64:
+        # Replicate the top bit of the source, which an unsigned Rust integer does not do
+        shift = 64 - src_dtype.bits
+        return fit_to_width(
+            f"{{ let v = ValueCastTo::<i64>::cast(&ValueCastTo::<u64>::cast(&{a})); "
+            f"ValueCastTo::<{dest_ty}>::cast(&((v << {shift}) >> {shift})) }}", dest_dtype)
+    if node.opcode == Cast.SEXT and not src_dtype.is_signed():
+        v = (f"SignedValueCastTo::<BigInt>::signed_cast("
+             f"&ValueCastTo::<BigUint>::cast(&{a}), {src_dtype.bits})")
+        return fit_to_width(f"ValueCastTo::<{dest_ty}>::cast(&{v})", dest_dtype)
     if node.opcode in [Cast.ZEXT, Cast.BITCAST, Cast.SEXT]:
-        return f"ValueCastTo::<{dtype_to_rust_type(dest_dtype)}>::cast(&{a})"
+        return fit_to_width(f"ValueCastTo::<{dest_ty}>::cast(&{a})", dest_dtype)
 
     return None
 
+
+def codegen_trace(node, module_ctx):
+    """Generate the `@trace` line of an array write or a FIFO push, or None for other nodes.
+
+    The value is printed as the hexadecimal bits of its width, as the Verilog testbench does,
+    so that the traces of both backends compare as text."""
+    if isinstance(node, ArrayWrite):
+        idx = dump_rval_ref(module_ctx, node.idx)
+        signal = f'{namify(node.array.name)}[{{}}]'
+        args = [f'ValueCastTo::<u64>::cast(&{idx})']
+    elif isinstance(node, FIFOPush):
+        signal = f'{namify(node.fifo.module.name)}.{namify(node.fifo.name)}'
+        args = []
+    else:
+        return None
+    args.append(_bit_pattern(node.val, module_ctx))
+    return f'println!("@trace {{}} {signal} {{:x}}", sim.stamp / 100, {", ".join(args)})'
+
+
+def codegen_probe(node, label, module_ctx):
+    """Generate the `@probe` line of a probed value, printed like a `@trace` one."""
+    return (f'println!("@probe {{}} {label} {{:x}}", sim.stamp / 100, '
+            f'{_bit_pattern(node, module_ctx)})')
+
+
 # Dispatch table mapping expression types to their codegen functions
 _EXPR_CODEGEN_DISPATCH = {
     BinaryOp: codegen_binary_op,
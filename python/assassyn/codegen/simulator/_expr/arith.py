@@ -6,7 +6,7 @@ This module contains helper functions to generate simulator code for arithmetic
 # pylint: disable=unused-argument
 
 from ....ir.expr import BinaryOp, UnaryOp
-from ..utils import dtype_to_rust_type
+from ..utils import dtype_to_rust_type, fit_to_width
 from ..node_dumper import dump_rval_ref
 
 
@@ -44,24 +44,56 @@ def codegen_binary_op(node: BinaryOp, module_ctx):
     else:
         rhs = dump_rval_ref(module_ctx, node.rhs)
 
+    if node.is_saturating():
+        return _codegen_saturating(node, lhs, rhs, rust_ty)
+
     # Special handling for shift operations with signed values
     if node.opcode == BinaryOp.SHR and node.lhs.dtype.is_signed():
         # For signed right shift, cast to signed type first
         if node.lhs.dtype.bits <= 64:
-            lhs = f"ValueCastTo::<i{node.lhs.dtype.bits}>::cast(&{lhs})"
-            rhs = f"ValueCastTo::<i{node.lhs.dtype.bits}>::cast(&{rhs})"
-        else:
-            lhs = f"ValueCastTo::<BigInt>::cast(&{lhs})"
-            rhs = f"ValueCastTo::<BigInt>::cast(&{rhs})"
+            # Odd widths live in the next Rust integer, e.g. `int<3>` in an `i8`
+            signed_ty = dtype_to_rust_type(node.lhs.dtype)
+            lhs = f"ValueCastTo::<{signed_ty}>::cast(&{lhs})"
+            rhs = f"ValueCastTo::<{signed_ty}>::cast(&{rhs})"
+            # The result is raw bits, as in the other branch
+            return fit_to_width(f"ValueCastTo::<{rust_ty}>::cast(&({lhs} >> {rhs}))",
+                                node.dtype)
+        lhs = f"ValueCastTo::<BigInt>::cast(&{lhs})"
+        rhs = f"ValueCastTo::<BigInt>::cast(&{rhs})"
     else:
         lhs = f"ValueCastTo::<{rust_ty}>::cast(&{lhs})"
         rhs = f"ValueCastTo::<{rust_ty}>::cast(&{rhs})"
 
-    return f"{lhs} {binop} {rhs}"
+    return fit_to_width(f"{lhs} {binop} {rhs}", node.dtype)
+
+
+def _codegen_saturating(node: BinaryOp, lhs: str, rhs: str, rust_ty: str):
+    """Generate code for saturating additions and subtractions.
+
+    Types that exactly fill a Rust integer use its `saturating_*` method; other widths
+    compute the exact result in `BigInt` and clamp it to the range of the type."""
+    dtype = node.dtype
+    bits = dtype.bits
+    if rust_ty[1:] == str(bits):
+        method = 'saturating_add' if node.opcode == BinaryOp.ADD_SAT else 'saturating_sub'
+        return (f"ValueCastTo::<{rust_ty}>::cast(&{lhs})"
+                f".{method}(ValueCastTo::<{rust_ty}>::cast(&{rhs}))")
+
+    if dtype.is_signed():
+        lo, hi = -(1 << (bits - 1)), (1 << (bits - 1)) - 1
+    else:
+        lo, hi = 0, (1 << bits) - 1
+    op = BinaryOp.OPERATORS[node.opcode][0]
+    return (f"{{ let exact = ValueCastTo::<BigInt>::cast(&{lhs}) {op} "
+            f"ValueCastTo::<BigInt>::cast(&{rhs}); "
+            f"let lo: BigInt = \"{lo}\".parse().unwrap(); "
+            f"let hi: BigInt = \"{hi}\".parse().unwrap(); "
+            f"ValueCastTo::<{rust_ty}>::cast(&exact.clamp(lo, hi)) }}")
 
 
 def codegen_unary_op(node: UnaryOp, module_ctx):
     """Generate code for unary operations."""
     operand = dump_rval_ref(module_ctx, node.x)
     uniop = UnaryOp.OPERATORS[node.opcode]
-    return f"{uniop}{operand}"
+    rust_ty = dtype_to_rust_type(node.dtype)
+    return fit_to_width(f"ValueCastTo::<{rust_ty}>::cast(&({uniop}{operand}))", node.dtype)
@@ -9,7 +9,7 @@ including async calls, FIFO operations, and bindings.
 from ....ir.expr import AsyncCall, FIFOPop, FIFOPush
 from ....ir.expr.call import Bind
 from ....utils import namify
-from ..utils import fifo_name
+from ..utils import fifo_name, fifo_front
 from ..node_dumper import dump_rval_ref
 
 
@@ -17,6 +17,12 @@ def codegen_async_call(node: AsyncCall, module_ctx):
     """Generate code for async call operations."""
     bind = node.bind
     event_q = f"{namify(bind.callee.name)}_event"
+    if node.replication > 1:
+        # One event per activation, each consumed in its own cycle
+        return f"""{{
+              let stamp = sim.stamp - sim.stamp % 100 + 100;
+              for _ in 0..{node.replication} {{ sim.{event_q}.push_back(stamp); }}
+            }}"""
     return f"""{{
               let stamp = sim.stamp - sim.stamp % 100 + 100;
               sim.{event_q}.push_back(stamp)
@@ -33,7 +39,7 @@ def codegen_fifo_pop(node: FIFOPop, module_ctx):
     return f"""{{
               let stamp = sim.stamp - sim.stamp % 100 + 50;
               sim.{fifo_id}.pop.push(FIFOPop::new(stamp, "{module_name}"));
-              match sim.{fifo_id}.payload.front() {{
+              match sim.{fifo_id}.{fifo_front(fifo)} {{
                 Some(value) => value.clone(),
                 None => panic!("{loc_info} is trying to pop an empty FIFO"),
               }}
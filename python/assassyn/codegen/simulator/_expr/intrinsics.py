@@ -8,21 +8,39 @@ This module contains helper functions to generate simulator code for intrinsic o
 
 from ....ir.expr.intrinsic import PureIntrinsic, Intrinsic, ExternalIntrinsic
 from ....utils import namify
+from ....utils import unwrap_operand
 from ..node_dumper import dump_rval_ref
+from ..utils import fifo_front
+from ..watchdog import watchdog_field
 
 
 def _codegen_fifo_peek(node, module_ctx):
     """Generate code for FIFO_PEEK intrinsic."""
     port_self = dump_rval_ref(module_ctx, node.get_operand(0))
-    return f"sim.{port_self}.front().cloned()"
+    return f"sim.{port_self}.{fifo_front(unwrap_operand(node.get_operand(0)))}.cloned()"
 
 
 def _codegen_fifo_valid(node, module_ctx):
     """Generate code for FIFO_VALID intrinsic."""
+    port = unwrap_operand(node.get_operand(0))
     port_self = dump_rval_ref(module_ctx, node.get_operand(0))
+    if port.module.flow_through:
+        return f"sim.{port_self}.{fifo_front(port)}.is_some()"
     return f"!sim.{port_self}.is_empty()"
 
 
+def _codegen_fifo_peek_n(node, module_ctx):
+    """Generate code for FIFO_PEEK_N intrinsic."""
+    port_self = dump_rval_ref(module_ctx, node.get_operand(0))
+    return f"sim.{port_self}.payload.get({node.lookahead}).cloned()"
+
+
+def _codegen_fifo_valid_n(node, module_ctx):
+    """Generate code for FIFO_VALID_N intrinsic."""
+    port_self = dump_rval_ref(module_ctx, node.get_operand(0))
+    return f"sim.{port_self}.payload.len() > {node.lookahead}"
+
+
 def _codegen_value_valid(node, module_ctx):
     """Generate code for VALUE_VALID intrinsic."""
     from ....ir.expr import Expr
@@ -86,6 +104,8 @@ def _codegen_external_output_read(node, module_ctx, **_kwargs):
 _PURE_INTRINSIC_DISPATCH = {
     PureIntrinsic.FIFO_PEEK: _codegen_fifo_peek,
     PureIntrinsic.FIFO_VALID: _codegen_fifo_valid,
+    PureIntrinsic.FIFO_PEEK_N: _codegen_fifo_peek_n,
+    PureIntrinsic.FIFO_VALID_N: _codegen_fifo_valid_n,
     PureIntrinsic.VALUE_VALID: _codegen_value_valid,
     PureIntrinsic.MODULE_TRIGGERED: _codegen_module_triggered,
     PureIntrinsic.HAS_MEM_RESP: _codegen_has_mem_resp,
@@ -115,7 +135,7 @@ def _codegen_wait_until(node, module_ctx):
 
 def _codegen_finish(node, module_ctx):
     """Generate code for FINISH intrinsic."""
-    return "std::process::exit(0);"
+    return "sim.on_finish(); std::process::exit(0);"
 
 
 def _codegen_assert(node, module_ctx):
@@ -124,6 +144,12 @@ def _codegen_assert(node, module_ctx):
     return f"assert!({value});"
 
 
+def _codegen_watchdog(node, module_ctx):
+    """Generate code for WATCHDOG intrinsic: record the cycle its signal is seen high."""
+    value = dump_rval_ref(module_ctx, node.args[0])
+    return f"if {value} {{ sim.{watchdog_field(node)}_seen = sim.stamp / 100; }}"
+
+
 def _codegen_send_read_request(node, module_ctx):
     """Generate code for SEND_READ_REQUEST intrinsic."""
     dram_module = node.args[0]
@@ -218,6 +244,7 @@ _INTRINSIC_DISPATCH = {
     Intrinsic.WAIT_UNTIL: _codegen_wait_until,
     Intrinsic.FINISH: _codegen_finish,
     Intrinsic.ASSERT: _codegen_assert,
+    Intrinsic.WATCHDOG: _codegen_watchdog,
     Intrinsic.SEND_READ_REQUEST: _codegen_send_read_request,
     Intrinsic.SEND_WRITE_REQUEST: _codegen_send_write_request,
     Intrinsic.EXTERNAL_INSTANTIATE: _codegen_external_instantiate,
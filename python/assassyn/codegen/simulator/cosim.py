@@ -0,0 +1,176 @@
+"""Co-simulation entry of the generated simulator.
+
+With `cosim=True`, `simulate()` does not run a fixed number of cycles. Instead, it serves
+line-based commands read from stdin, so that `assassyn.cosim.SimHandle` can drive FIFOs, access
+arrays, and advance the simulation cycle by cycle. Every command is answered by exactly one line
+starting with `@cosim`; all other lines are the logs of the design.
+
+    push <fifo> <value>           -> @cosim ok
+    pop <fifo>                    -> @cosim value <value> | @cosim none
+    read <array> <index>          -> @cosim value <value>
+    write <array> <index> <value> -> @cosim ok
+    step                          -> @cosim cycles 1 <cycle>
+    run <max>                     -> @cosim cycles <n> <cycle>
+    quit
+"""
+
+from __future__ import annotations
+
+from ...ir.module import Module
+from .utils import dtype_to_rust_type, fifo_name
+from ...utils import namify
+
+REPLY = '@cosim'
+
+
+def dump_cosim_methods(sys, arrays, fd):
+    """Generate `cosim_push` and `cosim_pop` of `Simulator`, dispatching on the FIFO name, and
+    `cosim_read` and `cosim_write`, dispatching on the array name.
+
+    `arrays` lists the `(field name, Rust type)` of every array field of the simulator."""
+    push_arms = []
+    pop_arms = []
+    for module in sys.modules:
+        if not isinstance(module, Module):
+            continue
+        event_q = f"{namify(module.name)}_event"
+        for fifo in module.ports:
+            name = fifo_name(fifo)
+            if dtype_to_rust_type(fifo.dtype) == 'bool':
+                parsed = 'value != "0"'
+                shown = '(v as u8).to_string()'
+            else:
+                parsed = 'value.parse().map_err(|_| format!("bad value {}", value))?'
+                shown = 'v.to_string()'
+            push_arms.append(f"""      "{name}" => {{
+        self.{name}.payload.push_back({parsed});
+        while self.{event_q}.len() < self.{name}.payload.len() {{
+          self.{event_q}.push_back(next);
+        }}
+      }}
+""")
+            pop_arms.append(
+                f'      "{name}" => Ok(self.{name}.payload.pop_front().map(|v| {shown})),\n')
+
+    fd.write("  pub fn cosim_push(&mut self, fifo: &str, value: &str) -> Result<(), String> {\n")
+    fd.write("    let next = self.stamp - self.stamp % 100 + 100;\n")
+    fd.write("    match fifo {\n")
+    for arm in push_arms:
+        fd.write(arm)
+    fd.write('      _ => return Err(format!("unknown fifo {}", fifo)),\n')
+    fd.write("    }\n")
+    fd.write("    Ok(())\n")
+    fd.write("  }\n\n")
+
+    fd.write("  pub fn cosim_pop(&mut self, fifo: &str) -> Result<Option<String>, String> {\n")
+    fd.write("    match fifo {\n")
+    for arm in pop_arms:
+        fd.write(arm)
+    fd.write('      _ => Err(format!("unknown fifo {}", fifo)),\n')
+    fd.write("    }\n")
+    fd.write("  }\n\n")
+
+    fd.write("  pub fn cosim_read(&self, array: &str, idx: usize) -> Result<String, String> {\n")
+    fd.write("    let shown = match array {\n")
+    for name, ty in arrays:
+        shown = '(*v as u8).to_string()' if ty == 'bool' else 'v.to_string()'
+        fd.write(f'      "{name}" => self.{name}.payload.get(idx).map(|v| {shown}),\n')
+    fd.write('      _ => return Err(format!("unknown array {}", array)),\n')
+    fd.write("    };\n")
+    fd.write('    shown.ok_or_else(|| format!("index {} out of {}", idx, array))\n')
+    fd.write("  }\n\n")
+
+    fd.write("  pub fn cosim_write(&mut self, array: &str, idx: usize, value: &str)\n")
+    fd.write("    -> Result<(), String> {\n")
+    fd.write("    match array {\n")
+    for name, ty in arrays:
+        if ty == 'bool':
+            parsed = 'value != "0"'
+        else:
+            parsed = 'value.parse().map_err(|_| format!("bad value {}", value))?'
+        fd.write(f'      "{name}" => {{\n')
+        fd.write(f"        let slot = self.{name}.payload.get_mut(idx)\n")
+        fd.write('          .ok_or_else(|| format!("index {} out of {}", idx, array))?;\n')
+        fd.write(f"        *slot = {parsed};\n")
+        fd.write("      }\n")
+    fd.write('      _ => return Err(format!("unknown array {}", array)),\n')
+    fd.write("    }\n")
+    fd.write("    Ok(())\n")
+    fd.write("  }\n\n")
+
+
+def dump_cosim_loop(fd, cycle_head: str, cycle_tail: str):
+    """Generate the command loop that replaces the fixed-length simulation loop.
+
+    `cycle_head` and `cycle_tail` are the two halves of the body of one simulated cycle,
+    shared with the regular simulation loop. `cycle_head` defines `any_module_triggered`.
+    """
+    fd.write(f"""
+      use std::io::BufRead;
+      let stdin = std::io::stdin();
+      for line in stdin.lock().lines() {{
+        let line = line.expect("Failed to read a co-simulation command");
+        let toks: Vec<&str> = line.split_whitespace().collect();
+        let max_cycles = match toks.as_slice() {{
+          ["push", fifo, value] => {{
+            match sim.cosim_push(fifo, value) {{
+              Ok(()) => println!("{REPLY} ok"),
+              Err(msg) => println!("{REPLY} error {{}}", msg),
+            }}
+            continue;
+          }}
+          ["pop", fifo] => {{
+            match sim.cosim_pop(fifo) {{
+              Ok(Some(value)) => println!("{REPLY} value {{}}", value),
+              Ok(None) => println!("{REPLY} none"),
+              Err(msg) => println!("{REPLY} error {{}}", msg),
+            }}
+            continue;
+          }}
+          ["read", array, idx] => {{
+            let res = idx.parse::<usize>()
+              .map_err(|_| format!("bad index {{}}", idx))
+              .and_then(|idx| sim.cosim_read(array, idx));
+            match res {{
+              Ok(value) => println!("{REPLY} value {{}}", value),
+              Err(msg) => println!("{REPLY} error {{}}", msg),
+            }}
+            continue;
+          }}
+          ["write", array, idx, value] => {{
+            let res = idx.parse::<usize>()
+              .map_err(|_| format!("bad index {{}}", idx))
+              .and_then(|idx| sim.cosim_write(array, idx, value));
+            match res {{
+              Ok(()) => println!("{REPLY} ok"),
+              Err(msg) => println!("{REPLY} error {{}}", msg),
+            }}
+            continue;
+          }}
+          ["step"] => 1,
+          ["run", max] => match max.parse::<usize>() {{
+            Ok(max) => max,
+            Err(_) => {{
+              println!("{REPLY} error bad cycle count {{}}", max);
+              continue;
+            }}
+          }},
+          ["quit"] => break,
+          _ => {{
+            println!("{REPLY} error unknown command {{}}", line);
+            continue;
+          }}
+        }};
+        let mut cycles = 0;
+        while cycles < max_cycles {{
+          sim.stamp = sim.stamp - sim.stamp % 100 + 100;
+{cycle_head}
+{cycle_tail}
+          cycles += 1;
+          if !any_module_triggered && toks[0] == "run" {{
+            break;
+          }}
+        }}
+        println!("{REPLY} cycles {{}} {{}}", cycles, sim.stamp / 100);
+      }}
+""")
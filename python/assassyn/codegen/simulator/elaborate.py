@@ -2,6 +2,8 @@
 
 from __future__ import annotations
 
+import hashlib
+import json
 import os
 import shutil
 import subprocess
@@ -9,10 +11,12 @@ import typing
 from pathlib import Path
 
 from .modules import dump_modules
-from .simulator import dump_simulator
+from .simulator import dump_simulator, analyze_and_register_ports
 from .verilator import emit_external_sv_ffis
 
-from ...utils import repo_path
+from ...analysis import check_types
+from ...ir.memory.base import MemoryBase
+from ...utils import build_simulator, get_simulator_binary_path, repo_path
 
 if typing.TYPE_CHECKING:
     from ...builder import SysBuilder
@@ -35,20 +39,49 @@ def _write_manifest(simulator_path: Path, sys_name: str, ffi_specs) -> Path:
     return manifest_path
 
 
-def elaborate_impl(sys, config):
-    """Internal implementation of the elaborate function.
+# The options that do not change the generated crate
+_UNHASHED_OPTIONS = ('path', 'verbose', 'enable_cache', 'build', 'force_regenerate')
 
-    This matches the Rust function in src/backend/simulator/elaborate.rs
-    """
+
+def _simulator_path(sys, config) -> Path:
     simulator_dirname = (
         config.get('simulator_dirname')
         or config.get('dirname')
         or f"{sys.name}_simulator"
     )
-    simulator_path = Path(config.get('path', os.getcwd())) / simulator_dirname
+    return Path(config.get('path', os.getcwd())) / simulator_dirname
+
+
+def compute_ir_hash(sys, config) -> str:
+    """Hash the printed IR of the system together with the options of the backend, the
+    initializers of the arrays and the contents of the init files of the memories."""
+    options = {k: v for k, v in config.items() if k not in _UNHASHED_OPTIONS}
+    content = repr(sys) + json.dumps(options, sort_keys=True, default=str)
+    digest = hashlib.sha256(content.encode())
+    # The printed IR leaves out the initializers
+    for array in sys.arrays:
+        digest.update(f'{array.name}={array.initializer!r}\n'.encode())
+    for memory in sys.modules + sys.downstreams:
+        if not isinstance(memory, MemoryBase) or not memory.init_file:
+            continue
+        path = Path(config.get('resource_base', '.')) / memory.init_file
+        digest.update(f'{memory.init_file}\n'.encode())
+        if path.is_file():
+            digest.update(path.read_bytes())
+    return digest.hexdigest()
+
+
+def elaborate_impl(sys, config):
+    """Internal implementation of the elaborate function.
+
+    This matches the Rust function in src/backend/simulator/elaborate.rs
+    """
+    simulator_path = _simulator_path(sys, config)
     verilator_root = simulator_path / config.get('verilator_dirname', f"{sys.name}_verilator")
 
-    if simulator_path.exists() and config.get('override_dump', True):
+    # An incremental build keeps the module files and the cargo target of the last build
+    incremental = config.get('incremental', False)
+    if simulator_path.exists() and config.get('override_dump', True) and not incremental:
         shutil.rmtree(simulator_path)
 
     simulator_path.mkdir(parents=True, exist_ok=True)
@@ -62,7 +95,10 @@ def elaborate_impl(sys, config):
 
     shutil.copy(Path(repo_path()) / "rustfmt.toml", simulator_path / "rustfmt.toml")
 
-    dump_modules(sys, simulator_path / "src" / "modules")
+    # Assign the array write ports up front, so that skipped modules do not shift them
+    analyze_and_register_ports(sys)
+    dump_modules(sys, simulator_path / "src" / "modules", incremental,
+                 config.get('trace', False), config.get('cover', False))
 
     with open(simulator_path / "src/simulator.rs", 'w', encoding='utf-8') as fd:
         dump_simulator(sys, config, fd)
@@ -76,10 +112,30 @@ def elaborate_impl(sys, config):
 
 
 def elaborate(sys, **config):
-    """Generate a Rust-based simulator for the given Assassyn system."""
+    """Generate a Rust-based simulator for the given Assassyn system.
+
+    The crate is kept as is when its `ir_hash.txt` matches the system and the options, unless
+    `force_regenerate` is set. With `build`, the crate is also compiled.
+
+    Returns:
+        The path to the Cargo.toml, and whether the existing crate was reused
+    """
 
     # pylint: disable=import-outside-toplevel
     from .port_mapper import reset_port_manager
+    check_types(sys)
+
+    ir_hash = compute_ir_hash(sys, config)
+    hash_path = _simulator_path(sys, config) / "ir_hash.txt"
+    manifest_path = hash_path.parent / "Cargo.toml"
+    if not config.get('force_regenerate', False) and manifest_path.exists() \
+            and hash_path.exists() and hash_path.read_text(encoding='utf-8') == ir_hash:
+        print(f"[Cache] Simulator is up to date: {hash_path.parent}")
+        if config.get('build', False) and \
+                not os.path.exists(get_simulator_binary_path(manifest_path)):
+            build_simulator(str(manifest_path))
+        return manifest_path, True
+
     reset_port_manager()
 
     manifest_path = elaborate_impl(sys, config)
@@ -94,4 +150,10 @@ def elaborate(sys, **config):
     except (subprocess.CalledProcessError, FileNotFoundError):
         print("Warning: Failed to format code with cargo fmt")
 
-    return manifest_path
+    if config.get('build', False):
+        build_simulator(str(manifest_path))
+
+    # Written last, so that an interrupted generation is not reused
+    hash_path.write_text(ir_hash, encoding='utf-8')
+
+    return manifest_path, False
@@ -2,7 +2,7 @@
 
 from __future__ import annotations
 
-from typing import Dict, Iterable, Set, Tuple
+from typing import Dict, Iterable, List, Optional, Tuple
 
 from ...analysis import expr_externally_used
 from ...ir.expr import Expr
@@ -21,36 +21,32 @@ def external_handle_field(module_name: str) -> str:
 class _ModuleValueExposureCollector(Visitor):
     """Collect expressions that need simulator-side caching."""
 
-    def __init__(self):
-        super().__init__()
-        self.exprs: Set[Expr] = set()
+    def visit_expr(self, node: Expr) -> Optional[Expr]:
+        return node if expr_externally_used(node, True) else None
 
-    def visit_expr(self, node: Expr) -> None:
-        if expr_externally_used(node, True):
-            self.exprs.add(node)
 
-
-def collect_module_value_exposures(module: Module) -> Set[Expr]:
+def collect_module_value_exposures(module: Module) -> List[Expr]:
     """Collect expressions that require simulator-side caching for a module."""
 
     body = getattr(module, "body", None)
     if not body:
-        return set()
+        return []
+
+    return _ModuleValueExposureCollector().walk(module)
 
-    collector = _ModuleValueExposureCollector()
-    collector.current_module = module
-    collector.visit_module(module)
-    return collector.exprs
+def gather_expr_validities(sys) -> Tuple[List[Expr], Dict[Module, List[Expr]]]:
+    """Aggregate expressions whose values must be cached on the simulator.
 
-def gather_expr_validities(sys) -> Tuple[Set[Expr], Dict[Module, Set[Expr]]]:
-    """Aggregate expressions whose values must be cached on the simulator."""
+    Both results are deduplicated in module and program order, so that the fields of
+    the simulator do not move between runs."""
 
-    exprs: Set[Expr] = set()
-    module_expr_map: Dict[Module, Set[Expr]] = {}
+    # Dicts keep the insertion order that sets of id-hashed expressions lack
+    exprs: Dict[Expr, None] = {}
+    module_expr_map: Dict[Module, Dict[Expr, None]] = {}
 
     def record(module: Module, expr: Expr) -> None:
-        exprs.add(expr)
-        module_expr_map.setdefault(module, set()).add(expr)
+        exprs[expr] = None
+        module_expr_map.setdefault(module, {})[expr] = None
 
     modules: Iterable[Module] = list(sys.modules) + list(sys.downstreams)
     for module in modules:
@@ -68,7 +64,7 @@ def gather_expr_validities(sys) -> Tuple[Set[Expr], Dict[Module, Set[Expr]]]:
                 if isinstance(expr, Expr):
                     record(module, expr)
 
-    return exprs, module_expr_map
+    return list(exprs), {module: list(m_exprs) for module, m_exprs in module_expr_map.items()}
 
 
 def has_module_body(module: Module) -> bool:
@@ -0,0 +1,125 @@
+"""Timing fuzzer of the generated simulator.
+
+With a `FuzzConfig`, the simulator owns a seeded `fuzz_rng`. Every activation of a module
+called by another one may be postponed by a cycle, together with the events queued behind it,
+and two modules activated in the same cycle may run in the other order. A design whose
+results depend on neither is robust to the timing variations the fuzzer stands for.
+
+A module reading the value of another module, rather than a FIFO or an array, relies on its
+producer having run in the same cycle, or in the previous one for a registered value. So does
+a module reading the read buffer of a memory, on the modules addressing it in the previous
+cycle, as the next read overwrites the buffer. Neither side of such a link is postponed or
+reordered, as the reader would see a value that was never produced, or another one.
+
+The seed of a run is read from the `ASSASSYN_FUZZ_SEED` environment variable if it is set, so
+that a single build can be run with many seeds."""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from typing import Set
+
+from ...analysis.topo import get_upstreams
+from ...ir.expr import ArrayRead, Bind, Expr, FIFOPush
+from ...ir.memory.base import MemoryBase
+from ...ir.module.base import ModuleBase
+from ...utils import namify
+
+FUZZ_SEED_ENV = 'ASSASSYN_FUZZ_SEED'
+
+# The modules activated every cycle by the simulator itself, rather than by a call
+_CLOCKED = ('Driver', 'Testbench')
+
+
+@dataclass(frozen=True)
+class FuzzConfig:
+    '''The timing variations injected by the simulator.
+
+    Attributes:
+        seed: The seed of the random generator, unless overridden at run time.
+        delay: The probability of postponing an activation by one cycle.
+        reorder: The probability of swapping two modules activated in the same cycle, drawn
+            for every pair of neighbours in the execution order.'''
+    seed: int = 0
+    delay: float = 0.0
+    reorder: float = 0.0
+
+    def __post_init__(self):
+        if not isinstance(self.seed, int) or isinstance(self.seed, bool) or self.seed < 0:
+            raise ValueError(f'The fuzz seed must be a non-negative integer, not {self.seed!r}')
+        for name in ('delay', 'reorder'):
+            value = getattr(self, name)
+            if isinstance(value, bool) or not isinstance(value, (int, float)) or \
+                    not 0 <= value <= 1:
+                raise ValueError(f'The fuzz {name} must be a probability, not {value!r}')
+
+
+def _read_buffers(module) -> Set[MemoryBase]:
+    """The memories whose read buffers `module` reads."""
+    res = set()
+    for expr in module.body or []:
+        if not isinstance(expr, ArrayRead) or not isinstance(expr.array.owner, MemoryBase):
+            continue
+        if expr.array.owner is not module and not expr.array.is_payload(expr.array.owner):
+            res.add(expr.array.owner)
+    return res
+
+
+def value_linked(sys) -> Set[ModuleBase]:
+    """The modules reading a value of another module or the read buffer of a memory, and the
+    modules producing them.
+
+    The pushes and binds of a call are not values: the callee reads them from its FIFOs."""
+    res = set()
+    for module in sys.modules + sys.downstreams:
+        for memory in _read_buffers(module):
+            res.update((module, memory))
+            res.update(get_upstreams(memory))
+        for value in module.externals:
+            if not isinstance(value, Expr) or isinstance(value, (FIFOPush, Bind)):
+                continue
+            if isinstance(value.parent, ModuleBase) and value.parent is not module:
+                res.update((module, value.parent))
+    return res
+
+
+def fuzzed(module, linked: Set[ModuleBase]) -> bool:
+    """Whether the activations of `module` may be postponed, i.e. it is called by others, and
+    neither reads nor produces a value in `linked`, as found by `value_linked`."""
+    return module.name not in _CLOCKED and module not in linked
+
+
+def dump_fuzz_methods(fuzz: FuzzConfig, fd):
+    """Generate `new_fuzz_rng`, `fuzz_postpone` and `fuzz_reorder` of `Simulator`."""
+    fd.write("  fn new_fuzz_rng() -> StdRng {\n")
+    fd.write(f'    let seed = std::env::var("{FUZZ_SEED_ENV}").ok()\n')
+    fd.write(f"      .and_then(|x| x.parse().ok()).unwrap_or({fuzz.seed}u64);\n")
+    fd.write("    StdRng::seed_from_u64(seed)\n")
+    fd.write("  }\n\n")
+
+    # The events queued behind a postponed one are postponed as well, keeping the order
+    fd.write("  fn fuzz_postpone(&mut self, event: fn(&mut Simulator) -> &mut VecDeque<usize>)"
+             " -> bool {\n")
+    fd.write(f"    if !self.fuzz_rng.gen_bool({float(fuzz.delay)}) {{\n")
+    fd.write("      return false;\n")
+    fd.write("    }\n")
+    fd.write("    let next = self.stamp - self.stamp % 100 + 100;\n")
+    fd.write("    for stamp in event(self).iter_mut() {\n")
+    fd.write("      if *stamp < next { *stamp = next; }\n")
+    fd.write("    }\n")
+    fd.write("    true\n")
+    fd.write("  }\n\n")
+
+    fd.write("  fn fuzz_reorder(&mut self, simulators: &mut [fn(&mut Simulator)]) {\n")
+    fd.write("    for i in 1..simulators.len() {\n")
+    fd.write(f"      if self.fuzz_rng.gen_bool({float(fuzz.reorder)}) {{\n")
+    fd.write("        simulators.swap(i - 1, i);\n")
+    fd.write("      }\n")
+    fd.write("    }\n")
+    fd.write("  }\n\n")
+
+
+def dump_fuzz_postpone(module) -> str:
+    """The condition, ANDed to the event guard of `module`, letting the fuzzer postpone it."""
+    name = namify(module.name)
+    return f" && !self.fuzz_postpone(|sim| &mut sim.{name}_event)"
@@ -12,6 +12,7 @@ from ...ir.memory.dram import DRAM
 from ...utils import namify
 from .node_dumper import dump_rval_ref
 from ...analysis import expr_externally_used
+from ...analysis.coverage import cover_key, cover_points
 from ...ir.module.external import ExternalSV
 from .external import has_module_body
 
@@ -23,9 +24,15 @@ if typing.TYPE_CHECKING:
 class ElaborateModule(Visitor):  # pylint: disable=too-many-instance-attributes
     """Visitor for elaborating modules with ExternalSV support."""
 
-    def __init__(self, sys):
+    def __init__(self, sys, trace: bool = False, cover: bool = False):
         super().__init__()
         self.sys = sys
+        # Whether array writes and FIFO pushes print their `@trace` line
+        self.trace = trace
+        # Whether each condition block counts the cycles it is entered
+        self.cover = cover
+        self.cover_keys = {}  # The counter of each block of the current module, by id
+        self.probe_labels = {}  # The label of each probed value of the current module, by id
         self.indent = 0
         self.module_name = ""
         self.module_ctx = None
@@ -34,6 +41,12 @@ class ElaborateModule(Visitor):  # pylint: disable=too-many-instance-attributes
         """Visit a module and generate its implementation."""
         self.module_name = node.name
         self.module_ctx = node
+        self.cover_keys = {}
+        self.probe_labels = {id(signal): label for signal, label in self.sys.probes
+                             if signal.parent is node}
+        if self.cover:
+            self.cover_keys = {id(block): cover_key(node, field)
+                               for field, block in cover_points(node)}
 
         if isinstance(node, ExternalSV) and not has_module_body(node):
             return self.visit_external_module(node)
@@ -65,6 +78,7 @@ class ElaborateModule(Visitor):  # pylint: disable=too-many-instance-attributes
         code = None
         if isinstance(node, IRIntrinsic) and node.opcode in (
             IRIntrinsic.PUSH_CONDITION,
+            IRIntrinsic.PUSH_ELSE,
             IRIntrinsic.POP_CONDITION,
         ):
             indent_str = " " * self.indent
@@ -73,7 +87,16 @@ class ElaborateModule(Visitor):  # pylint: disable=too-many-instance-attributes
                 result = f"{indent_str}if {cond_val} {{\n"
                 # Increase indentation for the body inside this condition
                 self.indent += 2
-                return result
+                return result + self._count_block(node)
+            if node.opcode == IRIntrinsic.PUSH_ELSE:
+                # The else predicate is the negation of the shared condition, which nested
+                # blocks may refer to
+                cond_val = dump_rval_ref(self.module_ctx, node.args[0])
+                id_expr = id_and_exposure[0]
+                result = f"{indent_str}let {id_expr} = !{cond_val};\n"
+                result += f"{indent_str}if {id_expr} {{\n"
+                self.indent += 2
+                return result + self._count_block(node)
             # POP_CONDITION closes the current scope
             self.indent = max(0, self.indent - 2)
             return f"{' ' * self.indent}}}\n"
@@ -95,14 +118,31 @@ class ElaborateModule(Visitor):  # pylint: disable=too-many-instance-attributes
                 # pylint: disable=import-outside-toplevel
                 from ...ir.expr.intrinsic import ExternalIntrinsic
                 if need_exposure and not isinstance(node, ExternalIntrinsic):
-                    lines.append(f"{indent_str}sim.{id_expr}_value = Some({id_expr}.clone());")
+                    field = 'next' if node.registered else 'value'
+                    lines.append(f"{indent_str}sim.{id_expr}_{field} = Some({id_expr}.clone());")
                 result = "\n".join(lines) + "\n"
         else:
             if code:
                 result += f"{indent_str}{code};\n"
 
+        if self.trace:
+            from ._expr import codegen_trace  # pylint: disable=import-outside-toplevel
+            trace = codegen_trace(node, self.module_ctx)
+            if trace:
+                result += f"{indent_str}{trace};\n"
+
+        label = self.probe_labels.get(id(node))
+        if label:
+            from ._expr import codegen_probe  # pylint: disable=import-outside-toplevel
+            result += f"{indent_str}{codegen_probe(node, label, self.module_ctx)};\n"
+
         return result
 
+    def _count_block(self, block) -> str:
+        '''The increment of the coverage counter of `block`, if it has one.'''
+        key = self.cover_keys.get(id(block))
+        return f"{' ' * self.indent}sim.{key} += 1;\n" if key else ''
+
     def _emit_body(self, body_nodes):
         result = []
         visited = set()
@@ -139,11 +179,25 @@ class ElaborateModule(Visitor):  # pylint: disable=too-many-instance-attributes
         )
 
 
-def dump_modules(sys: SysBuilder, modules_dir):
-    """Generate individual module files in the modules/ directory."""
+def dump_modules(sys: SysBuilder, modules_dir, incremental: bool = False, trace: bool = False,
+                 cover: bool = False):
+    """Generate individual module files in the modules/ directory.
+
+    With `incremental`, each `<module>.rs` is written along with a `<module>.hash` holding the
+    fingerprint of the module, and a module whose fingerprint is unchanged since the last dump
+    keeps its file. The files of the modules no longer in the system are removed. With `trace`,
+    the array writes and FIFO pushes print their `@trace` line, and with `cover`, each
+    condition block increments its coverage counter."""
     modules_dir.mkdir(exist_ok=True)
+    all_modules = sys.modules[:] + sys.downstreams[:]
+    reused = 0
+    if incremental:
+        names = {namify(m.name) for m in all_modules}
+        for path in list(modules_dir.glob('*.rs')) + list(modules_dir.glob('*.hash')):
+            if path.stem not in names and path.name != 'mod.rs':
+                path.unlink()
 
-    em = ElaborateModule(sys)
+    em = ElaborateModule(sys, trace, cover)
 
     mod_rs_path = modules_dir / "mod.rs"
     with open(mod_rs_path, 'w', encoding="utf-8") as mod_fd:
@@ -157,11 +211,27 @@ use std::sync::Arc;
 
 """)
 
-        for module in sys.modules[:] + sys.downstreams[:]:
+        for module in all_modules:
             module_name = namify(module.name)
             mod_fd.write(f"pub mod {module_name};\n")
 
             module_file_path = modules_dir / f"{module_name}.rs"
+            if incremental:
+                hash_path = modules_dir / f"{module_name}.hash"
+                fingerprint = str(sys.compute_fingerprint(module))
+                if trace:
+                    # The traced code differs, so it does not reuse an untraced file
+                    fingerprint += ' trace'
+                if cover:
+                    fingerprint += ' cover'
+                # A probe prints its value from the code of its module
+                fingerprint += ''.join(f' probe {label}' for signal, label in sys.probes
+                                       if signal.parent is module)
+                if module_file_path.exists() and hash_path.exists() \
+                        and hash_path.read_text(encoding="utf-8") == fingerprint:
+                    reused += 1
+                    continue
+                hash_path.write_text(fingerprint, encoding="utf-8")
             with open(module_file_path, 'w', encoding="utf-8") as module_fd:
                 module_fd.write("""use sim_runtime::*;
 use sim_runtime::num_bigint::{BigInt, BigUint};
@@ -208,4 +278,6 @@ use std::ffi::c_void;
                 module_code = em.visit_module(module)
                 module_fd.write(module_code)
 
+    if incremental:
+        print(f"[Incremental] Reused {reused} of {len(all_modules)} module files")
     return True
@@ -47,7 +47,8 @@ def _handle_expr(unwrapped, module_ctx):
             }}.clone()"""
 
     ref = namify(unwrapped.as_operand())
-    if isinstance(unwrapped, PureIntrinsic) and unwrapped.opcode == PureIntrinsic.FIFO_PEEK:
+    if isinstance(unwrapped, PureIntrinsic) and \
+            unwrapped.opcode in [PureIntrinsic.FIFO_PEEK, PureIntrinsic.FIFO_PEEK_N]:
         return f"{ref}.clone().unwrap()"
 
     dtype = unwrapped.dtype
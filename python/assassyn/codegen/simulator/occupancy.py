@@ -0,0 +1,76 @@
+"""FIFO occupancy trace and histograms of the generated simulator.
+
+The trace is a CSV file with a `cycle` column and one column per FIFO, named after its
+simulator field, i.e. `<module>_<port>`. Each row holds the number of entries of every FIFO
+at the end of a cycle, after the pushes and pops of the cycle are committed.
+
+With `sim_stats`, every FIFO also keeps an `OccupancyHistogram` of the same samples, which
+is summarized in a table at the end of the simulation."""
+
+from __future__ import annotations
+
+import json
+import os
+
+OCCUPANCY_MSG = 'FIFO occupancy written to'
+HISTOGRAM_TITLE = 'FIFO occupancy histograms'
+
+
+def occupancy_path(config: dict) -> str:
+    """The absolute path of the occupancy CSV, in the system directory."""
+    path = os.path.join(config.get('path', os.getcwd()), 'fifo_occupancy.csv')
+    return os.path.abspath(path)
+
+
+def dump_occupancy_methods(fifos, fd):
+    """Generate `open_occupancy` and `record_occupancy` of `Simulator`.
+
+    `fifos` lists the field names of all the FIFOs of the simulator."""
+    header = ','.join(['cycle'] + fifos)
+    fd.write("  pub fn open_occupancy(&self, path: &str) -> std::fs::File {\n")
+    fd.write("    use std::io::Write;\n")
+    fd.write('    let mut out = std::fs::File::create(path).expect("Failed to create the CSV");\n')
+    fd.write(f'    writeln!(out, "{header}").expect("Failed to write the CSV");\n')
+    fd.write(f'    println!("{OCCUPANCY_MSG} {{}}", path);\n')
+    fd.write("    out\n")
+    fd.write("  }\n\n")
+
+    # Rows are written unbuffered, so that a `finish` exiting the process keeps them
+    fd.write("  pub fn record_occupancy(&self, out: &mut std::fs::File) {\n")
+    fd.write("    use std::io::Write;\n")
+    fd.write("    let mut row = format!(\"{}\", self.stamp / 100);\n")
+    for name in fifos:
+        fd.write(f'    row.push_str(&format!(",{{}}", self.{name}.payload.len()));\n')
+    fd.write('    writeln!(out, "{}", row).expect("Failed to write the CSV");\n')
+    fd.write("  }\n\n")
+
+
+def dump_occupancy_open(config: dict) -> str:
+    """The code creating the CSV before the simulation, if asked for."""
+    if not config.get('fifo_occupancy_csv', False):
+        return ''
+    path = json.dumps(occupancy_path(config))
+    return f"  let mut occupancy = sim.open_occupancy({path});\n"
+
+
+def dump_occupancy_record(config: dict) -> str:
+    """The code appended to the end of every cycle to record its row, if asked for."""
+    if not config.get('fifo_occupancy_csv', False):
+        return ''
+    return "        sim.record_occupancy(&mut occupancy);\n"
+
+
+def dump_histogram_table(fifos, fd):
+    """Generate the code printing the max, mean and p99 occupancy of every FIFO.
+
+    `fifos` lists the field name and the `module.port` name of every FIFO."""
+    if not fifos:
+        return
+    width = max(len('FIFO'), *(len(label) for _, label in fifos))
+    fd.write(f'    println!("{HISTOGRAM_TITLE} over {{}} cycles:", self.stamp / 100);\n')
+    fd.write(f'    println!("{{:<{width}}} {{:>6}} {{:>8}} {{:>6}}", '
+             '"FIFO", "max", "mean", "p99");\n')
+    for name, label in fifos:
+        fd.write(f'    println!("{{:<{width}}} {{:>6}} {{:>8.2}} {{:>6}}", {json.dumps(label)}, '
+                 f'self.{name}_hist.max(), self.{name}_hist.mean(), '
+                 f'self.{name}_hist.percentile(99.0));\n')
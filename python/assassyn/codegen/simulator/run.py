@@ -0,0 +1,96 @@
+"""Building and running a generated simulator crate, capturing its output."""
+
+from __future__ import annotations
+
+import os
+import subprocess
+import sys
+from dataclasses import dataclass, field
+from typing import List, Optional
+
+from ...analysis.pipeline import SimReport
+from ...utils import get_simulator_binary_path
+
+# The lines of rustc context kept after the first error when no blank line ends it
+_ERROR_CONTEXT = 20
+
+
+class SimulatorBuildError(RuntimeError):
+    '''The generated simulator crate failed to compile.'''
+
+    def __init__(self, manifest_path: str, error: str, log: str):
+        super().__init__(f'{manifest_path} failed to compile:\n{error}')
+        self.manifest_path = manifest_path
+        # The first rustc error, with its context
+        self.error = error
+        # The whole output of cargo
+        self.log = log
+
+
+@dataclass
+class SimOutput:
+    '''The output of a simulator run.'''
+
+    # The stdout and stderr of the simulator, interleaved
+    log: str
+    # The `[stats]` lines of the log, empty unless built with `sim_stats=True`
+    # or `cover=True`
+    report: SimReport = field(default_factory=SimReport)
+
+
+def first_rustc_error(log: str) -> Optional[str]:
+    '''The first `error` diagnostic of a cargo output, up to the blank line ending it.'''
+    lines = log.splitlines()
+    start = next((i for i, line in enumerate(lines) if line.startswith('error')), None)
+    if start is None:
+        return None
+    end = start + 1
+    while end < len(lines) and lines[end].strip() and end - start < _ERROR_CONTEXT:
+        end += 1
+    return '\n'.join(lines[start:end])
+
+
+def _run(cmd: List[str], stream: bool) -> subprocess.CompletedProcess:
+    env = os.environ.copy()
+    env.pop('RUSTC_WRAPPER', None)  # sccache fails under some sandboxed runners
+    if not stream:
+        return subprocess.run(cmd, env=env, stdout=subprocess.PIPE, stderr=subprocess.STDOUT,
+                              check=False, text=True)
+    captured = []
+    with subprocess.Popen(cmd, env=env, stdout=subprocess.PIPE, stderr=subprocess.STDOUT,
+                          text=True) as proc:
+        for line in proc.stdout:
+            sys.stdout.write(line)
+            captured.append(line)
+    return subprocess.CompletedProcess(cmd, proc.returncode, ''.join(captured))
+
+
+def run_crate(manifest_path: str, target_dir: Optional[str] = None,
+              stream: bool = False) -> SimOutput:
+    '''Build the crate of `manifest_path` in release mode and run its simulator.
+
+    Args:
+        manifest_path: Path to the Cargo.toml of the simulator
+        target_dir: The cargo target directory, `target` next to the manifest by default
+        stream: Whether to echo the output of cargo and of the simulator while capturing it
+
+    Raises:
+        SimulatorBuildError: If the crate does not compile
+        subprocess.CalledProcessError: If the simulator exits with an error, e.g. a panic
+    '''
+    manifest_path = str(manifest_path)
+    cmd = ['cargo', 'build', '--release', '--manifest-path', manifest_path]
+    if target_dir is not None:
+        cmd += ['--target-dir', str(target_dir)]
+    built = _run(cmd, stream)
+    if built.returncode != 0:
+        error = first_rustc_error(built.stdout) or built.stdout[-2000:]
+        raise SimulatorBuildError(manifest_path, error, built.stdout)
+
+    binary_path = get_simulator_binary_path(manifest_path)
+    if target_dir is not None:
+        binary_path = os.path.join(str(target_dir), 'release', os.path.basename(binary_path))
+    ran = _run([binary_path], stream)
+    if ran.returncode != 0:
+        raise subprocess.CalledProcessError(ran.returncode, [binary_path], output=ran.stdout)
+    return SimOutput(log=ran.stdout, report=SimReport.parse(ran.stdout))
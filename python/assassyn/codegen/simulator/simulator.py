@@ -3,11 +3,15 @@
 from __future__ import annotations
 
 import os
-from ...analysis import topo_downstream_modules, get_upstreams
-from .utils import dtype_to_rust_type, int_imm_dumper_impl, fifo_name
+from ...analysis import (topo_downstream_modules, get_upstreams, check_clock_domains,
+                         registered_inputs)
+from ...analysis.coverage import UNCOVERED_PREFIX, cover_key, cover_location, cover_points
+from .utils import (dtype_to_rust_type, int_imm_dumper_impl, str_imm_dumper_impl, str_literal,
+                    fifo_name)
 from ...builder import SysBuilder
 # from ...ir.block import CycledBlock  # legacy; kept for backward-compatible IRs
 from ...ir.expr import Bind
+from ...ir.dtype import Str
 from ...ir.module import Downstream, Module
 from ...ir.module.external import ExternalSV
 from ...ir.memory.sram import SRAM
@@ -21,6 +25,13 @@ from .external import (
 )
 from ...utils import namify, repo_path
 from .port_mapper import get_port_manager
+from .cosim import dump_cosim_methods, dump_cosim_loop
+from .snapshot import dump_snapshot_methods, dump_snapshot_trigger, dump_snapshot_restore
+from .occupancy import (
+    dump_occupancy_methods, dump_occupancy_open, dump_occupancy_record, dump_histogram_table,
+)
+from .watchdog import collect_watchdogs, dump_watchdog_methods, dump_watchdog_check, watchdog_field
+from .fuzz import dump_fuzz_methods, dump_fuzz_postpone, fuzzed, value_linked
 from ...utils.enforce_type import enforce_type
 
 
@@ -85,6 +96,14 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
             - random: Whether to randomize module execution order
             - resource_base: Path to resource files
             - fifo_depth: Default FIFO depth
+            - sim_stats: Whether to count executions, stalls, FIFO backlogs and occupancy
+            - cosim: Whether `simulate()` serves co-simulation commands from stdin
+            - snapshot_at: The cycle at the end of which the arrays are saved, or None
+            - restore_from: The snapshot file seeding the arrays before simulation, or None
+            - fifo_occupancy_csv: Whether to trace the FIFO occupancy of every cycle to a CSV
+            - clock_domains: The period of each clock domain, in cycles of the default clock
+            - cover: Whether to count the cycles each condition block is entered
+            - fuzz: The `FuzzConfig` of the timing variations to inject, or None
         fd: File descriptor to write to
     """
     # First, analyze the system to determine port requirements and collect DRAM modules
@@ -94,6 +113,18 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
         spec.original_module_name: spec for spec in config.get('external_ffis', [])
     }
     external_clock_handles = []
+    sim_stats = config.get('sim_stats', False)
+    cover = config.get('cover', False)
+    stats_dump = []
+    uncovered_dump = []  # The lines reporting the blocks never entered, after the counters
+    snapshot = config.get('snapshot_at') is not None or config.get('restore_from') is not None
+    snapshot_arrays = []
+    occupancy = config.get('fifo_occupancy_csv', False)
+    occupancy_fifos = []
+    hist_fifos = []  # (field, `module.port`) of each FIFO with an occupancy histogram
+    watchdogs = collect_watchdogs(sys)
+    fuzz = config.get('fuzz')
+    fuzz_linked = value_linked(sys) if fuzz is not None else set()
 
     # Write imports
     fd.write("use sim_runtime::*;\n")
@@ -103,11 +134,15 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
     # Platform-specific imports are no longer needed since we use the utility method
     fd.write("use std::sync::Arc;\n")
     fd.write("use sim_runtime::num_bigint::{BigInt, BigUint};\n")
-    fd.write("use sim_runtime::rand::seq::SliceRandom;\n\n")
+    fd.write("use sim_runtime::rand::seq::SliceRandom;\n")
+    if fuzz is not None:
+        fd.write("use sim_runtime::rand::{Rng, SeedableRng, rngs::StdRng};\n")
+    fd.write("\n")
 
     # Initialize data structures
     simulator_init = []
     downstream_reset = []
+    registered_ticks = []  # The registered values made visible when the registers tick
     registers = []
 
     expr_validities, module_expr_map = gather_expr_validities(sys)
@@ -120,6 +155,8 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
     # Begin simulator struct definition
     fd.write("pub struct Simulator { pub stamp: usize, ")
     fd.write("pub request_stamp_map_table: HashMap<i64, usize>,\n")
+    if fuzz is not None:
+        fd.write("pub fuzz_rng: StdRng,\n")
     home = repo_path()
     # Add per-DRAM memory interfaces and response fields
     for dram in dram_modules:
@@ -140,7 +177,10 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
         if array.initializer:
             init_values = []
             for x in array.initializer:
-                init_values.append(int_imm_dumper_impl(array.scalar_ty, x))
+                if isinstance(array.scalar_ty, Str):
+                    init_values.append(str_imm_dumper_impl(x))
+                else:
+                    init_values.append(int_imm_dumper_impl(array.scalar_ty, x))
             init_str = ", ".join(init_values)
             simulator_init.append(
                 f"{name} : Array::new_with_init_and_ports(vec![{init_str}], {num_ports}),"
@@ -148,6 +188,9 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
         else:
             simulator_init.append(f"{name} : Array::new_with_ports({array.size}, {num_ports}),")
         registers.append(name)
+        # The tables of strings are never written, and their elements may hold spaces
+        if not isinstance(array.scalar_ty, Str):
+            snapshot_arrays.append((name, dtype))
 
     # Add module fields to simulator struct
     for module in sys.modules[:] + sys.downstreams[:]:
@@ -158,11 +201,29 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
         simulator_init.append(f"{module_name}_triggered : false,")
         downstream_reset.append(f"self.{module_name}_triggered = false;")
 
+        for field, block in cover_points(module) if cover else []:
+            key = cover_key(module, field)
+            fd.write(f"pub {key} : usize, ")
+            simulator_init.append(f"{key} : 0,")
+            stats_dump.append(f'println!("[stats] cover {key} {{}}", self.{key});')
+            uncovered_dump.append(
+                f'if self.{key} == 0 {{ println!("{UNCOVERED_PREFIX} {key} at {{}}", '
+                f'{str_literal(cover_location(block))}); }}')
+
         if isinstance(module, Module):
             # Add event queue for non-downstream modules
             fd.write(f"pub {module_name}_event : VecDeque<usize>, ")
             simulator_init.append(f"{module_name}_event : VecDeque::new(),")
 
+            if sim_stats:
+                fd.write(f"pub {module_name}_executed : usize, pub {module_name}_stalled : usize, ")
+                simulator_init.append(f"{module_name}_executed : 0,")
+                simulator_init.append(f"{module_name}_stalled : 0,")
+                stats_dump.append(
+                    f'println!("[stats] module {module_name} {{}} {{}}", '
+                    f'self.{module_name}_executed, self.{module_name}_stalled);'
+                )
+
             # Add FIFO fields for each FIFO
             for fifo in module.ports:
                 name = fifo_name(fifo)
@@ -170,6 +231,19 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
                 fd.write(f"pub {name} : FIFO<{ty}>, ")
                 simulator_init.append(f"{name} : FIFO::new(),")
                 registers.append(name)
+                if occupancy:
+                    occupancy_fifos.append(name)
+                if sim_stats:
+                    fd.write(f"pub {name}_backlog : usize, pub {name}_hist : OccupancyHistogram, ")
+                    simulator_init.append(f"{name}_backlog : 0,")
+                    simulator_init.append(f"{name}_hist : OccupancyHistogram::new(),")
+                    stats_dump.append(
+                        f'println!("[stats] fifo {name} {module_name} {{}}", self.{name}_backlog);'
+                    )
+                    stats_dump.append(
+                        f'println!("[stats] hist {name} {{}}", self.{name}_hist.dump());'
+                    )
+                    hist_fifos.append((name, f'{module.name}.{fifo.name}'))
 
         if isinstance(module, ExternalSV):
             handle_field = external_handle_field(module.name)
@@ -216,7 +290,19 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
         dtype = dtype_to_rust_type(expr.dtype)
         fd.write(f"pub {name}_value : Option<{dtype}>, ")
         simulator_init.append(f"{name}_value : None,")
-        downstream_reset.append(f"self.{name}_value = None;")
+        if expr.registered:
+            # Set in `_next` by the producer, and read a half-stamp later from `_value`
+            fd.write(f"pub {name}_next : Option<{dtype}>, ")
+            simulator_init.append(f"{name}_next : None,")
+            downstream_reset.append(f"self.{name}_next = None;")
+            registered_ticks.append(f"self.{name}_value = self.{name}_next.take();")
+        else:
+            downstream_reset.append(f"self.{name}_value = None;")
+
+    for _, node in watchdogs:
+        field = watchdog_field(node)
+        fd.write(f"pub {field}_seen : usize, ")
+        simulator_init.append(f"{field}_seen : 0,")
 
     # Close simulator struct
     fd.write("}\n\n")
@@ -240,6 +326,8 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
     fd.write("    Simulator {\n")
     fd.write("      stamp: 0,\n")
     fd.write("      request_stamp_map_table: HashMap::new(),\n")
+    if fuzz is not None:
+        fd.write("      fuzz_rng: Simulator::new_fuzz_rng(),\n")
     for init in simulator_init:
         fd.write(f"      {init}\n")
     fd.write("    }\n")
@@ -250,6 +338,15 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
     fd.write("    event.front().map_or(false, |x| *x <= self.stamp)\n")
     fd.write("  }\n\n")
 
+    # A module of a slower clock domain runs in the cycles where its clock has an edge, i.e.
+    # where `cycle / period` reaches a new integer
+    periods = check_clock_domains(sys, config.get('clock_domains'))
+    for domain, period in periods.items():
+        fd.write(f"  fn clock_{domain}(&self) -> bool {{\n")
+        fd.write(f"    self.stamp / 100 * {period.denominator} % {period.numerator} "
+                 f"< {period.denominator}\n")
+        fd.write("  }\n\n")
+
     # Reset downstream method
     fd.write("  pub fn reset_downstream(&mut self) {\n")
     for reset in downstream_reset:
@@ -262,6 +359,8 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
         fd.write(f"    self.{reg}.tick(self.stamp);\n")
     for handle in external_clock_handles:
         fd.write(f"    self.{handle}.clock_tick();\n")
+    for tick in registered_ticks:
+        fd.write(f"    {tick}\n")
     # Tick ExternalIntrinsic instances with registered outputs
     for intr in external_intrinsics:
         cls_name = intr.external_class.__name__
@@ -291,6 +390,38 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
         fd.write(f"    self.{dram_name}_response.write_succ = false;\n")
     fd.write("  }\n\n")
 
+    # Statistics summary consumed by `analysis.pipeline.SimReport.parse`
+    if sim_stats or cover:
+        fd.write("  pub fn dump_stats(&self) {\n")
+        fd.write('    println!("[stats] cycles {}", self.stamp / 100);\n')
+        for line in stats_dump + uncovered_dump:
+            fd.write(f"    {line}\n")
+        dump_histogram_table(hist_fifos, fd)
+        fd.write("  }\n\n")
+    if sim_stats:
+        fd.write("  pub fn record_histograms(&mut self) {\n")
+        for name, _ in hist_fifos:
+            fd.write(f"    self.{name}_hist.record(self.{name}.payload.len());\n")
+        fd.write("  }\n\n")
+
+    # Called by `finish()` before it exits the process
+    fd.write("  pub fn on_finish(&self) {\n")
+    if sim_stats or cover:
+        fd.write("    self.dump_stats();\n")
+    fd.write("  }\n\n")
+
+    if config.get('cosim', False):
+        dump_cosim_methods(sys, snapshot_arrays, fd)
+
+    if snapshot:
+        dump_snapshot_methods(snapshot_arrays, fd)
+    if occupancy:
+        dump_occupancy_methods(occupancy_fifos, fd)
+    if watchdogs:
+        dump_watchdog_methods(watchdogs, fd)
+    if fuzz is not None:
+        dump_fuzz_methods(fuzz, fd)
+
     # Get topological order for downstream modules
     downstreams = topo_downstream_modules(sys)
 
@@ -305,15 +436,25 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
 
         if not isinstance(module, Downstream):
             # Event based triggering for non-downstream modules
-            fd.write(f"    if self.event_valid(&self.{module_name}_event) {{\n")
+            clock = f' && self.clock_{module.clock_domain}()' if module.clock_domain else ''
+            if fuzz is not None and fuzzed(module, fuzz_linked):
+                clock += dump_fuzz_postpone(module)
+            fd.write(f"    if self.event_valid(&self.{module_name}_event){clock} {{\n")
         else:
             # Dependency based triggering for downstream modules
             upstream_conds = []
-            for upstream in get_upstreams(module):
+            for upstream in sorted(get_upstreams(module), key=lambda m: m.name):
                 upstream_name = namify(upstream.name)
                 upstream_conds.append(f"self.{upstream_name}_triggered")
+            # A registered value triggers the downstream in the cycle after it is computed
+            for value in registered_inputs(module):
+                upstream_conds.append(f"self.{namify(value.as_operand())}_value.is_some()")
 
             conds = " || ".join(upstream_conds) if upstream_conds else "false"
+            if module.condition is not None:
+                # A conditional downstream also needs its condition set and true
+                cond = namify(module.condition.as_operand())
+                conds = f"({conds}) && self.{cond}_value.unwrap_or(false)"
             fd.write(f"    if {conds} {{\n")
 
         # Call module function and handle result
@@ -321,8 +462,25 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
 
         if not isinstance(module, Downstream):
             # Pop event on success
-            fd.write(f"      if succ {{ self.{module_name}_event.pop_front(); }}\n")
-            fd.write("      else {\n")
+            if module.merge_calls:
+                # All the calls of this cycle are served by this activation
+                fd.write("      if succ {\n")
+                fd.write(f"        while self.event_valid(&self.{module_name}_event) {{\n")
+                fd.write(f"          self.{module_name}_event.pop_front();\n")
+                fd.write("        }\n")
+                fd.write("      }\n")
+            else:
+                fd.write(f"      if succ {{ self.{module_name}_event.pop_front(); }}\n")
+            fd.write("      if !succ {\n")
+
+            if sim_stats:
+                # A valid event that fails is a stall; data waiting in a FIFO backs up.
+                fd.write(f"        self.{module_name}_stalled += 1;\n")
+                for fifo in module.ports:
+                    name = fifo_name(fifo)
+                    fd.write(f"        if !self.{name}.is_empty() {{\n")
+                    fd.write(f"          self.{name}_backlog += 1;\n")
+                    fd.write("        }\n")
 
             # Reset externally used values on failure
             for expr in module_expr_map.get(module, ()):  # type: ignore[arg-type]
@@ -332,9 +490,12 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
                 if isinstance(expr, ExternalIntrinsic):
                     continue
                 name = namify(expr.as_operand())
-                fd.write(f"        self.{name}_value = None;\n")
+                field = 'next' if expr.registered else 'value'
+                fd.write(f"        self.{name}_{field} = None;\n")
 
             fd.write("      }\n")
+            if sim_stats:
+                fd.write(f"      if succ {{ self.{module_name}_executed += 1; }}\n")
             simulators.append(module_name)
 
         # Update trigger state and close condition
@@ -362,9 +523,19 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
     if config.get('random', False):
         fd.write("  let mut rng = rand::thread_rng();\n")
         fd.write("  let mut simulators : Vec<fn(&mut Simulator)> = vec![")
+    elif fuzz is not None:
+        fd.write("  let mut simulators : Vec<fn(&mut Simulator)> = vec![")
     else:
         fd.write("  let simulators : Vec<fn(&mut Simulator)> = vec![")
 
+    # Flow-through modules see the pushes of the current cycle, so they run after the pushers
+    flow = {namify(m.name) for m in sys.modules if getattr(m, 'flow_through', False)}
+    # The fuzzer keeps the modules linked by their values in order, after the reordered ones
+    pinned = {namify(m.name) for m in fuzz_linked} - flow
+    unordered = [i for i in simulators if i not in flow and i not in pinned]
+    simulators = unordered + [i for i in simulators if i in pinned] + \
+        [i for i in simulators if i in flow]
+
     # Add simulators for all non-downstream modules
     for sim in simulators:
         fd.write(f"Simulator::simulate_{sim}, ")
@@ -390,9 +561,13 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
         init_file_path = init_file_path.replace('//', '/')
         array = sram._payload  # pylint: disable=protected-access
         array_name = namify(array.name)
-        fd.write(f'  load_hex_file(&mut sim.{array_name}.payload, "{init_file_path}");\n')
+        loader = 'load_bin_file' if sram.init_format == 'bin' else 'load_hex_file'
+        fd.write(f'  {loader}(&mut sim.{array_name}.payload, "{init_file_path}");\n')
+    fd.write(dump_snapshot_restore(config))
+    fd.write(dump_occupancy_open(config))
 
-    # Set simulation threshold and other parameters
+    # The number of cycles to simulate. Cycle `i`, from 1 to `sim_threshold`, runs at stamp
+    # `i * 100`; the Verilog testbench clocks as many cycles, numbered alike.
     sim_threshold = config.get('sim_threshold', 100)
 
     # Add initial events for driver if present
@@ -413,6 +588,9 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
     randomization = ""
     if config.get('random', False):
         randomization = "    simulators.shuffle(&mut rng);\n"
+    elif fuzz is not None:
+        # The flow-through modules, last, still run after the modules pushing to them
+        randomization = f"    sim.fuzz_reorder(&mut simulators[..{len(unordered)}]);\n"
 
     # Get idle threshold parameter
     idle_threshold = config.get('idle_threshold', 5)
@@ -421,10 +599,7 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
     any_module_triggered = 'let any_module_triggered =' + \
                            ' || '.join([f"sim.{namify(m.name)}_triggered" for m in sys.modules])
 
-    fd.write(f"""
-      let mut idle_count = 0;
-      for i in 1..={sim_threshold} {{
-        sim.stamp = i * 100;
+    cycle_head = f"""
         sim.reset_downstream();
 {randomization}
         for simulate in simulators.iter() {{
@@ -436,7 +611,32 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
         }}
 
         {any_module_triggered};
-
+{dump_watchdog_check(watchdogs)}"""
+    cycle_tail = """
+        sim.stamp += 50;
+        sim.tick_registers();
+        sim.reset_dram();
+        unsafe {
+            // Tick all DRAM memory interfaces
+"""
+    for dram in dram_modules:
+        dram_name = namify(dram.name)
+        cycle_tail += f"            sim.mi_{dram_name}.frontend_tick();\n"
+        cycle_tail += f"            sim.mi_{dram_name}.memory_system_tick();\n"
+    cycle_tail += "        }\n"
+    cycle_tail += dump_snapshot_trigger(config)
+    cycle_tail += dump_occupancy_record(config)
+    if sim_stats:
+        cycle_tail += "        sim.record_histograms();\n"
+
+    if config.get('cosim', False):
+        dump_cosim_loop(fd, cycle_head, cycle_tail)
+    else:
+        fd.write(f"""
+      let mut idle_count = 0;
+      for i in 1..={sim_threshold} {{
+        sim.stamp = i * 100;
+{cycle_head}
         // Handle idle threshold
         if !any_module_triggered {{
           idle_count += 1;
@@ -447,21 +647,11 @@ def dump_simulator( #pylint: disable=too-many-locals, too-many-branches, too-man
         }} else {{
           idle_count = 0;
         }}
-
-        sim.stamp += 50;
-        sim.tick_registers();
-        sim.reset_dram();
-        unsafe {{
-            // Tick all DRAM memory interfaces
+{cycle_tail}
+      }}
 """)
-
-    for dram in dram_modules:
-        dram_name = namify(dram.name)
-        fd.write(f"            sim.mi_{dram_name}.frontend_tick();\n")
-        fd.write(f"            sim.mi_{dram_name}.memory_system_tick();\n")
-
-    fd.write("        }\n")
-    fd.write("      }\n")
+    if sim_stats or cover:
+        fd.write("      sim.dump_stats();\n")
     fd.write("    ")
 
     # Close simulate function
@@ -0,0 +1,74 @@
+"""Snapshot and restore of the array contents of the generated simulator.
+
+A snapshot is a text file with one `<array> <index> <value>` line per array element, in
+decimal, after a `# cycle <n>` header. FIFOs are not included."""
+
+from __future__ import annotations
+
+import json
+import os
+
+SNAPSHOT_MSG = 'Array snapshot written to'
+
+
+def snapshot_path(config: dict, cycle: int) -> str:
+    """The absolute path of the snapshot taken at `cycle`, in the system directory."""
+    path = os.path.join(config.get('path', os.getcwd()), f'snapshot_{cycle}.txt')
+    return os.path.abspath(path)
+
+
+def dump_snapshot_methods(arrays, fd):
+    """Generate `snapshot_arrays` and `restore_arrays` of `Simulator`.
+
+    `arrays` lists the `(field name, Rust type)` of every array field of the simulator."""
+    fd.write("  pub fn snapshot_arrays(&self, path: &str) {\n")
+    fd.write('    let mut out = format!("# cycle {}\\n", self.stamp / 100);\n')
+    for name, ty in arrays:
+        shown = '*v as u8' if ty == 'bool' else 'v'
+        fd.write(f"    for (i, v) in self.{name}.payload.iter().enumerate() {{\n")
+        fd.write(f'      out.push_str(&format!("{name} {{}} {{}}\\n", i, {shown}));\n')
+        fd.write("    }\n")
+    fd.write('    std::fs::write(path, out).expect("Failed to write the array snapshot");\n')
+    fd.write(f'    println!("{SNAPSHOT_MSG} {{}}", path);\n')
+    fd.write("  }\n\n")
+
+    fd.write("  pub fn restore_arrays(&mut self, path: &str) {\n")
+    fd.write("    let text = std::fs::read_to_string(path)\n")
+    fd.write('      .expect("Failed to read the array snapshot");\n')
+    fd.write("    for line in text.lines() {\n")
+    fd.write("      let toks: Vec<&str> = line.split_whitespace().collect();\n")
+    fd.write("      if toks.is_empty() || toks[0] == \"#\" {\n")
+    fd.write("        continue;\n")
+    fd.write("      }\n")
+    fd.write('      let idx: usize = toks[1].parse().expect("Bad index in the array snapshot");\n')
+    fd.write("      match toks[0] {\n")
+    for name, ty in arrays:
+        if ty == 'bool':
+            parsed = 'toks[2] != "0"'
+        else:
+            parsed = 'toks[2].parse().expect("Bad value in the array snapshot")'
+        fd.write(f'        "{name}" => self.{name}.payload[idx] = {parsed},\n')
+    fd.write('        _ => panic!("Unknown array {} in the snapshot", toks[0]),\n')
+    fd.write("      }\n")
+    fd.write("    }\n")
+    fd.write("  }\n\n")
+
+
+def dump_snapshot_trigger(config: dict) -> str:
+    """The code appended to the end of every cycle to take the snapshot, if asked for."""
+    cycle = config.get('snapshot_at')
+    if cycle is None:
+        return ''
+    path = json.dumps(snapshot_path(config, cycle))
+    return f"""        if sim.stamp / 100 == {cycle} {{
+          sim.snapshot_arrays({path});
+        }}
+"""
+
+
+def dump_snapshot_restore(config: dict) -> str:
+    """The code seeding the arrays from a snapshot before the simulation, if asked for."""
+    restore_from = config.get('restore_from')
+    if restore_from is None:
+        return ''
+    return f"  sim.restore_arrays({json.dumps(os.path.abspath(restore_from))});\n"
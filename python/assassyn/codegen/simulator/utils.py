@@ -1,6 +1,6 @@
 """Utility functions for simulator generation."""
 
-from ...ir.dtype import DType, Void, ArrayType, Record, Bits
+from ...ir.dtype import DType, Void, ArrayType, Record, Bits, Str
 from ...ir.module import Port
 from ...utils import namify
 
@@ -51,6 +51,9 @@ def dtype_to_rust_type(dtype: DType) -> str:
     if isinstance(dtype, Void):
         return "Box<EventKind>"
 
+    if isinstance(dtype, Str):
+        return "String"
+
     if isinstance(dtype, ArrayType):
         elem_ty = dtype_to_rust_type(dtype.scalar_ty)
         size = dtype.size
@@ -58,6 +61,28 @@ def dtype_to_rust_type(dtype: DType) -> str:
     raise ValueError(f"Unsupported data type: {dtype}")
 
 
+def fit_to_width(code: str, dtype: DType) -> str:
+    """Wrap the Rust value `code` of `dtype` to the width of `dtype`.
+
+    A type narrower than its Rust integer, e.g. `uint<17>` in a `u32`, is kept in the low bits:
+    unsigned values are masked, and signed values are sign-extended from their top bit, so that
+    wrapping results match the hardware. Other types are returned unchanged."""
+    if isinstance(dtype, Record) or not (dtype.is_int() or dtype.is_raw()):
+        return code
+    rust_ty = dtype_to_rust_type(dtype)
+    bits = dtype.bits
+    if rust_ty == 'BigUint':
+        return f"UnsignedValueCastTo::<BigUint>::unsigned_cast(&({code}), {bits})"
+    if rust_ty == 'BigInt':
+        return f"SignedValueCastTo::<BigInt>::signed_cast(&({code}), {bits})"
+    if rust_ty == 'bool' or int(rust_ty[1:]) == bits:
+        return code
+    if rust_ty.startswith('u'):
+        return f"(({code}) & {(1 << bits) - 1}{rust_ty})"
+    shift = int(rust_ty[1:]) - bits
+    return f"{{ let v: {rust_ty} = {code}; (v << {shift}) >> {shift} }}"
+
+
 def int_imm_dumper_impl(ty: DType, value: int) -> str:
     """Generate Rust code for integer immediate values.
 
@@ -70,7 +95,23 @@ def int_imm_dumper_impl(ty: DType, value: int) -> str:
         return f"{value}{dtype_to_rust_type(ty)}"
 
     scalar_ty = "i64" if ty.is_signed() else "u64"
-    return f"ValueCastTo::<{dtype_to_rust_type(ty)}>::cast(&({value} as {scalar_ty}))"
+    lo, hi = (-(1 << 63), 1 << 63) if ty.is_signed() else (0, 1 << 64)
+    if lo <= value < hi:
+        return f"ValueCastTo::<{dtype_to_rust_type(ty)}>::cast(&({value} as {scalar_ty}))"
+    # Beyond the 64-bit literals, parsed from hexadecimal digits, with a sign for BigInt
+    digits = f"{'-' if value < 0 else ''}{abs(value):x}"
+    return f'{dtype_to_rust_type(ty)}::parse_bytes(b"{digits}", 16).unwrap()'
+
+
+def str_literal(value: str) -> str:
+    """Generate a Rust string literal holding `value`."""
+    escaped = value.replace('\\', '\\\\').replace('"', '\\"').replace('\n', '\\n')
+    return f'"{escaped}"'
+
+
+def str_imm_dumper_impl(value: str) -> str:
+    """Generate Rust code for a string immediate, an element of an array of `Str`."""
+    return f'String::from({str_literal(value)})'
 
 
 def fifo_name(fifo: Port):
@@ -80,3 +121,12 @@ def fifo_name(fifo: Port):
     """
     module = fifo.module
     return f"{namify(module.name)}_{namify(fifo.name)}"
+
+
+def fifo_front(fifo: Port) -> str:
+    """Generate the call getting the front of a FIFO, as an `Option<&T>`.
+
+    A flow-through FIFO also sees the value pushed in the current cycle."""
+    if fifo.module.flow_through:
+        return "flow_front(sim.stamp - sim.stamp % 100 + 50)"
+    return "front()"
@@ -15,7 +15,7 @@ from typing import Dict, Iterable, List, Optional
 
 from ...ir.dtype import DType
 from ...ir.module.external import ExternalSV
-from ...utils import namify, repo_path
+from ...utils import namify, repo_path, sanitize
 from .utils import camelize
 
 
@@ -89,7 +89,7 @@ def _dtype_to_port(name: str, wire_spec) -> FFIPort:
         c_type = _C_INT_TYPES_UNSIGNED[storage_bits]
         rust_type = _RUST_INT_TYPES_UNSIGNED[storage_bits]
     return FFIPort(
-        name=namify(name),
+        name=sanitize(name),  # Verilator names the C++ member after the SV port
         direction=wire_spec.direction,
         dtype=dtype,
         bits=bits,
@@ -0,0 +1,54 @@
+"""Watchdogs of the generated simulator.
+
+Each `watchdog(signal, cycles)` intrinsic owns a `<name>_seen` field of `Simulator`, holding
+the last cycle its signal was seen high, 0 at reset. The module sets it when it executes the
+intrinsic with the signal high, and `check_watchdogs` panics at the end of every cycle in
+which a watchdog has gone more than `cycles` cycles without its signal."""
+
+from __future__ import annotations
+
+from typing import List, Tuple
+
+from ...ir.expr.intrinsic import Intrinsic
+from ...utils import namify, unwrap_operand
+
+WATCHDOG_MSG = 'Watchdog expired'
+
+
+def watchdog_field(node: Intrinsic) -> str:
+    """The name of the simulator field of a watchdog.
+
+    Expression names are only unique within a module, so the name of the module is included."""
+    return f"watchdog_{namify(node.parent.name)}_{namify(node.as_operand())}"
+
+
+def collect_watchdogs(sys) -> List[Tuple[object, Intrinsic]]:
+    """Collect the (module, intrinsic) of every watchdog of `sys`, in body order."""
+    return [
+        (module, expr)
+        for module in sys.modules + sys.downstreams
+        for expr in module.body or []
+        if isinstance(expr, Intrinsic) and expr.opcode == Intrinsic.WATCHDOG
+    ]
+
+
+def dump_watchdog_methods(watchdogs, fd):
+    """Generate `check_watchdogs` of `Simulator`."""
+    fd.write("  pub fn check_watchdogs(&self) {\n")
+    fd.write("    let cycle = self.stamp / 100;\n")
+    for module, node in watchdogs:
+        field = watchdog_field(node)
+        cycles = unwrap_operand(node.args[1]).value
+        signal = unwrap_operand(node.args[0]).as_operand()
+        fd.write(f"    if cycle - self.{field}_seen > {cycles} {{\n")
+        fd.write(f'      panic!("{WATCHDOG_MSG}: {signal} of {namify(module.name)} has not been '
+                 f'seen for {cycles} cycles at cycle {{}}", cycle);\n')
+        fd.write("    }\n")
+    fd.write("  }\n\n")
+
+
+def dump_watchdog_check(watchdogs) -> str:
+    """The code appended to every cycle, once all the modules ran, to check the watchdogs."""
+    if not watchdogs:
+        return ''
+    return "        sim.check_watchdogs();\n"
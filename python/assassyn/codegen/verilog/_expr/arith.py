@@ -50,6 +50,10 @@ def codegen_binary_op(dumper, expr: BinaryOp) -> Optional[str]:
             f".{dump_type_cast(dtype)}"
         )
 
+    if expr.is_saturating():
+        _codegen_saturating(dumper, expr, a, b, rval)
+        return None
+
     if binop == BinaryOp.MOD:
         if expr.dtype.is_signed():
             op_class_name = "comb.ModSOp"
@@ -87,6 +91,35 @@ def codegen_binary_op(dumper, expr: BinaryOp) -> Optional[str]:
     return f'{rval} = {op_body}'
 
 
+def _codegen_saturating(dumper, expr: BinaryOp, a: str, b: str, rval: str):
+    """Expand a saturating add/sub into a one-bit-wider operation and a clamp mux."""
+    dtype = expr.dtype
+    bits = dtype.bits
+    signed = dtype.is_signed()
+
+    def widen(x):
+        msb = f"{x}.as_bits()[{bits - 1}]" if signed else "Bits(1)(0)"
+        return f"BitsSignal.concat([{msb}, {x}.as_bits()])"
+
+    op_class_name = "comb.AddOp" if expr.opcode == BinaryOp.ADD_SAT else "comb.SubOp"
+    dumper.append_code(f"{rval}_wide = {op_class_name}({widen(a)}, {widen(b)})")
+    wrapped = f"{rval}_wide[0:{bits}]"
+    if signed:
+        # The exact result does not fit iff its two top bits differ, and then the top bit
+        # tells the direction of the overflow.
+        hi, lo = (1 << (bits - 1)) - 1, 1 << (bits - 1)
+        dumper.append_code(
+            f"{rval}_clamp = Mux({rval}_wide[{bits}], Bits({bits})({hi}), Bits({bits})({lo}))")
+        dumper.append_code(
+            f"{rval}_ovf = {rval}_wide[{bits}] ^ {rval}_wide[{bits - 1}]")
+        body = f"Mux({rval}_ovf, {wrapped}, {rval}_clamp)"
+    else:
+        # The carry (borrow) out of an unsigned add (sub) clamps it to the max (zero).
+        bound = (1 << bits) - 1 if expr.opcode == BinaryOp.ADD_SAT else 0
+        body = f"Mux({rval}_wide[{bits}], {wrapped}, Bits({bits})({bound}))"
+    dumper.append_code(f"{rval} = {body}.{dump_type_cast(dtype)}")
+
+
 def codegen_unary_op(dumper, expr: UnaryOp) -> Optional[str]:
     """Generate code for unary operations."""
     uop = expr.opcode
@@ -109,12 +142,40 @@ def codegen_slice(dumper, expr: Slice) -> Optional[str]:
     return f"{rval} = {a}.as_bits()[{l}:{r+1}]"
 
 
+def _is_chained_concat(dumper, expr) -> bool:
+    """Check if a Concat only feeds another Concat, so it can be folded into its user."""
+    if not isinstance(expr, Concat) or len(expr.users) != 1:
+        return False
+    sys = getattr(dumper, 'sys', None)
+    if sys is not None and expr in sys.exposed_nodes:
+        return False
+    user = expr.users[0].user
+    return isinstance(user, Concat) and user.parent is expr.parent
+
+
+def _concat_leaves(dumper, expr: Concat) -> list:
+    """Collect the operands of a Concat chain, MSB first."""
+    leaves = []
+    for operand in (expr.msb, expr.lsb):
+        value = operand.value
+        if _is_chained_concat(dumper, value):
+            leaves.extend(_concat_leaves(dumper, value))
+        else:
+            leaves.append(value)
+    return leaves
+
+
 def codegen_concat(dumper, expr: Concat) -> Optional[str]:
-    """Generate code for concatenation operations."""
-    a = dumper.dump_rval(expr.msb, False)
-    b = dumper.dump_rval(expr.lsb, False)
+    """Generate code for concatenation operations.
+
+    A chain of single-use Concats is emitted as one flat concat at its root."""
+    if _is_chained_concat(dumper, expr):
+        return None
+    leaves = ", ".join(
+        f"{dumper.dump_rval(leaf, False)}.as_bits()" for leaf in _concat_leaves(dumper, expr)
+    )
     rval = dumper.dump_rval(expr, False)
-    return f"{rval} = BitsSignal.concat([{a}.as_bits(), {b}.as_bits()])"
+    return f"{rval} = BitsSignal.concat([{leaves}])"
 
 
 def codegen_cast(dumper, expr: Cast) -> Optional[str]:
@@ -8,11 +8,14 @@ including PureIntrinsic, Intrinsic, and Log.
 from typing import Optional, TYPE_CHECKING
 from string import Formatter
 
+from ....analysis.clock_domain import domain_periods, module_domain
 from ....ir.expr import Log
 from ....ir.expr.intrinsic import PureIntrinsic, Intrinsic, ExternalIntrinsic
 from ....ir.const import Const
 from ....ir.dtype import Int
 from ....utils import unwrap_operand, namify
+from ..utils import dump_type_cast, ensure_bits
+from ..dpi_log import dpi_log_call, sv_format, sv_spec
 
 if TYPE_CHECKING:
     from ..design import CIRCTDumper
@@ -39,6 +42,11 @@ def codegen_log(dumper, expr: Log) -> Optional[str]:
             name = name[5:]
         return name.replace(".", "_")
 
+    # The same conditions and arguments in the SystemVerilog of tb_vcs.sv, for `log_dpi`
+    sv_conditions = []
+    sv_args = []
+    sv_fracs = []
+
     meta_cond = expr.meta_cond
     if meta_cond is None:
         raise ValueError("Log.meta_cond is unexpectedly missing")
@@ -46,20 +54,41 @@ def codegen_log(dumper, expr: Log) -> Optional[str]:
     if isinstance(meta_cond, Const):
         if meta_cond.value == 0:
             append_condition('False')
+            sv_conditions.append("1'b0")
     else:
         exposed_name = _sanitize(dumper.dump_rval(meta_cond, True))
         valid_signal = f'dut.{module_name}.valid_{exposed_name}.value'
         expose_signal = f'dut.{module_name}.expose_{exposed_name}.value'
         append_condition(f'({valid_signal} & {expose_signal})')
-
-    for i in expr.operands[1:]:
+        sv_conditions.append(f'dut.{module_name}.valid_{exposed_name} && '
+                             f'dut.{module_name}.expose_{exposed_name}')
+
+    # A module of a slower clock domain holds its values over several cycles of the default
+    # clock, and is printed in the first one, where the simulator runs it
+    domain = module_domain(dumper.current_module)
+    if domain is not None:
+        period = domain_periods(dumper.clock_domains)[domain]
+        append_condition(f'int(dut.global_cycle_count.value) * {period.denominator} '
+                         f'% {period.numerator} < {period.denominator}')
+        sv_conditions.append(f'dut.global_cycle_count * {period.denominator} '
+                             f'% {period.numerator} < {period.denominator}')
+
+    for idx, i in enumerate(expr.operands[1:]):
         operand = unwrap_operand(i)
+        frac = expr.fixed[idx].frac_bits if idx in expr.fixed else None
         if not isinstance(operand, Const):
             exposed_name = _sanitize(dumper.dump_rval(operand, True))
             valid_signal = f'dut.{module_name}.valid_{exposed_name}.value'
             condition_snippets.append(valid_signal)
 
             base_value = f"dut.{module_name}.expose_{exposed_name}.value"
+            sv_conditions.append(f'dut.{module_name}.valid_{exposed_name}')
+            sv_value = f'dut.{module_name}.expose_{exposed_name}'
+            if isinstance(operand.dtype, Int):
+                sv_value = f'$signed({sv_value})'
+            if frac is not None:
+                sv_value = f'$itor({sv_value}) / {1 << frac}.0'
+            sv_args.append(sv_value)
             if isinstance(operand.dtype, Int):
                 bits = operand.dtype.bits
                 expose_signal = (
@@ -68,12 +97,20 @@ def codegen_log(dumper, expr: Log) -> Optional[str]:
                 )
             else:
                 expose_signal = f"int({base_value})"
+            if frac is not None:
+                expose_signal = f"format(({expose_signal}) / {1 << frac}, '.{frac}f')"
             arg_print_snippets.append(expose_signal)
+        elif frac is not None:
+            arg_print_snippets.append(repr(f'{operand.value / (1 << frac):.{frac}f}'))
+            sv_args.append(f'{operand.value / (1 << frac)!r}')
         else:
             arg_print_snippets.append(str(operand.value))
+            sv_args.append(str(operand.value))
+        sv_fracs.append(frac)
 
     f_string_content_parts = []
     arg_iterator = iter(arg_print_snippets)
+    sv_specs = []
 
     for literal_text, field_name, format_spec, conversion \
         in Formatter().parse(formatter_str):
@@ -82,6 +119,8 @@ def codegen_log(dumper, expr: Log) -> Optional[str]:
             f_string_content_parts.append(literal_text)
 
         if field_name is not None:
+            if dumper.log_dpi:
+                sv_specs.append(sv_spec(format_spec, sv_fracs[len(sv_specs)]))
             if format_spec == '?':
                 conversion = 'r'
                 format_spec = None
@@ -121,6 +160,13 @@ def codegen_log(dumper, expr: Log) -> Optional[str]:
     else:
         dumper.logs.append(f'print({final_print_string})')
 
+    if dumper.log_dpi:
+        fmt = f'{line_info} Cycle @%0d.00: {module_info:<20} '
+        fmt += sv_format(formatter_str, sv_specs)
+        dumper.dpi_logs.append(f'// {expr.loc}')
+        dumper.dpi_logs.append(dpi_log_call(sv_conditions, namify(dumper.current_module.name),
+                                            fmt, ['dut.global_cycle_count'] + sv_args))
+
 
 def _handle_fifo_intrinsic(dumper, expr, intrinsic, rval):
     """Handle FIFO_VALID and FIFO_PEEK intrinsics."""
@@ -134,6 +180,23 @@ def _handle_fifo_intrinsic(dumper, expr, intrinsic, rval):
     return f'{rval} = self.{fifo_name}_valid'
 
 
+def _handle_fifo_lookahead(dumper, expr, intrinsic, rval):
+    """Handle FIFO_PEEK_N and FIFO_VALID_N intrinsics, reading the lookahead taps."""
+    if intrinsic not in (PureIntrinsic.FIFO_PEEK_N, PureIntrinsic.FIFO_VALID_N):
+        return None
+
+    fifo = expr.args[0]
+    fifo_name = dumper.dump_rval(fifo, False)
+    k = expr.lookahead
+    if intrinsic == PureIntrinsic.FIFO_VALID_N:
+        return f'{rval} = self.{fifo_name}_peek_valid[{k - 1}]'
+    bits = fifo.dtype.bits
+    return (
+        f'{rval} = self.{fifo_name}_peek_data[{(k - 1) * bits}:{k * bits}]'
+        f'.{dump_type_cast(fifo.dtype)}'
+    )
+
+
 def _handle_value_valid(dumper, expr, intrinsic, rval):
     """Handle VALUE_VALID intrinsic."""
     if intrinsic != PureIntrinsic.VALUE_VALID:
@@ -211,7 +274,8 @@ def codegen_pure_intrinsic(dumper, expr: PureIntrinsic) -> Optional[str]:
     if intrinsic == PureIntrinsic.CURRENT_CYCLE:
         return f"{rval} = self.cycle_count"
 
-    for handler in (_handle_fifo_intrinsic, _handle_value_valid, _handle_external_output):
+    for handler in (_handle_fifo_intrinsic, _handle_fifo_lookahead, _handle_value_valid,
+                    _handle_external_output):
         result = handler(dumper, expr, intrinsic, rval)
         if result is not None:
             return result
@@ -287,14 +351,26 @@ def codegen_intrinsic(dumper, expr: Intrinsic) -> Optional[str]:
         return None
     if intrinsic == Intrinsic.ASSERT:
         return None
+    if intrinsic == Intrinsic.WATCHDOG:
+        # The counter is emitted by cleanup, once executed_wire is known
+        return None
     if intrinsic == Intrinsic.WAIT_UNTIL:
         cond = dumper.dump_rval(expr.args[0], False)
+        pred_value = unwrap_operand(expr.meta_cond)
+        if pred_value is not None and not (isinstance(pred_value, Const) and pred_value.value):
+            # Inside a condition block, the module only waits when the block is taken
+            pred = dumper.format_predicate(expr.meta_cond, extra_conditions=[])
+            cond = f'(~{pred} | {ensure_bits(cond)})'
         dumper.wait_conditions.append(cond)
         return None
     if intrinsic == Intrinsic.PUSH_CONDITION:
         return None
     if intrinsic == Intrinsic.POP_CONDITION:
         return None
+    if intrinsic == Intrinsic.PUSH_ELSE:
+        # The predicate of an else block is the negation of the condition it shares
+        cond = dumper.dump_rval(expr.args[0], False)
+        return f'{dumper.dump_rval(expr, False)} = ~({ensure_bits(cond)})'
     if intrinsic == Intrinsic.EXTERNAL_INSTANTIATE:
         # Should be handled by ExternalIntrinsic check above
         raise RuntimeError("EXTERNAL_INSTANTIATE should be handled by ExternalIntrinsic")
@@ -183,6 +183,10 @@ class FIFOAnalysisVisitor(Visitor):
             metadata.record_finish(node)
             return
 
+        if intrinsic == Intrinsic.WATCHDOG:
+            metadata.record_watchdog(node)
+            return
+
         if intrinsic == Intrinsic.ASSERT:
             if node.args:
                 self._record_value_exposure(metadata, node.args[0])
@@ -275,3 +279,52 @@ def collect_external_metadata(sys: "SysBuilder") -> ExternalRegistry:
 
     registry.freeze()
     return registry
+
+
+_FIFO_STATUS = (
+    PureIntrinsic.FIFO_VALID,
+    PureIntrinsic.FIFO_PEEK,
+    PureIntrinsic.FIFO_VALID_N,
+    PureIntrinsic.FIFO_PEEK_N,
+)
+
+
+def _reads_fifo_status(value, port, visited: Set[int]):
+    """Find a read of the valid or peek signals of *port* that *value* depends on."""
+    value = unwrap_operand(value)
+    if not isinstance(value, Expr) or id(value) in visited:
+        return None
+    visited.add(id(value))
+    if isinstance(value, PureIntrinsic) and value.opcode in _FIFO_STATUS \
+            and unwrap_operand(value.args[0]) is port:
+        return value
+    # Side-effect expressions are not values, so they never feed a predicate
+    for operand in value.operands:
+        found = _reads_fifo_status(operand, port, visited)
+        if found is not None:
+            return found
+    return None
+
+
+def find_pop_ready_loop(port):
+    """Find a status read of *port* that the predicate of one of its pops depends on.
+
+    The predicate of a pop drives the ``pop_ready`` of the FIFO. Returns the offending
+    ``valid``/``peek`` intrinsic, or None if the pops do not depend on the FIFO status.
+    """
+    module = port.module
+    waits = []
+    for expr in module.body or []:
+        if isinstance(expr, Intrinsic) and expr.opcode == Intrinsic.WAIT_UNTIL:
+            waits.append(expr)
+            continue
+        if not isinstance(expr, FIFOPop) or unwrap_operand(expr.fifo) is not port:
+            continue
+        visited: Set[int] = set()
+        # The predicate is the condition carry and the waits the pop comes after
+        for value in [expr.meta_cond] + [w.meta_cond for w in waits] + \
+                [w.args[0] for w in waits]:
+            found = _reads_fifo_status(value, port, visited)
+            if found is not None:
+                return found
+    return None
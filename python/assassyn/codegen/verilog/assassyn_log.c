@@ -0,0 +1,55 @@
+// The DPI-C side of the `log_dpi` option: tb_vcs.sv calls `assassyn_log` once per printed log.
+// A callback registered with `assassyn_set_log_callback` receives each log; without one, the
+// logs are appended to the file named by $ASSASSYN_LOG_FILE, `assassyn_log.bin` by default, as
+// records of a 32-bit level, then the module name and the message, each a 32-bit length
+// followed by its bytes, all integers being little-endian.
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+typedef void (*assassyn_log_callback_t)(int level, const char *module, const char *msg);
+
+static assassyn_log_callback_t callback = NULL;
+static FILE *records = NULL;
+
+void assassyn_set_log_callback(assassyn_log_callback_t cb) { callback = cb; }
+
+static void close_records(void) {
+  if (records) {
+    fclose(records);
+    records = NULL;
+  }
+}
+
+static void put_u32(uint32_t value) {
+  unsigned char bytes[4] = {value & 0xff, (value >> 8) & 0xff, (value >> 16) & 0xff,
+                            (value >> 24) & 0xff};
+  fwrite(bytes, 1, sizeof(bytes), records);
+}
+
+static void put_str(const char *text) {
+  size_t length = strlen(text);
+  put_u32((uint32_t)length);
+  fwrite(text, 1, length, records);
+}
+
+void assassyn_log(int level, const char *module, const char *msg) {
+  if (callback) {
+    callback(level, module, msg);
+    return;
+  }
+  if (!records) {
+    const char *path = getenv("ASSASSYN_LOG_FILE");
+    records = fopen(path ? path : "assassyn_log.bin", "wb");
+    if (!records) {
+      perror("assassyn_log");
+      return;
+    }
+    // $finish exits the process, which flushes the records
+    atexit(close_records);
+  }
+  put_u32((uint32_t)level);
+  put_str(module);
+  put_str(msg);
+}
@@ -0,0 +1,85 @@
+// A FIFO between two clock domains. The pusher and the popper each keep a binary pointer
+// and its gray code, and see the gray pointer of the other side through SYNC_STAGES flops,
+// so that at most one bit of it changes per cycle of the other clock.
+module async_fifo #(
+    parameter WIDTH = 8,
+    parameter DEPTH_LOG2 = 2, // At least 1, the pointers having one more bit than the index
+    parameter SYNC_STAGES = 2
+) (
+    input  logic               push_clk,
+    input  logic               push_rst_n,
+    input  logic               push_valid,
+    input  logic [WIDTH - 1:0] push_data,
+    output logic               push_ready,
+
+    input  logic               pop_clk,
+    input  logic               pop_rst_n,
+    output logic               pop_valid,
+    output logic [WIDTH - 1:0] pop_data,
+    input  logic               pop_ready
+);
+
+localparam DEPTH = 1 << DEPTH_LOG2;
+// The FIFO is full when the gray pointers differ in exactly their two top bits.
+localparam logic [DEPTH_LOG2:0] FULL_MASK = 3 << (DEPTH_LOG2 - 1);
+
+logic [WIDTH - 1:0] q[0:DEPTH - 1];
+
+logic [DEPTH_LOG2:0] push_bin;
+logic [DEPTH_LOG2:0] push_gray;
+logic [DEPTH_LOG2:0] push_bin_next;
+logic [DEPTH_LOG2:0] pop_bin;
+logic [DEPTH_LOG2:0] pop_gray;
+logic [DEPTH_LOG2:0] pop_bin_next;
+
+// The gray pointer of the other side, the last stage being the synchronized one.
+logic [DEPTH_LOG2:0] pop_gray_sync[0:SYNC_STAGES - 1];
+logic [DEPTH_LOG2:0] push_gray_sync[0:SYNC_STAGES - 1];
+
+assign push_ready    = (push_gray ^ FULL_MASK) != pop_gray_sync[SYNC_STAGES - 1];
+assign pop_valid     = pop_gray != push_gray_sync[SYNC_STAGES - 1];
+assign pop_data      = q[pop_bin[DEPTH_LOG2 - 1:0]];
+assign push_bin_next = push_bin + (push_valid && push_ready ? 1 : 0);
+assign pop_bin_next  = pop_bin + (pop_valid && pop_ready ? 1 : 0);
+
+always @(posedge push_clk) begin
+    if (push_valid && push_ready) begin
+        q[push_bin[DEPTH_LOG2 - 1:0]] <= push_data;
+    end
+end
+
+always @(posedge push_clk or negedge push_rst_n) begin
+    if (!push_rst_n) begin
+        push_bin <= 0;
+        push_gray <= 0;
+        for (int i = 0; i < SYNC_STAGES; i++) begin
+            pop_gray_sync[i] <= 0;
+        end
+    end else begin
+        push_bin <= push_bin_next;
+        push_gray <= push_bin_next ^ (push_bin_next >> 1);
+        pop_gray_sync[0] <= pop_gray;
+        for (int i = 1; i < SYNC_STAGES; i++) begin
+            pop_gray_sync[i] <= pop_gray_sync[i - 1];
+        end
+    end
+end
+
+always @(posedge pop_clk or negedge pop_rst_n) begin
+    if (!pop_rst_n) begin
+        pop_bin <= 0;
+        pop_gray <= 0;
+        for (int i = 0; i < SYNC_STAGES; i++) begin
+            push_gray_sync[i] <= 0;
+        end
+    end else begin
+        pop_bin <= pop_bin_next;
+        pop_gray <= pop_bin_next ^ (pop_bin_next >> 1);
+        push_gray_sync[0] <= push_gray;
+        for (int i = 1; i < SYNC_STAGES; i++) begin
+            push_gray_sync[i] <= push_gray_sync[i - 1];
+        end
+    end
+end
+
+endmodule
@@ -0,0 +1,221 @@
+"""The AXI4-Lite register map of the exposed arrays, and its slave in the Top harness."""
+
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass
+from pathlib import Path
+from typing import TYPE_CHECKING, Any, List, Union
+
+from ...analysis.clock_domain import array_domain
+from ...ir.array import Array
+from ...ir.memory.base import MemoryBase
+from ...utils import namify
+from .utils import dump_type_cast
+
+if TYPE_CHECKING:
+    from .design import CIRCTDumper
+else:
+    CIRCTDumper = Any  # type: ignore
+
+# The width of the data bus, each array element taking one word of it
+AXIL_DATA_WIDTH = 32
+
+# The exposing kinds which let the bus write an array, the others only read it
+WRITABLE_KINDS = ('Input', 'Inout')
+
+# The file the address map is written to, next to design.py, for the tools driving the bus
+REGMAP_FILE = 'regmap.json'
+
+
+@dataclass
+class AxilRegion:
+    '''The words of an exposed array on the AXI4-Lite bus.'''
+
+    array: Array
+    # The byte address of element 0, aligned to the power of two spanning the array
+    base: int
+    writable: bool
+
+    @property
+    def name(self) -> str:
+        '''The name of the array in the design.'''
+        return namify(self.array.name)
+
+    @property
+    def word(self) -> int:
+        '''The word address of element 0.'''
+        return self.base // (AXIL_DATA_WIDTH // 8)
+
+
+def axil_regmap(sys) -> List[AxilRegion]:
+    '''Lay out the exposed arrays of `sys` on the bus, one element per word, in exposing order.
+
+    Each array starts at a multiple of its size rounded up to a power of two, so that the
+    high bits of a word address select the array and its low bits the element. Exposed
+    expressions have no register to map, so they are left out.
+
+    Raises:
+        ValueError: If an exposed array is the payload of a memory, is clocked by another
+            domain, or has elements wider than the bus
+    '''
+    res = []
+    word = 0
+    for node, kind in sys.exposed_nodes.items():
+        if not isinstance(node, Array):
+            continue
+        owner = node.owner
+        if isinstance(owner, MemoryBase) and node.is_payload(owner):
+            raise ValueError(f'{node.name} is the payload of a memory, which has no register '
+                             'file to map on the AXI4-Lite bus')
+        if array_domain(node) is not None:
+            raise ValueError(f'{node.name} is clocked by domain {array_domain(node)}, but the '
+                             'AXI4-Lite bus is clocked by the default clock')
+        if node.scalar_ty.bits > AXIL_DATA_WIDTH:
+            raise ValueError(f'{node.name} has {node.scalar_ty.bits}-bit elements, wider than '
+                             f'the {AXIL_DATA_WIDTH}-bit AXI4-Lite bus')
+        span = 1 << node.index_bits
+        word = (word + span - 1) // span * span
+        res.append(AxilRegion(node, word * (AXIL_DATA_WIDTH // 8), kind in WRITABLE_KINDS))
+        word += span
+    return res
+
+
+def axil_addr_width(regions: List[AxilRegion]) -> int:
+    '''The bits of a byte address of the bus, covering all the words of `regions`.'''
+    words = max((i.word + (1 << i.array.index_bits) for i in regions), default=1)
+    return max((words - 1).bit_length(), 1) + 2
+
+
+def dump_regmap(path: Union[str, Path], regions: List[AxilRegion]) -> None:
+    '''Write the address map of `regions` as JSON, for the software driving the bus.'''
+    regmap = {
+        'addr_width': axil_addr_width(regions),
+        'data_width': AXIL_DATA_WIDTH,
+        'registers': [{
+            'name': region.name,
+            'base': region.base,
+            'size': region.array.size,
+            'bits': region.array.scalar_ty.bits,
+            'access': 'rw' if region.writable else 'ro',
+        } for region in regions],
+    }
+    with open(str(path), 'w', encoding='utf-8') as f:
+        json.dump(regmap, f, indent=2)
+        f.write('\n')
+
+
+def axil_ports(dumper: CIRCTDumper, array) -> tuple:
+    '''The (write, read) ports the bus adds to the register file of `array`.'''
+    region = dumper.axil_regions.get(array)
+    if region is None:
+        return 0, 0
+    return int(region.writable), 1
+
+
+def axil_port_list(regions: List[AxilRegion]) -> List[tuple]:
+    '''The (name, direction, bits) of the AXI4-Lite slave ports of the Top harness, the
+    names without their `s_axil_` prefix.'''
+    addr = axil_addr_width(regions)
+    strb = AXIL_DATA_WIDTH // 8
+    return [
+        ('awaddr', 'Input', addr), ('awvalid', 'Input', 1), ('awready', 'Output', 1),
+        ('wdata', 'Input', AXIL_DATA_WIDTH), ('wstrb', 'Input', strb),
+        ('wvalid', 'Input', 1), ('wready', 'Output', 1),
+        ('bresp', 'Output', 2), ('bvalid', 'Output', 1), ('bready', 'Input', 1),
+        ('araddr', 'Input', addr), ('arvalid', 'Input', 1), ('arready', 'Output', 1),
+        ('rdata', 'Output', AXIL_DATA_WIDTH), ('rresp', 'Output', 2),
+        ('rvalid', 'Output', 1), ('rready', 'Input', 1)]
+
+
+def declare_axil_ports(dumper: CIRCTDumper):
+    '''Declare the AXI4-Lite slave ports of the Top harness.'''
+    for name, direction, bits in axil_port_list(list(dumper.axil_regions.values())):
+        dumper.append_code(f's_axil_{name} = {direction}(Bits({bits}))')
+
+
+def generate_axil(dumper: CIRCTDumper, ports: dict):
+    '''Instantiate the AXI4-Lite slave in the Top harness and connect it to the arrays.
+
+    Args:
+        dumper: The dumper generating the Top harness
+        ports: The (write, read) port indices of the bus on the register file of each array,
+            the write one being None for a read-only array
+
+    The bus takes the last write port of an array, so a module writing the same element
+    in the same cycle wins over it.
+    '''
+    regions = list(dumper.axil_regions.values())
+    addr = axil_addr_width(regions)
+    word_bits = addr - 2
+    dumper.append_code('\n# --- AXI4-Lite Registers ---')
+    dumper.append_code(f'axil_rd_data = Wire(Bits({AXIL_DATA_WIDTH}))')
+    dumper.append_code('axil_rd_ok = Wire(Bits(1))')
+    dumper.append_code('axil_wr_ok = Wire(Bits(1))')
+    channels = ['awaddr', 'awvalid', 'wdata', 'wstrb', 'wvalid', 'bready',
+                'araddr', 'arvalid', 'rready']
+    connections = ', '.join(f's_axil_{i}=self.s_axil_{i}' for i in channels)
+    dumper.append_code(
+        f'axil = AxilRegs(ADDR_WIDTH={addr})(clk=self.clk, rst_n=~self.rst, {connections}, '
+        'rd_data=axil_rd_data, rd_ok=axil_rd_ok, wr_ok=axil_wr_ok)'
+    )
+    for name in ['awready', 'wready', 'bresp', 'bvalid', 'arready', 'rdata', 'rresp',
+                 'rvalid']:
+        dumper.append_code(f'self.s_axil_{name} = axil.s_axil_{name}')
+
+    rd_data = f'Bits({AXIL_DATA_WIDTH})(0)'
+    hits = []
+    writable_hits = []
+    for region in regions:
+        arr = region.array
+        arr_name = region.name
+        index_bits = arr.index_bits
+        bits = arr.scalar_ty.bits
+        write_port, read_port = ports[arr]
+        dumper.append_code(f'# {arr_name} at 0x{region.base:x}')
+        hit = f'axil_hit_{arr_name}'
+        if index_bits < word_bits:
+            dumper.append_code(
+                f'{hit} = axil.addr[{index_bits}:{word_bits}] == '
+                f'Bits({word_bits - index_bits})({region.word >> index_bits})'
+            )
+        else:
+            dumper.append_code(f'{hit} = Bits(1)(1)')
+        if arr.size < 1 << index_bits:
+            # The words past the last element of the span are not mapped
+            dumper.append_code(
+                f'{hit} = {hit} & (axil.addr[0:{index_bits}].as_uint() < '
+                f'UInt({index_bits})({arr.size}))'
+            )
+        hits.append(hit)
+
+        rdata = f'aw_{arr_name}_rdata_port{read_port}'
+        if index_bits > 0:
+            dumper.append_code(
+                f'aw_{arr_name}_ridx_port{read_port}.assign(axil.addr[0:{index_bits}])'
+            )
+        word = f'{rdata}.as_bits()'
+        if bits < AXIL_DATA_WIDTH:
+            word = f'BitsSignal.concat([Bits({AXIL_DATA_WIDTH - bits})(0), {word}])'
+        rd_data = f'Mux({hit}, {rd_data}, {word})'
+
+        if write_port is None:
+            continue
+        writable_hits.append(hit)
+        suffix = f'_port{write_port}'
+        dumper.append_code(f'aw_{arr_name}_w{suffix}.assign(axil.wr_en & {hit})')
+        dumper.append_code(
+            f'aw_{arr_name}_wdata{suffix}.assign('
+            f'axil.wr_data[0:{bits}].{dump_type_cast(arr.scalar_ty)})'
+        )
+        widx = f'axil.addr[0:{index_bits}]' if index_bits > 0 else 'Bits(1)(0)'
+        dumper.append_code(f'aw_{arr_name}_widx{suffix}.assign({widx})')
+
+    dumper.append_code(f'axil_rd_data.assign({rd_data})')
+    dumper.append_code(f"axil_rd_ok.assign(reduce(operator.or_, [{', '.join(hits)}]))")
+    if writable_hits:
+        dumper.append_code(
+            f"axil_wr_ok.assign(reduce(operator.or_, [{', '.join(writable_hits)}]))"
+        )
+    else:
+        dumper.append_code('axil_wr_ok.assign(Bits(1)(0))')
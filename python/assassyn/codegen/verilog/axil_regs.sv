@@ -0,0 +1,111 @@
+// An AXI4-Lite slave in front of the exposed arrays. It serves one transaction at a time:
+// the address of the accepted write or read is held in `addr`, the top decodes it into
+// `rd_data`, the word at `addr`, and into `wr_ok`/`rd_ok`, whether it may be written or read.
+// A write lands one cycle after its handshake, merging the strobed bytes into `rd_data`.
+module axil_regs #(
+    parameter ADDR_WIDTH = 4, // The bits of a byte address, two more than of a word one
+    parameter DATA_WIDTH = 32
+) (
+    input  logic                      clk,
+    input  logic                      rst_n,
+
+    input  logic [ADDR_WIDTH - 1:0]   s_axil_awaddr,
+    input  logic                      s_axil_awvalid,
+    output logic                      s_axil_awready,
+    input  logic [DATA_WIDTH - 1:0]   s_axil_wdata,
+    input  logic [DATA_WIDTH/8 - 1:0] s_axil_wstrb,
+    input  logic                      s_axil_wvalid,
+    output logic                      s_axil_wready,
+    output logic [1:0]                s_axil_bresp,
+    output logic                      s_axil_bvalid,
+    input  logic                      s_axil_bready,
+    input  logic [ADDR_WIDTH - 1:0]   s_axil_araddr,
+    input  logic                      s_axil_arvalid,
+    output logic                      s_axil_arready,
+    output logic [DATA_WIDTH - 1:0]   s_axil_rdata,
+    output logic [1:0]                s_axil_rresp,
+    output logic                      s_axil_rvalid,
+    input  logic                      s_axil_rready,
+
+    output logic [ADDR_WIDTH - 3:0]   addr,
+    input  logic [DATA_WIDTH - 1:0]   rd_data,
+    input  logic                      rd_ok,
+    input  logic                      wr_ok,
+    output logic                      wr_en,
+    output logic [DATA_WIDTH - 1:0]   wr_data
+);
+
+localparam logic [1:0] OKAY = 2'b00;
+localparam logic [1:0] SLVERR = 2'b10;
+
+typedef enum logic [2:0] { IDLE, WRITE, WRESP, READ, RRESP } state_t;
+state_t state;
+
+logic [DATA_WIDTH - 1:0]   wdata_q;
+logic [DATA_WIDTH/8 - 1:0] wstrb_q;
+logic [DATA_WIDTH - 1:0]   wmask;
+
+// A write is taken once both its address and data are valid, before any read.
+logic take_write;
+logic take_read;
+assign take_write = state == IDLE && s_axil_awvalid && s_axil_wvalid;
+assign take_read  = state == IDLE && !take_write && s_axil_arvalid;
+
+assign s_axil_awready = take_write;
+assign s_axil_wready  = take_write;
+assign s_axil_arready = take_read;
+assign s_axil_bvalid  = state == WRESP;
+assign s_axil_rvalid  = state == RRESP;
+
+always_comb begin
+    for (int i = 0; i < DATA_WIDTH / 8; i++) begin
+        wmask[i * 8 +: 8] = {8{wstrb_q[i]}};
+    end
+end
+
+assign wr_en   = state == WRITE && wr_ok;
+assign wr_data = (rd_data & ~wmask) | (wdata_q & wmask);
+
+always @(posedge clk or negedge rst_n) begin
+    if (!rst_n) begin
+        state <= IDLE;
+        addr <= '0;
+        wdata_q <= '0;
+        wstrb_q <= '0;
+        s_axil_bresp <= OKAY;
+        s_axil_rdata <= '0;
+        s_axil_rresp <= OKAY;
+    end else begin
+        case (state)
+            IDLE: begin
+                if (take_write) begin
+                    addr <= s_axil_awaddr[ADDR_WIDTH - 1:2];
+                    wdata_q <= s_axil_wdata;
+                    wstrb_q <= s_axil_wstrb;
+                    state <= WRITE;
+                end else if (take_read) begin
+                    addr <= s_axil_araddr[ADDR_WIDTH - 1:2];
+                    state <= READ;
+                end
+            end
+            WRITE: begin
+                s_axil_bresp <= wr_ok ? OKAY : SLVERR;
+                state <= WRESP;
+            end
+            WRESP: begin
+                if (s_axil_bready) state <= IDLE;
+            end
+            READ: begin
+                s_axil_rdata <= rd_ok ? rd_data : '0;
+                s_axil_rresp <= rd_ok ? OKAY : SLVERR;
+                state <= RRESP;
+            end
+            RRESP: begin
+                if (s_axil_rready) state <= IDLE;
+            end
+            default: state <= IDLE;
+        endcase
+    end
+end
+
+endmodule
@@ -3,15 +3,20 @@
 from collections import defaultdict
 from typing import TYPE_CHECKING, Callable, Dict, List, NamedTuple, Optional, Sequence, TypeVar
 
-from .utils import dump_type, dump_type_cast, get_sram_info
+from .utils import addr_width, dump_type, dump_type_cast, get_sram_info
+from .utils import module_probes, probe_field
+from ..simulator.watchdog import WATCHDOG_MSG, watchdog_field
+from .formal import formal_properties
+from ...analysis.coverage import cover_points
 
-from ...analysis.topo import get_upstreams
+from ...analysis.topo import get_upstreams, registered_inputs
 from ...ir.module import Downstream
 from ...ir.memory.sram import SRAM
 from ...ir.array import Slice
 from ...ir.memory.base import MemoryBase
 from ...ir.const import Const
 from ...ir.expr import Expr, FIFOPop, FIFOPush
+from ...ir.expr.intrinsic import Intrinsic
 from ...utils import namify, unwrap_operand
 
 if TYPE_CHECKING:
@@ -38,6 +43,19 @@ def _expr_wait_conditions(dumper, expr):
     return tuple(table.get(expr, ()))
 
 
+def _append_trace(dumper, enable: str, signal: str, data: str, bits: int, tag: str = 'trace'):
+    """Make the testbench print a `@<tag>` line in the cycles where `enable` is set.
+
+    The line matches the one of the simulator: the value is printed as the hexadecimal bits
+    of its width."""
+    mask = (1 << bits) - 1
+    dumper.logs.append(f'if {enable}.value:')
+    dumper.logs.append(
+        f'    print(f"@{tag} {{int(dut.global_cycle_count.value)}} {signal} '
+        f'{{int({data}.value) & {mask}:x}}")'
+    )
+
+
 def resolve_value_exposure_render(dumper, expr: Expr) -> ValueExposureRender:
     """Compute the rendered name, dtype, and rval for a value exposure."""
 
@@ -102,7 +120,7 @@ def generate_sram_control_signals(dumper, sram_info, module_view):
     elif read_addr:
         dumper.append_code(f'self.mem_address = {read_addr}.as_bits()')
     else:
-        dumper.append_code(f'self.mem_address = Bits({array.index_bits})(0)')
+        dumper.append_code(f'self.mem_address = Bits({addr_width(array.size)})(0)')
 
     dumper.append_code(f'self.mem_write_data = {write_data}')
     dumper.append_code('self.mem_read_enable = Bits(1)(1)')  # Always enable reads
@@ -172,11 +190,20 @@ def cleanup_post_generation(dumper):
             f'self.{namify(dep.name)}_executed'
             for dep in upstream_modules
         ]
+        # A registered value triggers the downstream in the cycle after it is computed
+        dep_signals += [
+            f'self.{dumper.get_external_port_name(value)}_valid'
+            for value in registered_inputs(node)
+        ]
 
         executed_expr = _format_reduction_expr(
             dep_signals,
             default_literal="Bits(1)(0)",
         )
+        if node.condition is not None:
+            # A conditional downstream also needs its condition valid and true
+            port = dumper.get_external_port_name(node.condition)
+            executed_expr = f'({executed_expr}) & self.{port}_valid & self.{port}.as_bits()'
         dumper.append_code(f"executed_wire = {executed_expr}")
     else:
         # Note: wait_until should NOT gate executed_wire because it should only
@@ -208,12 +235,76 @@ def cleanup_post_generation(dumper):
     )
     dumper.append_code(f"self.finish = {finish_expr}")
 
+    module_name = namify(dumper.current_module.name)
+    for watchdog in module_metadata.watchdogs:
+        # Counts the cycles since the signal was last seen, holding once expired
+        name = watchdog_field(watchdog)
+        cycles = unwrap_operand(watchdog.args[1]).value
+        bits = (cycles + 1).bit_length()
+        predicate = dumper.format_predicate(
+            watchdog.meta_cond,
+            extra_conditions=_expr_wait_conditions(dumper, watchdog),
+        )
+        signal = dumper.dump_rval(watchdog.args[0], False)
+        dumper.append_code(
+            f"{name}_seen = executed_wire & ({predicate}) & {signal}.as_bits()"
+        )
+        dumper.append_code(
+            f"{name}_count = Reg(UInt({bits}), clk=self.clk, rst=self.rst, rst_value=0)"
+        )
+        dumper.append_code(f"{name}_expired = {name}_count > UInt({bits})({cycles})")
+        dumper.append_code(
+            f"{name}_next = ({name}_count + UInt({bits})(1)).as_bits()[0:{bits}].as_uint()"
+        )
+        dumper.append_code(
+            f"{name}_count.assign(Mux({name}_seen, "
+            f"Mux({name}_expired, {name}_next, {name}_count), UInt({bits})(0)))"
+        )
+        dumper.append_code(f"self.{name} = {name}_expired")
+        dumper.logs.append(f'# {watchdog}')
+        dumper.logs.append(f'if dut.{module_name}.{name}.value:')
+        dumper.logs.append(
+            f'    raise AssertionError("{WATCHDOG_MSG}: {module_name} waited over {cycles} cycles")'
+        )
+
+    if dumper.formal:
+        for field, expr in formal_properties(dumper.current_module):
+            # An assertion is only checked after the waits before it, while the condition of
+            # a wait_until is assumed whenever the module is triggered
+            waits = _expr_wait_conditions(dumper, expr) if field.startswith('formal_assert') \
+                else ()
+            predicate = dumper.format_predicate(expr.meta_cond, extra_conditions=waits)
+            cond = dumper.dump_rval(expr.args[0], False)
+            dumper.append_code(
+                f"self.{field} = ~(executed_wire & ({predicate})) | {cond}.as_bits()"
+            )
+
+    if dumper.cover:
+        for field, block in cover_points(dumper.current_module):
+            # The predicate of a block is its condition, or the else intrinsic itself
+            predicate = dumper.format_predicate(
+                block.meta_cond, extra_conditions=_expr_wait_conditions(dumper, block))
+            taken = block.args[0] if block.opcode == Intrinsic.PUSH_CONDITION else block
+            taken = dumper.dump_rval(taken, False)
+            dumper.append_code(
+                f"self.{field} = executed_wire & ({predicate}) & {taken}.as_bits()"
+            )
+
+    for signal, label in module_probes(dumper.sys, dumper.current_module):
+        value = dumper.dump_rval(signal, False)
+        field = probe_field(label)
+        dumper.append_code(f"self.{field} = {value}.as_bits()")
+        # Valid in the cycles where the simulator computes the signal, and prints it
+        dumper.append_code(f"self.{field}_valid = executed_wire & ({dumper.get_pred(signal)})")
+        ports = f'dut.{namify(dumper.current_module.name)}.{field}'
+        _append_trace(dumper, f'{ports}_valid', label, ports, signal.dtype.bits, 'probe')
+
     if isinstance(dumper.current_module, SRAM):
         sram_info = get_sram_info(dumper.current_module)
         if sram_info:
             generate_sram_control_signals(dumper, sram_info, module_view)
 
-    all_arrays = set(module_view.writes.keys()) | set(module_view.reads.keys())
+    all_arrays = dict.fromkeys(list(module_view.writes) + list(module_view.reads))
     for arr in all_arrays:
         module_writes = list(module_view.writes.get(arr, ()))
         owner = arr.owner
@@ -286,6 +377,15 @@ def cleanup_post_generation(dumper):
 
                 dumper.append_code(f'self.{array_name}_wdata{port_suffix} = {wdata_expr}')
                 dumper.append_code(f'self.{array_name}_widx{port_suffix} = {widx_expr}.as_bits()')
+                if dumper.trace:
+                    ports = f'dut.{module_name}.{array_name}'
+                    _append_trace(
+                        dumper,
+                        f'{ports}_w{port_suffix}',
+                        f'{namify(arr.name)}[{{int({ports}_widx{port_suffix}.value)}}]',
+                        f'{ports}_wdata{port_suffix}',
+                        array_dtype.bits,
+                    )
 
         module_reads = module_view.reads.get(arr, ())
         if module_reads and arr.index_bits > 0:
@@ -318,8 +418,18 @@ def cleanup_post_generation(dumper):
         if isinstance(unwrap_operand(expr), Const):
             continue
         render = resolve_value_exposure_render(dumper, expr)
+        # Only the producing module registers the value, the others pass it through
+        registered = getattr(expr, 'registered', False) and \
+            getattr(expr, 'parent', None) is dumper.current_module
         dumper.append_code(f'# Expose: {expr}')
-        dumper.append_code(f'self.expose_{render.exposed_name} = {render.rval}')
+        exposed = render.rval
+        if registered:
+            # The consumers read the value and the valid of the previous cycle
+            exposed = f'expose_{render.exposed_name}_reg'
+            dumper.append_code(f'{exposed} = Reg({render.dtype_str}, clk=self.clk, '
+                               'rst=self.rst, rst_value=0)')
+            dumper.append_code(f'{exposed}.assign({render.rval})')
+        dumper.append_code(f'self.expose_{render.exposed_name} = {exposed}')
         predicate_terms = []
         for entry in grouped_exposures:
             predicate = getattr(entry, "meta_cond", None)
@@ -340,25 +450,30 @@ def cleanup_post_generation(dumper):
             if predicate_terms
             else "Bits(1)(1)"
         )
-        dumper.append_code(
-            f'self.valid_{render.exposed_name} = executed_wire & ({pred_condition})'
-        )
+        valid = f'executed_wire & ({pred_condition})'
+        if registered:
+            dumper.append_code(f'valid_{render.exposed_name}_reg = Reg(Bits(1), clk=self.clk, '
+                               'rst=self.rst, rst_value=0)')
+            dumper.append_code(f'valid_{render.exposed_name}_reg.assign({valid})')
+            valid = f'valid_{render.exposed_name}_reg'
+        dumper.append_code(f'self.valid_{render.exposed_name} = {valid}')
 
     async_groups = dumper.interactions.async_ledger.calls_for_module(dumper.current_module)
     for callee, trigger_entries in async_groups.items():
         rval = dumper.dump_rval(callee, False)
         trigger_predicates = [
-            dumper.format_predicate(
+            (dumper.format_predicate(
                 getattr(call, "meta_cond", None),
                 extra_conditions=_expr_wait_conditions(dumper, call),
-            )
+            ), getattr(call, "replication", 1))
             for call in trigger_entries
         ]
         if not trigger_predicates:
             dumper.append_code(f'self.{rval}_trigger = UInt(8)(0)')
             continue
         dumper.append_code(f'# Summing triggers for {rval}')
-        add_terms = [f"Mux({pred}, UInt(8)(0), UInt(8)(1))" for pred in trigger_predicates]
+        # A replicated call adds one credit per activation
+        add_terms = [f"Mux({pred}, UInt(8)(0), UInt(8)({n}))" for pred, n in trigger_predicates]
         sum_expression = f"reduce(operator.add, [{', '.join(add_terms)}])"
         resized_sum = f"(({sum_expression}).as_bits()[0:8].as_uint())"
         final_trigger_value = f"Mux(executed_wire, UInt(8)(0), {resized_sum})"
@@ -406,6 +521,15 @@ def cleanup_post_generation(dumper):
                 f"({fifo_predicate_expr}) & {ready_signal}"
             )
             dumper.append_code(f"{fifo_prefix}_push_data = {fifo_data_expr}")
+            if dumper.trace:
+                ports = f"dut.{module_name}.{namify(fifo_port.module.name)}_{fifo_name}"
+                _append_trace(
+                    dumper,
+                    f"{ports}_push_valid",
+                    f"{namify(fifo_port.module.name)}.{namify(fifo_port.name)}",
+                    f"{ports}_push_data",
+                    fifo_port.dtype.bits,
+                )
 
         if local_pops:
             pop_predicates = []
@@ -10,6 +10,7 @@ from pathlib import Path
 
 from .utils import (
     HEADER,
+    addr_width,
     dump_type,
     extract_sram_params,
     ensure_bits,
@@ -25,6 +26,7 @@ from ...ir.dtype import RecordValue
 from ...utils import namify, unwrap_operand
 from ...utils.enforce_type import enforce_type
 from ...ir.expr import Expr
+from ...ir.module.base import render_expr
 from ._expr import codegen_expr
 from .cleanup import cleanup_post_generation
 from .rval import dump_rval as dump_rval_impl
@@ -33,6 +35,8 @@ from .system import generate_system
 from .metadata import ExternalRegistry, InteractionMatrix, ModuleMetadata
 from .analysis import collect_external_metadata, collect_fifo_metadata
 from .array import ArrayMetadataRegistry
+from .axil import AxilRegion, axil_ports, axil_regmap, dump_regmap, REGMAP_FILE
+from .dpi_log import LOG_DPI_INCLUDE
 
 
 class CIRCTDumper(Visitor):  # pylint: disable=too-many-instance-attributes,too-many-statements
@@ -45,7 +49,7 @@ class CIRCTDumper(Visitor):  # pylint: disable=too-many-instance-attributes,too-
     current_module: Module
     sys: SysBuilder
     is_top_generation: bool
-    memory_defs: set
+    memory_defs: dict
     expr_wait_conditions: Dict[Expr, List[str]]
 
     def __init__(
@@ -65,8 +69,24 @@ class CIRCTDumper(Visitor):  # pylint: disable=too-many-instance-attributes,too-
         self.sys = None
         self.is_top_generation = False
         self.array_metadata = ArrayMetadataRegistry()
-        self.memory_defs = set()
+        self.memory_defs = {}  # Insertion-ordered set of the SRAM blackboxes
         self.default_fifo_depth: int = 1
+        # Whether the testbench prints a `@trace` line per array write and FIFO push
+        self.trace: bool = False
+        # The period of each clock domain, and the synchronizer flops between two domains
+        self.clock_domains: Optional[dict] = None
+        self.cdc_sync_stages: int = 2
+        # The AXI4-Lite region of each exposed array, empty unless the `axi_lite` option is set
+        self.axil_regions: Dict[Array, AxilRegion] = {}
+        # Whether the logs are also lowered to `assassyn_log` calls for tb_vcs.sv, and the calls
+        self.log_dpi: bool = False
+        self.dpi_logs: List[str] = []
+        # Whether the modules output the properties checked by the formal harness
+        self.formal: bool = False
+        # Whether the modules output whether each condition block is entered
+        self.cover: bool = False
+        # The reset of the FIFOs and trigger counters, one of `RESET_STYLES`
+        self.reset_style: str = 'async_low'
         self.expr_to_name = {}
         self.name_counters = defaultdict(int)
         self.expr_wait_conditions: Dict[Expr, List[str]] = {}
@@ -185,7 +205,7 @@ class CIRCTDumper(Visitor):  # pylint: disable=too-many-instance-attributes,too-
     # pylint: disable=arguments-renamed
     def visit_expr(self, expr: Expr):
         self.expr_wait_conditions[expr] = list(self.wait_conditions)
-        self.append_code(f'# {expr}')
+        self.append_code(f'# {render_expr(expr)}')
 
         # Add location comment if available
         if hasattr(expr, 'loc') and expr.loc:
@@ -267,9 +287,12 @@ class CIRCTDumper(Visitor):  # pylint: disable=too-many-instance-attributes,too-
         else:
             num_write_ports = len(metadata.write_ports)
             num_read_ports = len(metadata.read_order)
+        axil_write, axil_read = axil_ports(self, array)
+        num_write_ports += axil_write
+        num_read_ports += axil_read
 
         class_name = namify(array.name)
-        addr_width = index_bits if index_bits > 0 else 1
+        index_width = addr_width(size)
         include_read_index = index_bits > 0
         initializer = array.initializer
 
@@ -280,7 +303,7 @@ class CIRCTDumper(Visitor):  # pylint: disable=too-many-instance-attributes,too-
         self.append_code(f'{size},')
         self.append_code(f'num_write_ports={num_write_ports},')
         self.append_code(f'num_read_ports={num_read_ports},')
-        self.append_code(f'addr_width={addr_width},')
+        self.append_code(f'addr_width={index_width},')
         self.append_code(f'include_read_index={str(include_read_index)},')
         if initializer is not None:
             self.append_code(f'initializer={repr(initializer)},')
@@ -373,8 +396,25 @@ def generate_design(
     sys: SysBuilder,
     *,
     default_fifo_depth: int = 1,
+    trace: bool = False,
+    clock_domains: Optional[dict] = None,
+    cdc_sync_stages: int = 2,
+    axi_lite: bool = False,
+    log_dpi: bool = False,
+    formal: bool = False,
+    cover: bool = False,
+    reset_style: str = 'async_low',
 ) -> None:
-    """Generate a complete Verilog design file for the system."""
+    """Generate a complete Verilog design file for the system.
+
+    `clock_domains` gives the period of each clock domain, and `cdc_sync_stages` the
+    synchronizer flops of the FIFOs between domains. With `axi_lite`, the exposed arrays are
+    mapped on an AXI4-Lite slave of the Top harness, whose address map is written to
+    `regmap.json` next to `fname`. With `log_dpi`, the logs are also written, as calls of
+    the DPI-C `assassyn_log` for tb_vcs.sv, to `dpi_logs.svh` next to `fname`. With
+    `formal`, the modules and the Top harness output the properties of the formal harness.
+    With `cover`, the modules output whether each of their condition blocks is entered.
+    `reset_style` is the reset of the FIFOs and trigger counters, see `reset.py`."""
     with open(str(fname), 'w', encoding='utf-8') as fd:
         fd.write(HEADER)
 
@@ -385,6 +425,20 @@ def generate_design(
             external_metadata=collect_external_metadata(sys),
         )
         dumper.default_fifo_depth = default_fifo_depth
+        dumper.trace = trace
+        dumper.clock_domains = clock_domains
+        dumper.cdc_sync_stages = cdc_sync_stages
+        dumper.log_dpi = log_dpi
+        dumper.formal = formal
+        dumper.cover = cover
+        dumper.reset_style = reset_style
+        if axi_lite:
+            regions = axil_regmap(sys)
+            if not regions:
+                raise ValueError('The axi_lite option maps the exposed arrays, but no array is '
+                                 'exposed')
+            dumper.axil_regions = {region.array: region for region in regions}
+            dump_regmap(Path(fname).parent / REGMAP_FILE, regions)
 
         # Generate sramBlackbox module definitions for each SRAM
         sram_modules = [m for m in sys.downstreams if isinstance(m, SRAM)]
@@ -393,11 +447,10 @@ def generate_design(
                 params = extract_sram_params(sram)
                 array_name = params['array_name']
                 data_width = params['data_width']
-                addr_width = params['addr_width']
-                dumper.memory_defs.add((data_width, addr_width, array_name))
+                dumper.memory_defs[(data_width, params['addr_width'], array_name)] = None
 
             # Write sramBlackbox module definitions
-            for data_width, addr_width, array_name in dumper.memory_defs:
+            for data_width, address_width, array_name in dumper.memory_defs:
                 fd.write(f'''
 @modparams
 def sramBlackbox_{array_name}():
@@ -405,7 +458,7 @@ def sramBlackbox_{array_name}():
         module_name = "sram_blackbox_{array_name}"
         clk = Clock()
         rst_n = Input(Bits(1))
-        address = Input(Bits({addr_width}))
+        address = Input(Bits({address_width}))
         wd = Input(Bits({data_width}))
         banksel = Input(Bits(1))
         read = Input(Bits(1))
@@ -418,6 +471,9 @@ def sramBlackbox_{array_name}():
         code = '\n'.join(dumper.code)
         code = code.replace('system.compile()")', 'system.compile()')
         fd.write(code)
+    if log_dpi:
+        with open(Path(fname).parent / LOG_DPI_INCLUDE, 'w', encoding='utf-8') as fd:
+            fd.writelines(f'{line}\n' for line in dumper.dpi_logs)
     logs = dumper.logs
     return logs
 
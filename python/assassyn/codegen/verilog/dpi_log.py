@@ -0,0 +1,86 @@
+"""The logs of the VCS testbench, printed through the DPI-C function `assassyn_log`."""
+
+from __future__ import annotations
+
+import re
+import struct
+from pathlib import Path
+from string import Formatter
+from typing import List, Optional, Tuple, Union
+
+# The C side of `assassyn_log`, copied next to the testbench and compiled by run_vcs.sh
+LOG_DPI_SOURCE = 'assassyn_log.c'
+
+# The log statements of the design, included by the loop of tb_vcs.sv once per cycle
+LOG_DPI_INCLUDE = 'dpi_logs.svh'
+
+LOG_DPI_IMPORT = ('import "DPI-C" function void assassyn_log(input int level, '
+                  'input string module_name, input string msg);')
+
+# The Python format specs of an integer log argument which $sformatf renders alike: an
+# optional zero fill and width, then the radix
+_INT_SPEC = re.compile(r'(0?)(\d*)([bdox]?)')
+
+
+def sv_string(text: str) -> str:
+    '''`text` as a SystemVerilog string literal.'''
+    escaped = text.replace('\\', '\\\\').replace('"', '\\"').replace('\n', '\\n')
+    return f'"{escaped}"'
+
+
+def sv_spec(format_spec: Optional[str], frac: Optional[int] = None) -> str:
+    '''The $sformatf specifier printing an argument like the Python `format_spec`, `?` and
+    empty ones printing in decimal. A fixed-point argument of `frac` fractional bits is
+    printed as a real with `frac` digits.'''
+    if frac is not None:
+        return f'%.{frac}f'
+    if not format_spec or format_spec == '?':
+        return '%0d'
+    match = _INT_SPEC.fullmatch(format_spec)
+    if match is None:
+        raise ValueError(f'The log format spec {format_spec!r} has no $sformatf equivalent')
+    fill, width, radix = match.groups()
+    return f'%{fill}{width or "0"}{radix or "d"}'
+
+
+def sv_format(formatter_str: str, specs: List[str]) -> str:
+    '''Replace the fields of the Python `formatter_str` by the $sformatf `specs`, in order,
+    escaping the literal `%`.'''
+    parts = []
+    specs = iter(specs)
+    for literal_text, field_name, _, _ in Formatter().parse(formatter_str):
+        parts.append(literal_text.replace('%', '%%'))
+        if field_name is not None:
+            parts.append(next(specs))
+    return ''.join(parts)
+
+
+def dpi_log_call(conditions: List[str], module_name: str, fmt: str, args: List[str]) -> str:
+    '''The statement of tb_vcs.sv calling `assassyn_log` with the formatted message when all
+    the `conditions` hold.'''
+    message = ', '.join([sv_string(fmt)] + args)
+    call = f'assassyn_log(0, {sv_string(module_name)}, $sformatf({message}));'
+    if not conditions:
+        return call
+    return f"if ({' && '.join(conditions)}) {call}"
+
+
+def read_log_records(path: Union[str, Path]) -> List[Tuple[int, str, str]]:
+    '''The `(level, module, message)` records written by the default `assassyn_log`.'''
+    data = Path(path).read_bytes()
+    res = []
+    offset = 0
+
+    def take_str():
+        nonlocal offset
+        (length,) = struct.unpack_from('<I', data, offset)
+        text = data[offset + 4:offset + 4 + length].decode('utf-8')
+        offset += 4 + length
+        return text
+
+    while offset < len(data):
+        (level,) = struct.unpack_from('<i', data, offset)
+        offset += 4
+        module = take_str()
+        res.append((level, module, take_str()))
+    return res
@@ -4,12 +4,17 @@ import os
 import re
 from pathlib import Path
 import shutil
-from .testbench import generate_testbench
+from .testbench import generate_testbench, generate_vcs_script
 from .design import generate_design
+from .dpi_log import LOG_DPI_SOURCE
+from .formal import generate_formal
+from .reset import SYNC_HIGH_RESOURCES, sync_high_reset
+from ...ir.dtype import Str
 from ...ir.memory.sram import SRAM
 from .utils import extract_sram_params
 
 from ...builder import SysBuilder
+from ...analysis import check_types
 from ...utils import create_dir, repo_path
 from ..simulator.external import collect_external_intrinsics
 
@@ -34,24 +39,38 @@ def _resolve_alias_resources(top_sv_path: Path, files_to_copy):
     for resource_file in files_to_copy:
         base_module = Path(resource_file).stem
         pattern = rf"\b{base_module}_(\d+)\b"
-        for suffix in set(re.findall(pattern, top_content)):
+        for suffix in sorted(set(re.findall(pattern, top_content))):
             alias_module = f"{base_module}_{suffix}"
             alias_resource_files.append((resource_file, alias_module))
     return alias_resource_files
 
 
-def _copy_core_resources(resource_path: Path, destination: Path, files_to_copy):
+def _resource_content(source_file: Path, reset_style: str) -> str:
+    """The content of a resource file, its reset rewritten for `reset_style`."""
+    content = source_file.read_text(encoding='utf-8')
+    if reset_style == 'sync_high' and source_file.name in SYNC_HIGH_RESOURCES:
+        content = sync_high_reset(content)
+    return content
+
+
+def _copy_core_resources(resource_path: Path, destination: Path, files_to_copy,
+                         reset_style='async_low'):
     """Copy standard SV helper files used by the testbench."""
     for file_name in files_to_copy:
         source_file = resource_path / file_name
         if source_file.is_file():
             destination_file = destination / file_name
-            shutil.copy(source_file, destination_file)
+            if reset_style == 'sync_high' and file_name in SYNC_HIGH_RESOURCES:
+                destination_file.write_text(sync_high_reset(source_file.read_text(
+                    encoding='utf-8')), encoding='utf-8')
+            else:
+                shutil.copy(source_file, destination_file)
         else:
             print(f"Warning: Resource file not found: {source_file}")
 
 
-def _copy_alias_resources(resource_path: Path, destination: Path, alias_resource_files):
+def _copy_alias_resources(resource_path: Path, destination: Path, alias_resource_files,
+                          reset_style='async_low'):
     """Materialize alias modules emitted by CIRCT to keep resource names in sync."""
     for base_file, alias_module in alias_resource_files:
         source_file = resource_path / base_file
@@ -63,7 +82,7 @@ def _copy_alias_resources(resource_path: Path, destination: Path, alias_resource
         if alias_path.exists():
             continue
 
-        content = source_file.read_text(encoding='utf-8')
+        content = _resource_content(source_file, reset_style)
         base_module = Path(base_file).stem
         alias_content = content.replace(f"module {base_module}", f"module {alias_module}", 1)
         alias_path.write_text(alias_content, encoding='utf-8')
@@ -118,9 +137,10 @@ module sram_blackbox_{array_name} #(
         if sram_info['init_file']:
             init_file = sram_info['init_file']
             src_file = os.path.join(resource_base, init_file) if resource_base else init_file
+            readmem = '$readmemb' if sram_info['init_format'] == 'bin' else '$readmemh'
             verilog_code += f'''
     initial begin
-        $readmemh("{src_file}", mem);
+        {readmem}("{src_file}", mem);
     end
 
     always @ (posedge clk) begin
@@ -162,11 +182,27 @@ def elaborate(sys: SysBuilder, **kwargs) -> str:
             - idle_threshold: Idle threshold
             - random: Whether to randomize execution
             - fifo_depth: Default FIFO depth
+            - verilog_defines, include_dirs, verilator_flags: Options of the Verilator build
+            - clock_domains, cdc_sync_stages: The clock domains and their synchronizers
+            - axi_lite, axil_transactions: The AXI4-Lite slave of the exposed arrays, and
+              the transactions the testbench runs on it
+            - log_dpi: Whether the VCS testbench prints the logs through DPI-C
+            - formal, formal_depth: Whether to write the SymbiYosys harness, and the cycles
+              it checks
+            - cover: Whether the testbenches count the cycles each block is entered
+            - reset_style: The reset of the FIFOs and trigger counters, 'async_low' or
+              'sync_high'
 
     Returns:
         Path to the generated Verilog files
     """
 
+    check_types(sys)
+    for array in sys.arrays:
+        if isinstance(array.scalar_ty, Str):
+            raise ValueError(f'{array.name} is an array of strings, which only the simulator '
+                             'implements; build it with verilog=False')
+
     path = kwargs.get('path', os.getcwd())
     path = Path(path) / "verilog"
 
@@ -179,29 +215,60 @@ def elaborate(sys: SysBuilder, **kwargs) -> str:
         path / "design.py",
         sys,
         default_fifo_depth=kwargs.get('fifo_depth', 2),
+        trace=kwargs.get('trace', False),
+        clock_domains=kwargs.get('clock_domains'),
+        cdc_sync_stages=kwargs.get('cdc_sync_stages', 2),
+        axi_lite=kwargs.get('axi_lite', False),
+        log_dpi=kwargs.get('log_dpi', False),
+        formal=kwargs.get('formal', False),
+        cover=kwargs.get('cover', False),
+        reset_style=kwargs.get('reset_style', 'async_low'),
     )
 
-    files_to_copy = ["fifo.sv", "trigger_counter.sv"]
+    files_to_copy = ["fifo.sv", "trigger_counter.sv", "async_fifo.sv"]
     top_sv_path = path / "sv" / "hw" / "Top.sv"
     alias_resource_files = _resolve_alias_resources(top_sv_path, files_to_copy)
 
     additional_files = sorted(
         set(external_file_names + [f"{alias}.sv" for _, alias in alias_resource_files])
     )
+    if kwargs.get('axi_lite', False):
+        files_to_copy.append('axil_regs.sv')
+        additional_files.append('axil_regs.sv')
+    if kwargs.get('log_dpi', False):
+        files_to_copy.append(LOG_DPI_SOURCE)
 
     generate_testbench(
         path / "tb.py",
         sys,
         kwargs['sim_threshold'],
         logs,
-        additional_files
+        additional_files,
+        defines=kwargs.get('verilog_defines'),
+        include_dirs=kwargs.get('include_dirs'),
+        verilator_flags=kwargs.get('verilator_flags'),
+        clock_domains=kwargs.get('clock_domains'),
+        axil_transactions=kwargs.get('axil_transactions'),
+        cover=kwargs.get('cover', False),
     )
+    if str(kwargs.get('verilog')).lower() == 'vcs':
+        generate_vcs_script(path, kwargs['sim_threshold'], additional_files, sys,
+                            clock_domains=kwargs.get('clock_domains'),
+                            axi_lite=kwargs.get('axi_lite', False),
+                            log_dpi=kwargs.get('log_dpi', False),
+                            cover=kwargs.get('cover', False))
+    if kwargs.get('formal', False):
+        generate_formal(path, sys, kwargs.get('formal_depth', 20), additional_files,
+                        axi_lite=kwargs.get('axi_lite', False),
+                        clock_domains=kwargs.get('clock_domains'),
+                        reset_style=kwargs.get('reset_style', 'async_low'))
 
     default_home = os.getenv('ASSASSYN_HOME', os.getcwd())
     resource_path = Path(default_home) / "python/assassyn/codegen/verilog"
     generate_sram_blackbox_files(sys, path, kwargs.get('resource_base'))
-    _copy_core_resources(resource_path, path, files_to_copy)
-    _copy_alias_resources(resource_path, path, alias_resource_files)
+    reset_style = kwargs.get('reset_style', 'async_low')
+    _copy_core_resources(resource_path, path, files_to_copy, reset_style)
+    _copy_alias_resources(resource_path, path, alias_resource_files, reset_style)
     _copy_external_sources(external_sources, path)
 
     return path
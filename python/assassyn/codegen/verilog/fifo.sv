@@ -1,7 +1,19 @@
 
+`ifdef FORMAL
+// The solver would pick any value for an 'x, so that the data of an empty FIFO would
+// differ from one step to the next; it is held at 0 instead.
+`define FIFO_UNDEF '0
+`else
+`define FIFO_UNDEF 'x
+`endif
+
 module fifo #(
     parameter WIDTH = 8,
-    parameter DEPTH_LOG2 = 2 // Special case when DEPTH_LOG2 = 0, single element FIFO
+    parameter DEPTH_LOG2 = 2, // Special case when DEPTH_LOG2 = 0, single element FIFO
+    // When set, data pushed into an empty FIFO is visible to the popper in the same cycle.
+    parameter FLOW_THROUGH = 0,
+    // The number of entries behind the head exposed by peek_data, for peek_n.
+    parameter PEEK_N = 1
     // parameter NAME = "fifo" // TODO(@were): Open this later
 ) (
     input  logic               clk,
@@ -13,37 +25,70 @@ module fifo #(
 
     output logic               pop_valid,
     output logic [WIDTH - 1:0] pop_data,
-    input  logic               pop_ready
+    input  logic               pop_ready,
+
+    // Entry k (1 <= k <= PEEK_N) behind the head is peek_data[(k-1)*WIDTH +: WIDTH],
+    // valid when peek_valid[k-1] is set.
+    output logic [PEEK_N - 1:0]         peek_valid,
+    output logic [PEEK_N * WIDTH - 1:0] peek_data
 );
 
+// The registered FIFO below works on these internal signals. They are
+// connected to the ports directly, or through the flow-through bypass.
+logic               q_push_valid;
+logic               q_pop_ready;
+logic               q_pop_valid;
+logic [WIDTH - 1:0] q_pop_data;
+
+generate
+    if (FLOW_THROUGH != 0) begin : flow_through
+        // When the FIFO is empty, the pushed data goes to the popper directly.
+        // If the popper takes it, it is never stored.
+        logic bypass;
+        assign bypass       = push_valid && !q_pop_valid;
+        assign pop_valid    = q_pop_valid || push_valid;
+        assign pop_data     = q_pop_valid ? q_pop_data : push_data;
+        assign q_push_valid = push_valid && !(bypass && pop_ready);
+        assign q_pop_ready  = pop_ready && q_pop_valid;
+    end else begin : registered
+        assign pop_valid    = q_pop_valid;
+        assign pop_data     = q_pop_data;
+        assign q_push_valid = push_valid;
+        assign q_pop_ready  = pop_ready;
+    end
+endgenerate
+
 generate
     if (DEPTH_LOG2 == 0) begin : single_element_fifo
         // Single element FIFO for DEPTH_LOG2 = 0
 
         logic fifo_full; 
 
-        assign push_ready = ~fifo_full || (fifo_full && pop_ready); 
-        assign pop_valid  = fifo_full;                              
+        assign peek_valid = '0;
+        assign peek_data  = `FIFO_UNDEF;
+
+        assign push_ready = ~fifo_full || (fifo_full && q_pop_ready); 
+        assign q_pop_valid  = fifo_full;                              
 
         always @(posedge clk or negedge rst_n) begin
             if (!rst_n) begin
                 fifo_full <= 1'b0;
-                pop_data <= 'x;
+                q_pop_data <= `FIFO_UNDEF;
             end else begin
                 
-                if (push_valid && pop_ready) begin
-                    pop_data <= push_data; 
+                if (q_push_valid && q_pop_ready) begin
+                    q_pop_data <= push_data; 
                     fifo_full <= 1'b1;     
                 end
                 
-                else if (push_valid && ~fifo_full) begin
-                    pop_data <= push_data; 
+                else if (q_push_valid && ~fifo_full) begin
+                    q_pop_data <= push_data; 
                     fifo_full <= 1'b1;     
                 end
                 
-                else if (pop_ready && fifo_full) begin
+                else if (q_pop_ready && fifo_full) begin
                     fifo_full <= 1'b0;     
-                    pop_data <= 'x;        
+                    q_pop_data <= `FIFO_UNDEF;        
                 end
             end
         end
@@ -65,22 +110,30 @@ generate
         logic temp_pop_valid;
 
         // The number of elements in the queue after this cycle.
-        assign new_count = count + (push_valid ? 1 : 0) - (pop_ready ? 1 : 0);
+        assign new_count = count + (q_push_valid ? 1 : 0) - (q_pop_ready ? 1 : 0);
 
         // The new front of the queue after this cycle.
-        assign new_front = front + (pop_ready && count != 0 ? 1 : 0);
+        assign new_front = front + (q_pop_ready && count != 0 ? 1 : 0);
+
+        // The lookahead taps read the entries behind the current front.
+        for (genvar k = 1; k <= PEEK_N; k++) begin : lookahead
+            logic [`IDX_DECL:0] idx;
+            assign idx = front + k;
+            assign peek_valid[k - 1] = count > k;
+            assign peek_data[(k - 1) * WIDTH +: WIDTH] = q[idx];
+        end
 
         always @(posedge clk or negedge rst_n) begin
             if (!rst_n) begin
                 front <= 0;
                 back <= 0;
-                pop_valid <= 1'b0;
-                pop_data <= 'x;
+                q_pop_valid <= 1'b0;
+                q_pop_data <= `FIFO_UNDEF;
                 count <= 0;
                 push_ready <= 1'b1;
             end else begin
 
-                if (push_valid && new_count <= `FIFO_SIZE) begin
+                if (q_push_valid && new_count <= `FIFO_SIZE) begin
                     q[back] <= push_data;
                     back <= (back + 1);
                 end
@@ -90,25 +143,33 @@ generate
 
                 push_ready <= new_count < `FIFO_SIZE;
 
-                temp_pop_valid = new_count != 0 || push_valid;
-                pop_valid <= temp_pop_valid;
+                temp_pop_valid = new_count != 0 || q_push_valid;
+                q_pop_valid <= temp_pop_valid;
     // This is the most tricky part of the code:
-    // If new_count is 0, we have noting to pop, so we just give pop_valid a 0,
-    // and pop_data a 'x. Otherwise, we have to pop something real from the FIFO.
+    // If new_count is 0, we have noting to pop, so we just give q_pop_valid a 0,
+    // and q_pop_data a 'x. Otherwise, we have to pop something real from the FIFO.
     // Because the array write uses a non-blocking "<=" operator, the result
     // of array write will not be visible until the next cycle. However, we
     // need this result when new_front == back. This indicates the newly
     // pushed data is also the front of the FIFO. Instead of reading it from
-    // the array buffer, we directly forward the push_data to pop_data.
-                pop_data <= temp_pop_valid ? (new_front == back && push_valid ? push_data : q[new_front]) : 'x;
+    // the array buffer, we directly forward the push_data to q_pop_data.
+                q_pop_data <= temp_pop_valid ? (new_front == back && q_push_valid ? push_data : q[new_front]) : `FIFO_UNDEF;
 
             end
         end
 
+`ifdef FORMAL
+        // No module pushes a full FIFO or pops an empty one, so the occupancy never
+        // leaves [0, depth]; the bound also helps the solver with induction.
+        always @(*) if (rst_n) assert (count <= `FIFO_SIZE);
+`endif
+
         `undef FIFO_SIZE
         `undef IDX_DECL
         `undef CNT_DECL
     end
 endgenerate
 
-endmodule
\ No newline at end of file
+endmodule
+
+`undef FIFO_UNDEF
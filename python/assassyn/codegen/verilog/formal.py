@@ -0,0 +1,160 @@
+"""The SymbiYosys harness checking the assertions of a design by bounded model checking."""
+
+from __future__ import annotations
+
+import os
+from pathlib import Path
+from typing import List, Optional, Tuple, Union
+
+from ...ir.expr.intrinsic import Intrinsic
+from ...utils import namify
+from .axil import axil_port_list, axil_regmap
+
+# The top of the formal flow, instantiating the Top harness with a free environment
+FORMAL_TOP = 'formal_top'
+
+# The templates of the FIFOs and the trigger counters, written next to design.py
+FORMAL_TEMPLATES = ['fifo.sv', 'trigger_counter.sv']
+
+FORMAL_TOP_TEMPLATE = '''// The formal harness of {sys}. Reset is held in the first cycle, then the
+// assertions of the design must hold, assuming that no module is triggered before
+// its wait_until holds.
+module {top} (
+  input logic clk
+);
+  logic rst = 1'b1;
+  always @(posedge clk) rst <= 1'b0;
+{free}{checks}
+  Top dut (.clk(clk), .rst(rst){ports});
+{properties}endmodule
+'''
+
+SBY_TEMPLATE = '''[options]
+mode bmc
+depth {depth}
+
+[engines]
+smtbmc
+
+[script]
+{reads}
+prep -top {top}
+
+[files]
+{files}
+'''
+
+FORMAL_SCRIPT_TEMPLATE = '''#!/bin/sh
+# Generate the design, then check it with SymbiYosys.
+set -e
+cd "$(dirname "$0")"
+python design.py
+test -f sv/hw/Top.sv
+python -c "from assassyn.utils import patch_fifo; patch_fifo('sv/hw/Top.sv')"
+python -c "from assassyn.codegen.verilog.formal import write_sby; \\
+write_sby('.', '{name}', {depth}, {extra!r})"
+sby -f {name}.sby
+'''
+
+
+def formal_properties(module) -> List[Tuple[str, Intrinsic]]:
+    '''The (output port, intrinsic) of each property `module` adds to the formal harness, in
+    program order: an `assert` for each `assume` of the frontend, which only holds on the
+    cycles the module runs, and an `assume` for each `wait_until`, its condition holding
+    whenever the module is triggered.'''
+    res = []
+    for expr in module.body or []:
+        if not isinstance(expr, Intrinsic):
+            continue
+        if expr.opcode == Intrinsic.ASSERT:
+            res.append((f'formal_assert_{len(res)}', expr))
+        elif expr.opcode == Intrinsic.WAIT_UNTIL:
+            res.append((f'formal_assume_{len(res)}', expr))
+    return res
+
+
+def top_formal_port(module, field: str) -> str:
+    '''The output of the Top harness forwarding the property `field` of `module`.'''
+    return f'{namify(module.name)}_{field}'
+
+
+def generate_formal_top(path: Union[str, Path], sys, axi_lite: bool = False) -> None:
+    '''Write `formal_top.sv` under `path`, instantiating Top. The inputs of its AXI4-Lite
+    slave, if any, are free symbolic inputs, and the properties of the modules are asserted
+    and assumed once reset is released.'''
+    ports = []
+    free = []
+    if axi_lite:
+        for name, direction, bits in axil_port_list(axil_regmap(sys)):
+            if direction != 'Input':
+                continue
+            free.append(f'  (* anyseq *) logic [{bits - 1}:0] s_axil_{name};\n')
+            ports.append(f'.s_axil_{name}(s_axil_{name})')
+    checks = []
+    properties = []
+    for module in sys.modules + sys.downstreams:
+        for field, expr in formal_properties(module):
+            port = top_formal_port(module, field)
+            checks.append(f'  logic {port};\n')
+            ports.append(f'.{port}({port})')
+            kind = 'assert' if expr.opcode == Intrinsic.ASSERT else 'assume'
+            properties.append(f'  {kind} property (rst || {port});\n')
+    with open(Path(path) / f'{FORMAL_TOP}.sv', 'w', encoding='utf-8') as f:
+        f.write(FORMAL_TOP_TEMPLATE.format(
+            sys=sys.name, top=FORMAL_TOP, free=''.join(free), checks=''.join(checks),
+            ports=''.join(f',\n      {i}' for i in ports), properties=''.join(properties)))
+
+
+def sby_script(sources: List[str], depth: int) -> str:
+    '''The SymbiYosys script checking `FORMAL_TOP` for `depth` cycles. `sources` are the
+    paths of the Verilog files, relative to the directory of the script; SymbiYosys copies
+    them flat, so that they are read by their base names.'''
+    names = [os.path.basename(i) for i in sources]
+    return SBY_TEMPLATE.format(
+        depth=depth,
+        reads='\n'.join(f'read -formal {i}' for i in names),
+        top=FORMAL_TOP,
+        files='\n'.join(sources))
+
+
+def write_sby(path: Union[str, Path], name: str, depth: int,
+              extra_files: Optional[List[str]] = None) -> Path:
+    '''Write `<name>.sby` under `path`, the verilog folder of the system, once the design
+    is generated: its sources are those of sv/hw/filelist.f, the SRAM blackboxes, the
+    templates, the `extra_files` and the formal top. Returns the path of the script.'''
+    path = Path(path)
+    with open(path / 'sv' / 'hw' / 'filelist.f', encoding='utf-8') as f:
+        sources = [f'sv/hw/{line.strip()}' for line in f if line.strip()]
+    sources += sorted(i.name for i in path.glob('sram_blackbox_*.sv'))
+    sources += FORMAL_TEMPLATES + list(extra_files or []) + [f'{FORMAL_TOP}.sv']
+    sby = path / f'{name}.sby'
+    with open(sby, 'w', encoding='utf-8') as f:
+        f.write(sby_script(sources, depth))
+    return sby
+
+
+def generate_formal(  # pylint: disable=too-many-arguments
+        path: Union[str, Path], sys, depth: int, external_files: List[str],
+        axi_lite: bool = False, clock_domains: Optional[dict] = None,
+        reset_style: str = 'async_low') -> None:
+    '''Write the formal top and `run_formal.sh` under `path`, which generates the design,
+    writes `<system>.sby` with `write_sby`, reading the `external_files` too, and runs
+    SymbiYosys for `depth` cycles.
+
+    Raises:
+        ValueError: If the design has clock domains, as the harness drives only `clk`, or a
+            reset style other than `async_low`, the only one the harness is written for
+    '''
+    if clock_domains:
+        raise ValueError('The formal harness only drives the default clock, but the design '
+                         f'has the clock domains {", ".join(sorted(clock_domains))}')
+    if reset_style != 'async_low':
+        raise ValueError('The formal harness only drives the async_low reset, but the design '
+                         f'has the reset style {reset_style}')
+    path = Path(path)
+    generate_formal_top(path, sys, axi_lite)
+    script = path / 'run_formal.sh'
+    with open(script, 'w', encoding='utf-8') as f:
+        f.write(FORMAL_SCRIPT_TEMPLATE.format(depth=depth, name=sys.name,
+                                            extra=list(external_files)))
+    os.chmod(script, 0o755)
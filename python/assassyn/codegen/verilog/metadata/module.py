@@ -65,9 +65,11 @@ class ModuleMetadata:  # pylint: disable=too-many-instance-attributes
     matrix: InteractionMatrix
     _value_exposures: list["Expr"] = field(default_factory=list)
     _finish_sites: list["Intrinsic"] = field(default_factory=list)
+    _watchdogs: list["Intrinsic"] = field(default_factory=list)
     _calls: list["AsyncCall"] = field(default_factory=list)
     _value_snapshot: Tuple["Expr", ...] | None = field(init=False, default=None)
     _finish_snapshot: Tuple["Intrinsic", ...] | None = field(init=False, default=None)
+    _watchdog_snapshot: Tuple["Intrinsic", ...] | None = field(init=False, default=None)
     _calls_snapshot: Tuple["AsyncCall", ...] | None = field(init=False, default=None)
     _interactions: ModuleInteractionView | None = field(init=False, default=None)
     _frozen: bool = field(init=False, default=False)
@@ -82,6 +84,11 @@ class ModuleMetadata:  # pylint: disable=too-many-instance-attributes
         self._ensure_mutable()
         self._finish_sites.append(expr)
 
+    def record_watchdog(self, expr: "Intrinsic") -> None:
+        """Record a WATCHDOG intrinsic so cleanup can emit its counter."""
+        self._ensure_mutable()
+        self._watchdogs.append(expr)
+
     def record_call(self, call: "AsyncCall") -> None:
         """Register an async call issued by this module."""
         self._ensure_mutable()
@@ -94,9 +101,11 @@ class ModuleMetadata:  # pylint: disable=too-many-instance-attributes
         self.matrix.freeze()
         self._value_snapshot = tuple(self._value_exposures)
         self._finish_snapshot = tuple(self._finish_sites)
+        self._watchdog_snapshot = tuple(self._watchdogs)
         self._calls_snapshot = tuple(self._calls)
         self._value_exposures.clear()
         self._finish_sites.clear()
+        self._watchdogs.clear()
         self._calls.clear()
         self._interactions = self.matrix.module_view(self.module)
         self._frozen = True
@@ -115,6 +124,13 @@ class ModuleMetadata:  # pylint: disable=too-many-instance-attributes
             return self._finish_snapshot
         return tuple(self._finish_sites)
 
+    @property
+    def watchdogs(self) -> Tuple["Intrinsic", ...]:
+        """Return the WATCHDOG intrinsics of the module."""
+        if self._watchdog_snapshot is not None:
+            return self._watchdog_snapshot
+        return tuple(self._watchdogs)
+
     @property
     def calls(self) -> Tuple["AsyncCall", ...]:
         """Return async calls issued by the module."""
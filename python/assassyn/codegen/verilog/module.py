@@ -1,7 +1,10 @@
 """Module port generation utilities for Verilog code generation."""
 
 from .cleanup import resolve_value_exposure_render
-from .utils import dump_type, get_sram_info
+from .utils import addr_width, dump_type, get_sram_info, module_probes, probe_field
+from ..simulator.watchdog import watchdog_field
+from .formal import formal_properties
+from ...analysis.coverage import cover_points
 from ...analysis.topo import get_upstreams
 from ...ir.module import Module, Downstream
 from ...ir.memory.sram import SRAM
@@ -36,6 +39,17 @@ def generate_module_ports(dumper, node: Module) -> None:
     dumper.append_code('executed = Output(Bits(1))')
     dumper.append_code('cycle_count = Input(UInt(64))')
     dumper.append_code('finish = Output(Bits(1))')
+    for watchdog in module_metadata.watchdogs:
+        dumper.append_code(f'{watchdog_field(watchdog)} = Output(Bits(1))')
+    if dumper.formal:
+        for field, _ in formal_properties(node):
+            dumper.append_code(f'{field} = Output(Bits(1))')
+    if dumper.cover:
+        for field, _ in cover_points(node):
+            dumper.append_code(f'{field} = Output(Bits(1))')
+    for signal, label in module_probes(dumper.sys, node):
+        dumper.append_code(f'{probe_field(label)} = Output(Bits({signal.dtype.bits}))')
+        dumper.append_code(f'{probe_field(label)}_valid = Output(Bits(1))')
 
     if is_downstream:
         upstream_modules = sorted(get_upstreams(node), key=lambda mod: mod.name)
@@ -46,8 +60,8 @@ def generate_module_ports(dumper, node: Module) -> None:
             if sram_info:
                 sram_array = sram_info['array']
                 dumper.append_code(f'mem_dataout = Input({dump_type(sram_array.scalar_ty)})')
-                index_bits = sram_array.index_bits if sram_array.index_bits > 0 else 1
-                dumper.append_code(f'mem_address = Output(Bits({index_bits}))')
+                dumper.append_code(
+                    f'mem_address = Output(Bits({addr_width(sram_array.size)}))')
                 dumper.append_code(f'mem_write_data = Output({dump_type(sram_array.scalar_ty)})')
                 dumper.append_code('mem_write_enable = Output(Bits(1))')
                 dumper.append_code('mem_read_enable = Output(Bits(1))')
@@ -93,14 +107,19 @@ def generate_module_ports(dumper, node: Module) -> None:
             name = namify(i.name)
             dumper.append_code(f'{name} = Input({dump_type(i.dtype)})')
             dumper.append_code(f'{name}_valid = Input(Bits(1))')
+            lookahead = i.lookahead_depth()
+            if lookahead:
+                dumper.append_code(f'{name}_peek_valid = Input(Bits({lookahead}))')
+                dumper.append_code(f'{name}_peek_data = Input(Bits({lookahead * i.dtype.bits}))')
             popped_fifos = {p.fifo for p in pops}
             has_pop = i in popped_fifos
             if has_pop:
                 dumper.append_code(f'{name}_pop_ready = Output(Bits(1))')
 
-    unique_push_handshake_targets = {(p.fifo.module, p.fifo.name) for p in pushes}
-    unique_call_handshake_targets = {c.bind.callee for c in calls}
-    unique_output_push_ports = {p.fifo for p in pushes}
+    # Deduplicated in program order, so that the ports do not move between runs
+    unique_push_handshake_targets = dict.fromkeys((p.fifo.module, p.fifo.name) for p in pushes)
+    unique_call_handshake_targets = dict.fromkeys(c.bind.callee for c in calls)
+    unique_output_push_ports = dict.fromkeys(p.fifo for p in pushes)
 
     for module, fifo_name in unique_push_handshake_targets:
         port_name = f'fifo_{namify(module.name)}_{namify(fifo_name)}_push_ready'
@@ -149,7 +168,7 @@ def generate_module_ports(dumper, node: Module) -> None:
 
         if read_port_indices or writes_idx is not None:
             index_bits = arr.index_bits
-            idx_type = index_bits if index_bits > 0 else 1
+            idx_type = addr_width(arr.size)
             for port_idx in read_port_indices:
                 port_suffix = f"_port{port_idx}"
                 if index_bits > 0:
@@ -0,0 +1,50 @@
+"""The synthesis pragmas of the modules and arrays in the Verilog backend.
+
+PyCDE gives no handle on the `module` declarations and the registers CIRCT emits, so the
+pragmas are added to the SystemVerilog once `design.py` has compiled it. A module pragma is
+emitted as `(* <text> *)` in front of its `module` declaration, and an array pragma in front of
+the `reg` declarations of its register file, which only holds the storage of the array."""
+
+from __future__ import annotations
+
+import re
+from pathlib import Path
+
+from ...utils import namify
+
+_DECLARATION = re.compile(r'^module\s+(\w+)\b.*?^endmodule\b', re.M | re.S)
+_REG = re.compile(r'^(\s*)(reg\b)', re.M)
+
+
+def _attribute(text: str) -> str:
+    '''The SystemVerilog attribute of the pragma `text`.'''
+    return f'(* {text} *)'
+
+
+def collect_synth_pragmas(sys) -> tuple[dict, dict]:
+    '''The pragmas of the modules and of the arrays of `sys`, keyed by the names of their
+    SystemVerilog modules, i.e. the modules and the register files of the arrays.'''
+    modules = {namify(m.name): m.synth_pragmas for m in sys.modules if m.synth_pragmas}
+    arrays = {namify(a.name): a.synth_pragmas for a in sys.arrays if a.synth_pragmas}
+    return modules, arrays
+
+
+def apply_synth_pragmas(directory, modules: dict, arrays: dict) -> None:
+    '''Add the pragmas of `modules` and `arrays`, as returned by `collect_synth_pragmas`, to
+    the SystemVerilog files of `directory` and its subdirectories.'''
+    for path in sorted(Path(directory).rglob('*.sv')):
+        source = path.read_text(encoding='utf-8')
+        patched = _DECLARATION.sub(lambda m: _annotate(m, modules, arrays), source)
+        if patched != source:
+            path.write_text(patched, encoding='utf-8')
+
+
+def _annotate(match, modules: dict, arrays: dict) -> str:
+    '''The declaration of a module, with the pragmas of the module and of its registers.'''
+    name, text = match.group(1), match.group(0)
+    if name in arrays:
+        regs = ' '.join(_attribute(i) for i in arrays[name])
+        text = _REG.sub(lambda m: f'{m.group(1)}{regs} {m.group(2)}', text)
+    if name in modules:
+        text = ''.join(f'{_attribute(i)}\n' for i in modules[name]) + text
+    return text
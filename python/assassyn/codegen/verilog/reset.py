@@ -0,0 +1,51 @@
+"""The reset style of the FIFOs and trigger counters of the Verilog backend.
+
+The registers PyCDE builds for the arrays and the modules reset synchronously on the
+active-high `rst` of the Top harness. The SystemVerilog resources reset asynchronously on an
+active-low `rst_n` by default, which the Top drives with `~rst`. With the `sync_high` style,
+the resources are rewritten as they are copied, so that the whole design resets synchronously
+on `rst`."""
+
+from __future__ import annotations
+
+import re
+
+RESET_STYLES = ('async_low', 'sync_high')
+
+# The resources rewritten under the `sync_high` style, the other ones keeping their reset
+SYNC_HIGH_RESOURCES = ('fifo.sv', 'trigger_counter.sv', 'async_fifo.sv')
+
+_SENSITIVITY = re.compile(r'always @\(posedge (\w+) or negedge \w*rst_n\)')
+_DECLARATION = re.compile(r'(input\s+logic\s+)(\w*)rst_n\b')
+_ASSERTED = re.compile(r'!(\w*)rst_n\b')
+_DEASSERTED = re.compile(r'\b(\w*)rst_n\b')
+
+
+def check_reset_style(style: str) -> None:
+    '''Raise a `ValueError` unless `style` is one of `RESET_STYLES`.'''
+    if style not in RESET_STYLES:
+        raise ValueError(f'reset_style is one of {", ".join(RESET_STYLES)}, not {style!r}')
+
+
+def sync_high_reset(source: str) -> str:
+    '''The SystemVerilog `source` of a resource, its active-low asynchronous resets made
+    active-high synchronous ones: `push_rst_n` becomes `push_rst`, an `always @(posedge clk
+    or negedge rst_n)` becomes an `always @(posedge clk)`, and `!rst_n` becomes `rst`.'''
+    source = _SENSITIVITY.sub(r'always @(posedge \1)', source)
+    source = _DECLARATION.sub(r'\1\2rst', source)
+    source = _ASSERTED.sub(r'\1rst', source)
+    return _DEASSERTED.sub(r'!\1rst', source)
+
+
+def reset_port(style: str, prefix: str = '') -> str:
+    '''The connection of the `<prefix>rst_n` input of a resource to the reset of the Top
+    harness, as the `<prefix>rst` input under the `sync_high` style.'''
+    if style == 'sync_high':
+        return f'{prefix}rst=self.rst'
+    return f'{prefix}rst_n=~self.rst'
+
+
+def sync_reset_arg(style: str) -> str:
+    '''The argument of the `pycde_wrapper` factory of a rewritten resource, selecting its
+    `rst` input under the `sync_high` style.'''
+    return ', sync_reset=True' if style == 'sync_high' else ''
@@ -14,10 +14,25 @@ def _dump_fifo_pop(_dumper, node, with_namespace: bool, _module_name: str = None
     return namify(node.fifo.module.name) + "_" + namify(node.fifo.name)
 
 
+# The width of the chunks a constant beyond the 64-bit range is built from
+_CONST_CHUNK = 32
+
+
 def _dump_const(_dumper, node, _with_namespace: bool, _module_name: str = None) -> str:
     value = node.value
     ty = dump_type(node.dtype)
-    return f"{ty}({value})"
+    if -(1 << 63) <= value < (1 << 63):
+        return f"{ty}({value})"
+    # The attribute of a constant op holds 64 bits, so wider values are concatenated
+    bits = node.dtype.bits
+    chunks = []
+    for lo in range(0, bits, _CONST_CHUNK):
+        width = min(_CONST_CHUNK, bits - lo)
+        chunks.append(f"Bits({width})({(node.raw_bits >> lo) & ((1 << width) - 1)})")
+    res = f"BitsSignal.concat([{', '.join(reversed(chunks))}])"
+    if node.dtype.is_raw():
+        return res
+    return f"{res}.{'as_sint' if node.dtype.is_signed() else 'as_uint'}()"
 
 
 def _dump_str(_dumper, node, _with_namespace: bool, _module_name: str = None) -> str:
@@ -1,30 +1,43 @@
 
 """Testbench generation for Verilog simulation."""
 
-from typing import List, Union
+import os
+from typing import Dict, List, Optional, Union
 from pathlib import Path
+from ...analysis import check_clock_domains
+from ...analysis.coverage import UNCOVERED_PREFIX, cover_key, cover_location, cover_points
 from ...builder import SysBuilder
+from ...utils import namify
+from .utils import probe_field
+from .dpi_log import LOG_DPI_IMPORT, LOG_DPI_INCLUDE, LOG_DPI_SOURCE
+
+# The hardware top generated by the design, i.e. `sv/hw/Top.sv`.
+TOP_MODULE = 'Top'
 
 TEMPLATE = '''
 import os
+import sys
 import glob
 from pathlib import Path
 
 import cocotb
-from cocotb.triggers import Timer
+from cocotb.clock import Clock
+from cocotb.triggers import FallingEdge, Timer
 from cocotb.runner import get_runner
 
 
 
 @cocotb.test()
 async def test_tb(dut):
-
+    {}
     dut.clk.value = 1
     dut.rst.value = 1
     await Timer(500, units="ns")
     dut.clk.value = 0
     dut.rst.value = 0
     await Timer(500, units="ns")
+    {}
+    # `sim_threshold` cycles, global_cycle_count being 1 in the first one as in the simulator
     for cycle in range({}):
         dut.clk.value = 1
         await Timer(500, units="ns")
@@ -33,7 +46,7 @@ async def test_tb(dut):
         {}
         if dut.global_finish.value == 1:
             break
-
+    {}
 
 
 def runner():
@@ -43,19 +56,283 @@ def runner():
         srcs = [path / i.strip() for i in f.readlines()]
     sram_blackbox_files = glob.glob('sram_blackbox_*.sv')
     srcs = srcs + sram_blackbox_files
-    srcs = srcs + ['fifo.sv', 'trigger_counter.sv'{}]
+    srcs = srcs + ['fifo.sv', 'trigger_counter.sv', 'async_fifo.sv'{}]
+    defines = {}
+    includes = {}
+    build_args = {}
+    # `python tb.py --fst` dumps the whole design to an FST waveform
+    waves = '--fst' in sys.argv[1:]
+    if waves:
+        build_args = build_args + ['--trace-fst', '--trace-structs']
+    # The same sources and options, for running Verilator without cocotb
+    with open('verilator.vc', 'w') as f:
+        f.writelines(f'+incdir+{{i}}\\n' for i in includes)
+        f.writelines(f'+define+{{k}}={{v}}\\n' for k, v in defines.items())
+        f.writelines(f'{{i}}\\n' for i in build_args + [str(i) for i in srcs])
     runner = get_runner(sim)
-    runner.build(sources=srcs, hdl_toplevel='Top', always=True)
-    runner.test(hdl_toplevel='Top', test_module='tb')
+    runner.build(sources=srcs, hdl_toplevel='Top', always=True, defines=defines,
+                 includes=includes, build_args=build_args)
+    runner.test(hdl_toplevel='Top', test_module='tb', waves=waves)
 
 if __name__ == "__main__":
     runner()'''
 
-def generate_testbench(fname: Union[str, Path], _sys: SysBuilder, sim_threshold: int,
-                       dump_logger: List[str], external_files: List[str]):
-    """Generate a testbench file for the given system."""
+# A bus functional model of an AXI4-Lite master, running the transactions of the
+# `axil_transactions` option once the reset is released. The signals are driven at the
+# falling edges of the clock, and sampled right after them, before the rising edges which
+# complete the handshakes.
+AXIL_BFM = '''
+async def axil_handshake(signal):
+    # From a falling edge, until `signal` is high at a rising edge, returning at the falling
+    # edge after it
+    while True:
+        await Timer(1, units="ns")
+        high = signal.value == 1
+        await FallingEdge(dut.clk)
+        if high:
+            return
+
+async def axil_write(addr, data, strb=0xf):
+    dut.s_axil_awaddr.value = addr
+    dut.s_axil_wdata.value = data
+    dut.s_axil_wstrb.value = strb
+    dut.s_axil_awvalid.value = 1
+    dut.s_axil_wvalid.value = 1
+    await axil_handshake(dut.s_axil_awready)
+    dut.s_axil_awvalid.value = 0
+    dut.s_axil_wvalid.value = 0
+    dut.s_axil_bready.value = 1
+    await axil_handshake(dut.s_axil_bvalid)
+    dut.s_axil_bready.value = 0
+    resp = int(dut.s_axil_bresp.value)
+    print(f'[axil] write 0x{{addr:x}} 0x{{data:x}} resp {{resp}}')
+
+async def axil_read(addr):
+    dut.s_axil_araddr.value = addr
+    dut.s_axil_arvalid.value = 1
+    await axil_handshake(dut.s_axil_arready)
+    dut.s_axil_arvalid.value = 0
+    dut.s_axil_rready.value = 1
+    await axil_handshake(dut.s_axil_rvalid)
+    dut.s_axil_rready.value = 0
+    data = int(dut.s_axil_rdata.value)
+    resp = int(dut.s_axil_rresp.value)
+    print(f'[axil] read 0x{{addr:x}} 0x{{data:x}} resp {{resp}}')
+
+async def axil_script():
+    for op, *args in {transactions}:
+        if op == 'write':
+            await axil_write(*args)
+        elif op == 'read':
+            await axil_read(*args)
+        else:
+            for _ in range(args[0]):
+                await FallingEdge(dut.clk)
+
+for name in ['awvalid', 'wvalid', 'bready', 'arvalid', 'rready']:
+    getattr(dut, f's_axil_{{name}}').value = 0
+cocotb.start_soon(axil_script())'''
+
+# The VCD waveform of the probes, written next to the testbench
+PROBES_VCD = 'probes.vcd'
+
+
+def _vcd_id(idx: int) -> str:
+    '''The VCD identifier of the `idx`-th probe, from the printable characters.'''
+    res = ''
+    while True:
+        res += chr(33 + idx % 94)
+        idx //= 94
+        if not idx:
+            return res
+
+
+def _probe_paths(sys: SysBuilder) -> List[tuple]:
+    '''The (module, port, bits) of each probe, the port being `dut.<module>.<port>`.'''
+    return [(namify(signal.parent.name), probe_field(label), signal.dtype.bits)
+            for signal, label in sys.probes]
+
+
+def _probe_dump(sys: SysBuilder):
+    '''The lines opening the VCD of the probes, sampling them every cycle, and closing it.'''
+    paths = _probe_paths(sys)
+    if not paths:
+        return [], [], []
+    header = ['$timescale 1us $end']
+    samples = ["probes.write(f'#{int(dut.global_cycle_count.value)}\\n')"]
+    for module in dict.fromkeys(module for module, _, _ in paths):
+        header.append(f'$scope module {module} $end')
+        for idx, (owner, port, bits) in enumerate(paths):
+            if owner == module:
+                header.append(f'$var wire {bits} {_vcd_id(idx)} {port} $end')
+        header.append('$upscope $end')
+    header.append('$enddefinitions $end')
+    for idx, (module, port, _) in enumerate(paths):
+        samples.append(f"probes.write(f'b{{dut.{module}.{port}.value}} {_vcd_id(idx)}\\n')")
+    setup = [f"probes = open(Path(__file__).parent / '{PROBES_VCD}', 'w', encoding='utf-8')"]
+    setup += [f"probes.write('{line}\\n')" for line in header]
+    return setup, samples, ['probes.close()']
+
+
+def _cover_paths(sys: SysBuilder) -> List[tuple]:
+    '''The (key, module, port, location) of each block, the port being `dut.<module>.<port>`.'''
+    return [(cover_key(module, field), namify(module.name), field, cover_location(block))
+            for module in sys.modules + sys.downstreams
+            for field, block in cover_points(module)]
+
+
+def _cover_counts(sys: SysBuilder):
+    '''The lines creating the counters of the blocks, counting the cycles each block is
+    entered, and printing them, then the blocks never entered, as the simulator does.'''
+    paths = _cover_paths(sys)
+    if not paths:
+        return [], [], []
+    setup = [f'cover_hits = {dict.fromkeys([key for key, _, _, _ in paths], 0)!r}']
+    samples = [f"cover_hits['{key}'] += int(dut.{module}.{port}.value)"
+               for key, module, port, _ in paths]
+    teardown = ['for key, hits in cover_hits.items():',
+                "    print(f'[stats] cover {key} {hits}')"]
+    for key, _, _, loc in paths:
+        teardown.append(f"if not cover_hits['{key}']:")
+        teardown.append(f"    print('{UNCOVERED_PREFIX} {key} at ' + {loc!r})")
+    return setup, samples, teardown
+
+
+def _axil_bfm(transactions) -> List[str]:
+    '''The lines of the AXI4-Lite master running `transactions`, each of them being
+    `('write', addr, data)`, `('write', addr, data, strb)`, `('read', addr)` or
+    `('wait', cycles)`, the addresses being byte ones.'''
+    transactions = [tuple(i) for i in transactions]
+    for op in transactions:
+        if not op or op[0] not in ('write', 'read', 'wait') or \
+                len(op) not in {'write': (3, 4), 'read': (2,), 'wait': (2,)}[op[0]]:
+            raise ValueError(f'Invalid AXI4-Lite transaction: {op!r}')
+    return AXIL_BFM.format(transactions=repr(transactions)).strip('\n').split('\n')
+
+
+def _domain_clocks(sys: SysBuilder, clock_domains) -> List[tuple]:
+    '''The (name, half period in ps) of the clock of each domain, the default clock being
+    1000 ns long. Its first rising edge is at time 0, like the one of the default clock.'''
+    periods = check_clock_domains(sys, clock_domains)
+    return [(f'clk_{domain}', round(period * 500_000)) for domain, period in periods.items()]
+
+
+def generate_testbench(  # pylint: disable=too-many-arguments,too-many-locals
+        fname: Union[str, Path], sys: SysBuilder, sim_threshold: int,
+        dump_logger: List[str], external_files: List[str],
+        defines: Optional[Dict[str, str]] = None,
+        include_dirs: Optional[List[str]] = None,
+        verilator_flags: Optional[List[str]] = None,
+        clock_domains: Optional[dict] = None,
+        axil_transactions: Optional[list] = None,
+        cover: bool = False):
+    """Generate a testbench file for the given system.
+
+    `defines`, `include_dirs` and `verilator_flags` are passed to the Verilator build. The
+    clock of each domain in `clock_domains` is driven by a cocotb `Clock`. When
+    `axil_transactions` is not None, an AXI4-Lite master runs them on the `s_axil_*` ports
+    after the reset, printing an `[axil]` line per write and read. With `cover`, the cycles
+    each condition block is entered are counted, and printed at the end."""
+    defines = {str(k): str(v) for k, v in (defines or {}).items()}
+    include_dirs = [str(Path(i).resolve()) for i in include_dirs or []]
+    setup, samples, teardown = _probe_dump(sys)
+    if cover:
+        counts = _cover_counts(sys)
+        setup, samples, teardown = setup + counts[0], samples + counts[1], teardown + counts[2]
+    if axil_transactions is not None:
+        setup += _axil_bfm(axil_transactions)
+    clocks = [f"cocotb.start_soon(Clock(dut.{name}, {2 * half}, units='ps')"
+              ".start(start_high=True))" for name, half in _domain_clocks(sys, clock_domains)]
     with open(str(fname), "w", encoding='utf-8') as f:
-        dump_logger = '\n        '.join(dump_logger)
+        dump_logger = '\n        '.join(samples + dump_logger)
         extra_sources = ''.join(f", '{name}'" for name in external_files)
-        tb_dump = TEMPLATE.format(sim_threshold, dump_logger, extra_sources)
+        tb_dump = TEMPLATE.format('\n    '.join(clocks), '\n    '.join(setup), sim_threshold,
+                                  dump_logger, '\n    '.join(teardown), extra_sources,
+                                  repr(defines), repr(include_dirs),
+                                  repr(list(verilator_flags or [])))
         f.write(tb_dump)
+
+
+VCS_TB_TEMPLATE = '''`timescale 1ns/1ps
+
+// Clock/reset driver for the VCS flow, mirroring the cocotb testbench.
+module tb;{dpi_import}
+  logic clk = 1'b1;
+  logic rst = 1'b1;
+  logic global_finish;{clocks}{covers}
+
+  {top} dut (.clk(clk), .rst(rst), .global_finish(global_finish){ports});
+
+  initial begin{dumps}
+    #500 clk = 1'b0; rst = 1'b0;
+    #500;
+    for (int cycle = 0; cycle < {threshold}; cycle++) begin
+      clk = 1'b1; #500;
+      clk = 1'b0; #500;{dpi_logs}{cover_samples}
+      if (global_finish) break;
+    end{cover_report}
+    $finish;
+  end
+endmodule
+'''
+
+VCS_SCRIPT_TEMPLATE = '''#!/bin/sh
+# Compile and run the generated design with Synopsys VCS.
+set -e
+cd "$(dirname "$0")"
+python design.py
+test -f sv/hw/{top}.sv
+python -c "from assassyn.utils import patch_fifo; patch_fifo('sv/hw/{top}.sv')"
+SRCS="$(sed 's|^|sv/hw/|' sv/hw/filelist.f) $(ls sram_blackbox_*.sv 2>/dev/null || true)"
+SRCS="$SRCS fifo.sv trigger_counter.sv async_fifo.sv{extra}"
+vcs -full64 -sverilog -timescale=1ns/1ps -top tb -o simv tb_vcs.sv $SRCS
+./simv
+'''
+
+
+def generate_vcs_script(  # pylint: disable=too-many-arguments,too-many-locals
+        path: Union[str, Path], sim_threshold: int, external_files: List[str],
+        sys: SysBuilder = None, clock_domains: Optional[dict] = None, axi_lite: bool = False,
+        log_dpi: bool = False, cover: bool = False):
+    """Generate `run_vcs.sh` and its clock/reset testbench `tb_vcs.sv` under `path`. The
+    valids and readies of the AXI4-Lite slave of an `axi_lite` design are tied low. With
+    `log_dpi`, the testbench prints the logs of `dpi_logs.svh` through the DPI-C
+    `assassyn_log` once per cycle, and the script compiles its C side. With `cover`, it counts
+    the cycles each condition block is entered, and prints them at the end."""
+    path = Path(path)
+    paths = _probe_paths(sys) if sys is not None else []
+    domain_clocks = _domain_clocks(sys, clock_domains) if sys is not None else []
+    clocks = ''.join(f"\n  logic {name} = 1'b1;\n  always #{half / 1000:.3f} {name} = ~{name};"
+                     for name, half in domain_clocks)
+    ports = ''.join(f', .{name}({name})' for name, _ in domain_clocks)
+    if axi_lite:
+        ports += ''.join(f", .s_axil_{name}(1'b0)"
+                         for name in ['awvalid', 'wvalid', 'bready', 'arvalid', 'rready'])
+    dumps = ''
+    if paths:
+        dumps = '\n    $fsdbDumpfile("probes.fsdb");'
+        dumps += ''.join(f'\n    $fsdbDumpvars(0, tb.dut.{module}.{port});'
+                         for module, port, _ in paths)
+    dpi_import = dpi_logs = ''
+    if log_dpi:
+        dpi_import = f'\n  {LOG_DPI_IMPORT}\n'
+        dpi_logs = f'\n      `include "{LOG_DPI_INCLUDE}"'
+        external_files = list(external_files) + [LOG_DPI_SOURCE]
+    covers = cover_samples = cover_report = ''
+    if cover and sys is not None:
+        for key, module, port, loc in _cover_paths(sys):
+            covers += f'\n  int {key} = 0;'
+            cover_samples += f'\n      {key} += tb.dut.{module}.{port};'
+            cover_report += f'\n    $display("[stats] cover {key} %0d", {key});'
+            cover_report += f'\n    if ({key} == 0) $display("{UNCOVERED_PREFIX} {key} at {loc}");'
+    with open(path / 'tb_vcs.sv', 'w', encoding='utf-8') as f:
+        f.write(VCS_TB_TEMPLATE.format(top=TOP_MODULE, threshold=sim_threshold, dumps=dumps,
+                                       clocks=clocks, covers=covers, ports=ports,
+                                       dpi_import=dpi_import, dpi_logs=dpi_logs,
+                                       cover_samples=cover_samples,
+                                       cover_report=cover_report))
+    script = path / 'run_vcs.sh'
+    with open(script, 'w', encoding='utf-8') as f:
+        extra = ''.join(f' {name}' for name in external_files)
+        f.write(VCS_SCRIPT_TEMPLATE.format(top=TOP_MODULE, extra=extra))
+    os.chmod(script, 0o755)
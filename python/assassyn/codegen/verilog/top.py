@@ -6,23 +6,37 @@ from collections import defaultdict
 from typing import TYPE_CHECKING, Any
 
 from .utils import (
+    addr_width,
     dump_type,
     dump_type_cast,
     get_sram_info,
 )
 
 from ...analysis import topo_downstream_modules, get_upstreams
+from ...analysis.clock_domain import (
+    array_domain,
+    check_clock_domains,
+    crosses_domain,
+    module_domain,
+    source_domains,
+)
 from ...ir.memory.base import MemoryBase
 from ...ir.module import Downstream
 from ...ir.module.base import ModuleBase
 from ...ir.memory.sram import SRAM
 from ...ir.expr import (
+    AsyncCall,
     Bind,
 )
 from ...ir.expr.intrinsic import ExternalIntrinsic
 from ...ir.dtype import Record
 from ...utils import namify, unwrap_operand
 from ...ir.const import Const
+from .analysis import find_pop_ready_loop
+from .axil import axil_ports, declare_axil_ports, generate_axil
+from .formal import formal_properties, top_formal_port
+from .reset import reset_port, sync_reset_arg
+from .pragma import collect_synth_pragmas
 
 if TYPE_CHECKING:
     from .design import CIRCTDumper
@@ -36,12 +50,30 @@ def generate_top_harness(dumper: CIRCTDumper):
     the analyzed dependencies (async calls, array usage).
     """
 
+    # The clock of each domain is an input of its own, the default one being `clk`
+    domains = check_clock_domains(dumper.sys, dumper.clock_domains)
+    sources = source_domains(dumper.sys)
+
+    def clock_of(domain):
+        return 'self.clk' if domain is None else f'self.clk_{domain}'
+
+    def source_clock(module):
+        return clock_of(next(iter(sources[module])))
+
     dumper.append_code('class Top(Module):')
     dumper.indent += 4
     dumper.append_code('clk = Clock()')
+    for domain in domains:
+        dumper.append_code(f'clk_{domain} = Clock()')
     dumper.append_code('rst = Reset()')
     dumper.append_code('global_cycle_count = Output(UInt(64))')
     dumper.append_code('global_finish = Output(Bits(1))')
+    if dumper.axil_regions:
+        declare_axil_ports(dumper)
+    if dumper.formal:
+        for module in dumper.sys.modules + dumper.sys.downstreams:
+            for field, _ in formal_properties(module):
+                dumper.append_code(f'{top_formal_port(module, field)} = Output(Bits(1))')
     dumper.append_code('')
     dumper.append_code('@generator')
     dumper.append_code('def construct(self):')
@@ -50,9 +82,9 @@ def generate_top_harness(dumper: CIRCTDumper):
     sram_modules = [m for m in dumper.sys.downstreams if isinstance(m, SRAM)]
     if sram_modules:
         dumper.append_code('\n# --- SRAM Memory Blackbox Instances ---')
-        for data_width, addr_width, array_name in dumper.memory_defs:
+        for data_width, address_width, array_name in dumper.memory_defs:
             dumper.append_code(f'mem_{array_name}_dataout = Wire(Bits({data_width}))')
-            dumper.append_code(f'mem_{array_name}_address = Wire(Bits({addr_width}))')
+            dumper.append_code(f'mem_{array_name}_address = Wire(Bits({address_width}))')
             dumper.append_code(f'mem_{array_name}_write_data = Wire(Bits({data_width}))')
             dumper.append_code(f'mem_{array_name}_write_enable = Wire(Bits(1))')
             dumper.append_code(f'mem_{array_name}_read_enable = Wire(Bits(1))')
@@ -118,7 +150,10 @@ def generate_top_harness(dumper: CIRCTDumper):
                 raise RuntimeError(
                     f"Inconsistent FIFO depths for module {module.name}: {depths}"
                 )
-        module_trigger_widths[module] = width
+        # The counter has to hold the credits of a replicated call
+        replication = max((user.replication for user in module.users
+                           if isinstance(user, AsyncCall)), default=1)
+        module_trigger_widths[module] = max(width, replication.bit_length() + 1)
 
     # --- 1. Wire Declarations (Generic) ---
     dumper.append_code('# --- Wires for FIFOs, Triggers, and Arrays ---')
@@ -132,6 +167,12 @@ def generate_top_harness(dumper: CIRCTDumper):
             dumper.append_code(f'{fifo_base_name}_pop_valid = Wire(Bits(1))')
             dumper.append_code(f'{fifo_base_name}_pop_data = Wire(Bits({port.dtype.bits}))')
             dumper.append_code(f'{fifo_base_name}_pop_ready = Wire(Bits(1))')
+            lookahead = port.lookahead_depth()
+            if lookahead:
+                dumper.append_code(f'{fifo_base_name}_peek_valid = Wire(Bits({lookahead}))')
+                dumper.append_code(
+                    f'{fifo_base_name}_peek_data = Wire(Bits({lookahead * port.dtype.bits}))'
+                )
 
     # Wires for TriggerCounters (one per module)
     for module in dumper.sys.modules:
@@ -143,13 +184,15 @@ def generate_top_harness(dumper: CIRCTDumper):
         dumper.append_code(f'{tc_base_name}_pop_valid = Wire(Bits(1))')
         dumper.append_code(f'{tc_base_name}_pop_ready = Wire(Bits(1))')
 
+    # The write and read ports of the AXI4-Lite bus on each mapped array
+    axil_port_indices = {}
     for arr_container in dumper.sys.arrays:
         arr = arr_container
         if arr.is_payload(SRAM):
             continue
         arr_name = namify(arr.name)
         index_bits = arr.index_bits
-        index_bits_type = index_bits if index_bits > 0 else 1
+        index_bits_type = addr_width(arr.size)
         metadata = dumper.array_metadata.metadata_for(arr)
         if metadata is None:
             num_write_ports = len(arr.get_write_ports())
@@ -157,6 +200,11 @@ def generate_top_harness(dumper: CIRCTDumper):
         else:
             num_write_ports = len(metadata.write_ports)
             num_read_ports = len(metadata.read_order)
+        axil_write, axil_read = axil_ports(dumper, arr)
+        if axil_read:
+            axil_port_indices[arr] = (num_write_ports if axil_write else None, num_read_ports)
+        num_write_ports += axil_write
+        num_read_ports += axil_read
         dumper.append_code(
             f'# Multi-port array {arr_name} with '
             f'{num_write_ports} write ports and {num_read_ports} read ports'
@@ -184,7 +232,7 @@ def generate_top_harness(dumper: CIRCTDumper):
             )
 
         # Instantiate multi-port array
-        port_connections = ['clk=self.clk', 'rst=self.rst']
+        port_connections = [f'clk={clock_of(array_domain(arr))}', 'rst=self.rst']
         for port_idx in range(num_write_ports):
             port_suffix = f"_port{port_idx}"
             port_connections.extend([
@@ -216,11 +264,48 @@ def generate_top_harness(dumper: CIRCTDumper):
         for port in module.ports:
             fifo_base_name = f'fifo_{namify(module.name)}_{namify(port.name)}'
             depth = depth_map.get(port, default_fifo_depth)
-            dumper.append_code(
-                f'{fifo_base_name}_inst = FIFO(WIDTH={port.dtype.bits}, DEPTH_LOG2={depth})'
-                f'(clk=self.clk, rst_n=~self.rst, push_valid={fifo_base_name}_push_valid, '
-                f'push_data={fifo_base_name}_push_data, pop_ready={fifo_base_name}_pop_ready)'
-            )
+            flow = ', FLOW_THROUGH=1' if module.flow_through else ''
+            if module.flow_through and depth == 0:
+                # The valid of a flow-through single-element FIFO passes its push through,
+                # and its push_ready depends on pop_ready
+                status = find_pop_ready_loop(port)
+                if status is not None:
+                    raise ValueError(
+                        f'Combinational loop on {module.name}.{port.name}: its pop is '
+                        f'predicated on {status.as_operand()}, but the valid of a flow-through '
+                        'single-element FIFO depends on pop_ready. Use a deeper FIFO'
+                    )
+            lookahead = port.lookahead_depth()
+            if lookahead:
+                if lookahead >= 1 << depth:
+                    raise ValueError(
+                        f'{module.name}.{port.name} peeks entry {lookahead}, '
+                        f'but its FIFO only holds {1 << depth} entries'
+                    )
+                flow += f', PEEK_N={lookahead}'
+            if crosses_domain(module, sources):
+                dumper.append_code(
+                    f'{fifo_base_name}_inst = AsyncFIFO(WIDTH={port.dtype.bits}, '
+                    f'DEPTH_LOG2={max(depth, 1)}, SYNC_STAGES={dumper.cdc_sync_stages}'
+                    f'{sync_reset_arg(dumper.reset_style)})'
+                    f'(push_clk={source_clock(module)}, '
+                    f'{reset_port(dumper.reset_style, "push_")}, '
+                    f'push_valid={fifo_base_name}_push_valid, '
+                    f'push_data={fifo_base_name}_push_data, '
+                    f'pop_clk={clock_of(module_domain(module))}, '
+                    f'{reset_port(dumper.reset_style, "pop_")}, '
+                    f'pop_ready={fifo_base_name}_pop_ready)'
+                )
+            else:
+                dumper.append_code(
+                    f'{fifo_base_name}_inst = FIFO(WIDTH={port.dtype.bits}, '
+                    f'DEPTH_LOG2={depth}{flow}{sync_reset_arg(dumper.reset_style)})'
+                    f'(clk={clock_of(module_domain(module))}, '
+                    f'{reset_port(dumper.reset_style)}, '
+                    f'push_valid={fifo_base_name}_push_valid, '
+                    f'push_data={fifo_base_name}_push_data, '
+                    f'pop_ready={fifo_base_name}_pop_ready)'
+                )
 
             dumper.append_code(
                 f'{fifo_base_name}_push_ready.assign({fifo_base_name}_inst.push_ready)'
@@ -231,14 +316,39 @@ def generate_top_harness(dumper: CIRCTDumper):
             dumper.append_code(
                 f'{fifo_base_name}_pop_data.assign({fifo_base_name}_inst.pop_data)'
             )
+            if lookahead:
+                for tap in ('peek_valid', 'peek_data'):
+                    dumper.append_code(
+                        f'{fifo_base_name}_{tap}.assign({fifo_base_name}_inst.{tap})'
+                    )
 
     # Instantiate TriggerCounters
     for module in dumper.sys.modules:
         tc_base_name = f'{namify(module.name)}_trigger_counter'
         width = module_trigger_widths.get(module, default_fifo_depth)
+        if crosses_domain(module, sources):
+            # The credits cross the domains one per cycle, as the tokens of an async FIFO
+            dumper.append_code(
+                f'{tc_base_name}_inst = AsyncFIFO(WIDTH=1, DEPTH_LOG2={max(width, 1)}, '
+                f'SYNC_STAGES={dumper.cdc_sync_stages}{sync_reset_arg(dumper.reset_style)})'
+                f'(push_clk={source_clock(module)}, {reset_port(dumper.reset_style, "push_")}, '
+                f'push_valid=({tc_base_name}_delta != Bits({width})(0)), '
+                f'push_data=Bits(1)(1), '
+                f'pop_clk={clock_of(module_domain(module))}, '
+                f'{reset_port(dumper.reset_style, "pop_")}, '
+                f'pop_ready={tc_base_name}_pop_ready)'
+            )
+            dumper.append_code(
+                f'{tc_base_name}_delta_ready.assign({tc_base_name}_inst.push_ready)'
+            )
+            dumper.append_code(
+                f'{tc_base_name}_pop_valid.assign({tc_base_name}_inst.pop_valid)'
+            )
+            continue
         dumper.append_code(
-            f'{tc_base_name}_inst = TriggerCounter(WIDTH={width})'
-            f'(clk=self.clk, rst_n=~self.rst, '
+            f'{tc_base_name}_inst = TriggerCounter(WIDTH={width}'
+            f'{sync_reset_arg(dumper.reset_style)})'
+            f'(clk={clock_of(module_domain(module))}, {reset_port(dumper.reset_style)}, '
             f'delta={tc_base_name}_delta, pop_ready={tc_base_name}_pop_ready)'
         )
         dumper.append_code(
@@ -343,7 +453,8 @@ def generate_top_harness(dumper: CIRCTDumper):
         is_sram = isinstance(module, SRAM)
 
         dumper.append_code(f'# Instantiation for {module.name}')
-        port_map = ['clk=self.clk', 'rst=self.rst', 'cycle_count=cycle_count']
+        port_map = [f'clk={clock_of(module_domain(module))}', 'rst=self.rst',
+                    'cycle_count=cycle_count']
         connection_lines = pending_connection_assignments.pop(module, [])
         module_connection_map[module] = connection_lines
 
@@ -359,6 +470,9 @@ def generate_top_harness(dumper: CIRCTDumper):
                         f"{fifo_base_name}_pop_data.{dump_type_cast(port.dtype)}"
                     )
                 port_map.append(f"{namify(port.name)}_valid={fifo_base_name}_pop_valid")
+                if port.lookahead_depth():
+                    for tap in ('peek_valid', 'peek_data'):
+                        port_map.append(f"{namify(port.name)}_{tap}={fifo_base_name}_{tap}")
 
             handled_ports = _attach_consumer_external_entries(module, port_map)
             _attach_external_values(module, port_map, handled_ports)
@@ -409,8 +523,9 @@ def generate_top_harness(dumper: CIRCTDumper):
             # Store the actual Port object that is the target of a push
             all_driven_fifo_ports.add(push.fifo)
 
-        unique_push_targets = {(push.fifo.module, push.fifo) for push in pushes}
-        unique_call_targets = {c.bind.callee for c in calls}
+        # In program order, matching the port declarations of the module
+        unique_push_targets = dict.fromkeys((push.fifo.module, push.fifo) for push in pushes)
+        unique_call_targets = dict.fromkeys(c.bind.callee for c in calls)
 
         for (callee_mod, callee_port) in unique_push_targets:
             port_map.append(
@@ -424,6 +539,10 @@ def generate_top_harness(dumper: CIRCTDumper):
             )
 
         dumper.append_code(f"inst_{mod_name} = {mod_name}({', '.join(port_map)})")
+        if dumper.formal:
+            for field, _ in formal_properties(module):
+                port = top_formal_port(module, field)
+                dumper.append_code(f'self.{port} = inst_{mod_name}.{field}')
 
         if is_sram:
             sram_info = get_sram_info(module)
@@ -523,6 +642,8 @@ def generate_top_harness(dumper: CIRCTDumper):
         metadata = dumper.array_metadata.metadata_for(arr_container)
         if metadata and metadata.users:
             dumper._connect_array(arr_container)
+    if dumper.axil_regions:
+        generate_axil(dumper, axil_port_indices)
 
     dumper.append_code('\n# --- Trigger Counter Delta Connections ---')
     for module in dumper.sys.modules:
@@ -535,6 +656,10 @@ def generate_top_harness(dumper: CIRCTDumper):
                 for c in async_callers
             ]
             summed_triggers = f"reduce(operator.add, [{', '.join(trigger_terms)}])"
+            if module.merge_calls:
+                # At most one credit per cycle, however many callers there are
+                fired = ' | '.join(f"({t} != UInt(8)(0))" for t in trigger_terms)
+                summed_triggers = f"Mux({fired}, UInt({width})(0), UInt({width})(1))"
 
             dumper.append_code(
                 f"{mod_name}_trigger_counter_delta.assign("
@@ -552,3 +677,8 @@ def generate_top_harness(dumper: CIRCTDumper):
     # Copying of external SystemVerilog files occurs during elaboration.
 
     dumper.append_code('system.compile()')
+
+    modules, arrays = collect_synth_pragmas(dumper.sys)
+    if modules or arrays:
+        dumper.append_code('from assassyn.codegen.verilog.pragma import apply_synth_pragmas')
+        dumper.append_code(f"apply_synth_pragmas('sv', {modules!r}, {arrays!r})")
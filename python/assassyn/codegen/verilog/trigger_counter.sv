@@ -31,6 +31,12 @@ assign new_count = temp >= (pop_ready ? 1 : 0) ? temp - (pop_ready ? 1 : 0) : 0;
 always @(posedge clk or negedge rst_n) begin
   if (!rst_n) begin
     count <= '0;
+`ifdef FORMAL
+    // Not reset in simulation, where they are set in the first cycle, but the solver
+    // would start them at any value
+    delta_ready <= 1'b1;
+    pop_valid <= 1'b0;
+`endif
   end else begin
     // If the counter is gonna overflow, this counter cannot accept any new
     // deltas.
@@ -2,17 +2,27 @@
 import re
 from typing import Optional
 
+from ...ir.array import index_width
 from ...ir.module import Module
 from ...ir.memory.sram import SRAM
 from ...ir.expr import Intrinsic
 from ...ir.dtype import Int, UInt, Bits, DType, Record
 from ...utils import namify
 
+def addr_width(size: int) -> int:
+    """Width of the index of an array with `size` elements, i.e. ceil(log2(size)).
+
+    A single-element array needs no index, but its ports still carry a 1-bit one, as
+    PyCDE has no zero-width signals."""
+    return max(index_width(size), 1)
+
+
 def get_sram_info(node: SRAM) -> dict:
     """Extract SRAM-specific information."""
     return {  # pylint: disable=protected-access
         'array': node._payload,
         'init_file': node.init_file,
+        'init_format': node.init_format,
         'width': node.width,
         'depth': node.depth
     }
@@ -31,24 +41,50 @@ def extract_sram_params(node: SRAM) -> dict:
     array = sram_info['array']
     array_name = namify(array.name)
     data_width = array.scalar_ty.bits
-    addr_width = array.index_bits if array.index_bits > 0 else 1
 
     return {
         'sram_info': sram_info,
         'array': array,
         'array_name': array_name,
         'data_width': data_width,
-        'addr_width': addr_width
+        'addr_width': addr_width(array.size)
     }
 
+def _waits_with_depth(module: Module):
+    """Yield each WAIT_UNTIL of a module with the number of condition blocks enclosing it."""
+    depth = 0
+    for elem in getattr(module, 'body', None) or []:
+        if not isinstance(elem, Intrinsic):
+            continue
+        if elem.opcode in (Intrinsic.PUSH_CONDITION, Intrinsic.PUSH_ELSE):
+            depth += 1
+        elif elem.opcode == Intrinsic.POP_CONDITION:
+            depth -= 1
+        elif elem.opcode == Intrinsic.WAIT_UNTIL:
+            yield elem, depth
+
+
 def find_wait_until(module: Module) -> Optional[Intrinsic]:
-    """Find the WAIT_UNTIL intrinsic in a module if it exists."""
-    body = getattr(module, 'body', None) or []
-    for elem in body:
-        if isinstance(elem, Intrinsic):
-            if elem.opcode == Intrinsic.WAIT_UNTIL:
-                return elem
-    return None
+    """Find the first WAIT_UNTIL at the top level of a module, which always holds it.
+
+    A WAIT_UNTIL inside a condition block only holds the module when the block is taken,
+    so it is skipped, even if a condition block comes before it."""
+    return next((wait for wait, depth in _waits_with_depth(module) if depth == 0), None)
+
+
+def find_wait_until_recursive(module: Module) -> Optional[Intrinsic]:
+    """Find the first WAIT_UNTIL of a module, including those inside condition blocks."""
+    return next((wait for wait, _ in _waits_with_depth(module)), None)
+
+
+def probe_field(label: str) -> str:
+    """The output port of the module computing the probe `label`."""
+    return f'probe_{label}'
+
+
+def module_probes(sys, module) -> list:
+    """The (signal, label) of the probes of `sys` computed in `module`."""
+    return [(signal, label) for signal, label in sys.probes if signal.parent is module]
 
 
 def ensure_bits(expr_str: str) -> str:
@@ -109,6 +145,6 @@ from pycde.signals import Struct, BitsSignal
 from pycde.dialects import comb,sv
 from functools import reduce
 import operator
-from assassyn.pycde_wrapper import FIFO, TriggerCounter, build_register_file
+from assassyn.pycde_wrapper import FIFO, TriggerCounter, build_register_file, AsyncFIFO, AxilRegs
 
 '''
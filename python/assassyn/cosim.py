@@ -0,0 +1,241 @@
+'''Co-simulation: drive the FIFOs of a generated simulator cycle by cycle from Python, and
+compare the traces of the simulator and of the Verilog testbench.'''
+
+from __future__ import annotations
+
+import subprocess
+import typing
+from dataclasses import dataclass, field
+
+from .backend import config, elaborate
+from .builder import SysBuilder
+from .ir.array import Array
+from .ir.memory.base import MemoryBase
+from .ir.module import Module
+from .codegen.simulator.utils import fifo_name
+from .codegen.simulator.cosim import REPLY
+from . import utils
+from .utils import namify
+
+
+class SimHandle:
+    '''A running simulator built with `cosim=True`.
+
+    Lines printed by the design (e.g. `log`) are collected in `logs`.'''
+
+    def __init__(self, sys: SysBuilder, binary_path: str):
+        self.logs: typing.List[str] = []
+        self.cycle = 0
+        self._fifos = {}
+        self._arrays = {id(array): namify(array.name) for array in sys.arrays}
+        for module in sys.modules:
+            if isinstance(module, Module):
+                for port in module.ports:
+                    self._fifos[(module.name, port.name)] = fifo_name(port)
+        # pylint: disable=consider-using-with
+        self._process = subprocess.Popen(
+            [str(binary_path)],
+            stdin=subprocess.PIPE,
+            stdout=subprocess.PIPE,
+            text=True,
+            bufsize=1,
+        )
+
+    def _fifo(self, module_name: str, port_name: str) -> str:
+        key = (module_name, port_name)
+        if key not in self._fifos:
+            raise KeyError(f'No FIFO {port_name} in module {module_name}')
+        return self._fifos[key]
+
+    def _request(self, command: str) -> typing.List[str]:
+        '''Send one command and return the tokens of its reply.'''
+        if self._process.poll() is not None:
+            raise RuntimeError('The simulator has exited')
+        self._process.stdin.write(command + '\n')
+        self._process.stdin.flush()
+        for line in self._process.stdout:
+            line = line.rstrip('\n')
+            if not line.startswith(REPLY + ' '):
+                self.logs.append(line)
+                continue
+            reply = line.split()[1:]
+            if reply[0] == 'error':
+                raise RuntimeError(f'{command}: {" ".join(reply[1:])}')
+            return reply
+        raise RuntimeError(f'The simulator exited while serving "{command}"')
+
+    def push_to_fifo(self, module_name: str, port_name: str, value: int) -> None:
+        '''Append `value` to a FIFO and trigger its module in the next cycle.'''
+        self._request(f'push {self._fifo(module_name, port_name)} {int(value)}')
+
+    def pop_from_fifo(self, module_name: str, port_name: str) -> typing.Optional[int]:
+        '''Remove the front of a FIFO, or return None if it is empty.'''
+        reply = self._request(f'pop {self._fifo(module_name, port_name)}')
+        return int(reply[1]) if reply[0] == 'value' else None
+
+    def _array(self, array: typing.Union[Array, MemoryBase]) -> str:
+        if isinstance(array, MemoryBase):
+            array = array._payload  # pylint: disable=protected-access
+        if id(array) not in self._arrays:
+            raise KeyError(f'No array {array.name} in the system')
+        return self._arrays[id(array)]
+
+    def read_array(self, array: typing.Union[Array, MemoryBase], index: int) -> int:
+        '''Return the element `index` of an array, or of the contents of a memory.'''
+        reply = self._request(f'read {self._array(array)} {int(index)}')
+        return int(reply[1])
+
+    def write_array(self, array: typing.Union[Array, MemoryBase], index: int, value: int) -> None:
+        '''Overwrite the element `index` of an array, or of the contents of a memory, as seen
+        from the next simulated cycle.'''
+        self._request(f'write {self._array(array)} {int(index)} {int(value)}')
+
+    def _advance(self, command: str) -> int:
+        reply = self._request(command)
+        self.cycle = int(reply[2])
+        return int(reply[1])
+
+    def run_one_cycle(self) -> int:
+        '''Simulate one cycle and return the current cycle.'''
+        self._advance('step')
+        return self.cycle
+
+    def run_until_idle(self, max_cycles: int) -> int:
+        '''Simulate until a cycle triggers no module, or at most `max_cycles` cycles.
+
+        Returns the number of simulated cycles.'''
+        return self._advance(f'run {max_cycles}')
+
+    def close(self) -> None:
+        '''Stop the simulator and collect its remaining output.'''
+        if self._process.poll() is None:
+            out, _ = self._process.communicate('quit\n')
+            self.logs.extend(out.splitlines())
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.close()
+
+
+def elaborate_for_sim(sys: SysBuilder, **kwargs) -> SimHandle:
+    '''Elaborate and build a co-simulation simulator of `sys`, and start it.
+
+    `kwargs` are the same as `backend.config`; the Verilog backend and the build cache
+    are disabled unless asked for.'''
+    cfg = config(verilog=False, enable_cache=False)
+    cfg.update(kwargs)
+    cfg['cosim'] = True
+    manifest_path, _ = elaborate(sys, **cfg)
+    binary_path = utils.build_simulator(manifest_path)
+    return SimHandle(sys, binary_path)
+
+
+def run_one_cycle(handle: SimHandle) -> int:
+    '''Simulate one cycle of `handle`.'''
+    return handle.run_one_cycle()
+
+
+def run_until_idle(handle: SimHandle, max_cycles: int) -> int:
+    '''Simulate `handle` until it is idle, or at most `max_cycles` cycles.'''
+    return handle.run_until_idle(max_cycles)
+
+
+TRACE_PREFIX = '@trace'
+
+# Cycle -> the (signal, value) of the array writes and FIFO pushes of the cycle
+Trace = typing.Dict[int, typing.List[typing.Tuple[str, str]]]
+
+
+def parse_trace(raw: str) -> Trace:
+    '''Collect the `@trace <cycle> <signal> <value>` lines of a simulator or testbench output.'''
+    res = {}
+    for line in raw.splitlines():
+        tokens = line.split()
+        if len(tokens) != 4 or tokens[0] != TRACE_PREFIX:
+            continue
+        res.setdefault(int(tokens[1]), []).append((tokens[2], tokens[3]))
+    return res
+
+
+@dataclass
+class Divergence:
+    '''The first signal written differently by the two backends.'''
+
+    # The cycle, as numbered by the simulator
+    cycle: int
+    # `<array>[<index>]` for an array write, `<module>.<port>` for a FIFO push
+    signal: str
+    # The hexadecimal values written to the signal in the cycle by each backend
+    simulator: typing.List[str] = field(default_factory=list)
+    verilog: typing.List[str] = field(default_factory=list)
+
+    def __str__(self):
+        return (f'cycle {self.cycle}: {self.signal} is {self.simulator} in the simulator, '
+                f'but {self.verilog} in Verilog')
+
+
+@dataclass
+class CosimReport:
+    '''The result of comparing the traces of the two backends.'''
+
+    # The number of traced cycles that match, before the divergence if any
+    cycles: int = 0
+    divergence: typing.Optional[Divergence] = None
+
+    @property
+    def ok(self) -> bool:
+        '''Whether the traces match.'''
+        return self.divergence is None
+
+
+def compare_traces(simulator_raw: str, verilog_raw: str) -> CosimReport:
+    '''Compare the `@trace` lines of a simulator and a testbench output, cycle by cycle.
+
+    The testbench counts its cycles from a different origin, so the traces are aligned on their
+    first traced cycle. Only the cycles both backends simulated are compared, as they may stop
+    a cycle apart. Within a cycle, the order of the lines does not matter.'''
+    sim = parse_trace(simulator_raw)
+    ver = parse_trace(verilog_raw)
+    if not sim or not ver:
+        if not sim and not ver:
+            return CosimReport()
+        # Only one backend traced anything, so it diverges on its first line
+        cycle = min(sim or ver)
+        signal = min(sim.get(cycle, []) + ver.get(cycle, []))[0]
+        sim_values = sorted(v for s, v in sim.get(cycle, []) if s == signal)
+        ver_values = sorted(v for s, v in ver.get(cycle, []) if s == signal)
+        return CosimReport(0, Divergence(cycle, signal, sim_values, ver_values))
+    offset = min(ver) - min(sim)
+    ver = {cycle - offset: entries for cycle, entries in ver.items()}
+    last = min(max(sim), max(ver))
+    report = CosimReport()
+    for cycle in sorted(set(sim) | set(ver)):
+        if cycle > last:
+            break
+        sim_entries = sim.get(cycle, [])
+        ver_entries = ver.get(cycle, [])
+        for signal in sorted({s for s, _ in sim_entries} | {s for s, _ in ver_entries}):
+            sim_values = sorted(v for s, v in sim_entries if s == signal)
+            ver_values = sorted(v for s, v in ver_entries if s == signal)
+            if sim_values != ver_values:
+                report.divergence = Divergence(cycle, signal, sim_values, ver_values)
+                return report
+        report.cycles += 1
+    return report
+
+
+def compare_backends(sys: SysBuilder, **kwargs) -> CosimReport:
+    '''Elaborate `sys` for both backends with `trace=True`, run them, and compare their traces.
+
+    `kwargs` are the same as `backend.config`; the build cache is disabled unless asked for.
+    Verilator must be available.'''
+    if not utils.has_verilator():
+        raise RuntimeError('Comparing the backends needs Verilator')
+    cfg = config(enable_cache=False)
+    cfg.update(kwargs)
+    cfg.update(simulator=True, verilog=True, trace=True)
+    manifest_path, verilog_path = elaborate(sys, **cfg)
+    return compare_traces(utils.run_simulator(manifest_path), utils.run_verilator(verilog_path))
+
@@ -1,14 +1,22 @@
 '''Programming interfaces exposes as the frontend of assassyn'''
 
 #pylint: disable=unused-import
-from .ir.array import RegArray, Array
-from .ir.dtype import DType, Int, UInt, Float, Bits, Record
-from .builder import SysBuilder, ir_builder, Singleton, rewrite_assign
-from .ir.expr import Expr, log, concat, finish, wait_until, assume
+from .ir.array import RegArray, Array, WritePorts, SynthPragma, create_array_with_data
+from .ir.array import create_array_from_file
+from .ir.array import create_counter, create_counter_with_enable, create_counter_with_wrap
+from .ir.array import create_gray_counter
+from .ir.partition import CyclicPartition, PartitionedArray
+from .ir.dtype import (DType, Int, UInt, Float, Bits, Record, Fixed, Str, to_fixed,
+                       from_fixed)
+from .ir.const import imm, bool_imm, zero
+from .builder import SysBuilder, ir_builder, Singleton, rewrite_assign, probe
+from .ir.expr import Expr, log, concat, finish, wait_until, assume, watchdog
+from .ir.expr import one_hot_mux, binary_mux, binary_to_gray, gray_to_binary
 from .ir.expr import push_condition, pop_condition, get_pred
 from .ir.expr import send_read_request, send_write_request
 from .ir.expr import has_mem_resp
-from .ir.module import Module, Port, Downstream, fsm
+from .ir.module import Module, Port, Downstream, DownstreamContext, create_downstream, fsm, stage
+from .ir.module import create_downstream_with_condition
 from .ir.module.external import (
     ExternalSV,
     external,
@@ -18,7 +26,8 @@ from .ir.module.external import (
 )
 from .ir.memory.sram import SRAM
 from .ir.memory.dram import DRAM
-from .ir.block import Condition, Cycle
+from .ir.memory.banked import BankedSRAM
+from .ir.block import Condition, ConditionWithElse, Cycle, CycleRange, Else, Switch
 from .ir import module
 from .ir.module import downstream
 from .ir.value import Value
@@ -0,0 +1,61 @@
+"""File stimulus IP.
+
+This module feeds the values listed in a data file into a module port, one value per cycle,
+so that a testbench can be driven by the same stimulus files as the memories.
+"""
+
+from assassyn.frontend import *
+
+
+def parse_stimulus_file(path: str, fmt: str = 'hex'):
+    """Parse a stimulus file into the list of its values.
+
+    The format follows the `$readmemh` files accepted by `load_hex_file` in the simulator
+    runtime: `//` starts a comment, `_` separates digits, and `@addr` moves the write pointer.
+    `fmt` is either `'hex'` or `'dec'`, the radix of the values (addresses are always hex).
+    """
+    radix = {'hex': 16, 'dec': 10}.get(fmt)
+    assert radix is not None, f'Unknown stimulus format: {fmt}'
+    values = []
+    idx = 0
+    with open(path, encoding='utf-8') as f:
+        for line in f:
+            line = line.split('//')[0].strip().replace('_', '')
+            if not line:
+                continue
+            if line.startswith('@'):
+                idx = int(line[1:], 16)
+                continue
+            if idx >= len(values):
+                values.extend([0] * (idx + 1 - len(values)))
+            values[idx] = int(line, radix)
+            idx += 1
+    assert values, f'Empty stimulus file: {path}'
+    return values
+
+
+def file_stimulus(port: Port, path: str, fmt: str = 'hex', finish_at_end: bool = False):
+    """Push the values of a stimulus file into `port`, one value per cycle.
+
+    It is called in the `build` of a module active in every cycle, i.e. the `Driver` or the
+    `Testbench`. The file is loaded into a register array at build time, and a pointer register
+    walks it: while values remain, the owner of `port` is async-called with the next one.
+    With `finish_at_end`, the simulation finishes once the last value is consumed.
+
+    Returns the pointer register value, i.e. the number of values pushed so far.
+    """
+    values = parse_stimulus_file(path, fmt)
+    n = len(values)
+    owner = Singleton.peek_builder().current_module
+    data = RegArray(port.dtype, n, initializer=values, name=f'{port.name}_stimulus')
+    ptr = RegArray(UInt(max(1, n.bit_length())), 1, name=f'{port.name}_stimulus_ptr')
+    cur = ptr[0]
+    with Condition(cur < cur.dtype(n)):
+        index = cur[0:data.index_bits - 1] if data.index_bits else 0
+        port.module.async_called(**{port.name: data[index]})
+        (ptr & owner)[0] <= cur + cur.dtype(1)
+    if finish_at_end:
+        # The last value pushed in the previous cycle is consumed in this cycle
+        with Condition(cur == cur.dtype(n)):
+            finish()
+    return cur
@@ -2,9 +2,9 @@
 
 # Import core IR classes for convenience
 from .array import Array, RegArray
-from .block import Condition, Cycle
+from .block import Condition, ConditionWithElse, Cycle, CycleRange, Else
 from .const import Const
-from .dtype import DType, Int, UInt, Record, to_uint, to_int
+from .dtype import DType, Int, UInt, Record, Fixed, to_fixed, from_fixed, to_uint, to_int
 from .value import Value
 from .visitor import Visitor
 
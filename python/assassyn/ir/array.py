@@ -21,6 +21,42 @@ else:
     OwnerType = typing.Any
 
 
+class WritePorts:  # pylint: disable=too-few-public-methods
+    '''The array attribute declaring its write ports. Each module writing the array gets a
+    port of its own, so at most `count` modules may write it.'''
+
+    count: int
+
+    def __init__(self, count: int):
+        assert isinstance(count, int) and count > 0, f'Invalid number of write ports: {count}'
+        self.count = count
+
+    def __repr__(self):
+        return f'write_ports({self.count})'
+
+
+class SynthPragma:  # pylint: disable=too-few-public-methods
+    '''The attribute passing a synthesis directive, e.g. `ram_style = "block"`, to the
+    synthesis tool. The Verilog backend emits it as `(* <text> *)` on the storage of the
+    array, or on the declaration of a module.'''
+
+    text: str
+
+    def __init__(self, text: str):
+        if not isinstance(text, str) or not text.strip() or '*)' in text or '\n' in text:
+            raise ValueError(f'A synthesis pragma is a line of text without "*)", not {text!r}')
+        self.text = text.strip()
+
+    def __repr__(self):
+        return f'synth_pragma({self.text!r})'
+
+
+def index_width(size: int) -> int:
+    '''The bits indexing `size` elements, i.e. ceil(log2(size)): 0 for 1, 5 for 32, 6 for 33.'''
+    assert size > 0, f'Invalid array size {size}'
+    return (size - 1).bit_length()
+
+
 def _validate_owner(owner: typing.Any) -> OwnerType:
     '''Ensure the provided owner reference is recognised.'''
     # pylint: disable=import-outside-toplevel
@@ -107,16 +143,35 @@ def RegArray(  # pylint: disable=invalid-name,too-many-arguments
     Args:
         scalar_ty: The data type of the array elements.
         size: The size of the array. MUST be a compilation time constant.
-        attr: The attribute list of the array.
+        attr: The attribute list of the array. With a `CyclicPartition`, the array is split
+        into banks, and a `PartitionedArray` is returned. With `WritePorts(n)`, at most `n`
+        modules may write the array, or each of its banks.
         initializer: The initializer of the register array. If not set, it is 0-initialized.
     '''
 
     attr = attr if attr is not None else []
     resolved_owner = _resolve_owner(owner)
+    if initializer is not None:
+        _check_initializer(scalar_ty, size, initializer)
+
+    # pylint: disable=import-outside-toplevel
+    from .partition import CyclicPartition, partition_array
+    partitions = [i for i in attr if isinstance(i, CyclicPartition)]
+    others = [i for i in attr if not isinstance(i, CyclicPartition)]
+    assert len([i for i in attr if isinstance(i, WritePorts)]) <= 1, \
+        'The write ports of an array can only be declared once'
+    if partitions:
+        assert len(partitions) == 1, 'An array can only be partitioned once'
+        res = partition_array(scalar_ty, size, initializer, name, partitions[0], owner)
+        for bank in res.banks:
+            bank.attr = list(others)
+        return res
 
     res = Array(scalar_ty, size, initializer, resolved_owner)
+    res.attr = others
     if name is not None:
-        res.name = name
+        builder = Singleton.peek_builder()
+        res.name = _unique_array_name(builder, builder.scoped_name(name))
 
     manager = getattr(Singleton, 'naming_manager', None)
     if manager is not None:
@@ -137,6 +192,123 @@ def RegArray(  # pylint: disable=invalid-name,too-many-arguments
 
     return res
 
+def _unique_array_name(builder, name: str) -> str:
+    '''`name`, suffixed by `_1`, `_2`, ... when an array of the system already has it.'''
+    taken = {array.name for array in builder.arrays}
+    res, suffix = name, 0
+    while res in taken:
+        suffix += 1
+        res = f'{name}_{suffix}'
+    return res
+
+
+def verify_array_names(sys) -> None:
+    '''Check that no two arrays of `sys` share a name, which both backends would merge.'''
+    seen = {}
+    for array in sys.arrays:
+        if seen.setdefault(array.name, array) is not array:
+            raise ValueError(f'Two arrays are named {array.name}, so their signals would merge')
+
+
+def verify_write_ports(sys) -> None:
+    '''Check that no array of `sys` has more writers than its `WritePorts` declare.'''
+    for array in sys.arrays:
+        limit = array.write_port_limit
+        writers = list(array.get_write_ports())
+        if limit is not None and len(writers) > limit:
+            names = ', '.join(module.name for module in writers)
+            raise ValueError(f'{array.name} has {limit} write ports, but {len(writers)} '
+                             f'modules write it: {names}')
+
+
+def _check_initializer(scalar_ty: DType, size: int, data: list):
+    '''Check that `data` holds exactly `size` values of `scalar_ty`.'''
+    assert len(data) == size, \
+        f'Initializer of {len(data)} values for an array of {size} elements'
+    for i, value in enumerate(data):
+        assert scalar_ty.inrange(value), \
+            f'Initial value {value} of element {i} does not fit in {scalar_ty}'
+
+
+def create_array_with_data(name: str, scalar_ty: DType, data: list, **kwargs):
+    '''Declare a register array holding `data`, one element per value.
+
+    It is a shorthand for `RegArray(scalar_ty, len(data), initializer=data, name=name)`, and
+    the other arguments of `RegArray` are passed along. Each value must fit in `scalar_ty`.'''
+    return RegArray(scalar_ty, len(data), initializer=list(data), name=name, **kwargs)
+
+
+def create_array_from_file(name: str, scalar_ty: DType, size: int, path: str, **kwargs):
+    '''Declare a register array of `size` elements initialized from a `$readmemh` file.
+
+    The file is read when the array is declared, in the format of SRAM initialization files,
+    so that both backends see the same initializer. The elements the file does not cover are
+    0-initialized, and each value must fit in `scalar_ty`.'''
+    #pylint: disable=import-outside-toplevel
+    from ..ip.stimulus import parse_stimulus_file
+    data = parse_stimulus_file(path)
+    assert len(data) <= size, \
+        f'{path} holds {len(data)} values, more than the {size} elements of the array'
+    data += [0] * (size - len(data))
+    return RegArray(scalar_ty, size, initializer=data, name=name, **kwargs)
+
+
+def create_counter(  # pylint: disable=too-many-arguments
+        bits: int, init: int = 0, step: int = 1, *,
+        enable: Value = None, wrap: int = None, name: str = None):
+    '''Declare a `UInt(bits)` counter starting at `init`, written by the current module.
+
+    Returns `(array, value)`: the 1-element array holding the counter, and its value in this
+    cycle. The counter adds `step` in every cycle the module executes, or only in the cycles
+    where `enable` is high. It wraps around at `2 ** bits`, or with `wrap`, counts modulo
+    `wrap + 1`, so that it never exceeds `wrap`.'''
+    # pylint: disable=import-outside-toplevel
+    from .dtype import UInt
+    from .block import Condition
+    limit = (1 << bits) - 1 if wrap is None else wrap
+    assert 0 < limit < 1 << bits, f'Counter bound {wrap} does not fit in {bits} bits'
+    assert 0 <= init <= limit, f'Counter initial value {init} is not in [0, {limit}]'
+    assert 0 < step <= limit, f'Counter step {step} is not in [1, {limit}]'
+    array = RegArray(UInt(bits), 1, initializer=[init], name=name)
+    value = array[0]
+    nxt = value + UInt(bits)(step)
+    if wrap is not None:
+        # Past `wrap - step` the sum would exceed `wrap`, so subtract `wrap + 1` instead
+        over = value > UInt(bits)(wrap - step)
+        nxt = over.select(value - UInt(bits)(wrap + 1 - step), nxt)
+    if enable is None:
+        array[0] = nxt
+    else:
+        array.write_if(enable, nxt)
+    return array, value
+
+
+def create_counter_with_enable(bits: int, init: int, step: int, enable: Value, name: str = None):
+    '''Declare a counter adding `step` only in the cycles where `enable` is high.
+
+    It is a shorthand for `create_counter(bits, init, step, enable=enable)`.'''
+    return create_counter(bits, init, step, enable=enable, name=name)
+
+
+def create_counter_with_wrap(bits: int, init: int, step: int, max_value: int, name: str = None):
+    '''Declare a modulo counter, counting modulo `max_value + 1`.
+
+    It is a shorthand for `create_counter(bits, init, step, wrap=max_value)`.'''
+    return create_counter(bits, init, step, wrap=max_value, name=name)
+
+
+def create_gray_counter(bits: int, init: int = 0, *, enable: Value = None, name: str = None):
+    '''Declare a `bits`-bit counter whose value is read in Gray code, so that a single bit of
+    it changes per increment, as the pointers crossing clock domains need.
+
+    Returns `(array, value)`: the 1-element array holding the binary count, and the Gray code
+    of its value in this cycle. `init` and `enable` are those of `create_counter`.'''
+    # pylint: disable=import-outside-toplevel
+    from .expr import binary_to_gray
+    array, value = create_counter(bits, init, enable=enable, name=name)
+    return array, binary_to_gray(value)
+
+
 class Array:  #pylint: disable=too-many-instance-attributes
     '''The class represents a register array in the AST IR.'''
 
@@ -247,6 +419,10 @@ class Array:  #pylint: disable=too-many-instance-attributes
         '''Enhanced repr to show read/write operations in tree format'''
         # Start with array declaration
         res = f'{self.name} = [{self.scalar_ty}; {self.size}];'
+        pragmas = [i for i in self.attr if isinstance(i, SynthPragma)]
+        if pragmas:
+            # Part of the dump, so that a new pragma changes the build cache key
+            res += f' #[{", ".join(repr(i) for i in pragmas)}]'
 
         # Collect read and write operations
         read_ops = []
@@ -291,8 +467,7 @@ class Array:  #pylint: disable=too-many-instance-attributes
     @property
     def index_bits(self):
         '''Get the number of bits needed to index the array.'''
-        is_p2 = self.size & (self.size - 1) == 0
-        return self.size.bit_length() - is_p2
+        return index_width(self.size)
 
     def index_type(self):
         '''Get the type of the index.'''
@@ -304,6 +479,17 @@ class Array:  #pylint: disable=too-many-instance-attributes
         '''Get the write_ports.'''
         return getattr(self, '_write_ports', {})
 
+    @property
+    def write_port_limit(self):
+        '''The number of write ports declared by a `WritePorts` attribute, or None.'''
+        limits = [i.count for i in self.attr if isinstance(i, WritePorts)]
+        return limits[0] if limits else None
+
+    @property
+    def synth_pragmas(self):
+        '''The texts of the `SynthPragma` attributes of the array, in order.'''
+        return [i.text for i in self.attr if isinstance(i, SynthPragma)]
+
     @ir_builder
     def __getitem__(self, index: typing.Union[int, Value]):
         if isinstance(index, int):
@@ -326,3 +512,30 @@ class Array:  #pylint: disable=too-many-instance-attributes
         current_module = Singleton.peek_builder().current_module
         write_port = self & current_module
         return write_port._create_write(index, value)
+
+    def write_if(self, enable: Value, value, index=0):
+        '''Write `value` to `index` only in the cycles where `enable` is high.
+
+        Unlike `self[index] = enable.select(value, self[index])`, the write is guarded by the
+        predicate, so the write enable is `enable` instead of always-on.'''
+        from .block import Condition  # pylint: disable=import-outside-toplevel
+        with Condition(enable):
+            self[index] = value
+
+    def store_masked(self, index, value: Value, mask: Value):
+        '''Write the bytes of `value` whose bit of `mask` is high to `index`, keeping the others.
+
+        Bit `i` of `mask` selects the byte `8 * i` to `8 * i + 7`, so `mask` has one bit per
+        byte of the element.'''
+        bits = self.scalar_ty.bits
+        if bits % 8 != 0:
+            raise ValueError(f'{self.name} has {bits}-bit elements, not whole bytes')
+        if not isinstance(mask, Value) or mask.dtype.bits != bits // 8:
+            raise ValueError(f'The mask of {self.name} must have {bits // 8} bits, got {mask!r}')
+        old = self[index]
+        lanes = [mask[i:i].select(value[8 * i:8 * i + 7], old[8 * i:8 * i + 7])
+                 for i in range(bits // 8)]
+        merged = lanes[-1]
+        for lane in reversed(lanes[:-1]):
+            merged = merged.concat(lane)
+        self[index] = merged.bitcast(self.scalar_ty)
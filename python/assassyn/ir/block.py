@@ -26,6 +26,21 @@ class _PredicateScope:  # pylint: disable=too-few-public-methods
         pop_condition()
 
 
+class _ElseScope:  # pylint: disable=too-few-public-methods
+    '''Context manager opening the else block of the condition block closed right before.'''
+
+    def __enter__(self):
+        # pylint: disable=import-outside-toplevel
+        from .expr.intrinsic import push_else
+        push_else()
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        # pylint: disable=import-outside-toplevel
+        from .expr.intrinsic import pop_condition
+        pop_condition()
+
+
 def Condition(cond):  # pylint: disable=invalid-name
     # pylint: disable=import-outside-toplevel
     '''Frontend API for conditionally guarding statements using predicate intrinsics.'''
@@ -34,11 +49,177 @@ def Condition(cond):  # pylint: disable=invalid-name
     return _PredicateScope(cond)
 
 
-def Cycle(cycle: int):  # pylint: disable=invalid-name
+def Else():  # pylint: disable=invalid-name
+    '''Frontend API for guarding statements by the negation of the condition block right before.
+
+    It must directly follow a `Condition` block, which has no else block yet.'''
+    return _ElseScope()
+
+
+def ConditionWithElse(cond):  # pylint: disable=invalid-name
+    '''Frontend API for creating a pair of mutually exclusive blocks, taken if `cond` holds and
+    otherwise. The else block must be entered right after the then block.'''
+    return Condition(cond), Else()
+
+
+class Switch:
+    '''Frontend API for multi-way condition blocks on a value, like a `match`/`case`.
+
+    Each `case` is a `Condition` block taken if the value equals one of its keys, and the
+    `default` block, declared last, is taken if no case matches:
+
+        with Switch(opcode) as sw:
+            with sw.case(0b0110111):
+                ...
+            with sw.case(0b0010011, 0b0000011):
+                ...
+            with sw.default():
+                ...
+    '''
+
+    value: Value  # The value switched on
+    keys: list  # The integer keys of the cases declared so far
+    arms: list  # The predicate of each case declared so far
+    has_default: bool  # Whether the default block was declared
+
+    def __init__(self, value):
+        # pylint: disable=import-outside-toplevel
+        from .value import Value
+        assert isinstance(value, Value), f'Expecting a Value to switch on, got {value}'
+        self.value = value
+        self.keys = []
+        self.arms = []
+        self.has_default = False
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        pass
+
+    def case(self, *keys):
+        '''A condition block taken if the value equals one of `keys`, which are integers or
+        values. Integer keys are cast to the type of the value, and must be distinct.'''
+        assert keys, 'Expecting at least one key'
+        assert not self.has_default, 'A case after the default block is never taken'
+        pred = None
+        for key in keys:
+            if isinstance(key, int):
+                assert key not in self.keys, f'Duplicated case key: {key}'
+                self.keys.append(key)
+                key = self.value.dtype(key)
+            eq = self.value == key
+            pred = eq if pred is None else pred | eq
+        self.arms.append(pred)
+        return Condition(pred)
+
+    def default(self):
+        '''A condition block taken if none of the cases declared before matches.'''
+        assert self.arms, 'Expecting at least one case before the default block'
+        assert not self.has_default, 'A switch has at most one default block'
+        self.has_default = True
+        matched = self.arms[0]
+        for pred in self.arms[1:]:
+            matched = matched | pred
+        return Condition(~matched)
+
+
+def _is_intrinsic(expr, opcode) -> bool:
+    # pylint: disable=import-outside-toplevel
+    from .expr.intrinsic import Intrinsic
+    return isinstance(expr, Intrinsic) and expr.opcode == opcode
+
+
+def closed_block(body: list):
+    '''Get the PUSH_CONDITION of the condition block closed by the last expression of `body`.
+
+    Raises ValueError if `body` does not end with a condition block, or if the block is an
+    else block, since an else block has no else block of its own.'''
+    # pylint: disable=import-outside-toplevel
+    from .expr.intrinsic import Intrinsic
+    if not body or not _is_intrinsic(body[-1], Intrinsic.POP_CONDITION):
+        raise ValueError('An else block must directly follow a condition block')
+    depth = 0
+    for expr in reversed(body[:-1]):
+        if _is_intrinsic(expr, Intrinsic.POP_CONDITION):
+            depth += 1
+        elif _is_intrinsic(expr, Intrinsic.PUSH_CONDITION) and depth == 0:
+            return expr
+        elif _is_intrinsic(expr, Intrinsic.PUSH_ELSE) and depth == 0:
+            raise ValueError('A condition block has at most one else block')
+        elif _is_intrinsic(expr, Intrinsic.PUSH_CONDITION) or \
+                _is_intrinsic(expr, Intrinsic.PUSH_ELSE):
+            depth -= 1
+    raise ValueError('Unbalanced condition blocks')
+
+
+def verify_blocks(module) -> None:
+    '''Check that the condition blocks of `module` are balanced, and that every else block
+    directly follows the condition block it matches, sharing its condition value.'''
+    # pylint: disable=import-outside-toplevel
+    from .expr.intrinsic import Intrinsic
+    from ..utils import unwrap_operand
+    body = module.body or []
+    depth = 0
+    for i, expr in enumerate(body):
+        if _is_intrinsic(expr, Intrinsic.PUSH_ELSE):
+            then = closed_block(body[:i])
+            if then is not expr.matching:
+                raise ValueError(f'{module.name}: else block not following its condition block')
+            if unwrap_operand(then.args[0]) is not unwrap_operand(expr.args[0]):
+                raise ValueError(f'{module.name}: else block with a different condition')
+        if _is_intrinsic(expr, Intrinsic.PUSH_CONDITION) or \
+                _is_intrinsic(expr, Intrinsic.PUSH_ELSE):
+            depth += 1
+        elif _is_intrinsic(expr, Intrinsic.POP_CONDITION):
+            depth -= 1
+            if depth < 0:
+                raise ValueError(f'{module.name}: POP_CONDITION without a condition block')
+    if depth:
+        raise ValueError(f'{module.name}: {depth} condition blocks not closed')
+
+
+def Cycle(cycle: typing.Union[int, range]):  # pylint: disable=invalid-name
     # pylint: disable=line-too-long
-    '''Frontend helper returning a Condition sugar that checks current_cycle equals the given cycle.'''
+    '''Frontend helper returning a Condition sugar that checks current_cycle equals the given cycle.
+
+    A `range` checks that current_cycle is one of its values instead, see `CycleRange`.'''
+    if isinstance(cycle, range):
+        return CycleRange(cycle.start, cycle.stop, cycle.step)
     assert isinstance(cycle, int)
     # pylint: disable=import-outside-toplevel
     from .expr.intrinsic import current_cycle
     from .dtype import UInt
     return Condition(current_cycle() == UInt(64)(cycle))
+
+
+def CycleRange(start: int, end: typing.Optional[int] = None, stride: int = 1):
+    # pylint: disable=invalid-name
+    '''Frontend helper returning a Condition sugar that holds in cycles start, start + stride, ...
+    up to, but excluding, `end`. Without `end`, the cycles are unbounded.'''
+    assert isinstance(start, int) and start >= 0, f'Invalid start cycle: {start}'
+    assert end is None or isinstance(end, int), f'Invalid end cycle: {end}'
+    assert isinstance(stride, int) and stride > 0, f'Invalid cycle stride: {stride}'
+    # pylint: disable=import-outside-toplevel
+    from .expr.intrinsic import current_cycle
+    from .dtype import Bits, UInt
+    cycle = current_cycle()
+    conds = []
+    if start > 0:
+        conds.append(cycle >= UInt(64)(start))
+    if end is not None:
+        conds.append(cycle < UInt(64)(end))
+    if stride > 1:
+        offset = cycle - UInt(64)(start) if start % stride else cycle
+        if stride & (stride - 1) == 0:
+            # A power of two only needs the low bits, instead of a divider
+            low = offset[0:stride.bit_length() - 2]
+            conds.append(low == low.dtype(0))
+        else:
+            conds.append(offset % UInt(64)(stride) == UInt(64)(0))
+    if not conds:
+        return Condition(Bits(1)(1))
+    cond = conds[0]
+    for i in conds[1:]:
+        cond = cond & i
+    return Condition(cond)
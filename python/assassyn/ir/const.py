@@ -1,5 +1,7 @@
 '''The AST node module for constant values.'''
 
+import typing
+
 from .value import Value
 from .dtype import Bits, DType
 from ..utils.enforce_type import enforce_type
@@ -33,25 +35,25 @@ class Const(Value):
         '''Dump the constant as an operand.'''
         return repr(self)
 
+    @property
+    def raw_bits(self) -> int:
+        '''The two's-complement bits of the value in its width, e.g. 0xFF for `Int(8)(-1)`.'''
+        return self.value & ((1 << self.dtype.bits) - 1)
+
     @enforce_type
     def __getitem__(self, x: slice) -> 'Const':
-        '''Override the value slicing operation.
-        
-        Note: Currently limited to 32 bits due to implementation constraints.
-        This is a known limitation documented in Phase 2.
-        '''
+        '''Override the value slicing operation, folded into a constant of any width.'''
         bits = x.stop - x.start + 1
-        assert 0 < bits <= 32, "TODO: Support more than 32 bits later"
+        assert bits > 0, f"Got an empty slice [{x.start}:{x.stop}]"
         assert self.dtype.bits >= bits, f"Got {self.dtype.bits} bits, but {bits} bits are needed"
-        dtype = Bits(bits)
-        return _const_impl(dtype, (self.value >> x.start) & ((1 << bits) - 1))
+        return imm((self.raw_bits >> x.start) & ((1 << bits) - 1), Bits(bits))
 
     def concat(self, other):
         '''Concatenate two values together.'''
 
         if isinstance(other, Const):
             shift = other.dtype.bits
-            return Bits(shift + self.dtype.bits)((self.value << shift) | other.value)
+            return imm((self.raw_bits << shift) | other.raw_bits, Bits(shift + self.dtype.bits))
 
         return super().concat(other)
 
@@ -82,3 +84,25 @@ def _const_impl(dtype, value: int):
         builder.const_cache[cache_key] = const
 
     return const
+
+
+@enforce_type
+def imm(value: typing.Union[int, bool], dtype: DType) -> Const:
+    '''Create an integer immediate of the given type. All integer constants go through here,
+    e.g. `UInt(8)(5)` is `imm(5, UInt(8))`. A bool is taken as 0 or 1.'''
+    value = int(value)
+    if not dtype.inrange(value):
+        raise ValueError(f"Immediate {value} does not fit in {dtype}")
+    return _const_impl(dtype, value)
+
+
+@enforce_type
+def bool_imm(value: bool) -> Const:
+    '''Create a `b1` immediate from a Python bool.'''
+    return imm(int(value), Bits(1))
+
+
+@enforce_type
+def zero(dtype: DType) -> Const:
+    '''Create the all-zero immediate of the given type.'''
+    return imm(0, dtype)
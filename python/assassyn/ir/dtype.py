@@ -108,8 +108,8 @@ class Int(DType):
 
     def __call__(self, value: int):
         #pylint: disable=import-outside-toplevel
-        from .const import _const_impl
-        return _const_impl(self, value)
+        from .const import imm
+        return imm(value, self)
 
     def inrange(self, value):
         left = -(1 << (self.bits - 1))
@@ -129,8 +129,8 @@ class UInt(DType):
 
     def __call__(self, value: int):
         #pylint: disable=import-outside-toplevel
-        from .const import _const_impl
-        return _const_impl(self, value)
+        from .const import imm
+        return imm(value, self)
 
     def inrange(self, value):
         return 0 <= value < (1 << self.bits)
@@ -144,6 +144,21 @@ class Float(DType):
     def __repr__(self):
         return 'f32'
 
+class Str(DType):
+    '''String data type, for the tables of names printed by `log`, e.g. a disassembler's.
+
+    Only arrays initialized with strings hold it, and only the simulator implements it, as a
+    Rust `String`; it has no bits in hardware.'''
+
+    def __init__(self):
+        super().__init__(0)
+
+    def __repr__(self):
+        return 'str'
+
+    def inrange(self, value):
+        return isinstance(value, str)
+
 class Bits(DType):
     '''Raw bits data type'''
 
@@ -155,8 +170,8 @@ class Bits(DType):
 
     def __call__(self, value: int):
         #pylint: disable=import-outside-toplevel
-        from .const import _const_impl
-        return _const_impl(self, value)
+        from .const import imm
+        return imm(value, self)
 
     def inrange(self, value):
         right = (1 << self.bits) - 1
@@ -263,6 +278,60 @@ class Record(DType):
         return res
 
 
+class Fixed(DType):
+    '''Fixed-point data type in Q-format: `int_bits` integer bits, including the sign bit
+    if signed, and `frac_bits` fractional bits.
+
+    The values are carried by `FixedValue` over an `Int` or `UInt` of `int_bits + frac_bits`
+    bits, so the backends only see integers.'''
+
+    int_bits: int  # Number of bits before the binary point
+    frac_bits: int  # Number of bits after the binary point
+    signed: bool  # Whether the underlying integer is signed
+
+    def __init__(self, int_bits: int, frac_bits: int, signed: bool = True):
+        assert isinstance(int_bits, int) and isinstance(frac_bits, int), \
+            'Expecting integers for the Q-format'
+        assert int_bits >= 0 and frac_bits >= 0 and int_bits + frac_bits > 0, \
+            f'Invalid Q-format: {int_bits}.{frac_bits}'
+        super().__init__(int_bits + frac_bits)
+        self.int_bits = int_bits
+        self.frac_bits = frac_bits
+        self.signed = signed
+
+    def __eq__(self, other):
+        return isinstance(other, Fixed) and self.int_bits == other.int_bits and \
+            self.frac_bits == other.frac_bits and self.signed == other.signed
+
+    def __hash__(self):
+        return hash((Fixed, self.int_bits, self.frac_bits, self.signed))
+
+    def __repr__(self):
+        prefix = 'q' if self.signed else 'uq'
+        return f'{prefix}{self.int_bits}.{self.frac_bits}'
+
+    def raw_type(self):
+        '''The integer type carrying the values of this type.'''
+        return Int(self.bits) if self.signed else UInt(self.bits)
+
+    def inrange(self, value):
+        return self.raw_type().inrange(round(value * (1 << self.frac_bits)))
+
+    def __call__(self, value):
+        '''Create a constant, rounding `value` to the nearest representable number.'''
+        raw = round(value * (1 << self.frac_bits))
+        assert self.raw_type().inrange(raw), f'{value} is out of the range of {self}'
+        return FixedValue(self, self.raw_type()(raw))
+
+    def view(self, value):
+        '''Interpret the bits of `value` as a value of this type.'''
+        assert value.dtype.bits == self.bits, \
+            f'Expecting {self.bits} bits for {self}, got {value.dtype}'
+        if value.dtype != self.raw_type():
+            value = value.bitcast(self.raw_type())
+        return FixedValue(self, value)
+
+
 def to_uint(value: int, bits=None):
     '''
     Convert an integer to an unsigned integer constant with minimized bits
@@ -361,3 +430,143 @@ class RecordValue:
     # object and the wrapped object.
     def __getattr__(self, name):
         return self._dtype.attributize(self._payload, name)
+
+
+class FixedValue:
+    '''The value class for the fixed-point type. Like `RecordValue`, it is a virtual node that
+    only wraps the integer payload, which is what goes into the IR.'''
+
+    _payload: Value  # The underlying integer value
+    _dtype: Fixed  # The Q-format of this value
+
+    def __init__(self, dtype: Fixed, payload: Value):
+        self._dtype = dtype
+        self._payload = payload
+
+    def value(self):
+        '''Return the integer payload, e.g. to write it to an array or a port.'''
+        return self._payload
+
+    def as_operand(self):
+        '''Return the payload as an operand'''
+        return self._payload.as_operand()
+
+    @property
+    def dtype(self):
+        '''Return the fixed-point type of this value.'''
+        return self._dtype
+
+    def _check_format(self, other):
+        assert isinstance(other, FixedValue), f'Expecting a fixed-point value, got {other}'
+        assert self._dtype == other.dtype, \
+            f'Operands of different formats: {self._dtype} and {other.dtype}'
+
+    def _check_signedness(self, other):
+        assert isinstance(other, FixedValue), f'Expecting a fixed-point value, got {other}'
+        assert self._dtype.signed == other.dtype.signed, \
+            f'Operands of different signedness: {self._dtype} and {other.dtype}'
+
+    def _align(self, other):
+        '''Both operands in their common format, with the wider integer and fractional parts.'''
+        self._check_signedness(other)
+        ty = Fixed(max(self._dtype.int_bits, other.dtype.int_bits),
+                   max(self._dtype.frac_bits, other.dtype.frac_bits), self._dtype.signed)
+        return ty, self.cast(ty), other.cast(ty)
+
+    def _extend(self, bits):
+        '''The payload sign- or zero-extended to `bits` bits, keeping its integer value.'''
+        if bits == self._dtype.bits:
+            return self._payload
+        ty = Int(bits) if self._dtype.signed else UInt(bits)
+        return self._payload.sext(ty) if self._dtype.signed else self._payload.zext(ty)
+
+    def cast(self, dtype: Fixed):
+        '''Convert to another format of the same signedness. Dropped fractional bits round
+        towards negative infinity, and dropped integer bits wrap around.'''
+        assert isinstance(dtype, Fixed), f'Expecting a fixed-point type, got {dtype}'
+        assert dtype.signed == self._dtype.signed, \
+            f'Cannot cast {self._dtype} to {dtype} of a different signedness'
+        if dtype == self._dtype:
+            return self
+        payload = self._payload
+        width = self._dtype.bits
+        # Move the binary point first, then resize the integer part
+        shift = dtype.frac_bits - self._dtype.frac_bits
+        if shift > 0:
+            payload = payload.concat(Bits(shift)(0))
+        elif shift < 0:
+            assert width + shift > 0, f'Casting {self._dtype} to {dtype} drops all the bits'
+            payload = payload[-shift:width - 1]
+        width += shift
+        if width > dtype.bits:
+            payload = payload[0:dtype.bits - 1]
+        elif width < dtype.bits:
+            payload = Fixed(width, 0, dtype.signed).view(payload)._extend(dtype.bits)
+        return dtype.view(payload)
+
+    def fadd(self, other):
+        '''Add two values of the same signedness. Both are cast to their common format first,
+        so the binary points are aligned, and the sum wraps around on overflow.'''
+        ty, lhs, rhs = self._align(other)
+        return FixedValue(ty, lhs.value() + rhs.value())
+
+    def fsub(self, other):
+        '''Subtract two values of the same signedness, aligned like `fadd`.'''
+        ty, lhs, rhs = self._align(other)
+        return FixedValue(ty, lhs.value() - rhs.value())
+
+    def fmul_full(self, other):
+        '''The exact product of two values of the same signedness, of any formats. Both the
+        integer and the fractional bits add up, e.g. Q4.4 times Q2.6 is Q6.10.'''
+        self._check_signedness(other)
+        ty = Fixed(self._dtype.int_bits + other.dtype.int_bits,
+                   self._dtype.frac_bits + other.dtype.frac_bits, self._dtype.signed)
+        full = self._extend(ty.bits) * other._extend(ty.bits)
+        return ty.view(full[0:ty.bits - 1])
+
+    def fmul(self, other):
+        '''Multiply two values of the same format. The full product has twice the fractional
+        bits, so it is shifted back by `frac_bits` and truncated to the format, rounding
+        towards negative infinity and wrapping around on overflow.'''
+        self._check_format(other)
+        full = self._payload * other.value()
+        frac = self._dtype.frac_bits
+        return self._dtype.view(full[frac:frac + self._dtype.bits - 1])
+
+    def __add__(self, other):
+        return self.fadd(other)
+
+    def __sub__(self, other):
+        return self.fsub(other)
+
+    def __mul__(self, other):
+        return self.fmul(other)
+
+    def __repr__(self):
+        return f'FixedValue({self._dtype}, {self._payload})'
+
+
+def to_fixed(value, dtype: Fixed) -> FixedValue:
+    '''Convert an integer value, or a fixed-point value of another format, to `dtype`.
+    An unsigned integer may become a signed fixed-point value, not the other way around.'''
+    if isinstance(value, FixedValue):
+        return value.cast(dtype)
+    assert isinstance(dtype, Fixed), f'Expecting a fixed-point type, got {dtype}'
+    assert value.dtype.is_int(), f'Expecting an integer value, got {value.dtype}'
+    bits = value.dtype.bits
+    if value.dtype.is_signed():
+        assert dtype.signed, f'Cannot convert the signed {value.dtype} to {dtype}'
+    elif dtype.signed:
+        # One more bit keeps the value non-negative
+        value = value.zext(UInt(bits + 1))
+        bits += 1
+    return Fixed(bits, 0, dtype.signed).view(value).cast(dtype)
+
+
+def from_fixed(value: FixedValue, dtype: DType) -> Value:
+    '''The integer part of a fixed-point value, rounded towards negative infinity, as an
+    integer of `dtype` with the signedness of the value.'''
+    assert isinstance(value, FixedValue), f'Expecting a fixed-point value, got {value}'
+    assert dtype.is_int() and dtype.is_signed() == value.dtype.signed, \
+        f'Cannot convert {value.dtype} to {dtype}'
+    return value.cast(Fixed(dtype.bits, 0, value.dtype.signed)).value()
@@ -3,11 +3,12 @@
 #pylint: disable=wildcard-import
 from .expr import *
 from .arith import *
-from .intrinsic import Intrinsic, PureIntrinsic, finish, wait_until, assume
+from .intrinsic import Intrinsic, PureIntrinsic, finish, wait_until, assume, watchdog
 from .intrinsic import push_condition, pop_condition, get_pred
 from .intrinsic import send_read_request, send_write_request
 from .intrinsic import has_mem_resp
 from .call import Bind, AsyncCall, FIFOPush
 from .comm import concat
+from .expr import one_hot_mux, binary_mux, binary_to_gray, gray_to_binary
 from .array import ArrayRead, ArrayWrite
 from . import comm
@@ -7,7 +7,8 @@ from __future__ import annotations
 import typing
 
 from ..value import Value
-from .expr import Expr
+from .expr import Expr, Cast
+from ...utils import unwrap_operand
 
 if typing.TYPE_CHECKING:
     from ..dtype import DType
@@ -36,6 +37,8 @@ class BinaryOp(Expr):
     SHL         = 214
     SHR         = 215
     NEQ         = 216
+    ADD_SAT     = 217
+    SUB_SAT     = 218
 
     OPERATORS = {
       ADD: '+',
@@ -57,6 +60,9 @@ class BinaryOp(Expr):
 
       SHL: '<<',
       SHR: '>>',
+
+      ADD_SAT: '+|',
+      SUB_SAT: '-|',
     }
 
     def __init__(self, opcode, lhs, rhs):
@@ -84,7 +90,8 @@ class BinaryOp(Expr):
             bits = max(self.lhs.dtype.bits, self.rhs.dtype.bits)
             tyclass = self.lhs.dtype.__class__
             return tyclass(bits)
-        if self.opcode in [BinaryOp.SUB, BinaryOp.DIV, BinaryOp.MOD]:
+        if self.opcode in [BinaryOp.SUB, BinaryOp.DIV, BinaryOp.MOD,
+                           BinaryOp.ADD_SAT, BinaryOp.SUB_SAT]:
             return type(self.lhs.dtype)(self.lhs.dtype.bits)
         if self.opcode in [BinaryOp.MUL]:
             bits = self.lhs.dtype.bits + self.rhs.dtype.bits
@@ -109,7 +116,33 @@ class BinaryOp(Expr):
     def is_computational(self):
         '''Check if this operation is computational'''
         return self.opcode in [BinaryOp.ADD, BinaryOp.SUB, BinaryOp.MUL, BinaryOp.DIV,
-                               BinaryOp.MOD]
+                               BinaryOp.MOD, BinaryOp.ADD_SAT, BinaryOp.SUB_SAT]
+
+    def is_add_carry(self):
+        '''Check if this is the addition of two zero-extended values, one bit wider than the
+        wider of them, which is how `Value.addc` is expanded'''
+        if self.opcode != BinaryOp.ADD:
+            return False
+        lhs, rhs = unwrap_operand(self.lhs), unwrap_operand(self.rhs)
+        if not all(isinstance(i, Cast) and i.opcode == Cast.ZEXT for i in (lhs, rhs)):
+            return False
+        bits = max(lhs.x.dtype.bits, rhs.x.dtype.bits) + 1
+        return lhs.dtype.bits == rhs.dtype.bits == self.dtype.bits == bits
+
+    def carry(self):
+        '''The frontend API to get the carry-out bit of an `addc` result'''
+        assert self.is_add_carry(), f'{self} is not created by addc'
+        bits = self.dtype.bits
+        return self[bits - 1:bits - 1]
+
+    def sum(self):
+        '''The frontend API to get the sum bits of an `addc` result, without the carry-out'''
+        assert self.is_add_carry(), f'{self} is not created by addc'
+        return self[0:self.dtype.bits - 2]
+
+    def is_saturating(self):
+        '''Check if this operation clamps to the range of its type instead of wrapping'''
+        return self.opcode in [BinaryOp.ADD_SAT, BinaryOp.SUB_SAT]
 
     def is_comparative(self):
         '''Check if this operation is comparative'''
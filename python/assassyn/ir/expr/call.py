@@ -4,7 +4,7 @@ from __future__ import annotations
 
 import typing
 
-from ...builder import ir_builder
+from ...builder import ir_builder, user_location
 from .expr import Expr
 
 if typing.TYPE_CHECKING:
@@ -61,6 +61,7 @@ class Bind(Expr):
     callee: Module  # Module being bound
     fifo_depths: dict  # Dictionary of FIFO depths
     pushes: list[FIFOPush] # List of FIFOPush operations
+    strict: bool  # Whether every port must be bound before the async call
 
     BIND = 501
 
@@ -81,8 +82,8 @@ class Bind(Expr):
             # Type check using the extracted dtype
             if not port.dtype.type_eq(value_dtype):
                 raise ValueError(
-                    f"Type mismatch in Bind: port '{k}' expects type {port.dtype}, "
-                    f"but got value of type {value_dtype}"
+                    f"Type mismatch in Bind: port '{k}' of {self.callee.name} expects type "
+                    f"{port.dtype}, but got value of type {value_dtype} at {user_location()}"
                 )
 
             # v is already unwrapped if it was RecordValue
@@ -97,10 +98,12 @@ class Bind(Expr):
 
     def is_fully_bound(self):
         '''The helper function to check if all the ports are bound.'''
+        return not self.unbound_ports()
+
+    def unbound_ports(self):
+        '''The names of the callee ports not bound yet, in port order.'''
         fifo_names = set(push.fifo.name for push in self.pushes)
-        ports = self.callee.ports
-        cnt = sum(i.name in fifo_names for i in ports)
-        return cnt == len(ports)
+        return [i.name for i in self.callee.ports if i.name not in fifo_names]
 
     @property
     def pushes(self):
@@ -110,12 +113,34 @@ class Bind(Expr):
     @ir_builder
     def async_called(self, **kwargs):
         '''The exposed frontend function to instantiate an async call operation'''
+        return self._async_call(1, kwargs)
+
+    @ir_builder
+    def async_called_n(self, n, **kwargs):
+        '''Instantiate an async call activating the callee `n` times, one per cycle, as `n`
+        calls would. The ports are still pushed once.'''
+        return self._async_call(n, kwargs)
+
+    def _async_call(self, n, kwargs):
         self._push(**kwargs)
-        return AsyncCall(self)
+        if self.strict and not self.is_fully_bound():
+            raise ValueError(
+                f"Cannot async call {self.callee.name}: ports {self.unbound_ports()} are not bound"
+            )
+        return AsyncCall(self, replication=n)
+
+    def async_called_if(self, cond, **kwargs):
+        '''Instantiate an async call guarded by `cond`, as if it were called in a
+        `Condition(cond)` block. The pushes of the ports bound earlier keep their predicates.'''
+        # pylint: disable=import-outside-toplevel
+        from ..block import Condition
+        with Condition(cond):
+            return self.async_called(**kwargs)
 
     def __init__(self, callee, **kwargs):
         super().__init__(Bind.BIND, [])
         self.callee = callee
+        self.strict = False
         self._push(**kwargs)
         self.fifo_depths = {}
 
@@ -168,8 +193,13 @@ class AsyncCall(Expr):
     # Call operations
     ASYNC_CALL = 500
 
-    def __init__(self, bind: Bind, meta_cond=None):
+    replication: int  # The number of activations of the callee per call
+
+    def __init__(self, bind: Bind, meta_cond=None, replication: int = 1):
+        assert isinstance(replication, int) and replication > 0, \
+            f'Invalid replication factor: {replication}'
         super().__init__(AsyncCall.ASYNC_CALL, [bind], meta_cond=meta_cond)
+        self.replication = replication
         bind.callee.users.append(self)
 
     @property
@@ -186,6 +216,8 @@ class AsyncCall(Expr):
 
     def __repr__(self):
         bind = self.bind.as_operand()
+        if self.replication > 1:
+            bind = f'{bind} x{self.replication}'
         meta = self.meta_cond
         if meta is None:
             return f'async_call {bind}'
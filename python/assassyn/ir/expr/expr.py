@@ -46,6 +46,7 @@ class Expr(Value):
     loc: str  # Source location information
     parent: typing.Optional[ModuleBase]  # Parent module of this expression
     users: typing.List[Operand]  # List of users of this expression
+    registered: bool  # Whether the other modules see it a cycle late, see register_value
     _operands: typing.List[
         typing.Union[Operand, Port, Array, int]
     ] # List of operands of this expression
@@ -56,6 +57,7 @@ class Expr(Value):
         self.opcode = opcode
         self.loc = self.parent = None
         self.name = None  # Initialize name attribute
+        self.registered = False
         # NOTE: We only wrap values in Operand, not Ports or Arrays
         self._operands = []
         for operand in operands:
@@ -107,6 +109,7 @@ class Expr(Value):
         '''Wrap an expression operand and enforce module ownership rules'''
         #pylint: disable=import-outside-toplevel
         from ..module.downstream import Downstream
+        from ...builder import Singleton
         from .call import Bind
 
         if isinstance(expr_operand, Bind):
@@ -114,6 +117,10 @@ class Expr(Value):
             expr_operand.users.append(wrapped)
             return wrapped
 
+        pipeline = getattr(Singleton.peek_builder().current_context, 'pipeline', None)
+        if pipeline is not None:
+            expr_operand = pipeline.forward(expr_operand, current_module)
+
         if not isinstance(current_module, Downstream):
             expr_module = expr_operand.parent if expr_operand.parent else None
             if not self._is_cross_module_allowed(expr_operand):
@@ -241,6 +248,7 @@ class Log(Expr):
     non-synthesizable. It is used for debugging purpose only.'''
 
     args: tuple  # Arguments to the log operation
+    fixed: dict  # The fixed-point format of the values printed as decimals, by their index
 
     LOG = 600
 
@@ -252,6 +260,7 @@ class Log(Expr):
             meta_cond = get_pred()
         super().__init__(Log.LOG, args, meta_cond=meta_cond)
         self.args = args
+        self.fixed = {}
 
     @property
     def fmt(self):
@@ -261,7 +270,10 @@ class Log(Expr):
     @property
     def values(self):
         '''Return the payload values excluding the format string.'''
-        return self.args[1:]
+        # Read back from the operands, which may have been forwarded across stages.
+        return tuple(
+            i.value if isinstance(i, Operand) else i for i in self._operands[1:]
+        )
 
     @property
     def dtype(self):
@@ -349,9 +361,15 @@ class Cast(Expr):
 
 @ir_builder
 def log(*args):
-    '''The exposed frontend function to instantiate a log operation'''
+    '''The exposed frontend function to instantiate a log operation. A fixed-point value is
+    printed as a decimal with as many fractional digits as its fractional bits.'''
+    # pylint: disable=import-outside-toplevel
+    from ..dtype import FixedValue
     assert isinstance(args[0], str)
-    return Log(*args)
+    fixed = {i: v.dtype for i, v in enumerate(args[1:]) if isinstance(v, FixedValue)}
+    res = Log(*(v.value() if isinstance(v, FixedValue) else v for v in args))
+    res.fixed = fixed
+    return res
 
 
 class Select(Expr):
@@ -426,3 +444,65 @@ class Select1Hot(Expr):
         cond = self.cond.as_operand()
         values = ', '.join(i.as_operand() for i in self.values)
         return f'{lval} = select_1hot {cond} ({values})'
+
+
+def _check_same_type(values):
+    for i in values:
+        if not isinstance(i, Value):
+            raise TypeError(f'{type(i)} is not a Value!')
+    reference = values[0].dtype
+    for i in values:
+        if i.dtype != reference:
+            raise ValueError(f'mux inputs must share one type, but got {reference} and {i.dtype}')
+
+
+def one_hot_mux(selector: Value, inputs) -> Value:
+    '''Select the input whose bit of the one-hot `selector` is set, bit 0 selecting `inputs[0]`.
+    `selector` must have one bit per input, and the inputs one type.'''
+    inputs = list(inputs)
+    if not inputs:
+        raise ValueError('one_hot_mux needs at least one input')
+    _check_same_type(inputs)
+    if selector.dtype.bits != len(inputs):
+        raise ValueError(f'one_hot_mux selector {selector.dtype} does not have one bit for each '
+                         f'of the {len(inputs)} inputs')
+    return selector.select1hot(*inputs)
+
+
+def binary_mux(cond: Value, true_value: Value, false_value: Value) -> Value:
+    '''Select `true_value` when the 1-bit `cond` is set, `false_value` otherwise.'''
+    if cond.dtype.bits != 1:
+        raise ValueError(f'binary_mux condition must be 1 bit, but got {cond.dtype}')
+    _check_same_type([true_value, false_value])
+    return cond.select(true_value, false_value)
+
+
+def _check_unsigned(value: Value, func: str):
+    if not isinstance(value, Value):
+        raise TypeError(f'{type(value)} is not a Value!')
+    if not (value.dtype.is_raw() or value.dtype.is_int()) or value.dtype.is_signed():
+        raise ValueError(f'{func} takes a UInt or Bits value, but got {value.dtype}')
+
+
+def binary_to_gray(value: Value) -> Value:
+    '''The Gray code of the UInt or Bits `value`, `(value >> 1) ^ value`, whose consecutive
+    values differ in a single bit.'''
+    _check_unsigned(value, 'binary_to_gray')
+    bits = value.dtype.bits
+    if bits == 1:
+        return value
+    # The XOR gives Bits, cast back to the type of `value`
+    return ((value >> type(value.dtype)(bits)(1)) ^ value).bitcast(value.dtype)
+
+
+def gray_to_binary(value: Value) -> Value:
+    '''The binary value of the Gray code `value`, bit `i` being the XOR of its bits `i` and
+    up. The XOR is folded by doubling shifts, so it takes log2 of the bits XORs.'''
+    _check_unsigned(value, 'gray_to_binary')
+    bits = value.dtype.bits
+    res = value
+    shift = 1
+    while shift < bits:
+        res = (res ^ (res >> type(value.dtype)(bits)(shift))).bitcast(value.dtype)
+        shift *= 2
+    return res
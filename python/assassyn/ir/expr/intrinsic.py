@@ -1,8 +1,9 @@
 '''The module for intrinsic expressions'''
 #pylint: disable=cyclic-import
 
-from ...builder import ir_builder
+from ...builder import ir_builder, Singleton
 from .expr import Expr
+from ...utils import namify, unwrap_operand
 
 INTRIN_INFO = {
     # Intrinsic operations opcode: (mnemonic, num of args, valued, side effect)
@@ -11,6 +12,8 @@ INTRIN_INFO = {
     902: ('assert', 1, False, True),
     914: ('PUSH_CONDITION', 1, False, True),
     915: ('POP_CONDITION', 0, False, True),
+    916: ('PUSH_ELSE', 1, True, True),
+    917: ('watchdog', 2, False, True),
     906: ('send_read_request', 3, True, True),
     908: ('send_write_request', 4, True, True),
     913: ('external_instantiate', None, True, True),  # None = variable args
@@ -35,8 +38,11 @@ class Intrinsic(Expr):
     EXTERNAL_INSTANTIATE = 913
     PUSH_CONDITION = 914
     POP_CONDITION = 915
+    PUSH_ELSE = 916
+    WATCHDOG = 917
 
     opcode: int  # Operation code for this intrinsic
+    matching: 'Intrinsic'  # The PUSH_CONDITION a PUSH_ELSE is the else block of
 
     def __init__(self, opcode, *args, meta_cond=None):
         payload = list(args)
@@ -77,10 +83,20 @@ class Intrinsic(Expr):
 
 @ir_builder
 def wait_until(cond):
-    '''Frontend API for creating a wait-until block.'''
+    '''Frontend API for creating a wait-until block.
+
+    Everything after it in the module body runs only once `cond` holds. Inside a condition
+    block, the module only waits when the block is taken. Multiple `wait_until`s accumulate:
+    the rest of the body waits for all of the conditions seen so far.'''
     #pylint: disable=import-outside-toplevel
     from ..value import Value
+    from ..module.downstream import Downstream
     assert isinstance(cond, Value)
+    module = Singleton.peek_builder().current_module
+    if isinstance(module, Downstream):
+        raise ValueError(
+            f'wait_until in downstream module {module.name} is not supported: '
+            'a downstream module has no event to hold until the condition holds')
     return Intrinsic(Intrinsic.WAIT_UNTIL, cond)
 
 @ir_builder
@@ -92,6 +108,23 @@ def assume(cond):
     assert isinstance(cond, Value)
     return Intrinsic(Intrinsic.ASSERT, cond)
 
+@ir_builder
+def watchdog(signal, cycles: int):
+    '''Frontend API for creating a watchdog.
+
+    The simulation fails once `signal` has not held for more than `cycles` cycles in a row.
+    `signal` is sampled when this intrinsic is executed, so the cycles in which the module
+    does not run, or the enclosing block is not taken, count as cycles without it.'''
+    #pylint: disable=import-outside-toplevel
+    from ..value import Value
+    from ..dtype import to_uint
+    assert isinstance(signal, Value)
+    if signal.dtype.bits != 1:
+        raise ValueError(f'watchdog signal must be 1 bit, but got {signal.dtype}')
+    if not isinstance(cycles, int) or isinstance(cycles, bool) or cycles <= 0:
+        raise ValueError(f'watchdog cycles must be a positive int, but got {cycles!r}')
+    return Intrinsic(Intrinsic.WATCHDOG, signal, to_uint(cycles))
+
 
 def is_wait_until(expr):
     '''Check if the expression is a wait-until intrinsic.'''
@@ -131,6 +164,27 @@ def push_condition(cond):
     return Intrinsic(Intrinsic.PUSH_CONDITION, cond)
 
 
+@ir_builder
+def _else_intrinsic(then):
+    res = Intrinsic(Intrinsic.PUSH_ELSE, unwrap_operand(then.args[0]))
+    res.matching = then
+    return res
+
+
+def push_else():
+    '''Open the else block of the condition block closed right before.
+
+    The PUSH_ELSE intrinsic shares the condition value of the matching PUSH_CONDITION, and is
+    itself the predicate of the else block, i.e. the negation of the condition, so that no
+    flip expression is materialized.'''
+    #pylint: disable=import-outside-toplevel
+    from ..block import closed_block
+    builder = Singleton.peek_builder()
+    res = _else_intrinsic(closed_block(builder.current_body))
+    builder.push_predicate(res)
+    return res
+
+
 @ir_builder
 def pop_condition():
     '''Pop a predicate condition from the builder condition stack and IR.'''
@@ -156,6 +210,8 @@ class PureIntrinsic(Expr):
     MODULE_TRIGGERED = 304
     VALUE_VALID = 305
     CURRENT_CYCLE = 307
+    FIFO_PEEK_N = 308
+    FIFO_VALID_N = 309
 
     # External module operations
     EXTERNAL_OUTPUT_READ = 306  # Unified opcode for both wire and reg outputs
@@ -172,6 +228,8 @@ class PureIntrinsic(Expr):
         FIFO_PEEK: 'peek',
         MODULE_TRIGGERED: 'triggered',
         VALUE_VALID: 'valid',
+        FIFO_PEEK_N: 'peek_n',
+        FIFO_VALID_N: 'valid_n',
     }
 
     def __init__(self, opcode, *args, meta_cond=None):
@@ -196,7 +254,7 @@ class PureIntrinsic(Expr):
         # pylint: disable=import-outside-toplevel
         from ..dtype import Bits, UInt
 
-        if self.opcode == PureIntrinsic.FIFO_PEEK:
+        if self.opcode in [PureIntrinsic.FIFO_PEEK, PureIntrinsic.FIFO_PEEK_N]:
             # pylint: disable=import-outside-toplevel
             from ..module import Port
             fifo = self.args[0]
@@ -204,7 +262,8 @@ class PureIntrinsic(Expr):
             return fifo.dtype
 
         if self.opcode in [PureIntrinsic.FIFO_VALID, PureIntrinsic.MODULE_TRIGGERED,
-                           PureIntrinsic.VALUE_VALID, PureIntrinsic.HAS_MEM_RESP]:
+                           PureIntrinsic.VALUE_VALID, PureIntrinsic.HAS_MEM_RESP,
+                           PureIntrinsic.FIFO_VALID_N]:
             return Bits(1)
 
         if self.opcode == PureIntrinsic.GET_MEM_RESP:
@@ -227,6 +286,9 @@ class PureIntrinsic(Expr):
                            PureIntrinsic.MODULE_TRIGGERED, PureIntrinsic.VALUE_VALID]:
             fifo = self.args[0].as_operand()
             return f'{self.as_operand()} = {fifo}.{self.OPERATORS[self.opcode]}()'
+        if self.opcode in [PureIntrinsic.FIFO_PEEK_N, PureIntrinsic.FIFO_VALID_N]:
+            fifo = self.args[0].as_operand()
+            return f'{self.as_operand()} = {fifo}.{self.OPERATORS[self.opcode]}({self.lookahead})'
         if self.opcode in [PureIntrinsic.HAS_MEM_RESP, PureIntrinsic.GET_MEM_RESP,
                            PureIntrinsic.CURRENT_CYCLE]:
             mn, _ = PURE_INTRIN_INFO[self.opcode]
@@ -241,8 +303,14 @@ class PureIntrinsic(Expr):
             return f'{self.as_operand()} = {inst}.{port}'
         raise NotImplementedError
 
+    @property
+    def lookahead(self) -> int:
+        '''The index of the entry looked at by FIFO_PEEK_N and FIFO_VALID_N.'''
+        assert self.opcode in [PureIntrinsic.FIFO_PEEK_N, PureIntrinsic.FIFO_VALID_N]
+        return self.args[1].value.value
+
     def __getattr__(self, name):
-        if self.opcode == PureIntrinsic.FIFO_PEEK:
+        if self.opcode in [PureIntrinsic.FIFO_PEEK, PureIntrinsic.FIFO_PEEK_N]:
             port = self.args[0]
             # pylint: disable=import-outside-toplevel
             from ..module import Port
@@ -322,8 +390,10 @@ class ExternalIntrinsic(Intrinsic):
 
     @property
     def uid(self):
-        """Get unique identifier for this intrinsic instance."""
-        return id(self)
+        """Get unique identifier for this intrinsic instance, derived from its name.
+
+        Unlike the object id, the name is the same every time the system is built."""
+        return namify(self.as_operand())
 
     def get_output_dtype(self, port_name):
         """Get the dtype of an output port.
@@ -0,0 +1,115 @@
+"""Multi-ported SRAM built from single-port SRAM banks."""
+
+from __future__ import annotations
+
+from ...builder import Singleton
+from ..array import RegArray
+from ..dtype import UInt
+from .sram import SRAM
+
+
+def _or_all(values):
+    res = values[0]
+    for value in values[1:]:
+        res = res | value
+    return res
+
+
+class BankedSRAM:  # pylint: disable=too-many-instance-attributes
+    '''An SRAM with several ports, built from `SRAM` banks interleaved by address.
+
+    Word `i` lives in bank `i % banks`, at address `i // banks` of the bank, so that ports
+    accessing consecutive words hit different banks. Each bank serves one port per cycle:
+    when several ports hit the same bank, the lowest-numbered one wins. The banks are plain
+    `SRAM`s, so each of them is an independent BRAM in Verilog and an array in simulation.'''
+
+    width: int  # Width of a word in bits
+    depth: int  # Depth of the memory in words
+    read_ports: int  # The number of ports able to read
+    write_ports: int  # The number of ports able to write
+    banks: list  # The SRAM banks, `max(read_ports, write_ports)` of them
+    grants: list  # Whether the access of each port is served in this cycle
+    _rsel: list  # The bank read by each read port in the last cycle
+
+    def __init__(self, width: int, depth: int, read_ports: int = 1, write_ports: int = 1):
+        ports = max(read_ports, write_ports)
+        assert read_ports > 0 and write_ports > 0, \
+            f'Invalid port count: {read_ports} read, {write_ports} write'
+        assert ports & (ports - 1) == 0, f'The number of banks must be a power of 2, got {ports}'
+        assert depth % ports == 0 and depth > ports, \
+            f'Depth {depth} cannot be split into {ports} banks'
+        self.width = width
+        self.depth = depth
+        self.read_ports = read_ports
+        self.write_ports = write_ports
+        self.banks = [SRAM(width, depth // ports, None) for _ in range(ports)]
+        self.grants = []
+        self._rsel = []
+
+    @property
+    def ports(self) -> int:
+        '''The number of ports, which is also the number of banks.'''
+        return len(self.banks)
+
+    def _split(self, addr):
+        '''Split a word address into the bank id and the address inside the bank.'''
+        log2 = self.ports.bit_length() - 1
+        bits = self.banks[0].addr_width
+        if log2 == 0:
+            return None, addr[0:bits - 1]
+        return addr[0:log2 - 1], addr[log2:log2 + bits - 1]
+
+    def build(self, we: list, re: list, addr: list, wdata: list):
+        '''Connect the ports to the banks. It is called in the module driving the ports.
+
+        Args:
+            we: The write enable of each write port.
+            re: The read enable of each read port.
+            addr: The word address of each port.
+            wdata: The write data of each write port.
+
+        Returns the grant of each port, i.e. whether its access is served in this cycle.'''
+        assert len(we) == len(wdata) == self.write_ports, 'One we and wdata per write port'
+        assert len(re) == self.read_ports, 'One re per read port'
+        assert len(addr) == self.ports, 'One addr per port'
+        split = [self._split(a) for a in addr]
+        owner = Singleton.peek_builder().current_module
+        granted = []  # granted[b][p]: port `p` is served by bank `b`
+        for b, bank in enumerate(self.banks):
+            hits = []
+            for p, (sel, _) in enumerate(split):
+                hit = _or_all(([we[p]] if p < self.write_ports else [])
+                              + ([re[p]] if p < self.read_ports else []))
+                if sel is not None:
+                    hit = hit & (sel == sel.dtype(b))
+                hits.append(hit)
+            # The lowest-numbered port hitting this bank wins
+            granted.append([hits[0]] + [hits[p] & ~_or_all(hits[:p])
+                                        for p in range(1, self.ports)])
+            bank_addr = split[-1][1]
+            bank_wdata = wdata[-1]
+            for p in reversed(range(self.ports - 1)):
+                bank_addr = granted[b][p].select(split[p][1], bank_addr)
+            for p in reversed(range(self.write_ports - 1)):
+                bank_wdata = granted[b][p].select(wdata[p], bank_wdata)
+            bank.build(_or_all([granted[b][p] & we[p] for p in range(self.write_ports)]),
+                       _or_all([granted[b][p] & re[p] for p in range(self.read_ports)]),
+                       bank_addr, bank_wdata)
+        self.grants = [_or_all([row[p] for row in granted]) for p in range(self.ports)]
+        for p in range(self.read_ports if self.ports > 1 else 0):
+            log2 = self.ports.bit_length() - 1
+            rsel = RegArray(UInt(log2), 1, name=f'{self.banks[0].name}_rsel{p}')
+            served = self.grants[p] & re[p]
+            (rsel & owner)[0] <= served.select(split[p][0].bitcast(UInt(log2)), rsel[0])
+            self._rsel.append(rsel)
+        return self.grants
+
+    def rdata(self, port: int):
+        '''The word read by `port`, valid in the cycle after its read is granted.'''
+        res = self.banks[0].dout[0]
+        if self.ports == 1:
+            return res
+        sel = self._rsel[port][0]
+        for b in range(1, self.ports):
+            res = (sel == sel.dtype(b)).select(self.banks[b].dout[0], res)
+        return res
@@ -2,12 +2,13 @@
 
 from __future__ import annotations
 
-import math
 from ..module.downstream import Downstream
-from ..array import RegArray, Array
+from ..array import RegArray, Array, index_width
 from ..dtype import Bits
 from ..value import Value
 
+# The formats of init files: the radix of `$readmemh` and `$readmemb`
+MEMORY_FORMATS = ('hex', 'bin')
 
 class MemoryBase(Downstream):
     '''Base class for memory modules.'''
@@ -18,6 +19,7 @@ class MemoryBase(Downstream):
 
     # For simulation purpose only
     init_file: str | None  # Path to initialization file
+    init_format: str  # 'hex' for a `$readmemh` init file, 'bin' for a `$readmemb` one
     
     # All the combinational pins into this downstream module.
     we: Value       # Write enable signal
@@ -31,13 +33,14 @@ class MemoryBase(Downstream):
     # The array payload as per the depth and width
     _payload: Array  # Array holding the memory contents
     
-    def __init__(self, width: int, depth: int, init_file: str | None):
+    def __init__(self, width: int, depth: int, init_file: str | None, init_format: str = 'hex'):
         """Initialize memory base class.
         
         Args:
             width: Width of memory in bits
             depth: Depth of memory in words (must be power of 2)
             init_file: Path to initialization file (can be None)
+            init_format: 'hex' or 'bin', the radix of the values in init_file
         """
         super().__init__()
         
@@ -45,6 +48,8 @@ class MemoryBase(Downstream):
         assert isinstance(width, int) and width > 0, f"Width must be positive integer, got {width}"
         assert isinstance(depth, int) and depth > 0, f"Depth must be positive integer, got {depth}"
         assert init_file is None or isinstance(init_file, str), f"Init file must be string or None, got {type(init_file)}"
+        assert init_format in MEMORY_FORMATS, \
+            f"Init format must be one of {MEMORY_FORMATS}, got {init_format!r}"
         
         # Depth is required to be a power of 2
         assert (depth & (depth - 1)) == 0, f"Depth must be a power of 2, got {depth}"
@@ -52,9 +57,10 @@ class MemoryBase(Downstream):
         self.width = width
         self.depth = depth
         self.init_file = init_file
+        self.init_format = init_format
         
-        # Derive addr_width as log2 of depth
-        self.addr_width = int(math.log2(depth))
+        # Derive addr_width as log2 of depth, in integers so that no depth rounds it off
+        self.addr_width = index_width(depth)
         
         # Create the payload array with instance-prefixed name
         self._payload = RegArray(
@@ -18,15 +18,16 @@ class DRAM(MemoryBase):
     soon as response, using several intrinsics to achieve this.
     '''
 
-    def __init__(self, width: int, depth: int, init_file: str | None):
+    def __init__(self, width: int, depth: int, init_file: str | None, init_format: str = 'hex'):
         """Initialize DRAM module.
         
         Args:
             width: Width of memory in bits
             depth: Depth of memory in words (must be power of 2)
             init_file: Path to initialization file (can be None)
+            init_format: 'hex' or 'bin', the radix of the values in init_file
         """
-        super().__init__(width, depth, init_file)
+        super().__init__(width, depth, init_file, init_format)
 
     @combinational
     def build(self, we, re, addr, wdata):  # pylint: disable=too-many-arguments
@@ -16,15 +16,16 @@ class SRAM(MemoryBase):  # pylint: disable=too-many-instance-attributes
     # Additional attributes specific to SRAM
     dout: RegArray  # Register buffer that holds the result of read
 
-    def __init__(self, width: int, depth: int, init_file: str | None):
+    def __init__(self, width: int, depth: int, init_file: str | None, init_format: str = 'hex'):
         """Initialize SRAM module.
         
         Args:
             width: Width of memory in bits
             depth: Depth of memory in words (must be power of 2)
             init_file: Path to initialization file (can be None)
+            init_format: 'hex' or 'bin', the radix of the values in init_file
         """
-        super().__init__(width, depth, init_file)
+        super().__init__(width, depth, init_file, init_format)
         # Create dout register buffer with instance-prefixed name
         self.dout = RegArray(
             Bits(width),
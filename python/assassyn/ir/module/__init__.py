@@ -1,7 +1,9 @@
 '''The module for defining the AST nodes for the module and ports.'''
 
 from .module import Module, Port, combinational
-from .downstream import Downstream
+from .downstream import (Downstream, DownstreamContext, create_downstream,
+                         create_downstream_with_condition)
+from .stage import stage
 from ..memory.dram import DRAM
 
 # For backward compatibility, downstream_combinational is the same as combinational
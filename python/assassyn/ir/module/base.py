@@ -13,6 +13,17 @@ from ..expr import Operand, Expr
 from ..expr.intrinsic import PureIntrinsic
 
 
+def render_expr(expr) -> str:
+    '''Pretty-print an expression, annotating the value it defines with its data type.'''
+    text = repr(expr)
+    if isinstance(expr, Expr) and expr.is_valued() and '\n' not in text:
+        # Joined to the comment the expression already has, if any
+        if ' // ' in text:
+            return text.replace(' // ', f' // {expr.dtype}, ', 1)
+        text = f'{text} // {expr.dtype}'
+    return text
+
+
 def render_module_body(body: list[Expr] | None) -> str:
     '''Pretty-print a flat module body while honouring predicate intrinsics.'''
     # pylint: disable=import-outside-toplevel
@@ -31,11 +42,18 @@ def render_module_body(body: list[Expr] | None) -> str:
                     lines.append((' ' * Singleton.repr_ident) + f'if {cond} {{ // PUSH_CONDITION')
                     Singleton.repr_ident += 2
                     continue
+                if elem.opcode == Intrinsic.PUSH_ELSE:
+                    cond = elem.args[0].as_operand()
+                    name = elem.as_operand()
+                    lines.append((' ' * Singleton.repr_ident) +
+                                 f'else {{ // PUSH_ELSE {name} = !{cond}')
+                    Singleton.repr_ident += 2
+                    continue
                 if elem.opcode == Intrinsic.POP_CONDITION:
                     Singleton.repr_ident -= 2
                     lines.append((' ' * Singleton.repr_ident) + '} // POP_CONDITION')
                     continue
-            lines.append((' ' * Singleton.repr_ident) + repr(elem))
+            lines.append((' ' * Singleton.repr_ident) + render_expr(elem))
         return '\n'.join(lines)
     finally:
         Singleton.repr_ident -= 2
@@ -141,10 +159,13 @@ def combinational_for(module_type):  # pylint: disable=too-many-statements
                             if not has_hierarchical_name:
                                 argument.name = param_name
 
-                return new_func(*args, **kwargs)
+                res = new_func(*args, **kwargs)
             finally:
-                builder = Singleton.peek_builder()
-                builder.exit_context_of()
+                # `stage()` may have swapped the context to the last stage of a pipeline.
+                ctx = Singleton.peek_builder().exit_context_of()
+            if ctx.pipeline is not None:
+                ctx.pipeline.finalize()
+            return res
 
         wrapper._is_combinational = True  # pylint: disable=protected-access
         wrapper._module_class = module_type  # pylint: disable=protected-access
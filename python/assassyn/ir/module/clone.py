@@ -0,0 +1,106 @@
+'''Copies of a built module, with some of the nodes it refers to replaced.'''
+
+from __future__ import annotations
+
+import copy
+import typing
+
+from ..array import Array
+from ..expr import ArrayWrite, AsyncCall, Expr, Operand
+from .module import Module, Port
+
+# The attributes of a module set up by `Module.__init__` for the clone itself
+_OWN = ('name', 'body', '_ports', '_users', '_externals', '_attrs')
+
+
+class _Remap:  # pylint: disable=too-few-public-methods
+    '''The node of the clone standing for a node of the source module.'''
+
+    def __init__(self, source: Module, clone: Module, substitutions: dict):
+        self.nodes = {id(k): v for k, v in substitutions.items()}
+        self.nodes[id(source)] = clone
+        for port in source.ports:
+            self.nodes[id(port)] = getattr(clone, port.name)
+
+    def __call__(self, value):
+        if isinstance(value, (list, tuple)):
+            return type(value)(self(i) for i in value)
+        if isinstance(value, dict):
+            return {k: self(v) for k, v in value.items()}
+        return self.nodes.get(id(value), value)
+
+
+def clone_module(sys, module: Module, new_name: str,
+                 substitutions: typing.Optional[dict] = None) -> Module:
+    '''A copy of `module` named `new_name`, with ports of the same names and types, and a copy of
+    its body where the nodes keyed in `substitutions` are replaced by their values.
+
+    The copied expressions refer to the ports and the expressions of the clone instead of those
+    of `module`. The other nodes they refer to, e.g. the arrays and the modules they call, are
+    shared with `module` unless substituted. The clone writes the arrays on write ports of its
+    own, and the values of `module` that are registered or exposed are so in the clone too.'''
+    # pylint: disable=protected-access
+    if not isinstance(module, Module) or not isinstance(module.body, list):
+        raise ValueError(f'Only a built Module can be cloned, not {module!r}')
+    if not isinstance(new_name, str) or not new_name.isidentifier():
+        raise ValueError(f'A module is named by an identifier, got {new_name!r}')
+    if any(m.name == new_name for m in sys.modules + sys.downstreams):
+        raise ValueError(f'The system already has a module named {new_name}')
+
+    clone = type(module).__new__(type(module))
+    Module.__init__(clone, ports={i.name: Port(i.dtype) for i in module.ports})
+    clone.name = new_name
+    clone._attrs = copy.copy(module._attrs)
+    clone.body = []
+    remap = _Remap(module, clone, substitutions or {})
+
+    # All the copies first, so that an attribute may refer to a later expression, like the bind
+    # of a push. They are bare, as `copy.copy` would trip on the `__getattr__` of some of them.
+    copies = []
+    for expr in module.body:
+        new = object.__new__(type(expr))
+        remap.nodes[id(expr)] = new
+        copies.append((expr, new))
+
+    for expr, new in copies:
+        new.parent = clone
+        new.users = []
+        new.registered = False
+        for key, value in vars(expr).items():
+            if key not in ('parent', 'users', 'registered', '_operands'):
+                setattr(new, key, remap(value))
+        new._operands = [_copy_operand(i, new, remap) for i in expr._operands]
+        if isinstance(new, ArrayWrite):
+            _ = new.array & clone
+        elif isinstance(new, AsyncCall):
+            new.bind.callee.users.append(new)
+        clone.body.append(new)
+        if expr.registered:
+            sys.register_value(new)
+        if expr in sys.exposed_nodes:
+            sys.expose_on_top(new, sys.exposed_nodes[expr])
+
+    # The nodes the subclass of the module keeps, e.g. its parameters
+    ports = {i.name for i in module.ports}
+    for key, value in vars(module).items():
+        if key not in _OWN and key not in ports:
+            setattr(clone, key, remap(value))
+    return clone
+
+
+def _copy_operand(operand, user: Expr, remap: _Remap):
+    '''The operand of `user`, the copy of an expression, standing for `operand` of the original
+    one, registered in the users of its value and the externals of the clone.'''
+    if isinstance(operand, (Array, Port)):
+        value = remap(operand)
+        value.users.append(user)
+        return value
+    if not isinstance(operand, Operand):
+        return operand
+    value = remap(operand.value)
+    wrapped = Operand(value, user)
+    if isinstance(value, Expr):
+        value.users.append(wrapped)
+    user.parent.add_external(wrapped)
+    return wrapped
+
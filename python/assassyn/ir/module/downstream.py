@@ -3,20 +3,17 @@ chronological modules.'''
 
 from __future__ import annotations
 
-import typing
-
 from ...builder import Singleton
+from ..expr import Expr
 from .base import ModuleBase, combinational_for, render_module_body
 
-if typing.TYPE_CHECKING:
-    from ..expr import Expr
-
 
 class Downstream(ModuleBase):
     '''Downstream class implementation.'''
 
     _name: str  # Internal name storage
     body: list['Expr']  # Body of the downstream module
+    condition: Expr  # The upstream value also gating its execution, or None
 
     @property
     def name(self) -> str:
@@ -41,6 +38,7 @@ class Downstream(ModuleBase):
             self._name = base_name + self.as_operand()
 
         self.body = None
+        self.condition = None
 
         Singleton.peek_builder().downstreams.append(self)
 
@@ -49,6 +47,9 @@ class Downstream(ModuleBase):
         var_id = self.as_operand()
         body = render_module_body(self.body)
         ext = self._dump_externals()
+        if self.condition is not None:
+            head = f'{head}, condition: {self.condition.parent.name}.' \
+                   f'{self.condition.as_operand()}'
         return f'''{ext}  #[{head}]
   {var_id} = module {self.name} {{
 {body}
@@ -60,3 +61,84 @@ class Downstream(ModuleBase):
 
 # Create the combinational decorator for Downstream
 combinational = combinational_for(Downstream)
+
+
+def _dependency_path(source: ModuleBase, target: ModuleBase):
+    '''The modules from `source` to `target` following the values each one uses, if any.'''
+    # pylint: disable=import-outside-toplevel,cyclic-import
+    from ...analysis.topo import get_upstreams
+    parents = {source: None}
+    stack = [source]
+    while stack:
+        module = stack.pop()
+        if module is target:
+            path = []
+            while module is not None:
+                path.append(module)
+                module = parents[module]
+            return path[::-1]
+        for upstream in get_upstreams(module):
+            if upstream not in parents:
+                parents[upstream] = module
+                stack.append(upstream)
+    return None
+
+
+class DownstreamContext:
+    '''The handle `create_downstream` passes to the body of the downstream it builds.'''
+
+    def __init__(self, module: Downstream):
+        self.module = module
+
+    def use_value(self, value: Expr) -> Expr:
+        '''Use `value` of another module in this downstream.
+
+        The value is checked right away: if its module already depends on this downstream,
+        the use closes a combinational cycle, and a `ValueError` naming it is raised.
+        '''
+        source = getattr(value, 'parent', None)
+        if not isinstance(value, Expr) or not isinstance(source, ModuleBase):
+            raise ValueError(f'{self.module.name} can only use values of modules, got {value!r}')
+        path = None if source is self.module else _dependency_path(source, self.module)
+        if path is not None:
+            cycle = ' -> '.join(i.name for i in [self.module] + path)
+            raise ValueError(f'Using {value.as_operand()} of {source.name} in '
+                             f'{self.module.name} closes a combinational cycle: {cycle}')
+        return value
+
+
+def create_downstream(name: str, body) -> Downstream:
+    '''Create the downstream `name`, built by calling `body` with its `DownstreamContext`.
+
+    Unlike `@downstream.combinational`, `body` is called as is, so it can be a closure
+    capturing the values of the modules it combines.
+    '''
+    module = Downstream()
+    module.name = name
+    module.body = []
+    Singleton.peek_builder().enter_context_of(module)
+    try:
+        body(DownstreamContext(module))
+    finally:
+        ctx = Singleton.peek_builder().exit_context_of()
+    if ctx.pipeline is not None:
+        ctx.pipeline.finalize()
+    return module
+
+
+def create_downstream_with_condition(name: str, cond: Expr, body) -> Downstream:
+    '''Create the downstream `name` like `create_downstream`, which only executes in the cycles
+    where `cond`, a one-bit value of an upstream module, is valid and true.'''
+    if not isinstance(cond, Expr) or not isinstance(getattr(cond, 'parent', None), ModuleBase):
+        raise ValueError(f'The condition of {name} must be a value of a module, got {cond!r}')
+    if cond.dtype.bits != 1:
+        raise ValueError(f'The condition of {name} must be one bit, got {cond.dtype}')
+
+    def guarded(ctx):
+        # Makes the condition an input of the downstream, whatever the body uses
+        ctx.use_value(cond).valid()
+        body(ctx)
+
+    module = create_downstream(name, guarded)
+    module.condition = cond
+    return module
@@ -80,13 +80,13 @@ class _ExternalRegOutProxy:
         # pylint: disable=import-outside-toplevel
         from ...builder import ir_builder
         from ..expr.intrinsic import PureIntrinsic
-        from ..const import Const
+        from ..const import imm
         from ..dtype import UInt
 
         # Wrap index in Const if it's a Python int
         if isinstance(index, int):
             # Create a UInt constant for the index
-            index_const = Const(UInt(32), index)
+            index_const = imm(index, UInt(32))
         else:
             index_const = index
 
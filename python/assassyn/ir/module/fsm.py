@@ -1,5 +1,4 @@
 '''FSM (Finite State Machine) syntax sugar.'''
-import math
 from ..block import Condition
 from ..dtype import Bits
 from ..array import Array
@@ -18,7 +17,7 @@ class FSM:# pylint: disable=R0903
 
         self.transition_table = transition_table
 
-        self.state_bits = math.floor(math.log2(len(transition_table)))
+        self.state_bits = max(1, (len(transition_table) - 1).bit_length())
         print(f"State bits: {self.state_bits}")
 
 
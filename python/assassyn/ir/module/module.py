@@ -2,11 +2,13 @@
 
 from __future__ import annotations
 
+import sys
 import typing
 from ...builder import Singleton, ir_builder
-from ..dtype import DType
+from ..dtype import DType, to_uint
 from ..expr import Bind, FIFOPop, FIFOPush, AsyncCall, Expr
 from ..expr.intrinsic import wait_until, PureIntrinsic
+from ..array import SynthPragma
 from .base import ModuleBase, combinational_for, render_module_body
 
 if typing.TYPE_CHECKING:
@@ -15,6 +17,27 @@ if typing.TYPE_CHECKING:
 def _reserved_module_name(name):
     return name in ['Driver', 'Testbench']
 
+
+def declared_ports(cls) -> dict:
+    '''The ports declared as `name: Port[dtype]` annotations of `cls` and of its bases, base
+    classes first, each a new `Port` so that no two instances share one.
+
+    Under `from __future__ import annotations` the annotations are strings, so those spelled
+    `Port[...]` are evaluated in the module of their class; the others are left alone.'''
+    # pylint: disable=eval-used
+    res = {}
+    for klass in reversed(cls.__mro__):
+        if not issubclass(klass, Module) or klass is Module:
+            continue
+        for name, annotation in vars(klass).get('__annotations__', {}).items():
+            if isinstance(annotation, str):
+                if not annotation.lstrip().startswith('Port['):
+                    continue
+                annotation = eval(annotation, vars(sys.modules[klass.__module__]))
+            if isinstance(annotation, Port):
+                res[name] = Port(annotation.dtype)
+    return res
+
 #pylint: disable=too-few-public-methods
 class Timing:
     '''The enum class for the timing policy of a module.'''
@@ -34,20 +57,33 @@ class Module(ModuleBase):  # pylint: disable=too-many-instance-attributes
     _attrs: dict  # Dictionary of module attributes
     _ports: list  # List of ports
     _users: typing.List[Expr]  # Callers of this module
+    _response: typing.Optional[Port]  # The FIFO of the responses, see `respond`
 
     ATTR_DISABLE_ARBITER = 1
     ATTR_TIMING = 2
     ATTR_MEMORY = 3
     ATTR_EXTERNAL = 4
+    ATTR_DOWNSTREAM = 5
+    ATTR_FLOW_THROUGH = 6
+    ATTR_MERGE_CALLS = 7
+    ATTR_CLOCK_DOMAIN = 8
+    ATTR_SYNTH_PRAGMA = 9
 
     MODULE_ATTR_STR = {
       ATTR_DISABLE_ARBITER: 'no_arbiter',
       ATTR_MEMORY: 'memory',
       ATTR_TIMING: 'timing',
       ATTR_EXTERNAL: 'external',
+      ATTR_DOWNSTREAM: 'downstream',
+      ATTR_FLOW_THROUGH: 'flow_through',
+      ATTR_MERGE_CALLS: 'merge_calls',
+      ATTR_CLOCK_DOMAIN: 'clock_domain',
+      ATTR_SYNTH_PRAGMA: 'synth_pragma',
     }
 
-    def __init__(self, ports, no_arbiter=False):
+    # pylint: disable=too-many-arguments
+    def __init__(self, ports=None, no_arbiter=False, flow_through=False, merge_calls=False,
+                 clock_domain=None, synth_pragma=None):
         '''Construct the module with the given attributes.
 
         Args:
@@ -55,7 +91,14 @@ class Module(ModuleBase):  # pylint: disable=too-many-instance-attributes
           - timing(Timing): The timing policy of this module.
           - disable_arbiter_rewrite(bool): When there are multiple callers, if this module
           should be rewritten by the compiler.
-          - ports: The ports of this module.
+          - flow_through(bool): If a value pushed into an empty port FIFO is visible to this
+          module in the same cycle.
+          - merge_calls(bool): If all the calls to this module in a cycle activate it only once.
+          - clock_domain(str): The clock domain of this module, None for the default clock.
+          - synth_pragma(str or list): The synthesis directives of the module declaration,
+          e.g. `keep_hierarchy = "yes"`.
+          - ports: The ports of this module. When None, the ports are declared by the
+          `name: Port[dtype]` annotations of the class.
         '''
         super().__init__()
         self.body = None
@@ -64,7 +107,7 @@ class Module(ModuleBase):  # pylint: disable=too-many-instance-attributes
         if _reserved_module_name(base_name):
             self.name = base_name
             if manager is not None:
-                manager.assign_name(self, base_name)
+                manager.assign_name(self, base_name, scoped=False)
         else:
             if manager is not None:
                 self.name = manager.assign_name(self)
@@ -74,8 +117,21 @@ class Module(ModuleBase):  # pylint: disable=too-many-instance-attributes
         self._attrs = {}
         if no_arbiter:
             self._attrs[Module.ATTR_DISABLE_ARBITER] = True
+        if flow_through:
+            self._attrs[Module.ATTR_FLOW_THROUGH] = True
+        if merge_calls:
+            self._attrs[Module.ATTR_MERGE_CALLS] = True
+        if clock_domain is not None:
+            if not isinstance(clock_domain, str) or not clock_domain.isidentifier():
+                raise ValueError(f'A clock domain is named by an identifier, got {clock_domain!r}')
+            self._attrs[Module.ATTR_CLOCK_DOMAIN] = clock_domain
+        if synth_pragma is not None:
+            pragmas = [synth_pragma] if isinstance(synth_pragma, str) else synth_pragma
+            self._attrs[Module.ATTR_SYNTH_PRAGMA] = [SynthPragma(i).text for i in pragmas]
 
         self._ports = []
+        if ports is None:
+            ports = declared_ports(type(self))
         for name, port in ports.items():
             assert isinstance(port, Port)
             setattr(self, name, port)
@@ -83,6 +139,7 @@ class Module(ModuleBase):  # pylint: disable=too-many-instance-attributes
             port.module = self
             self._ports.append(getattr(self, name))
         self._users = []
+        self._response = None
 
         builder = Singleton.peek_builder()
         builder.modules.append(self)
@@ -124,12 +181,72 @@ class Module(ModuleBase):  # pylint: disable=too-many-instance-attributes
         bind = self.bind(**kwargs)
         return AsyncCall(bind)
 
+    @ir_builder
+    def async_called_n(self, n, **kwargs):
+        '''The frontend API for an async call activating `self` `n` times, see
+        `Bind.async_called_n`.'''
+        bind = self.bind(**kwargs)
+        return AsyncCall(bind, replication=n)
+
+    def async_called_if(self, cond, **kwargs):
+        '''The frontend API for an async call to `self` guarded by `cond`, as if it were called
+        in a `Condition(cond)` block: both the call and its FIFO pushes are predicated.'''
+        # pylint: disable=import-outside-toplevel
+        from ..block import Condition
+        with Condition(cond):
+            return self.async_called(**kwargs)
+
+    def respond(self, value):
+        '''The frontend API for sending `value` back to the module waiting for the calls of
+        `self` with `call_and_wait`. It is pushed into a response FIFO, which becomes a port of
+        that module once it waits.'''
+        assert self._response is None, f'{self.name} already responds'
+        self._response = Port(value.dtype)
+        self._response.push(value)
+
+    def call_and_wait(self, **kwargs):
+        '''The frontend API for an async call to `self` whose response is waited for.
+
+        The response FIFO of `self`, filled by its `respond`, becomes the port
+        `<callee>_response` of the calling module. The calling module then waits until the
+        FIFO holds a response, and the popped response is returned. So the response of a call
+        is consumed in a later activation of the calling module, not in the one calling.'''
+        # pylint: disable=import-outside-toplevel
+        from ...utils import namify
+        port = self._response
+        if port is None:
+            raise ValueError(f'{self.name} sends no response; call `respond` in its build '
+                             'before waiting for it')
+        caller = Singleton.peek_builder().current_module
+        if not isinstance(caller, Module):
+            raise ValueError(f'Only a module with ports can wait for {self.name}, not '
+                             f'{caller.name}')
+        if port.module is not None and port.module is not caller:
+            raise ValueError(f'The responses of {self.name} are already waited for by '
+                             f'{port.module.name}')
+        if port.module is None:
+            port.name = f'{namify(self.name)}_response'
+            port.module = caller
+            setattr(caller, port.name, port)
+            caller.ports.append(port)
+        self.async_called(**kwargs)
+        wait_until(port.valid())
+        return port.pop()
+
     @ir_builder
     def bind(self, **kwargs):
         '''The frontend API for creating a bind operation to this `self` module.'''
         bound = Bind(self, **kwargs)
         return bound
 
+    @ir_builder
+    def partial(self, **kwargs):
+        '''The frontend API for creating a bind that can be completed incrementally with
+        `Bind.bind`, and must be fully bound when `Bind.async_called` is invoked.'''
+        bound = Bind(self, **kwargs)
+        bound.strict = True
+        return bound
+
     def __repr__(self):
         ports = '\n    '.join(repr(v) for v in self.ports)
         if ports:
@@ -169,6 +286,40 @@ class Module(ModuleBase):  # pylint: disable=too-many-instance-attributes
         '''The helper function to get the no-arbiter setting.'''
         return self._attrs.get(Module.ATTR_DISABLE_ARBITER, False)
 
+    @property
+    def flow_through(self):
+        '''The helper function to get if the port FIFOs of this module are flow-through.'''
+        return self._attrs.get(Module.ATTR_FLOW_THROUGH, False)
+
+    @property
+    def merge_calls(self):
+        '''The helper function to get if the calls to this module in a cycle are merged.'''
+        return self._attrs.get(Module.ATTR_MERGE_CALLS, False)
+
+    @property
+    def clock_domain(self):
+        '''The helper function to get the clock domain of this module, None for the default.'''
+        return self._attrs.get(Module.ATTR_CLOCK_DOMAIN, None)
+
+    @property
+    def synth_pragmas(self):
+        '''The helper function to get the texts of the synthesis pragmas of this module.'''
+        return list(self._attrs.get(Module.ATTR_SYNTH_PRAGMA, []))
+
+    @property
+    def inferred_downstream(self):
+        '''The helper function to get if this module was inferred to be a downstream.'''
+        return self._attrs.get(Module.ATTR_DOWNSTREAM, False)
+
+    @inferred_downstream.setter
+    def inferred_downstream(self, value):
+        '''The helper function to mark this module as an inferred downstream.'''
+        if value:
+            self._attrs[Module.ATTR_DOWNSTREAM] = True
+        else:
+            self._attrs.pop(Module.ATTR_DOWNSTREAM, None)
+
+
 class Port:
     '''The AST node for defining a port in modules.'''
 
@@ -203,6 +354,30 @@ class Port:
         '''The frontend API for creating a FIFO.peek operation.'''
         return PureIntrinsic(PureIntrinsic.FIFO_PEEK, self)
 
+    def peek_n(self, k: int):
+        '''The frontend API for peeking the `k`-th entry of the FIFO, 0 being the head.'''
+        assert isinstance(k, int) and k >= 0, f'Invalid lookahead: {k}'
+        if k == 0:
+            return self.peek()
+        return self._lookahead(PureIntrinsic.FIFO_PEEK_N, k)
+
+    def valid_n(self, k: int):
+        '''The frontend API for checking if the FIFO holds more than `k` entries.'''
+        assert isinstance(k, int) and k >= 0, f'Invalid lookahead: {k}'
+        if k == 0:
+            return self.valid()
+        return self._lookahead(PureIntrinsic.FIFO_VALID_N, k)
+
+    @ir_builder
+    def _lookahead(self, opcode, k):
+        return PureIntrinsic(opcode, self, to_uint(k))
+
+    def lookahead_depth(self) -> int:
+        '''The largest `k` of the `peek_n` and `valid_n` of this port, 0 if none.'''
+        return max((user.lookahead for user in self.users if isinstance(user, PureIntrinsic)
+                    and user.opcode in [PureIntrinsic.FIFO_PEEK_N, PureIntrinsic.FIFO_VALID_N]),
+                   default=0)
+
     @ir_builder
     def pop(self):
         '''The frontend API for creating a pop operation.'''
@@ -213,12 +388,30 @@ class Port:
         '''The frontend API for creating a push operation.'''
         return FIFOPush(self, v)
 
+    @property
+    def fifo_depth(self) -> typing.Optional[int]:
+        '''The FIFO depth set by the binds pushing this port, None for the default depth.'''
+        return max((user.fifo_depth for user in self.users
+                    if isinstance(user, FIFOPush) and user.fifo_depth is not None), default=None)
+
     def __repr__(self):
-        return f'{self.name}: Port<{self.dtype}>'
+        depth = self.fifo_depth
+        depth = f' // depth {depth}' if depth is not None else ''
+        return f'{self.name}: Port<{self.dtype}>{depth}'
 
     def as_operand(self):
         '''Dump the port as a right-hand side reference.'''
         return f'{self.module.as_operand()}.{self.name}'
 
+
+
+def verify_responses(sys) -> None:
+    '''Check that the responses of every module of `sys` are waited for by a module.'''
+    for module in sys.modules:
+        port = getattr(module, '_response', None)
+        if port is not None and port.module is None:
+            raise ValueError(f'{module.name} responds, but no module waits for it with '
+                             '`call_and_wait`')
+
 # Create the combinational decorator for Module
 combinational = combinational_for(Module)
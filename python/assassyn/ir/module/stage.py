@@ -0,0 +1,122 @@
+'''User-directed pipelining: split a module body into a chain of stage modules.'''
+
+from __future__ import annotations
+
+import typing
+
+from ...builder import Singleton
+from ...utils import namify
+from .module import Module, Port
+
+if typing.TYPE_CHECKING:
+    from ..expr import Expr
+
+
+class StageModule(Module):
+    '''A pipeline stage carved out of the body of another module by `stage()`.'''
+
+    def __init__(self, root: Module, index: int):
+        super().__init__(ports={})
+        self.name = f'{root.name}_stage{index}'
+        self.body = []
+        self.timing = 'systolic'
+
+
+class StagePipeline:
+    '''Book-keeping of the stages created while building one module.'''
+
+    stages: list[Module]  # stages[0] is the user module, the rest are StageModules
+    # forwarded[i] maps a value from an earlier stage to its popped copy in stages[i]
+    forwarded: list[dict[Expr, Expr]]
+    # edges[i] holds the (port name, value) pairs stages[i] pushes to stages[i + 1]
+    edges: list[list[tuple[str, Expr]]]
+
+    def __init__(self, root: Module):
+        self.stages = [root]
+        self.forwarded = [{}]
+        self.edges = []
+
+    def split(self) -> StageModule:
+        '''Append a new stage after the last one.'''
+        stage_module = StageModule(self.stages[0], len(self.stages))
+        self.stages.append(stage_module)
+        self.forwarded.append({})
+        self.edges.append([])
+        return stage_module
+
+    def forward(self, value: Expr, current: Module) -> Expr:
+        '''Return the copy of `value` visible in `current`, adding ports along the way.'''
+        if current not in self.stages or value.parent not in self.stages:
+            return value
+        src = self.stages.index(value.parent)
+        dst = self.stages.index(current)
+        if src >= dst:
+            return value
+
+        builder = Singleton.peek_builder()
+        local = value
+        for i in range(src + 1, dst + 1):
+            if value in self.forwarded[i]:
+                local = self.forwarded[i][value]
+                continue
+            stage_module = self.stages[i]
+            port = Port(value.dtype)
+            port.name = self._port_name(stage_module, value)
+            port.module = stage_module
+            setattr(stage_module, port.name, port)
+            stage_module.ports.append(port)
+            self.edges[i - 1].append((port.name, local))
+
+            # Pops go to the stage prologue, unconditionally.
+            builder.enter_context_of(stage_module)
+            try:
+                popped = port.pop()
+            finally:
+                builder.exit_context_of()
+            # NOTE: `list.remove` would call the overloaded `==` of values.
+            assert stage_module.body[-1] is popped
+            stage_module.body.pop()
+            stage_module.body.insert(len(self.forwarded[i]), popped)
+            self.forwarded[i][value] = popped
+            local = popped
+        return local
+
+    def finalize(self) -> None:
+        '''Emit the async calls that chain each stage into the next one.'''
+        builder = Singleton.peek_builder()
+        for i, edge in enumerate(self.edges):
+            builder.enter_context_of(self.stages[i])
+            try:
+                self.stages[i + 1].async_called(**dict(edge))
+            finally:
+                builder.exit_context_of()
+
+    @staticmethod
+    def _port_name(stage_module: Module, value: Expr) -> str:
+        base = namify(value.name or 'v')
+        name, suffix = base, 0
+        while hasattr(stage_module, name):
+            suffix += 1
+            name = f'{base}_{suffix}'
+        return name
+
+
+def stage():
+    '''Mark a pipeline stage boundary in the current module body.
+
+    The code after the marker is built into a new stage module. Values defined before the
+    marker and used after it are forwarded through ports, and the stage before the marker
+    async-calls the stage after it.'''
+    builder = Singleton.peek_builder()
+    ctx = builder.current_context
+    module = ctx.module
+    if not isinstance(module, Module):
+        raise TypeError(f'stage() is only allowed in a Module, not {type(module).__name__}')
+    if ctx.cond_stack:
+        raise RuntimeError('stage() cannot be placed under a Condition')
+    if ctx.pipeline is None:
+        ctx.pipeline = StagePipeline(module)
+    next_stage = ctx.pipeline.split()
+    builder.exit_context_of()
+    builder.enter_context_of(next_stage)
+    builder.current_context.pipeline = ctx.pipeline
@@ -0,0 +1,151 @@
+'''Cyclic partitioning of register arrays into banks.'''
+
+from __future__ import annotations
+
+import typing
+
+from ..builder import Singleton
+from .block import Condition
+from .dtype import UInt
+from .value import Value
+
+if typing.TYPE_CHECKING:
+    from .array import Array
+    from .module.base import ModuleBase
+
+
+class CyclicPartition:  # pylint: disable=too-few-public-methods
+    '''The array attribute splitting a size-N array into `factor` banks of size N / factor.
+
+    Element `i` goes to bank `i % factor`, at index `i // factor` of the bank, so that every
+    `factor`-th element can be accessed in parallel.'''
+
+    factor: int
+
+    def __init__(self, factor: int):
+        assert isinstance(factor, int) and factor > 1, f'Invalid partition factor: {factor}'
+        self.factor = factor
+
+    def __repr__(self):
+        return f'cyclic_partition({self.factor})'
+
+
+def _split_index(index: Value, factor: int, bank: Array):
+    '''Split a dynamic element index into the bank id and the index inside the bank.'''
+    # i % factor and i // factor are bit slices, so no divider is generated
+    assert factor & (factor - 1) == 0, \
+        f'A dynamic index needs a power-of-two partition factor, not {factor}'
+    bits = index.dtype.bits
+    log2 = factor.bit_length() - 1
+    assert bits > log2, f'Index of {bits} bits cannot address {factor} banks'
+    sel = index[0:log2 - 1]
+    need = bank.index_bits
+    if need == 0:
+        return sel, 0
+    # The bank index has exactly the bits the bank needs
+    inner = index[log2:min(bits, log2 + need) - 1]
+    if inner.dtype.bits < need:
+        inner = inner.zext(UInt(need))
+    return sel, inner.bitcast(UInt(need))
+
+
+class PartitionedArray:
+    '''A register array split into banks by a `CyclicPartition`.
+
+    It is indexed like an `Array`. A constant index accesses one bank directly; a dynamic
+    index reads all the banks and selects, or writes the selected bank under a predicate.'''
+
+    banks: typing.List[Array]  # Bank `b` holds the elements `b, b + factor, ...`
+    factor: int  # The number of banks
+    size: int  # The number of elements of all the banks
+
+    def __init__(self, banks: typing.List[Array], factor: int):
+        self.banks = banks
+        self.factor = factor
+        self.size = sum(bank.size for bank in banks)
+
+    @property
+    def scalar_ty(self):
+        '''The data type of the array elements.'''
+        return self.banks[0].scalar_ty
+
+    def bank(self, b: int) -> Array:
+        '''Get the `b`-th bank, for accessing it in parallel with the other banks.'''
+        return self.banks[b]
+
+    def __getitem__(self, index: typing.Union[int, Value]):
+        if isinstance(index, int):
+            assert 0 <= index < self.size, f'Index {index} out of {self.size} elements'
+            return self.banks[index % self.factor][index // self.factor]
+        sel, inner = _split_index(index, self.factor, self.banks[0])
+        res = self.banks[0][inner]
+        for b in range(1, self.factor):
+            res = (sel == sel.dtype(b)).select(self.banks[b][inner], res)
+        return res
+
+    def __setitem__(self, index, value):
+        current_module = Singleton.peek_builder().current_module
+        (self & current_module)[index] = value
+
+    def __and__(self, module: ModuleBase):
+        return PartitionedWritePort(self, module)
+
+    def __repr__(self):
+        banks = ', '.join(bank.name for bank in self.banks)
+        return f'cyclic_partition({self.factor}) [{banks}]'
+
+
+class PartitionedWritePort:
+    '''Created via the (array & module) syntax to write a `PartitionedArray`.'''
+
+    array: PartitionedArray
+    module: ModuleBase
+
+    def __init__(self, array: PartitionedArray, module: ModuleBase):
+        self.array = array
+        self.module = module
+
+    def __getitem__(self, index):
+        return _IndexedPartitionedWrite(self, index)
+
+    def __setitem__(self, index, value):
+        self._create_write(index, value)
+
+    def _create_write(self, index, value):
+        banks = self.array.banks
+        factor = self.array.factor
+        if isinstance(index, int):
+            assert 0 <= index < self.array.size, \
+                f'Index {index} out of {self.array.size} elements'
+            (banks[index % factor] & self.module)[index // factor] = value
+            return
+        sel, inner = _split_index(index, factor, banks[0])
+        for b, bank in enumerate(banks):
+            with Condition(sel == sel.dtype(b)):
+                (bank & self.module)[inner] = value
+
+
+class _IndexedPartitionedWrite:  # pylint: disable=too-few-public-methods
+    '''The proxy handling the <= assignment of a `PartitionedWritePort`.'''
+
+    def __init__(self, write_port: PartitionedWritePort, index):
+        self.write_port = write_port
+        self.index = index
+
+    def __le__(self, value):
+        # pylint: disable=protected-access
+        return self.write_port._create_write(self.index, value)
+
+
+def partition_array(scalar_ty, size: int, initializer, name, attr: CyclicPartition, owner):
+    '''Create the banks of a cyclically partitioned array.'''
+    # pylint: disable=import-outside-toplevel,too-many-arguments
+    from .array import RegArray
+    factor = attr.factor
+    assert size % factor == 0, f'Array size {size} is not a multiple of the factor {factor}'
+    banks = []
+    for b in range(factor):
+        init = initializer[b::factor] if initializer is not None else None
+        bank_name = f'{name}_bank{b}' if name is not None else None
+        banks.append(RegArray(scalar_ty, size // factor, init, bank_name, owner=owner))
+    return PartitionedArray(banks, factor)
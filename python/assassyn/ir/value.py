@@ -33,6 +33,28 @@ class Value(ABC):
         from .expr import BinaryOp
         return BinaryOp(BinaryOp.MUL, self, other)
 
+    # Like `optional`, this is not annotated with "ir_builder", since the casts and the addition
+    # it builds are inserted into the AST by themselves.
+    def addc(self, other):
+        '''The frontend API to add two values with the carry-out: both are zero-extended to one
+        bit wider than the wider of them before the addition.'''
+        from .dtype import UInt
+        assert isinstance(other, Value), "Expecting a Value object"
+        ty = UInt(max(self.dtype.bits, other.dtype.bits) + 1)
+        return self.zext(ty) + other.zext(ty)
+
+    @ir_builder
+    def add_sat(self, other):
+        '''The frontend API to create an addition clamped to the range of this type'''
+        from .expr import BinaryOp
+        return BinaryOp(BinaryOp.ADD_SAT, self, other)
+
+    @ir_builder
+    def sub_sat(self, other):
+        '''The frontend API to create a subtraction clamped to the range of this type'''
+        from .expr import BinaryOp
+        return BinaryOp(BinaryOp.SUB_SAT, self, other)
+
     @ir_builder
     def __or__(self, other):
         from .expr import BinaryOp
@@ -1,7 +1,10 @@
 '''The module for the frontend AST visitor pattern'''
 
+import typing
+
 from ..builder import SysBuilder
 from .module import Module, Port
+from .module.base import ModuleBase
 from .expr import Expr
 
 class Visitor:
@@ -9,11 +12,31 @@ class Visitor:
     # Base visitor class with no attributes of its own - it just defines visit methods
 
     current_module: Module
+    # The results `dispatch` collects while `walk` runs, None otherwise
+    _walk_results: typing.Optional[list] = None
 
     def __init__(self):
         '''Initialize the visitor with no current module'''
         self.current_module = None
 
+    def walk(self, node) -> list:
+        '''Visit `node`, a system or a module, and collect the results of `visit_expr` which are
+        not None, in document order.'''
+        outer = self._walk_results
+        self._walk_results = []
+        try:
+            if isinstance(node, SysBuilder):
+                self.visit_system(node)
+            else:
+                current, self.current_module = self.current_module, node
+                try:
+                    self.visit_module(node)
+                finally:
+                    self.current_module = current
+            return self._walk_results
+        finally:
+            self._walk_results = outer
+
     def visit_system(self, node: SysBuilder):
         '''Enter a system'''
         for elem in node.arrays:
@@ -44,4 +67,154 @@ class Visitor:
     def dispatch(self, node):
         '''Dispatch the node in a block to the corresponding visitor'''
         if isinstance(node, Expr):
-            self.visit_expr(node)
+            res = self.visit_expr(node)
+            if res is not None and self._walk_results is not None:
+                self._walk_results.append(res)
+
+
+def _index(body: list, node) -> int:
+    '''Find `node` in `body` by identity, as values overload ==.'''
+    return next(i for i, elem in enumerate(body) if elem is node)
+
+
+def _detach(users: list, user) -> None:
+    idx = next((i for i, elem in enumerate(users) if elem is user), None)
+    if idx is not None:
+        del users[idx]
+
+
+def erase_expr(expr: Expr) -> None:
+    '''Remove `expr` from the body of its module, and from the users of its operands.'''
+    # pylint: disable=import-outside-toplevel,protected-access
+    from .array import Array
+    from .expr import Operand
+    assert not expr.users, f'Cannot erase {expr}, which is still used'
+    module = expr.parent
+    if module is not None and isinstance(module.body, list):
+        idx = next((i for i, elem in enumerate(module.body) if elem is expr), None)
+        if idx is not None:
+            del module.body[idx]
+    for operand in expr._operands:
+        if isinstance(operand, (Array, Port)):
+            _detach(operand.users, expr)
+        elif isinstance(operand, Operand) and isinstance(operand.value, Expr):
+            _detach(operand.value.users, operand)
+    # An expression without a parent is no longer part of any module
+    expr.parent = None
+
+
+def replace_expr(old: Expr, new: Expr) -> None:
+    '''Redirect all the users of `old` to `new`, and erase `old`.
+
+    The expressions predicated by `old`, i.e. whose `meta_cond` is `old`, are redirected too.'''
+    # pylint: disable=protected-access
+    for user in old.users:
+        user._value = new
+        new.users.append(user)
+    old.users = []
+    for expr in old.parent.body:
+        if getattr(expr, 'meta_cond', None) is old:
+            expr._meta_cond = new
+    erase_expr(old)
+
+
+def replace_operand(expr: Expr, idx: int, new) -> None:
+    '''Make the operand `idx` of `expr` use `new` instead, in place.
+
+    The old operand is cut from the users of its value and from the externals of the module of
+    `expr`, and the new one is registered in both, as the builder does for a new expression.'''
+    # pylint: disable=import-outside-toplevel,protected-access
+    from .array import Array
+    from .expr import Operand
+    old = expr.get_operand(idx)
+    module = expr.parent
+    if isinstance(old, (Array, Port)):
+        _detach(old.users, expr)
+    elif isinstance(old, Operand):
+        if isinstance(old.value, Expr):
+            _detach(old.value.users, old)
+        if module is not None and old.value in module.externals:
+            _detach(module.externals[old.value], old)
+            if not module.externals[old.value]:
+                del module.externals[old.value]
+
+    if isinstance(new, (Array, Port)):
+        new.users.append(expr)
+        expr._operands[idx] = new
+        return
+    wrapped = Operand(new, expr)
+    if isinstance(new, Expr):
+        new.users.append(wrapped)
+    if module is not None:
+        module.add_external(wrapped)
+    expr._operands[idx] = wrapped
+
+
+def insert_before(anchor: Expr, new: Expr) -> None:
+    '''Move `new` in front of `anchor`, in the same block of the same module.
+
+    `new` is usually created by the builder, which appends it to the end of the current module,
+    so it is taken out of its current position first. It takes the predicate of `anchor`.'''
+    # pylint: disable=protected-access
+    if new.parent is not None:
+        body = new.parent.body
+        idx = next((i for i, elem in enumerate(body) if elem is new), None)
+        if idx is not None:
+            del body[idx]
+    body = anchor.parent.body
+    body.insert(_index(body, anchor), new)
+    new.parent = anchor.parent
+    new._meta_cond = anchor.meta_cond
+
+
+class MutVisitor:
+    '''The visitor pattern class rewriting the frontend AST in place.
+
+    A block is collected before its expressions are dispatched, so the callbacks may erase,
+    insert, or replace expressions of the module being visited. Erased expressions not yet
+    visited are skipped, and inserted expressions are not visited.'''
+
+    current_module: ModuleBase
+
+    def __init__(self):
+        '''Initialize the visitor with no current module'''
+        self.current_module = None
+
+    def visit_system(self, node: SysBuilder):
+        '''Enter a system, visiting the modules and then the downstreams'''
+        for elem in node.modules + node.downstreams:
+            self.current_module = elem
+            self.visit_module_mut(elem)
+        self.current_module = None
+
+    def visit_module_mut(self, node: ModuleBase):
+        '''Enter a module, whose flat body is visited as a single block'''
+        body = getattr(node, "body", None)
+        if isinstance(body, list):
+            self.visit_block_mut(list(body))
+
+    def visit_block_mut(self, block: typing.List[Expr]):
+        '''Enter a list of expressions collected before the traversal'''
+        for elem in block:
+            # Erased by an earlier callback
+            if isinstance(elem, Expr) and elem.parent is not None:
+                self.visit_expr_mut(elem)
+
+    def visit_expr_mut(self, node: Expr):
+        '''Enter an expression, which the callback may erase or replace'''
+
+    def erase(self, node: Expr):
+        '''Erase `node` from its module'''
+        erase_expr(node)
+
+    def replace_expr(self, old: Expr, new: Expr):
+        '''Replace all the uses of `old` by `new`, and erase `old`'''
+        replace_expr(old, new)
+
+    def replace_operand(self, node: Expr, idx: int, new):
+        '''Make the operand `idx` of `node` use `new`'''
+        replace_operand(node, idx, new)
+
+    def insert_before(self, anchor: Expr, new: Expr):
+        '''Move `new` in front of `anchor`'''
+        insert_before(anchor, new)
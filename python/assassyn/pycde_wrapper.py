@@ -3,50 +3,156 @@
 from __future__ import annotations
 # pylint: disable=invalid-name,unused-argument,import-error,too-few-public-methods
 
+from functools import cache
+
 from pycde import Clock, Input, Module, Output, Reset, dim, generator
 from pycde import modparams
 from pycde.constructs import Mux, Reg
 from pycde.types import Bits
 
-__all__ = ("FIFO", "TriggerCounter", "build_register_file")
+from .codegen.verilog.utils import addr_width as verilog_addr_width
 
+__all__ = ("AsyncFIFO", "AxilRegs", "FIFO", "TriggerCounter", "build_register_file")
 
-@modparams
-def FIFO(WIDTH: int, DEPTH_LOG2: int):
-    """Depth-parameterized FIFO matching the backend's SystemVerilog resource."""
 
-    class FIFOImpl(Module):
-        """PyCDE module for the backend FIFO primitive."""
-        module_name = "fifo"
-        # Define inputs
-        clk = Clock()
-        rst_n = Input(Bits(1))
-        push_valid = Input(Bits(1))
-        push_data = Input(Bits(WIDTH))
-        pop_ready = Input(Bits(1))
-        # Define outputs
-        push_ready = Output(Bits(1))
-        pop_valid = Output(Bits(1))
-        pop_data = Output(Bits(WIDTH))
+def FIFO(WIDTH: int, DEPTH_LOG2: int, FLOW_THROUGH: int = 0, PEEK_N: int = 1, *,
+         sync_reset: bool = False):
+    """Depth-parameterized FIFO matching the backend's SystemVerilog resource. With
+    `sync_reset`, its reset is the active-high `rst` of the `sync_high` reset style."""
+    return _fifo(sync_reset)(WIDTH, DEPTH_LOG2, FLOW_THROUGH, PEEK_N)
+
+
+@cache
+def _fifo(sync_reset: bool):
+
+    @modparams
+    def FIFO(WIDTH: int, DEPTH_LOG2: int, FLOW_THROUGH: int = 0, PEEK_N: int = 1):
+
+        class FIFOImpl(Module):
+            """PyCDE module for the backend FIFO primitive."""
+            module_name = "fifo"
+            # Define inputs
+            clk = Clock()
+            if sync_reset:
+                rst = Input(Bits(1))
+            else:
+                rst_n = Input(Bits(1))
+            push_valid = Input(Bits(1))
+            push_data = Input(Bits(WIDTH))
+            pop_ready = Input(Bits(1))
+            # Define outputs
+            push_ready = Output(Bits(1))
+            pop_valid = Output(Bits(1))
+            pop_data = Output(Bits(WIDTH))
+            peek_valid = Output(Bits(PEEK_N))
+            peek_data = Output(Bits(WIDTH * PEEK_N))
+
+        return FIFOImpl
 
-    return FIFOImpl
+    return FIFO
+
+
+def AsyncFIFO(WIDTH: int, DEPTH_LOG2: int, SYNC_STAGES: int = 2, *, sync_reset: bool = False):
+    """FIFO between two clock domains, matching the backend's async_fifo resource. With
+    `sync_reset`, its resets are the active-high `push_rst` and `pop_rst`."""
+    return _async_fifo(sync_reset)(WIDTH, DEPTH_LOG2, SYNC_STAGES)
+
+
+@cache
+def _async_fifo(sync_reset: bool):
+
+    @modparams
+    def AsyncFIFO(WIDTH: int, DEPTH_LOG2: int, SYNC_STAGES: int = 2):
+
+        class AsyncFIFOImpl(Module):
+            """PyCDE module for the backend asynchronous FIFO primitive."""
+            module_name = "async_fifo"
+            push_clk = Clock()
+            if sync_reset:
+                push_rst = Input(Bits(1))
+            else:
+                push_rst_n = Input(Bits(1))
+            push_valid = Input(Bits(1))
+            push_data = Input(Bits(WIDTH))
+            push_ready = Output(Bits(1))
+            pop_clk = Clock()
+            if sync_reset:
+                pop_rst = Input(Bits(1))
+            else:
+                pop_rst_n = Input(Bits(1))
+            pop_valid = Output(Bits(1))
+            pop_data = Output(Bits(WIDTH))
+            pop_ready = Input(Bits(1))
+
+        return AsyncFIFOImpl
+
+    return AsyncFIFO
 
 
 @modparams
-def TriggerCounter(WIDTH: int):
-    """Credit counter primitive used to gate driver execution."""
+def AxilRegs(ADDR_WIDTH: int):
+    """AXI4-Lite slave over the exposed arrays, matching the backend's axil_regs resource."""
 
-    class TriggerCounterImpl(Module):
-        """PyCDE module mirroring the trigger_counter primitive."""
-        module_name = "trigger_counter"
+    class AxilRegsImpl(Module):
+        """PyCDE module for the backend AXI4-Lite register slave."""
+        module_name = "axil_regs"
         clk = Clock()
         rst_n = Input(Bits(1))
-        delta = Input(Bits(WIDTH))
-        delta_ready = Output(Bits(1))
-        pop_ready = Input(Bits(1))
-        pop_valid = Output(Bits(1))
-
-    return TriggerCounterImpl
+        s_axil_awaddr = Input(Bits(ADDR_WIDTH))
+        s_axil_awvalid = Input(Bits(1))
+        s_axil_awready = Output(Bits(1))
+        s_axil_wdata = Input(Bits(32))
+        s_axil_wstrb = Input(Bits(4))
+        s_axil_wvalid = Input(Bits(1))
+        s_axil_wready = Output(Bits(1))
+        s_axil_bresp = Output(Bits(2))
+        s_axil_bvalid = Output(Bits(1))
+        s_axil_bready = Input(Bits(1))
+        s_axil_araddr = Input(Bits(ADDR_WIDTH))
+        s_axil_arvalid = Input(Bits(1))
+        s_axil_arready = Output(Bits(1))
+        s_axil_rdata = Output(Bits(32))
+        s_axil_rresp = Output(Bits(2))
+        s_axil_rvalid = Output(Bits(1))
+        s_axil_rready = Input(Bits(1))
+        addr = Output(Bits(ADDR_WIDTH - 2))
+        rd_data = Input(Bits(32))
+        rd_ok = Input(Bits(1))
+        wr_ok = Input(Bits(1))
+        wr_en = Output(Bits(1))
+        wr_data = Output(Bits(32))
+
+    return AxilRegsImpl
+
+
+def TriggerCounter(WIDTH: int, *, sync_reset: bool = False):
+    """Credit counter primitive used to gate driver execution. With `sync_reset`, its reset
+    is the active-high `rst`."""
+    return _trigger_counter(sync_reset)(WIDTH)
+
+
+@cache
+def _trigger_counter(sync_reset: bool):
+
+    @modparams
+    def TriggerCounter(WIDTH: int):
+
+        class TriggerCounterImpl(Module):
+            """PyCDE module mirroring the trigger_counter primitive."""
+            module_name = "trigger_counter"
+            clk = Clock()
+            if sync_reset:
+                rst = Input(Bits(1))
+            else:
+                rst_n = Input(Bits(1))
+            delta = Input(Bits(WIDTH))
+            delta_ready = Output(Bits(1))
+            pop_ready = Input(Bits(1))
+            pop_valid = Output(Bits(1))
+
+        return TriggerCounterImpl
+
+    return TriggerCounter
 
 
 def build_register_file(  # pylint: disable=too-many-arguments
@@ -61,9 +167,8 @@ def build_register_file(  # pylint: disable=too-many-arguments
     initializer=None,
 ):
     """Create a parameterized register file module with the requested port counts."""
-    computed_addr_width = max(1, (depth - 1).bit_length()) if depth > 0 else 1
     if addr_width is None:
-        addr_width = computed_addr_width
+        addr_width = verilog_addr_width(depth) if depth > 0 else 1
     addr_width = max(1, addr_width)
 
     initializer_values = None
@@ -10,6 +10,7 @@ import re
 import glob
 import hashlib
 import json
+import keyword
 # Local imports
 from .enforce_type import enforce_type, validate_arguments, check_type
 
@@ -282,12 +283,55 @@ def create_dir(dir_path: str) -> None:
     """
     os.makedirs(dir_path, exist_ok=True)
 
+# The strict and reserved keywords of Rust, which the simulator cannot use as identifiers
+RUST_KEYWORDS = frozenset('''
+    as async await break const continue crate dyn else enum extern false fn for if impl in let
+    loop match mod move mut pub ref return self Self static struct super trait true type unsafe
+    use where while abstract become box do final gen macro override priv try typeof unsized
+    virtual yield
+'''.split())
+
+# The keywords of SystemVerilog (IEEE 1800-2017), which a Verilog design cannot use as names
+VERILOG_KEYWORDS = frozenset('''
+    accept_on alias always always_comb always_ff always_latch and assert assign assume automatic
+    before begin bind bins binsof bit break buf bufif0 bufif1 byte case casex casez cell chandle
+    checker class clocking cmos config const constraint context continue cover covergroup
+    coverpoint cross deassign default defparam design disable dist do edge else end endcase
+    endchecker endclass endclocking endconfig endfunction endgenerate endgroup endinterface
+    endmodule endpackage endprimitive endprogram endproperty endspecify endsequence endtable
+    endtask enum event eventually expect export extends extern final first_match for force
+    foreach forever fork forkjoin function generate genvar global highz0 highz1 if iff ifnone
+    ignore_bins illegal_bins implements implies import incdir include initial inout input
+    inside instance int integer interconnect interface intersect join join_any join_none large
+    let liblist library local localparam logic longint macromodule matches medium modport module
+    nand negedge nettype new nexttime nmos nor noshowcancelled not notif0 notif1 null or output
+    package packed parameter pmos posedge primitive priority program property protected pull0
+    pull1 pulldown pullup pulsestyle_ondetect pulsestyle_onevent pure rand randc randcase
+    randsequence rcmos real realtime ref reg reject_on release repeat restrict return rnmos
+    rpmos rtran rtranif0 rtranif1 s_always s_eventually s_nexttime s_until s_until_with
+    scalared sequence shortint shortreal showcancelled signed small soft solve specify
+    specparam static string strong strong0 strong1 struct super supply0 supply1 sync_accept_on
+    sync_reject_on table tagged task this throughout time timeprecision timeunit tran tranif0
+    tranif1 tri tri0 tri1 triand trior trireg type typedef union unique unique0 unsigned until
+    until_with untyped use uwire var vectored virtual void wait wait_order wand weak weak0 weak1
+    while wildcard wire with within wor xnor xor
+'''.split())
+
+def sanitize(name: str) -> str:
+    """Replace the characters of `name` which cannot appear in an identifier with `_`."""
+    return ''.join(c if c.isalnum() or c == '_' else '_' for c in name)
+
 def namify(name: str) -> str:
     """Convert a name to a valid identifier.
 
+    A name which is a keyword of Rust, SystemVerilog or Python, the languages the backends
+    generate, gets a trailing `_`: a module named `type` is simulated as `type_`.
     This matches the Rust function in src/backend/simulator/utils.rs
     """
-    return ''.join(c if c.isalnum() or c == '_' else '_' for c in name)
+    name = sanitize(name)
+    if name in RUST_KEYWORDS or name in VERILOG_KEYWORDS or keyword.iskeyword(name):
+        return name + '_'
+    return name
 
 def check_build_cache(src_dir: str, cache_key: str):
     """Check if cached build exists and is valid.
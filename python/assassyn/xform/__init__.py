@@ -0,0 +1,6 @@
+"""IR transformations of Assassyn."""
+from .licm import licm, hoist_array_reads
+from .cse import cse, eliminate_common_exprs
+from .simplify_casts import simplify_casts, simplify_module_casts
+from .strip_logs import strip_logs
+from .fifo_depth import apply_fifo_depths, set_fifo_depths
@@ -0,0 +1,96 @@
+"""Elimination of common subexpressions among selects, concatenations and slices."""
+
+from __future__ import annotations
+
+from typing import List
+
+from ..ir.array import Slice
+from ..ir.const import Const
+from ..ir.expr import Concat, Expr, Select1Hot
+from ..ir.expr.intrinsic import Intrinsic
+from ..ir.visitor import erase_expr, replace_expr
+from ..utils import unwrap_operand
+from .licm import _used_externally
+
+# The opcodes merged by this pass. Their operands are all values: the bounds of a slice are
+# constants, and a one-hot select has any number of values.
+_MERGED = (Select1Hot, Concat, Slice)
+
+
+def _operand_key(operand):
+    value = unwrap_operand(operand)
+    # Constants are distinct objects, so they are compared by value
+    if isinstance(value, Const):
+        return 'const', value.dtype, value.value
+    return 'value', id(value)
+
+
+def _concat_leaves(value) -> list:
+    '''The values a chain of concatenations rooted at `value` joins, most significant first.'''
+    if isinstance(value, Concat):
+        return _concat_leaves(unwrap_operand(value.msb)) + _concat_leaves(unwrap_operand(value.lsb))
+    return [value]
+
+
+def _expr_key(expr: Expr):
+    '''Key `expr` by its opcode and its operands, element-wise, so that arity counts too.
+
+    A concatenation is keyed by the leaves of its chain, so that the same values joined with
+    a different nesting are equal.'''
+    if isinstance(expr, Concat):
+        return expr.opcode, tuple(_operand_key(i) for i in _concat_leaves(expr))
+    return expr.opcode, tuple(_operand_key(i) for i in expr.operands)
+
+
+def _erase_dead_concats(operands, module, exposed, scopes, res):
+    '''Erase the concatenations among `operands` left without users, and their own chains.'''
+    for value in operands:
+        if isinstance(value, Concat) and value.parent is module and not value.users \
+                and not any(node is value for node in exposed):
+            inner = [unwrap_operand(i) for i in value.operands]
+            for scope in scopes:
+                for key in [k for k, v in scope.items() if v is value]:
+                    del scope[key]
+            erase_expr(value)
+            res.append(value)
+            _erase_dead_concats(inner, module, exposed, scopes, res)
+
+
+def eliminate_common_exprs(module, exposed=()) -> List[Expr]:
+    '''Replace each select1hot, concat or slice equal to an earlier one by the earlier one.
+
+    An earlier expression is only reused if it is in the same block or an enclosing one, as
+    a sibling block is not evaluated in the same cases. Returns the removed expressions.'''
+    res = []
+    scopes = [{}]  # The expressions by key, one table per open block
+    # Collected first, as replaced expressions are erased from the body
+    for expr in list(module.body):
+        if isinstance(expr, Intrinsic) and \
+                expr.opcode in (Intrinsic.PUSH_CONDITION, Intrinsic.PUSH_ELSE):
+            scopes.append({})
+        elif isinstance(expr, Intrinsic) and expr.opcode == Intrinsic.POP_CONDITION:
+            scopes.pop()
+        elif isinstance(expr, _MERGED):
+            key = _expr_key(expr)
+            same = next((scope[key] for scope in scopes if key in scope), None)
+            if same is not None and not _used_externally(expr, module, exposed):
+                operands = [unwrap_operand(i) for i in expr.operands]
+                # The users of `expr` now refer to `same`, so their keys match too
+                replace_expr(expr, same)
+                res.append(expr)
+                # A chain nested differently from `same` is left without users
+                _erase_dead_concats(operands, module, exposed, scopes, res)
+            else:
+                scopes[-1].setdefault(key, expr)
+    return res
+
+
+def cse(sys) -> List[Expr]:
+    '''Eliminate the common subexpressions of all the modules of `sys`.
+
+    Returns the removed expressions.'''
+    res = []
+    for module in sys.modules + sys.downstreams:
+        if module.body:
+            res.extend(eliminate_common_exprs(module, sys.exposed_nodes))
+    return res
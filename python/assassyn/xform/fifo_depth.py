@@ -0,0 +1,57 @@
+"""Application of the statically inferred FIFO depths, or of depths given by name."""
+
+from __future__ import annotations
+
+from typing import Dict, List, Optional
+
+from ..analysis.fifo_depth import infer
+from ..ir.expr import FIFOPush
+from ..ir.module import Module, Port
+
+
+def apply_fifo_depths(sys, depths: Optional[Dict[Port, int]] = None) -> List[Module]:
+    '''Set the FIFO depths of `sys` to the suggestions of `analysis.fifo_depth.infer`.
+
+    Like `Bind.set_fifo_depth`, a depth is the log2 of the number of entries, and the Verilog
+    backend requires all the ports of a module to share it, so a module gets the depth holding
+    the most entries suggested for its ports, and at least 2 entries, so that a full FIFO can
+    be pushed and popped in the same cycle. Modules with a depth given by the user, or with a
+    port without suggestion, are left alone. Returns the modules whose depths are set.'''
+    if depths is None:
+        depths = infer(sys)
+    res = []
+    for module in sys.modules:
+        if not module.ports or any(p.fifo_depth is not None for p in module.ports):
+            continue
+        if any(p not in depths for p in module.ports):
+            continue
+        entries = max(depths[p] for p in module.ports)
+        depth = max((entries - 1).bit_length(), 1)
+        for port in module.ports:
+            for push in port.users:
+                if isinstance(push, FIFOPush):
+                    push.fifo_depth = depth
+        res.append(module)
+    return res
+
+
+def set_fifo_depths(sys, depths: Dict[str, int]) -> List[Port]:
+    '''Set the depth of each FIFO of `sys` named `<module>.<port>` in `depths`, overriding the
+    depth given by the binds or inferred, to sweep the depths without editing the builder.
+
+    A depth is the log2 of the number of entries, as for `Bind.set_fifo_depth`, and must be a
+    positive integer. All the depths are checked before any is set. Returns the ports set.'''
+    ports = {f'{module.name}.{port.name}': port
+             for module in sys.modules for port in module.ports}
+    for name, depth in depths.items():
+        if name not in ports:
+            raise ValueError(f'No FIFO named {name}. Available FIFO names are: {list(ports)}')
+        if not isinstance(depth, int) or isinstance(depth, bool) or depth <= 0:
+            raise ValueError(f'Depth of FIFO {name} must be a positive integer, not {depth!r}')
+        if not any(isinstance(push, FIFOPush) for push in ports[name].users):
+            raise ValueError(f'FIFO {name} is never pushed, so it has no depth to set')
+    for name, depth in depths.items():
+        for push in ports[name].users:
+            if isinstance(push, FIFOPush):
+                push.fifo_depth = depth
+    return [ports[name] for name in depths]
@@ -0,0 +1,99 @@
+"""Hoisting of loop-invariant array reads out of condition blocks."""
+
+from __future__ import annotations
+
+from typing import List
+
+from ..ir.const import Const, bool_imm
+from ..ir.expr import ArrayRead, ArrayWrite, Expr
+from ..ir.expr.intrinsic import Intrinsic
+from ..ir.memory.base import MemoryBase
+from ..ir.visitor import replace_expr
+from ..utils import unwrap_operand
+
+
+def _is_block(expr, opcode) -> bool:
+    return isinstance(expr, Intrinsic) and expr.opcode == opcode
+
+
+def _read_key(read: ArrayRead):
+    idx = unwrap_operand(read.idx)
+    # Constant indices are distinct objects, so they are compared by value
+    idx_key = ('const', idx.value) if isinstance(idx, Const) else ('expr', id(idx))
+    return id(read.array), idx_key
+
+
+def _hoistable(read: ArrayRead, placed: set, written: set) -> bool:
+    '''Check if `read` can be moved in front of the outermost enclosing block.'''
+    if isinstance(read.array.owner, MemoryBase):
+        # Memory reads drive the read enable of the memory, so they stay predicated
+        return False
+    if id(read.array) in written:
+        return False
+    idx = unwrap_operand(read.idx)
+    return not isinstance(idx, Expr) or id(idx) in placed
+
+
+def _used_externally(read: ArrayRead, module, exposed) -> bool:
+    if any(node is read for node in exposed):
+        return True
+    return any(user.user.parent is not module for user in read.users)
+
+
+def hoist_array_reads(module, exposed=()) -> List[ArrayRead]:
+    '''Move the array reads inside condition blocks in front of the outermost block.
+
+    A read is moved when its index is a constant or is computed before the block, and the
+    module does not write the array earlier in the block. A moved read equal to a read
+    already at the top level is replaced by it, unless other modules or exposures use it.
+    Returns the moved reads.'''
+    res = []
+    body = []
+    depth = 0
+    start = 0  # Where the outermost open block starts in `body`
+    placed = set()  # The top-level expressions in front of `start`
+    top = {}  # The top-level reads in front of `start`, by array and index
+    written = set()  # The arrays written so far in the outermost open block
+    # Collected first, as replaced reads are erased from the body
+    for expr in list(module.body):
+        if _is_block(expr, Intrinsic.PUSH_CONDITION):
+            if depth == 0:
+                start = len(body)
+                written = set()
+            depth += 1
+        elif _is_block(expr, Intrinsic.PUSH_ELSE):
+            # An else block is hoisted out of along with its condition block, so the
+            # writes of the then block still count
+            depth += 1
+        elif _is_block(expr, Intrinsic.POP_CONDITION):
+            depth -= 1
+        elif depth == 0:
+            placed.add(id(expr))
+            if isinstance(expr, ArrayRead):
+                top.setdefault(_read_key(expr), expr)
+        elif isinstance(expr, ArrayWrite):
+            written.add(id(expr.array))
+        elif isinstance(expr, ArrayRead) and _hoistable(expr, placed, written):
+            key = _read_key(expr)
+            if key in top and not _used_externally(expr, module, exposed):
+                replace_expr(expr, top[key])
+                continue
+            expr._meta_cond = bool_imm(True)  # pylint: disable=protected-access
+            body.insert(start, expr)
+            start += 1
+            placed.add(id(expr))
+            top.setdefault(key, expr)
+            res.append(expr)
+            continue
+        body.append(expr)
+    module.body[:] = body
+    return res
+
+
+def licm(sys) -> List[ArrayRead]:
+    '''Hoist the invariant array reads of all the modules of `sys`. Returns the moved reads.'''
+    res = []
+    for module in sys.modules + sys.downstreams:
+        if module.body:
+            res.extend(hoist_array_reads(module, sys.exposed_nodes))
+    return res
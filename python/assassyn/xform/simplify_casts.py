@@ -0,0 +1,129 @@
+"""Removal of the casts that cancel out, and of the arithmetic round-trips through casts."""
+
+from __future__ import annotations
+
+from typing import List
+
+from ..ir.const import Const
+from ..ir.dtype import Bits
+from ..ir.expr import BinaryOp, Cast, Expr
+from ..ir.visitor import erase_expr, replace_expr, replace_operand
+from ..utils import unwrap_operand
+from .licm import _used_externally
+
+# The operations whose low bits do not depend on the signedness of their operands
+_SIGN_AGNOSTIC = (BinaryOp.ADD, BinaryOp.SUB, BinaryOp.BITWISE_AND, BinaryOp.BITWISE_OR,
+                  BinaryOp.BITWISE_XOR)
+
+
+def _removable(expr: Expr, module, exposed) -> bool:
+    '''Whether `expr` has no other user than the cast being rewritten, so that rewriting it
+    changes nothing else.'''
+    return len(expr.users) <= 1 and not _used_externally(expr, module, exposed) and \
+        not any(getattr(i, 'meta_cond', None) is expr for i in module.body)
+
+
+def _erase_if_dead(expr, module, exposed, res: List[Expr]) -> None:
+    if isinstance(expr, Cast) and expr.parent is module and not expr.users and \
+            _removable(expr, module, exposed):
+        erase_expr(expr)
+        res.append(expr)
+
+
+def _chained(cast: Cast):
+    '''The source `cast` can read directly, skipping the cast it reads, or None.'''
+    inner = unwrap_operand(cast.x)
+    if not isinstance(inner, Cast) or inner.opcode != cast.opcode:
+        return None
+    src = unwrap_operand(inner.x)
+    if cast.opcode == Cast.BITCAST:
+        # Only width-preserving bitcasts reinterpret the bits without touching them
+        same = src.dtype.bits == inner.dtype.bits == cast.dtype.bits
+        return src if same else None
+    # Two extensions of the same kind are one, as long as neither truncates
+    widening = src.dtype.bits <= inner.dtype.bits <= cast.dtype.bits
+    return src if widening else None
+
+
+def _retyped(const: Const, dtype) -> Const:
+    '''The constant of `dtype` with the same bits as `const`.'''
+    value = const.raw_bits
+    if dtype.is_signed() and value >> (dtype.bits - 1):
+        value -= 1 << dtype.bits
+    return Const(dtype, value)
+
+
+def _round_trip(cast: Cast, module, exposed):
+    '''The new operands of the sign-agnostic operation that `cast` bitcasts back to the type
+    of the values the operation bitcasts, if all of them have the same width, or None. A
+    constant operand is retyped.'''
+    op = unwrap_operand(cast.x)
+    if cast.opcode != Cast.BITCAST or not isinstance(op, BinaryOp) or \
+            op.opcode not in _SIGN_AGNOSTIC or op.parent is not module or \
+            not _removable(op, module, exposed):
+        return None
+    bits = cast.dtype.bits
+    srcs = []
+    for operand in (op.lhs, op.rhs):
+        inner = unwrap_operand(operand)
+        if isinstance(inner, Const) and inner.dtype.bits == bits:
+            srcs.append(_retyped(inner, cast.dtype))
+        elif isinstance(inner, Cast) and inner.opcode == Cast.BITCAST:
+            srcs.append(unwrap_operand(inner.x))
+        else:
+            return None
+    if any(i.dtype != cast.dtype for i in srcs) or op.dtype.bits != bits:
+        return None
+    # A bitwise operation is typed as bits, whatever its operands
+    if op.opcode not in (BinaryOp.ADD, BinaryOp.SUB) and cast.dtype != Bits(bits):
+        return None
+    return op, srcs
+
+
+def simplify_module_casts(module, exposed=()) -> List[Expr]:
+    '''Remove the casts of `module` that cancel out, and return the removed expressions.
+
+    A bitcast of a bitcast back to the original type is replaced by the original value, and a
+    chain of width-preserving bitcasts, zexts or sexts is collapsed into its last cast. A
+    sign-agnostic operation on bitcast values, bitcast back to the type of these values, is
+    done on the values themselves. A cast or an operation with other users is kept, as a
+    signed compare or shift may depend on its type.'''
+    res = []
+    # Collected first, as replaced casts are erased from the body
+    for expr in list(module.body):
+        if not isinstance(expr, Cast) or expr.parent is not module:
+            continue
+        inner = unwrap_operand(expr.x)
+        src = _chained(expr)
+        if src is not None:
+            if src.dtype == expr.dtype and isinstance(src, Expr) and src.parent is module \
+                    and not _used_externally(expr, module, exposed):
+                replace_expr(expr, src)
+                res.append(expr)
+            else:
+                replace_operand(expr, 0, src)
+            _erase_if_dead(inner, module, exposed, res)
+            continue
+        trip = _round_trip(expr, module, exposed)
+        if trip is None or _used_externally(expr, module, exposed):
+            continue
+        op, srcs = trip
+        casts = [unwrap_operand(op.lhs), unwrap_operand(op.rhs)]  # Constants are skipped
+        for idx, src in enumerate(srcs):
+            replace_operand(op, idx, src)
+        replace_expr(expr, op)
+        res.append(expr)
+        for cast in casts:
+            _erase_if_dead(cast, module, exposed, res)
+    return res
+
+
+def simplify_casts(sys) -> List[Expr]:
+    '''Simplify the casts of all the modules of `sys`.
+
+    Returns the removed expressions.'''
+    res = []
+    for module in sys.modules + sys.downstreams:
+        if module.body:
+            res.extend(simplify_module_casts(module, sys.exposed_nodes))
+    return res
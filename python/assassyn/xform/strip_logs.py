@@ -0,0 +1,20 @@
+"""Removal of the `log` expressions of a system."""
+
+from __future__ import annotations
+
+from ..ir.expr import Log
+from ..ir.visitor import erase_expr
+
+
+def strip_logs(sys) -> int:
+    '''Erase every `log` of the modules and downstreams of `sys`.
+
+    Logs have side effects, so no other pass drops them, but they only matter to simulation:
+    in Verilog, each logged value becomes an exposed port of its module. Returns the number
+    of erased logs.'''
+    res = 0
+    for module in sys.modules + sys.downstreams:
+        for expr in [e for e in module.body or [] if isinstance(e, Log)]:
+            erase_expr(expr)
+            res += 1
+    return res
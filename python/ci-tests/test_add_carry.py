@@ -0,0 +1,56 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+MULTIPLIERS = [0x9E3779B1, 0x85EBCA77, 0xC2B2AE3D, 0x27D4EB2F]
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+
+        x = cnt[0]
+        a = [(x * UInt(32)(m))[0:31].bitcast(UInt(32)) for m in MULTIPLIERS]
+        y = x + UInt(32)(7)
+        b = [(y * UInt(32)(m))[0:31].bitcast(UInt(32)) for m in MULTIPLIERS]
+        # All ones in the second limb, so that a carry out of the first ripples through it
+        b[1] = (~a[1]).bitcast(UInt(32))
+
+        carry = Bits(1)(0)
+        limbs = []
+        for lhs, rhs in zip(a, b):
+            partial = lhs.addc(rhs)
+            total = partial.sum().addc(carry)
+            limbs.append(total.sum())
+            carry = partial.carry() | total.carry()
+
+        ripple = concat(carry, limbs[3], limbs[2], limbs[1], limbs[0])
+        wide_a = concat(a[3], a[2], a[1], a[0])
+        wide_b = concat(b[3], b[2], b[1], b[0])
+        expected = wide_a.addc(wide_b)
+        log("addc: {} {} {} {}", wide_a, wide_b, ripple, expected)
+
+
+def check(raw):
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'addc:' not in line:
+            continue
+        a, b, ripple, expected = [int(i) for i in line.split('addc:')[1].split()]
+        assert ripple == expected == a + b, line
+        cnt += 1
+    assert cnt == 100, f'cnt: {cnt} != 100'
+
+
+def test_add_carry():
+    run_test('add_carry', top=lambda: Driver().build(), checker=check)
+
+
+if __name__ == '__main__':
+    test_add_carry()
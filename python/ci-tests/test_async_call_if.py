@@ -0,0 +1,50 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Adder(Module):
+
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(32)), 'b': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        log('add {} {} {}', a, b, a + b)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, adder: Adder):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        # Only every third cycle calls the adder
+        adder.async_called_if(cnt[0] % UInt(32)(3) == UInt(32)(0), a=cnt[0], b=cnt[0])
+
+
+def check(raw):
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'add ' not in line:
+            continue
+        a, b, c = [int(i) for i in line.split('add ')[1].split()]
+        assert a % 3 == 0 and a == b and c == a + b, line
+        cnt += 1
+    assert cnt >= 32, f'{cnt} < 32'
+
+
+def test_async_call_if():
+    def top():
+        adder = Adder()
+        adder.build()
+        Driver().build(adder)
+
+    run_test('async_call_if', top, check)
+
+
+if __name__ == '__main__':
+    test_async_call_if()
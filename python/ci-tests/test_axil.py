@@ -0,0 +1,60 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, cnt, ctrl):
+        v = cnt[0]
+        cnt[0] = v + UInt(32)(1)
+        log("cnt: {} ctrl: {}", v, ctrl[1])
+
+
+def top(sys):
+    cnt = RegArray(UInt(32), 1)
+    ctrl = RegArray(UInt(8), 2)
+    Driver().build(cnt, ctrl)
+    # cnt is read-only at 0x0, ctrl read-write at 0x8
+    sys.expose_on_top(cnt, kind='Output')
+    sys.expose_on_top(ctrl, kind='Inout')
+
+
+TRANSACTIONS = [
+    ('write', 0xc, 0x5a),
+    ('read', 0xc),
+    ('read', 0x0),
+    ('write', 0x0, 0x1),
+    ('write', 0xc, 0x1234, 0x2),
+    ('read', 0xc),
+]
+
+
+def check(raw):
+    axil = [line.split() for line in raw.split('\n') if line.startswith('[axil]')]
+    if not axil:
+        # The simulator has no bus, ctrl keeps its reset value
+        assert 'ctrl: 0' in raw
+        return
+    ops = [(toks[1], int(toks[2], 16), int(toks[3], 16), int(toks[5])) for toks in axil]
+    assert ops[0] == ('write', 0xc, 0x5a, 0)
+    assert ops[1] == ('read', 0xc, 0x5a, 0)
+    # The counter is read, but not written
+    assert ops[2][3] == 0 and ops[2][2] > 0
+    assert ops[3] == ('write', 0x0, 0x1, 2)
+    # The second byte is not in ctrl, which keeps the first
+    assert ops[4][3] == 0
+    assert ops[5] == ('read', 0xc, 0x5a, 0)
+    assert 'ctrl: 90' in raw
+
+
+def test_axil():
+    run_test('axil', top, check, sim_threshold=60, idle_threshold=60, axi_lite=True,
+             axil_transactions=TRANSACTIONS)
+
+
+if __name__ == '__main__':
+    test_axil()
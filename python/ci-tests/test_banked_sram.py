@@ -0,0 +1,59 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        c = cnt[0]
+        (cnt & self)[0] <= c + UInt(32)(1)
+        k = Bits(1)(0).concat(c[0:3])
+        phase = c[4:5]
+        write = phase == Bits(2)(0)
+        read = (phase == Bits(2)(1)) | (phase == Bits(2)(2))
+        even, odd = k.concat(Bits(1)(0)), k.concat(Bits(1)(1))
+        # Phase 2: both ports read bank 0, so only port 0 is served
+        other = (~k).concat(Bits(1)(0))
+        addr0 = (phase == Bits(2)(1)).select(odd, even)
+        addr1 = (phase == Bits(2)(0)).select(odd, (phase == Bits(2)(1)).select(even, other))
+        wdata = [(a.bitcast(UInt(6)).zext(UInt(32)) + UInt(32)(1000)).bitcast(Bits(32))
+                 for a in (addr0, addr1)]
+        mem = BankedSRAM(32, 64, read_ports=2, write_ports=2)
+        grants = mem.build([write, write], [read, read], [addr0, addr1], wdata)
+        log('mem {} {} {} {} {}', c, grants[0], grants[1], mem.rdata(0), mem.rdata(1))
+
+
+def check(raw):
+    reads = 0
+    for line in raw.split('\n'):
+        if 'mem ' not in line:
+            continue
+        c, grant0, grant1, rdata0, rdata1 = [int(i) for i in line.split('mem ')[1].split()]
+        if c > 64:
+            break
+        phase = (c >> 4) & 3
+        if phase != 3:
+            assert grant0 == 1, line
+            assert grant1 == (phase != 2), line
+        prev_phase, prev_k = ((c - 1) >> 4) & 3, (c - 1) & 15
+        if c == 0 or prev_phase not in (1, 2):
+            continue
+        if prev_phase == 1:
+            assert (rdata0, rdata1) == (2 * prev_k + 1001, 2 * prev_k + 1000), line
+        else:
+            assert rdata0 == 2 * prev_k + 1000, line
+        reads += 1
+    assert reads == 32, f'{reads} != 32'
+
+
+def test_banked_sram():
+    run_test('banked_sram', top=lambda: Driver().build(), checker=check)
+
+
+if __name__ == '__main__':
+    test_banked_sram()
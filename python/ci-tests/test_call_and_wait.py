@@ -0,0 +1,72 @@
+import re
+
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Compute(Module):
+
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        x = self.pop_all_ports(True)
+        result = x + x + x
+        log('computed: {}', result)
+        self.respond(result)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, compute: Compute):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        result = compute.call_and_wait(x=cnt[0])
+        log('received: {}', result)
+
+
+def top():
+    compute = Compute()
+    compute.build()
+    Driver().build(compute)
+
+
+def _cycles(raw, tag):
+    res = {}
+    for line in raw.splitlines():
+        if f'{tag}:' in line:
+            res[int(re.search(r'Cycle @(\d+)', line).group(1))] = int(line.split()[-1])
+    return res
+
+
+def check(raw):
+    computed, received = _cycles(raw, 'computed'), _cycles(raw, 'received')
+    assert len(received) > 40, raw
+    # Each result is consumed by the driver in the cycle after the compute module produced it
+    for cycle, value in received.items():
+        assert computed[cycle - 1] == value, (cycle, value, computed.get(cycle - 1))
+    assert len(received) >= len(computed) - 1, (computed, received)
+    assert [received[i] for i in sorted(received)][:3] == [0, 3, 6]
+
+
+def test_call_and_wait():
+    run_test('call_and_wait', top, check, sim_threshold=50, idle_threshold=50)
+
+
+def test_unclaimed_response():
+    try:
+        run_test('call_unclaimed', lambda: Compute().build(), lambda raw: None)
+    except ValueError as err:
+        assert 'no module waits' in str(err), err
+    else:
+        raise AssertionError('an unclaimed response is accepted')
+
+
+if __name__ == '__main__':
+    test_call_and_wait()
+    test_unclaimed_response()
@@ -0,0 +1,50 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Consumer(Module):
+
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(32))}, clock_domain='slow')
+
+    @module.combinational
+    def build(self):
+        data = self.pop_all_ports(True)
+        log("consumer: {}", data)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, consumer: Consumer):
+        cnt = RegArray(UInt(32), 1)
+        v = cnt[0]
+        cnt[0] = v + UInt(32)(1)
+        # One value per cycle of the consumer, whose clock is twice slower
+        with Condition(v[0:0] == UInt(1)(0)):
+            consumer.async_called(data=v)
+
+
+def top():
+    consumer = Consumer()
+    consumer.build()
+    Driver().build(consumer)
+
+
+def check(raw):
+    values = [int(line.split()[-1]) for line in raw.split('\n') if 'consumer:' in line]
+    # No value is lost or repeated across the domains
+    assert values == list(range(0, 2 * len(values), 2)), values
+    assert len(values) > 10
+
+
+def test_clock_domain():
+    run_test('clock_domain', top, check, sim_threshold=40, idle_threshold=40,
+             clock_domains={'slow': 2})
+
+
+if __name__ == '__main__':
+    test_clock_domain()
@@ -0,0 +1,62 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Writeback(Module):
+
+    def __init__(self):
+        super().__init__(ports={'rd': Port(UInt(2)), 'data': Port(UInt(16))})
+
+    @module.combinational
+    def build(self, rf: Array):
+        rd, data = self.pop_all_ports(True)
+        # The value the register had, and the one it gets
+        log('wb: {} {} {}', rd, rf[rd], data)
+        rf[rd] = data
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, wb: Writeback, copy: Writeback):
+        cnt = RegArray(UInt(16), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(16)(1)
+        rd = cnt[0][0:1].bitcast(UInt(2))
+        wb.async_called(rd=rd, data=cnt[0])
+        # Same registers, other values, so that a shared register file would mix them up
+        copy.async_called(rd=rd, data=cnt[0] + UInt(16)(1000))
+
+
+def top(sys):
+    rf, other = RegArray(UInt(16), 4, name='rf'), RegArray(UInt(16), 4, name='rf_copy')
+    wb = Writeback()
+    wb.build(rf)
+    copy = sys.clone_module(wb, 'WritebackCopy', {rf: other})
+    Driver().build(wb, copy)
+
+
+def check(raw):
+    written = {'wb': [], 'copy': []}
+    for line in raw.splitlines():
+        if 'wb:' in line:
+            module = 'copy' if '[WritebackCopy]' in line else 'wb'
+            written[module].append(tuple(int(i) for i in line.split()[-3:]))
+    for module, writes in written.items():
+        assert len(writes) > 10, raw
+        # Each copy reads back what it wrote itself, and nothing of the other
+        regs = [0] * 4
+        for rd, old, data in writes:
+            assert old == regs[rd], (module, rd, old, regs)
+            regs[rd] = data
+        assert all((data >= 1000) == (module == 'copy') for _, _, data in writes)
+
+
+def test_clone_module():
+    run_test('clone_module', top, check, sim_threshold=50, idle_threshold=50)
+
+
+if __name__ == '__main__':
+    test_clone_module()
@@ -0,0 +1,52 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(8), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+
+        # -1 - cnt, negative in every cycle
+        neg8 = (UInt(8)(255) - cnt[0]).bitcast(Int(8))
+        neg32 = neg8.sext(Int(32))
+        neg72 = neg8.sext(Int(72))
+        lo = concat(neg8, cnt[0])
+        hi = concat(cnt[0], neg32)
+        wide = concat(neg72, cnt[0])
+        # Bits of more than 64 bits with the MSB set read back as negative values
+        swide = wide.bitcast(Int(80))
+        sext96 = wide.sext(Int(96))
+        uwide = swide.bitcast(UInt(80))
+        log("sconcat: {} {} {} {} {} {} {} {}", cnt[0], neg8, lo, hi, wide, swide, sext96, uwide)
+
+
+def check(raw):
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'sconcat:' not in line:
+            continue
+        x, neg, lo, hi, wide, swide, sext96, uwide = \
+            [int(i) for i in line.split('sconcat:')[1].split()]
+        assert neg == -1 - x, line
+        # The operands contribute their two's-complement bits, as {a, b} does in Verilog
+        assert lo == ((neg & 0xff) << 8) | x, line
+        assert hi == (x << 32) | (neg & 0xffffffff), line
+        assert wide == ((neg & ((1 << 72) - 1)) << 8) | x, line
+        assert swide == sext96 == wide - (1 << 80), line
+        assert uwide == wide, line
+        cnt += 1
+    assert cnt == 100, f'cnt: {cnt} != 100'
+
+
+def test_concat_signed():
+    run_test('concat_signed', top=lambda: Driver().build(), checker=check)
+
+
+if __name__ == '__main__':
+    test_concat_signed()
@@ -0,0 +1,55 @@
+from assassyn.frontend import *
+from assassyn.cosim import elaborate_for_sim, run_one_cycle, run_until_idle
+
+
+class Sink(Module):
+
+    def __init__(self):
+        super().__init__(ports={'c': Port(UInt(32))})
+
+
+class Adder(Module):
+
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(32)), 'b': Port(UInt(32))})
+
+    @module.combinational
+    def build(self, sink: Sink, last: Array):
+        a, b = self.pop_all_ports(True)
+        c = a + b
+        log("add: {} + {} = {}", a, b, c)
+        last[0] = c
+        sink.async_called(c=c)
+
+
+def test_cosim():
+    sys = SysBuilder('cosim')
+    with sys:
+        sink = Sink()
+        adder = Adder()
+        last = RegArray(UInt(32), 1)
+        adder.build(sink, last)
+
+    with elaborate_for_sim(sys) as handle:
+        assert handle.pop_from_fifo(sink.name, 'c') is None
+
+        handle.push_to_fifo(adder.name, 'a', 3)
+        handle.push_to_fifo(adder.name, 'b', 4)
+        assert run_one_cycle(handle) == 1
+        assert any('add: 3 + 4 = 7' in line for line in handle.logs)
+        assert handle.pop_from_fifo(sink.name, 'c') == 7
+        assert handle.read_array(last, 0) == 7
+        handle.write_array(last, 0, 42)
+        assert handle.read_array(last, 0) == 42
+
+        for i in range(3):
+            handle.push_to_fifo(adder.name, 'a', i)
+            handle.push_to_fifo(adder.name, 'b', 10)
+        cycles = run_until_idle(handle, 100)
+        assert cycles < 100, cycles
+        assert [handle.pop_from_fifo(sink.name, 'c') for _ in range(4)] == [10, 11, 12, None]
+        assert handle.read_array(last, 0) == 12
+
+
+if __name__ == '__main__':
+    test_cosim()
@@ -0,0 +1,92 @@
+from unittest import mock
+
+from assassyn.frontend import *
+from assassyn.backend import elaborate
+from assassyn.codegen.simulator import _expr
+from assassyn.codegen.simulator._expr import arith
+from assassyn.cosim import compare_backends, compare_traces, parse_trace
+from assassyn import utils
+
+
+class Sink(Module):
+
+    def __init__(self):
+        super().__init__(ports={'flag': Port(Bits(1))})
+
+    @module.combinational
+    def build(self):
+        flag = self.flag.pop()
+        log('flag: {}', flag)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, sink: Sink):
+        cnt = RegArray(Int(8), 1, initializer=[-3])
+        (cnt & self)[0] <= cnt[0] + Int(8)(1)
+        # Only true for the first cycles, when compared as signed
+        flag = cnt[0] < Int(8)(1)
+        res = RegArray(Bits(1), 1, name='res')
+        res[0] = flag
+        sink.async_called(flag=flag)
+
+
+def top():
+    sys = SysBuilder('cosim_trace')
+    with sys:
+        sink = Sink()
+        sink.build()
+        Driver().build(sink)
+    return sys
+
+
+def _signed_as_unsigned(node, module_ctx):
+    # Compares the operands in the unsigned Rust type of their width
+    if not node.is_comparative():
+        return arith.codegen_binary_op(node, module_ctx)
+    real = arith.dtype_to_rust_type
+    with mock.patch.object(arith, 'dtype_to_rust_type', lambda d: real(d).replace('i', 'u')):
+        return arith.codegen_binary_op(node, module_ctx)
+
+
+def _trace(path, broken=False):
+    patch = {_expr.BinaryOp: _signed_as_unsigned} if broken else {}
+    with mock.patch.dict(_expr._EXPR_CODEGEN_DISPATCH, patch):
+        sim, _ = elaborate(top(), path=path, verbose=False, verilog=False, trace=True,
+                           sim_threshold=20, enable_cache=False)
+    return utils.run_simulator(sim)
+
+
+def test_detects_broken_signed_compare(tmp_path):
+    good = _trace(str(tmp_path / 'good'))
+    trace = parse_trace(good)
+    assert ('res[0]', '1') in trace[1] and ('SinkInstance.flag', '1') in trace[1], trace[1]
+    assert compare_traces(good, good).ok
+
+    # Stands for a backend getting the signedness of the comparison wrong
+    broken = _trace(str(tmp_path / 'broken'), broken=True)
+    report = compare_traces(good, broken)
+    assert not report.ok
+    assert report.cycles == 0
+    divergence = report.divergence
+    assert divergence.cycle == 1 and divergence.signal == 'SinkInstance.flag', divergence
+    assert divergence.simulator == ['1'] and divergence.verilog == ['0'], divergence
+
+
+def test_backends_agree():
+    if not utils.has_verilator():
+        return
+    report = compare_backends(top(), verbose=False, sim_threshold=20)
+    assert report.ok, report.divergence
+    assert report.cycles > 0
+
+
+if __name__ == '__main__':
+    import tempfile
+    from pathlib import Path
+    test_detects_broken_signed_compare(Path(tempfile.mkdtemp()))
+    test_backends_agree()
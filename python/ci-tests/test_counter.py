@@ -0,0 +1,41 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        _, cycle = create_counter(32)
+        # Only counts in the odd cycles
+        _, odd = create_counter_with_enable(32, 0, 1, cycle[0:0])
+        # 0, 3, 6, 9, 2, 5, ...
+        _, mod = create_counter_with_wrap(4, 0, 3, 9)
+        log('cycle: {} odd: {} mod: {}', cycle, odd, mod)
+
+
+def check(raw):
+    expected = 0
+    for i in raw.split('\n'):
+        if 'cycle:' in i:
+            toks = i.split()
+            assert int(toks[-5]) == expected, i
+            assert int(toks[-3]) == expected // 2, i
+            assert int(toks[-1]) == expected * 3 % 10, i
+            expected += 1
+    assert expected == 100, f'{expected} != 100'
+
+
+def test_counter():
+    def top():
+        driver = Driver()
+        driver.build()
+
+    run_test('counter', top, check)
+
+
+if __name__ == '__main__':
+    test_counter()
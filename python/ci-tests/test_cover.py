@@ -0,0 +1,87 @@
+import sys as py_sys
+from pathlib import Path
+
+from assassyn.frontend import *
+from assassyn.test import run_test
+from assassyn.analysis import cover_key, cover_points, uncovered_blocks
+from assassyn.analysis.pipeline import SimReport
+from assassyn.ir.expr import Log
+from assassyn.utils import repo_path
+
+py_sys.path.insert(0, str(Path(repo_path()) / 'examples' / 'minor-cpu' / 'src'))
+from decoder import decode_logic  # pylint: disable=wrong-import-position,import-error
+
+# addi x1, x0, 1; add x3, x1, x2; lw x4, 0(x1); beq x1, x2, 8
+PROGRAM = [0x00100093, 0x002081b3, 0x0000a203, 0x00208463]
+
+
+class Decoder(Module):
+
+    def __init__(self):
+        super().__init__(ports={'inst': Port(Bits(32))})
+
+    @module.combinational
+    def build(self):
+        inst = self.pop_all_ports(True)
+        decode_logic(inst)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, decoder: Decoder):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        program = RegArray(Bits(32), len(PROGRAM), initializer=PROGRAM)
+        decoder.async_called(inst=program[cnt[0][0:1]])
+
+
+built = {}
+
+
+def top(sys):
+    decoder = Decoder()
+    decoder.build()
+    driver = Driver()
+    driver.build(decoder)
+    built.update(sys=sys, decoder=decoder)
+
+
+def _unsupported_block(decoder):
+    '''The key of the block of `decode_logic` logging the unsupported instructions.'''
+    keys = {id(block): cover_key(decoder, field) for field, block in cover_points(decoder)}
+    block = None
+    for expr in decoder.body:
+        if id(expr) in keys:
+            block = expr
+        elif isinstance(expr, Log) and expr.fmt.startswith('Unsupported instruction'):
+            return keys[id(block)]
+    raise AssertionError('no unsupported instruction block')
+
+
+def check(raw):
+    report = SimReport.parse(raw)
+    sys, decoder = built['sys'], built['decoder']
+    blocks = {cover_key(decoder, field): block for field, block in cover_points(decoder)}
+    assert set(report.covered) >= set(blocks), report.covered
+    # Only supported instructions are decoded, so the unsupported branch is never taken
+    unsupported = _unsupported_block(decoder)
+    untaken = [key for key, _ in uncovered_blocks(sys, report)]
+    assert unsupported in untaken, untaken
+    assert f'[cover] untaken {unsupported} at {blocks[unsupported].loc}' in raw
+    # Each instruction of the program is decoded a quarter of the cycles
+    taken = [n for key, n in report.covered.items() if key in blocks and n]
+    assert len(taken) == len(PROGRAM), report.covered
+    assert max(taken) - min(taken) <= 1, taken
+
+
+def test_cover():
+    run_test('cover', top, check, sim_threshold=40, idle_threshold=40, cover=True,
+             verilog=False)
+
+
+if __name__ == '__main__':
+    test_cover()
@@ -0,0 +1,47 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+
+        vec = RegArray(UInt(32), 16, initializer=list(range(100, 116)), name='vec',
+                       attr=[CyclicPartition(4)])
+        idx = cnt[0][0:3]
+        (vec & self)[idx] <= cnt[0] + UInt(32)(1000)
+        # The four elements of the same row, one from each bank
+        row = idx[2:3]
+        lanes = [vec.bank(b)[row] for b in range(4)]
+        log('vec: {} {} {} {} {} {} {}', cnt[0], vec[idx], vec[5], *lanes)
+
+
+def check(raw):
+    mem = list(range(100, 116))
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'vec:' not in line:
+            continue
+        c, value, fifth, *lanes = [int(i) for i in line.split('vec:')[1].split()]
+        idx = c % 16
+        row = idx // 4
+        assert value == mem[idx], line
+        assert fifth == mem[5], line
+        assert lanes == mem[row * 4:row * 4 + 4], line
+        mem[idx] = c + 1000
+        cnt += 1
+    assert cnt == 100, f'cnt: {cnt} != 100'
+
+
+def test_cyclic_partition():
+    run_test('cyclic_partition', top=lambda: Driver().build(), checker=check)
+
+
+if __name__ == '__main__':
+    test_cyclic_partition()
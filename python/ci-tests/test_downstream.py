@@ -63,8 +63,29 @@ def build_system():
     driver.build(lhs, rhs)
     adder.build(a, b)
 
+def build_closure_system():
+    driver = Driver()
+    lhs = ForwardData()
+    rhs = ForwardData()
+    a = lhs.build()
+    b = rhs.build()
+
+    def adder(ctx):
+        x = ctx.use_value(a).optional(UInt(32)(1))
+        y = ctx.use_value(b).optional(UInt(32)(1))
+        z = x + y
+        log("downstream: {} + {} = {}", x, y, z)
+
+    create_downstream('Adder', adder)
+    driver.build(lhs, rhs)
+
 def test_downstream():
     run_test('downstream', build_system, check_raw, sim_threshold=100, idle_threshold=100)
 
+def test_downstream_closure():
+    run_test('downstream_closure', build_closure_system, check_raw,
+             sim_threshold=100, idle_threshold=100)
+
 if __name__ == '__main__':
     test_downstream()
+    test_downstream_closure()
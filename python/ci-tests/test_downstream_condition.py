@@ -0,0 +1,66 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Executor(Module):
+
+    def __init__(self):
+        super().__init__(ports={'inst': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        inst = self.pop_all_ports(True)
+        is_store = inst[0:1] == Bits(2)(0)
+        log('executed: {} {}', inst, is_store)
+        return inst, is_store
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, executor: Executor):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        executor.async_called(inst=cnt[0])
+
+
+def top():
+    executor = Executor()
+    inst, is_store = executor.build()
+    Driver().build(executor)
+
+    def scoreboard(ctx):
+        stores = RegArray(UInt(32), 1)
+        stores[0] = stores[0] + UInt(32)(1)
+        log('store: {} total {}', ctx.use_value(inst), stores[0])
+
+    # Only updated in the cycles the executor ran a store
+    create_downstream_with_condition('Scoreboard', is_store, scoreboard)
+
+
+def check(raw):
+    executed, stores = [], []
+    for line in raw.splitlines():
+        if 'executed:' in line:
+            inst, is_store = line.split()[-2:]
+            executed.append((int(inst), is_store in ('1', 'true')))
+        elif 'store:' in line:
+            toks = line.split()
+            stores.append((int(toks[-3]), int(toks[-1])))
+    assert len(executed) > 40, raw
+    expected = [inst for inst, is_store in executed if is_store]
+    assert all(inst % 4 == 0 for inst in expected), executed
+    # The scoreboard runs for every store and nothing else, counting them
+    assert [inst for inst, _ in stores] == expected, stores
+    assert [total for _, total in stores] == list(range(len(stores))), stores
+
+
+def test_downstream_condition():
+    run_test('downstream_condition', top, check, sim_threshold=50, idle_threshold=50)
+
+
+if __name__ == '__main__':
+    test_downstream_condition()
@@ -0,0 +1,70 @@
+import re
+
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Counter(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, step: int):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(step)
+        return cnt[0]
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, lhs: Counter, rhs: Counter):
+        lhs.async_called()
+        rhs.async_called()
+
+
+class Adder(Downstream):
+
+    def __init__(self):
+        super().__init__()
+
+    @downstream.combinational
+    def build(self, a: Value, b: Value):
+        a = a.optional(UInt(32)(0))
+        b = b.optional(UInt(32)(0))
+        log('sum: {} + {} = {}', a, b, a + b)
+
+
+def top():
+    lhs, rhs = Counter(), Counter()
+    a = lhs.build(1)
+    b = rhs.build(2)
+    Driver().build(lhs, rhs)
+    Adder().build(a, b)
+
+
+def check(raw):
+    sums = []
+    for line in raw.splitlines():
+        if 'sum:' in line:
+            cycle = int(re.search(r'Cycle @(\d+)', line).group(1))
+            a, b, c = (int(i) for i in line.split()[-5::2])
+            sums.append((cycle, a, b, c))
+    assert len(sums) >= 90, raw
+    first = sums[0][0]
+    for idx, (cycle, a, b, c) in enumerate(sums):
+        # Evaluated in every cycle, after both counters ran in the same cycle
+        assert cycle == first + idx, sums[idx - 1:idx + 1]
+        assert (a, b, c) == (idx, 2 * idx, 3 * idx), (cycle, a, b, c)
+
+
+def test_downstream_counters():
+    run_test('downstream_counters', top, check, sim_threshold=100, idle_threshold=100)
+
+
+if __name__ == '__main__':
+    test_downstream_counters()
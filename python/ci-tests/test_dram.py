@@ -49,7 +49,7 @@ class HandleResponse(Downstream):
         with Condition(has_mem_resp(dram) & read_succ):
             resp = get_mem_resp(dram)
             addr = resp[0:9].bitcast(Int(9))
-            data = resp[9:41]
+            data = resp[9:31]
             log('Read data: {} @{}', resp, addr)
 
 
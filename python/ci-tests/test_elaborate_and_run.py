@@ -0,0 +1,55 @@
+from assassyn.frontend import *
+from assassyn.backend import elaborate_and_run
+
+
+class Adder(Module):
+
+    def __init__(self):
+        super().__init__(
+            ports={
+                "a": Port(Int(32)),
+                "b": Port(Int(32)),
+            },
+        )
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        c = a + b
+        log("Adder: {} + {} = {}", a, b, c)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, adder: Adder):
+        cnt = RegArray(Int(32), 1)
+        (cnt & self)[0] <= cnt[0] + Int(32)(1)
+        with Condition(cnt[0] < Int(32)(50)):
+            adder.async_called(a=cnt[0], b=cnt[0])
+
+
+def test_elaborate_and_run(tmp_path):
+    sys = SysBuilder("elaborate_and_run")
+    with sys:
+        adder = Adder()
+        adder.build()
+        Driver().build(adder)
+
+    out = elaborate_and_run(sys, target_dir=tmp_path / "target", path=str(tmp_path),
+                            verbose=False, sim_threshold=100, sim_stats=True)
+
+    sums = [i for i in out.log.splitlines() if "Adder:" in i]
+    assert len(sums) == 50, out.log
+    assert sums[-1].endswith("49 + 49 = 98")
+    assert out.report.cycles == 100, out.report
+    assert out.report.executed[adder.name] == 50, out.report
+
+
+if __name__ == "__main__":
+    import tempfile
+    from pathlib import Path
+    test_elaborate_and_run(Path(tempfile.mkdtemp()))
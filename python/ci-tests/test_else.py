@@ -0,0 +1,41 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        c = cnt[0]
+        (cnt & self)[0] <= c + UInt(32)(1)
+        then, otherwise = ConditionWithElse(c[0:0])
+        with then:
+            log('odd {}', c)
+        with otherwise:
+            with Condition(c[1:1]):
+                log('two {}', c)
+            with Else():
+                log('four {}', c)
+
+
+def check(raw):
+    seen = 0
+    for line in raw.split('\n'):
+        for tag, rem in (('odd', (1, 3)), ('two', (2,)), ('four', (0,))):
+            if f'{tag} ' in line:
+                c = int(line.split(f'{tag} ')[1])
+                assert c % 4 in rem, line
+                seen += 1
+    assert seen == 100, f'{seen} != 100'
+
+
+def test_else():
+    run_test('else', top=lambda: Driver().build(), checker=check)
+
+
+if __name__ == '__main__':
+    test_else()
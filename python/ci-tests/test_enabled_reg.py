@@ -0,0 +1,47 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, held: Array):
+        cnt = RegArray(UInt(32), 1)
+        v = cnt[0]
+        cnt[0] = v + UInt(32)(1)
+        en = v[0:1] == UInt(2)(0)
+        held.write_if(en, v)
+        log("cnt: {} en: {} held: {}", v, en, held[0])
+
+
+def top(sys):
+    held = sys.create_enabled_reg(UInt(32), 'held', 7)
+    Driver().build(held)
+
+
+def check(raw):
+    expected = 7
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'cnt:' not in line:
+            continue
+        toks = line.split()
+        cnt = int(toks[-5])
+        en = int(toks[-3])
+        held = int(toks[-1])
+        assert held == expected, line
+        # Only enabled cycles load the register, all others hold it.
+        if en:
+            expected = cnt
+    assert cnt > 8
+
+
+def test_enabled_reg():
+    run_test('enabled_reg', top, check, sim_threshold=40, idle_threshold=40)
+
+
+if __name__ == '__main__':
+    test_enabled_reg()
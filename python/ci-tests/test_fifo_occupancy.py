@@ -0,0 +1,62 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+from assassyn.codegen.simulator.occupancy import OCCUPANCY_MSG
+
+
+class Adder(Module):
+
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(32)), 'b': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        # Pop every other cycle, so that the FIFOs fill up
+        slow = RegArray(Bits(1), 1)
+        (slow & self)[0] <= ~slow[0]
+        wait_until(slow[0])
+        a, b = self.pop_all_ports(False)
+        log('sum: {}', a + b)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, adder: Adder):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        with Condition(cnt[0] < UInt(32)(6)):
+            adder.async_called(a=cnt[0], b=cnt[0])
+
+
+def check(raw):
+    lines = [line for line in raw.split('\n') if OCCUPANCY_MSG in line]
+    assert len(lines) == 1, lines
+    path = lines[0].split(OCCUPANCY_MSG)[1].strip()
+    with open(path, encoding='utf-8') as f:
+        header, *rows = f.read().strip().split('\n')
+    columns = header.split(',')
+    assert columns[0] == 'cycle' and len(columns) == 3, header
+    assert all(i.endswith('_a') or i.endswith('_b') for i in columns[1:]), header
+    rows = [[int(i) for i in row.split(',')] for row in rows]
+    # One row per simulated cycle
+    assert [row[0] for row in rows] == list(range(1, 21)), rows
+    assert max(row[1] for row in rows) > 1, rows
+    assert all(row[1] == row[2] for row in rows), rows
+    assert rows[-1][1:] == [0, 0], rows
+
+
+def test_fifo_occupancy():
+    def top():
+        adder = Adder()
+        adder.build()
+        Driver().build(adder)
+
+    run_test('fifo_occupancy', top, check, fifo_occupancy_csv=True, sim_threshold=20,
+             idle_threshold=20)
+
+
+if __name__ == '__main__':
+    test_fifo_occupancy()
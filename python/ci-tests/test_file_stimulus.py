@@ -0,0 +1,50 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+from assassyn import utils
+from assassyn.ip.stimulus import file_stimulus
+
+
+class Consumer(Module):
+
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        data = self.pop_all_ports(True)
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        log('consumed {} {}', cnt[0] + UInt(32)(1), data)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, consumer: Consumer):
+        path = f'{utils.repo_path()}/python/ci-tests/resources/stimulus_16.hex'
+        file_stimulus(consumer.data, path, finish_at_end=True)
+
+
+def check(raw):
+    pops = []
+    for line in raw.split('\n'):
+        if 'consumed' in line:
+            pops.append([int(i) for i in line.split('consumed')[1].split()])
+    assert [cnt for cnt, _ in pops] == list(range(1, 17)), pops
+    assert [data for _, data in pops] == list(range(16, 32)), pops
+
+
+def test_file_stimulus():
+    def top():
+        consumer = Consumer()
+        consumer.build()
+        Driver().build(consumer)
+
+    run_test('file_stimulus', top, check)
+
+
+if __name__ == '__main__':
+    test_file_stimulus()
@@ -0,0 +1,48 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+Q44 = Fixed(4, 4)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        # Sweep all the Q4.4 values, from 0 to 7.9375 and then from -8 up
+        a = Q44.view(cnt[0][0:7])
+        b = Q44(-1.5)
+        prod = a * b
+        total = a.fadd(Q44(2.25))
+        log('fixed {} {} {}', a.value(), prod.value(), total.value())
+
+
+def wrap(raw):
+    return (raw + 128) % 256 - 128
+
+
+def check(raw):
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'fixed' not in line:
+            continue
+        a, prod, total = [int(i) for i in line.split('fixed')[1].split()]
+        assert a == wrap(cnt), line
+        # -1.5 is -24 in Q4.4; the product has 8 fractional bits, shifted back by 4
+        assert prod == wrap((a * -24) >> 4), line
+        # 2.25 is 36 in Q4.4
+        assert total == wrap(a + 36), line
+        cnt += 1
+    assert cnt == 100, cnt
+
+
+def test_fixed():
+    run_test('fixed', top=lambda: Driver().build(), checker=check)
+
+
+if __name__ == '__main__':
+    test_fixed()
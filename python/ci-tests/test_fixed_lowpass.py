@@ -0,0 +1,59 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+Q88 = Fixed(8, 8)
+ALPHA = 0.125
+THRESHOLD = 100
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        state = RegArray(Q88.raw_type(), 1)
+        # A sawtooth from 0 to 15, smoothed by y += alpha * (x - y)
+        x = to_fixed(cnt[0][0:3].bitcast(UInt(4)), Q88)
+        y = Q88.view(state[0])
+        step = (x - y).fmul_full(Q88(ALPHA)).cast(Q88)
+        (state & self)[0] <= (y + step).value()
+        log('lpf {} {} {}', cnt[0], x, y)
+
+
+def golden():
+    '''The output of the filter in floating point, by cycle'''
+    res, y = [], 0.0
+    for i in range(THRESHOLD):
+        res.append(y)
+        y += ALPHA * ((i % 16) - y)
+    return res
+
+
+def check(raw):
+    expected = golden()
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'lpf' not in line:
+            continue
+        i, x, y = line.split('lpf')[1].split()
+        i = int(i)
+        # Printed with as many fractional digits as fractional bits
+        assert x == f'{i % 16:.8f}', line
+        assert len(y.split('.')[1]) == 8, line
+        # Each step rounds down by less than 1/256, which the filter scales by 1/alpha
+        assert abs(float(y) - expected[i]) < 1 / 32, line
+        cnt += 1
+    assert cnt == THRESHOLD, cnt
+
+
+def test_fixed_lowpass():
+    run_test('fixed_lowpass', top=lambda: Driver().build(), checker=check,
+             sim_threshold=THRESHOLD)
+
+
+if __name__ == '__main__':
+    test_fixed_lowpass()
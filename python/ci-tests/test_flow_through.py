@@ -0,0 +1,64 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+from assassyn.ir.expr.intrinsic import current_cycle
+
+
+class Consumer(Module):
+
+    def __init__(self):
+        super().__init__(ports={
+            'tick': Port(Bits(1)),
+            'data': Port(UInt(32)),
+        }, flow_through=True)
+
+    @module.combinational
+    def build(self):
+        self.tick.pop()
+        with Condition(self.data.valid()):
+            data = self.data.pop()
+            log('consumed {} {}', data, current_cycle())
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, consumer: Consumer):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        # Keeps the consumer active in every cycle
+        consumer.async_called(tick=Bits(1)(1))
+        # From cycle 2 on, the consumer is active and drains the FIFO in every cycle,
+        # so the FIFO is always empty when pushed
+        with Condition(cnt[0][0:0] == Bits(1)(1)):
+            consumer.data.push(cnt[0])
+            log('pushed {} {}', cnt[0], current_cycle())
+
+
+def check(raw):
+    pushed = {}
+    consumed = {}
+    for line in raw.split('\n'):
+        for tag, record in (('pushed', pushed), ('consumed', consumed)):
+            if f'{tag} ' in line:
+                value, cycle = [int(i) for i in line.split(f'{tag} ')[1].split()]
+                record[value] = cycle
+    assert len(consumed) == 50, consumed
+    for value, cycle in consumed.items():
+        # Zero latency: the value is popped in the cycle it is pushed
+        assert pushed[value] == cycle, (value, pushed[value], cycle)
+
+
+def test_flow_through():
+    def top():
+        consumer = Consumer()
+        consumer.build()
+        Driver().build(consumer)
+
+    run_test('flow_through', top, check)
+
+
+if __name__ == '__main__':
+    test_flow_through()
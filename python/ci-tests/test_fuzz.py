@@ -0,0 +1,173 @@
+import os
+import re
+
+from assassyn.frontend import *
+from assassyn.backend import FuzzConfig, elaborate
+from assassyn.codegen.simulator.fuzz import FUZZ_SEED_ENV
+from assassyn import utils
+
+SEEDS = range(8)
+
+
+class Sink(Module):
+
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        data = self.pop_all_ports(True)
+        log('sink: {}', data)
+
+
+class Stage(Module):
+
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(32))})
+
+    @module.combinational
+    def build(self, sink: Sink):
+        data = self.pop_all_ports(True)
+        sink.async_called(data=data + data + data)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, stage: Stage):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        # Calls one cycle out of two, so that the postponed activations catch up
+        with Condition(cnt[0][0:0] == Bits(1)(0)):
+            stage.async_called(data=cnt[0])
+
+
+class Forward(Module):
+
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        return self.pop_all_ports(True)
+
+
+class Adder(Downstream):
+
+    def __init__(self):
+        super().__init__()
+
+    @downstream.combinational
+    def build(self, a: Value, b: Value):
+        a = a.optional(UInt(32)(0))
+        b = b.optional(UInt(32)(0))
+        log('sum: {} + {} = {}', a, b, a + b)
+
+
+class Feeder(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+        # Activated every cycle, as the driver of its system
+        self.name = 'Driver'
+
+    @module.combinational
+    def build(self, lhs: Forward, rhs: Forward):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        lhs.async_called(data=cnt[0])
+        rhs.async_called(data=cnt[0] + cnt[0])
+
+
+def _build(sys, fuzz):
+    sim, _ = elaborate(sys, verbose=False, verilog=False, sim_threshold=60, idle_threshold=60,
+                       enable_cache=False, fuzz=fuzz)
+    return utils.build_simulator(sim)
+
+
+def linked_top(fuzz):
+    sys = SysBuilder('fuzz_linked' if fuzz else 'fuzz_linked_nominal')
+    with sys:
+        lhs = Forward()
+        rhs = Forward()
+        Feeder().build(lhs, rhs)
+        Adder().build(lhs.build(), rhs.build())
+    return _build(sys, fuzz)
+
+
+def top(fuzz):
+    sys = SysBuilder('fuzz' if fuzz else 'fuzz_nominal')
+    with sys:
+        sink = Sink()
+        sink.build()
+        stage = Stage()
+        stage.build(sink)
+        Driver().build(stage)
+    return _build(sys, fuzz)
+
+
+def _received(raw):
+    '''The (cycle, value) of the sink logs.'''
+    return [(int(re.search(r'Cycle @(\d+)', line).group(1)), int(line.split()[-1]))
+            for line in raw.splitlines() if 'sink:' in line]
+
+
+def test_fuzz_keeps_values():
+    nominal = _received(utils.run_simulator(binary_path=top(None)))
+    assert nominal, 'nothing received'
+    binary = top(FuzzConfig(seed=0, delay=0.3, reorder=0.5))
+    varied = 0
+    for seed in SEEDS:
+        os.environ[FUZZ_SEED_ENV] = str(seed)
+        try:
+            received = _received(utils.run_simulator(binary_path=binary))
+        finally:
+            del os.environ[FUZZ_SEED_ENV]
+        # The values arrive in the same order, only later
+        values = [value for _, value in received]
+        assert values == [value for _, value in nominal][:len(values)], (seed, received)
+        assert len(values) >= len(nominal) - 4, (seed, received)
+        assert all(cycle >= ref for (cycle, _), (ref, _) in zip(received, nominal))
+        varied += received != nominal
+        # The seed makes a run reproducible
+        if seed == 0:
+            os.environ[FUZZ_SEED_ENV] = '0'
+            try:
+                assert _received(utils.run_simulator(binary_path=binary)) == received
+            finally:
+                del os.environ[FUZZ_SEED_ENV]
+    assert varied, 'no seed changed the timing'
+
+
+def test_fuzz_keeps_linked_values():
+    # The forwards produce the values the adder reads in the same cycle, so the fuzzer must
+    # neither postpone nor reorder them: the sums are those of the nominal run
+    nominal = [line for line in utils.run_simulator(binary_path=linked_top(None)).splitlines()
+               if 'sum:' in line]
+    assert nominal, 'nothing summed'
+    binary = linked_top(FuzzConfig(seed=0, delay=0.5, reorder=0.5))
+    for seed in SEEDS:
+        os.environ[FUZZ_SEED_ENV] = str(seed)
+        try:
+            raw = utils.run_simulator(binary_path=binary)
+        finally:
+            del os.environ[FUZZ_SEED_ENV]
+        assert [line for line in raw.splitlines() if 'sum:' in line] == nominal, seed
+
+
+def test_fuzz_config():
+    for bad in ({'delay': 1.5}, {'reorder': -0.1}, {'seed': -1}, {'delay': True}):
+        try:
+            FuzzConfig(**bad)
+        except ValueError:
+            continue
+        raise AssertionError(f'{bad} accepted')
+
+
+if __name__ == '__main__':
+    test_fuzz_keeps_values()
+    test_fuzz_keeps_linked_values()
+    test_fuzz_config()
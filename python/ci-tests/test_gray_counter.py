@@ -0,0 +1,66 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        _, cycle = create_counter(32)
+        ring = RegArray(UInt(32), 8)
+
+        # The writer pushes the cycle every other cycle, keeping its pointer in Gray code
+        push = cycle[0:0] == UInt(1)(0)
+        wptr, wgray = create_gray_counter(4, enable=push)
+        with Condition(push):
+            ring[wptr[0][0:2]] = cycle
+        log('cycle: {} wgray: {}', cycle, wgray)
+
+        # The reader sees the write pointer through two synchronizer flops, as it would from
+        # another clock domain, and pops until it catches up with it
+        sync0 = RegArray(UInt(4), 1)
+        sync1 = RegArray(UInt(4), 1)
+        sync0[0] = wgray
+        sync1[0] = sync0[0]
+        wbin = gray_to_binary(sync1[0])
+        rptr = RegArray(UInt(4), 1)
+        rbin = rptr[0]
+        with Condition(rbin != wbin):
+            rptr[0] = rbin + UInt(4)(1)
+            log('pop: {} rgray: {}', ring[rbin[0:2]], binary_to_gray(rbin))
+
+
+def check(raw):
+    grays = []
+    pops = []
+    for line in raw.split('\n'):
+        toks = line.split()
+        if 'wgray:' in line:
+            cycle, gray = int(toks[-3]), int(toks[-1])
+            pushed = (cycle + 1) // 2 % 16
+            assert gray == pushed ^ (pushed >> 1), line
+            grays.append(gray)
+        elif 'pop:' in line:
+            pops.append(int(toks[-3]))
+            assert int(toks[-1]) == (len(pops) - 1) % 16 ^ (len(pops) - 1) % 16 >> 1, line
+    # A single bit of the pointer changes at a time, including when it wraps around
+    for prev, cur in zip(grays, grays[1:]):
+        assert bin(prev ^ cur).count('1') <= 1, (prev, cur)
+    # Every pushed value is popped once, in order
+    assert pops == list(range(0, 2 * len(pops), 2)), pops
+    assert len(pops) > 40
+
+
+def test_gray_counter():
+
+    def top():
+        Driver().build()
+
+    run_test('gray_counter', top, check)
+
+
+if __name__ == '__main__':
+    test_gray_counter()
@@ -0,0 +1,51 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Consumer(Module):
+
+    def __init__(self):
+        super().__init__(ports={'match': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        match = self.pop_all_ports(True)
+        log("match: {}", match)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, consumer: Consumer):
+        # `type`, `match` and `loop` are keywords of Rust, which the backends rename
+        loop = RegArray(UInt(32), 1)
+        v = loop[0]
+        loop[0] = v + UInt(32)(1)
+        consumer.async_called(match=v)
+
+
+def top():
+    consumer = Consumer()
+    consumer.build()
+    consumer.name = 'type'
+    Driver().build(consumer)
+
+
+def check(raw):
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'match:' in line:
+            assert int(line.split()[-1]) == cnt, line
+            cnt += 1
+    assert cnt > 8
+
+
+def test_keyword_names():
+    run_test('keyword_names', top, check, sim_threshold=20, idle_threshold=20)
+
+
+if __name__ == '__main__':
+    test_keyword_names()
@@ -0,0 +1,71 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Counter(Module):
+
+    def __init__(self):
+        super().__init__(ports={'d0': Port(UInt(32)), 'd1': Port(UInt(32))}, merge_calls=True)
+
+    @module.combinational
+    def build(self):
+        # Each caller pushes its own port; one activation sums the deltas of the cycle
+        d0 = self.d0.valid().select(self.d0.pop(), UInt(32)(0))
+        d1 = self.d1.valid().select(self.d1.pop(), UInt(32)(0))
+        cnt = RegArray(UInt(32), 1)
+        new = cnt[0] + d0 + d1
+        (cnt & self)[0] <= new
+        log('count: {}', new)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, counter: Counter):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        with Condition(cnt[0] < UInt(32)(50)):
+            counter.async_called(d0=UInt(32)(1))
+
+
+class Testbench(Module):
+
+    __test__ = False
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, counter: Counter):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        with Condition(cnt[0] < UInt(32)(50)):
+            counter.async_called(d1=UInt(32)(1))
+
+
+def check(raw):
+    expected = 0
+    for line in raw.split('\n'):
+        if 'count:' not in line:
+            continue
+        expected += 2
+        assert int(line.split('count:')[1]) == expected, line
+    # One activation per cycle with calls: unmerged calls would activate it 50 more times
+    assert expected == 100, f'{expected} != 100'
+
+
+def test_merge_calls():
+    def top():
+        counter = Counter()
+        counter.build()
+        Driver().build(counter)
+        Testbench().build(counter)
+
+    run_test('merge_calls', top, check)
+
+
+if __name__ == '__main__':
+    test_merge_calls()
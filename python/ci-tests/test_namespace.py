@@ -0,0 +1,64 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Core(Module):
+
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(32))})
+
+    @module.combinational
+    def build(self, bypass_reg: Array, step: int):
+        a = self.pop_all_ports(True)
+        bypass_reg[0] = a + UInt(32)(step)
+        log("{}: {}", bypass_reg[0], a)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, cores: list):
+        cnt = RegArray(UInt(32), 1)
+        cnt[0] = cnt[0] + UInt(32)(1)
+        for core in cores:
+            core.async_called(a=cnt[0])
+
+
+def top(sys):
+    cores = []
+    for i in range(2):
+        with sys.with_namespace(f'core{i}'):
+            # Both cores declare an array of the same name.
+            bypass_reg = RegArray(UInt(32), 1, name='bypass_reg')
+            core = Core()
+            core.build(bypass_reg, i + 1)
+            assert bypass_reg.name == f'core{i}_bypass_reg', bypass_reg.name
+            assert core.name.startswith(f'core{i}_'), core.name
+            cores.append(core)
+    assert sys.namespace == ''
+    Driver().build(cores)
+
+
+def check(raw):
+    seen = {0: 0, 1: 0}
+    for line in raw.split('\n'):
+        for i in seen:
+            if f'[core{i}_' in line:
+                toks = line.split()
+                reg, a = int(toks[-2][:-1]), int(toks[-1])
+                # Each core reads back its own register: last input plus its own step.
+                if a > 0:
+                    assert reg == a - 1 + i + 1, line
+                seen[i] += 1
+    assert seen[0] > 0 and seen[0] == seen[1], seen
+
+
+def test_namespace():
+    run_test('namespace', top, check, sim_threshold=20, idle_threshold=20)
+
+
+if __name__ == '__main__':
+    test_namespace()
@@ -0,0 +1,69 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        v = cnt[0] * UInt(32)(2654435761)
+
+        # uint<17>: wrapping arithmetic and inversion keep the value in 17 bits
+        u17 = v[5:21].bitcast(UInt(17))
+        u17_add = u17 + UInt(17)(0x1ff00)
+        u17_inv = ~u17
+        u17_shl = u17 << UInt(17)(3)
+        # bits<13>: concat and slices straddling odd offsets
+        b13 = v[11:23]
+        b13_cat = b13.concat(v[0:2])
+        b13_mid = b13_cat[2:14]
+        # int<3>: reinterpretation, extension, and arithmetic of a negative-heavy type
+        i3 = v[29:31].bitcast(Int(3))
+        i3_sext = i3.sext(Int(8))
+        i3_zext = i3.zext(UInt(8))
+        i3_add = i3 + Int(3)(3)
+        i3_shr = i3 >> Int(3)(1)
+        i3_back = i3.bitcast(UInt(3))
+        log('odd: {} {} {} {} {} {} {} {} {} {} {} {} {} {}',
+            cnt[0], u17, u17_add, u17_inv, u17_shl, b13_cat, b13_mid,
+            i3, i3_sext, i3_zext, i3_add, i3_shr, i3_back, b13)
+
+
+def signed(x, bits):
+    x &= (1 << bits) - 1
+    return x - (1 << bits) if x >> (bits - 1) else x
+
+
+def check(raw):
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'odd:' not in line:
+            continue
+        values = [int(i) for i in line.split('odd:')[1].split()]
+        c = values[0]
+        v = c * 2654435761 & 0xffffffff
+        u17 = v >> 5 & 0x1ffff
+        b13 = v >> 11 & 0x1fff
+        b13_cat = b13 << 3 | v & 0x7
+        i3 = signed(v >> 29, 3)
+        expected = [
+            c, u17, (u17 + 0x1ff00) & 0x1ffff, ~u17 & 0x1ffff, (u17 << 3) & 0x1ffff,
+            b13_cat, b13_cat >> 2 & 0x1fff,
+            i3, i3, i3 & 0x7, signed(i3 + 3, 3), i3 >> 1 & 0x7, i3 & 0x7, b13,
+        ]
+        assert values == expected, f'{line}\nexpected: {expected}'
+        cnt += 1
+    assert cnt == 100, f'cnt: {cnt} != 100'
+
+
+def test_odd_widths():
+    run_test('odd_widths', top=lambda: Driver().build(), checker=check)
+
+
+if __name__ == '__main__':
+    test_odd_widths()
@@ -0,0 +1,54 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Decoder(Module):
+
+    def __init__(self):
+        super().__init__(ports={'inst': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        # Wait until the next two instructions are both in the queue
+        wait_until(self.inst.valid_n(1))
+        second = self.inst.peek_n(1)
+        first = self.inst.pop()
+        log('decode: {} {} {}', first, second, self.inst.valid_n(2))
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, decoder: Decoder):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        with Condition(cnt[0] < UInt(32)(50)):
+            decoder.async_called(inst=cnt[0])
+
+
+def check(raw):
+    expected = 0
+    for line in raw.split('\n'):
+        if 'decode:' not in line:
+            continue
+        first, second, _ = [int(i) for i in line.split('decode:')[1].split()]
+        assert first == expected and second == expected + 1, line
+        expected += 1
+    # The last instruction never has a successor to peek at
+    assert expected == 49, f'{expected} != 49'
+
+
+def test_peek_n():
+    def top():
+        decoder = Decoder()
+        decoder.build()
+        Driver().build(decoder)
+
+    run_test('peek_n', top, check)
+
+
+if __name__ == '__main__':
+    test_peek_n()
@@ -0,0 +1,85 @@
+import json
+
+from assassyn.frontend import *
+from assassyn.test import run_test
+from assassyn.analysis.pipeline import SimReport, analyze
+
+
+class Squarer(Module):
+
+    def __init__(self):
+        super().__init__(ports={'a': Port(Int(32))})
+
+    @module.combinational
+    def build(self):
+        a = self.pop_all_ports(True)
+        log("square: {}", a * a)
+
+
+class Agent(Module):
+
+    def __init__(self):
+        super().__init__(ports={'a': Port(Int(32))})
+
+    @module.combinational
+    def build(self, lock: Array, sqr: Squarer):
+        wait_until(lock[0])
+        a = self.pop_all_ports(False)
+        sqr.async_called(a=a)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, agent: Agent, lock: Array):
+        cnt = RegArray(Int(32), 1)
+        (cnt & self)[0] <= cnt[0] + Int(32)(1)
+        (lock & self)[0] <= ~lock[0]
+        agent.async_called(a=cnt[0])
+
+
+built = {}
+
+
+def top(sys):
+    sqr = Squarer()
+    sqr.build()
+    lock = RegArray(Bits(1), 1)
+    agent = Agent()
+    agent.build(lock, sqr)
+    driver = Driver()
+    driver.build(agent, lock)
+    built.update(sys=sys, sqr=sqr.name, agent=agent.name, driver=driver.name)
+
+
+def check(raw):
+    sim = SimReport.parse(raw)
+    report = analyze(built['sys'], sim)
+    agent, sqr, driver = built['agent'], built['sqr'], built['driver']
+    # The agent only proceeds every other cycle, so its input FIFO backs up.
+    assert report.bottleneck_stage == agent, report
+    assert report.bottleneck_fifo == f'{agent}_a', report
+    assert report.stages[agent].stalled > 0
+    assert report.stages[sqr].stalled == 0
+    chains = [chain.stages for chain in report.chains]
+    assert chains == [[driver, agent, sqr]], chains
+    assert 0.4 < report.chains[0].ipc < 0.6, report.chains[0].ipc
+    assert json.loads(report.to_json())['modules'][agent] == agent
+    # The backlog of the agent piles up, while the squarer never holds more than one entry
+    backlog = sim.occupancy_summary(f'{agent}_a')
+    assert backlog['max'] > 1 and backlog['p99'] > 1, backlog
+    assert sim.occupancy_summary(f'{sqr}_a')['max'] == 1, sim.occupancy[f'{sqr}_a']
+    assert sum(sim.occupancy[f'{sqr}_a']) == sim.cycles
+    assert 'FIFO occupancy histograms' in raw and f'{agent}.a' in raw
+
+
+def test_pipeline_stats():
+    run_test('pipeline_stats', top, check, sim_threshold=100, idle_threshold=100,
+             sim_stats=True, verilog=False)
+
+
+if __name__ == '__main__':
+    test_pipeline_stats()
@@ -0,0 +1,61 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Calc(Module):
+
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(16)), 'b': Port(UInt(16))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        log('calc: {} {}', a, b)
+        with Condition(a[0:0]):
+            with Condition(b[1:1]):
+                # Nothing returns it, so it is only seen through the probe
+                deep = ((a + b) ^ (a - b)) + (b & UInt(16)(0xff))
+                probe(deep, 'deep')
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, calc: Calc):
+        cnt = RegArray(UInt(16), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(16)(1)
+        calc.async_called(a=cnt[0], b=cnt[0] + UInt(16)(3))
+
+
+def top():
+    calc = Calc()
+    calc.build()
+    Driver().build(calc)
+
+
+def check(raw):
+    inputs, probed = [], []
+    for line in raw.splitlines():
+        toks = line.split()
+        if 'calc:' in line:
+            inputs.append((int(toks[-2]), int(toks[-1])))
+        elif line.startswith('@probe'):
+            assert toks[2] == 'deep', line
+            probed.append(int(toks[3], 16))
+    mask = 0xffff
+    expected = [((((a + b) & mask) ^ ((a - b) & mask)) + (b & 0xff)) & mask
+                for a, b in inputs if a & 1 and b & 2]
+    assert len(expected) > 5, raw
+    # Printed in the cycles where both conditions hold, and only then
+    assert probed == expected, (probed, expected)
+
+
+def test_probe():
+    run_test('probe', top, check, sim_threshold=50, idle_threshold=50)
+
+
+if __name__ == '__main__':
+    test_probe()
@@ -0,0 +1,90 @@
+import re
+
+from assassyn.frontend import *
+from assassyn.test import run_test
+from assassyn.cosim import compare_backends
+from assassyn import utils
+
+
+class Producer(Module):
+
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        data = self.pop_all_ports(True)
+        value = data * UInt(32)(3)
+        log('producer: {}', value)
+        return value
+
+
+class Consumer(Downstream):
+
+    def __init__(self):
+        super().__init__()
+
+    @downstream.combinational
+    def build(self, value: Value):
+        with Condition(value.valid()):
+            log('consumer: {}', value)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, producer: Producer):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        # The producer idles one cycle out of three
+        with Condition(cnt[0] % UInt(32)(3) != UInt(32)(0)):
+            producer.async_called(data=cnt[0])
+
+
+def top(sys):
+    producer = Producer()
+    value = producer.build()
+    Driver().build(producer)
+    Consumer().build(sys.register_value(value))
+
+
+def _cycles(raw, tag):
+    '''The {cycle: value} of the lines logged with `tag`.'''
+    res = {}
+    for line in raw.splitlines():
+        if f'{tag}:' in line:
+            cycle = int(re.search(r'Cycle @(\d+)', line).group(1))
+            res[cycle] = int(line.split()[-1])
+    return res
+
+
+def check(raw):
+    produced, consumed = _cycles(raw, 'producer'), _cycles(raw, 'consumer')
+    assert len(produced) > 10, raw
+    # The consumer sees the value of the previous cycle, and only after the producer ran
+    assert consumed == {cycle + 1: value for cycle, value in produced.items()
+                        if cycle + 1 in consumed}, (produced, consumed)
+    assert len(consumed) >= len(produced) - 1, (produced, consumed)
+
+
+def test_registered_value():
+    run_test('registered_value', top, check, sim_threshold=30, idle_threshold=30)
+
+
+def test_backends_agree():
+    if not utils.has_verilator():
+        return
+    sys = SysBuilder('registered_value_cosim')
+    with sys:
+        top(sys)
+    report = compare_backends(sys, verbose=False, sim_threshold=30)
+    assert report.ok, report.divergence
+    assert report.cycles > 0
+
+
+if __name__ == '__main__':
+    test_registered_value()
+    test_backends_agree()
@@ -0,0 +1,55 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Compute(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        runs = RegArray(UInt(32), 1)
+        (runs & self)[0] <= runs[0] + UInt(32)(1)
+        log('compute {}', runs[0])
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, compute: Compute):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        # Each call activates the compute module 4 times, once every 8 cycles
+        fire = (cnt[0][0:2] == Bits(3)(0)) & (cnt[0] < UInt(32)(80))
+        with Condition(fire):
+            compute.async_called_n(4)
+            log('fire {}', cnt[0])
+
+
+def check(raw):
+    fires = computes = 0
+    for line in raw.split('\n'):
+        if 'fire ' in line:
+            fires += 1
+        if 'compute ' in line:
+            assert int(line.split('compute ')[1]) == computes, line
+            computes += 1
+    assert fires == 10, f'fires: {fires} != 10'
+    assert computes == 4 * fires, f'computes: {computes} != {4 * fires}'
+
+
+def test_replicated_call():
+    def top():
+        compute = Compute()
+        compute.build()
+        Driver().build(compute)
+
+    run_test('replicated_call', top, check)
+
+
+if __name__ == '__main__':
+    test_replicated_call()
@@ -0,0 +1,74 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+U128_MAX = (1 << 128) - 1
+I128_MAX = (1 << 127) - 1
+I128_MIN = -(1 << 127)
+
+
+def as_signed(value):
+    return UInt(8)(value & 0xff).bitcast(Int(8))
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+
+        x8 = cnt[0][0:7].bitcast(UInt(8))
+        u8_add = UInt(8)(200).add_sat(x8)
+        u8_sub = x8.sub_sat(UInt(8)(50))
+        i8_add = as_signed(100).add_sat(x8.bitcast(Int(8)))
+        i8_sub = as_signed(-100).sub_sat(x8.bitcast(Int(8)))
+
+        # Immediates are at most 64 bits, so the 128-bit operands near the boundaries are
+        # concatenated from a constant upper half and a lower half that depends on x.
+        x64 = x8.zext(UInt(64))
+        x128 = x8.zext(UInt(128))
+        near_max = UInt(64)((1 << 64) - 128) | x64
+        u128_add = concat(UInt(64)((1 << 64) - 1), near_max).bitcast(UInt(128)).add_sat(x128)
+        u128_sub = x128.sub_sat(UInt(128)(50))
+        # I128_MAX - 127 + x, and its negation minus one, I128_MIN + 127 - x
+        i128_near_max = concat(UInt(64)((1 << 63) - 1), near_max).bitcast(Int(128))
+        i128_near_min = Int(128)(-1) - i128_near_max
+        i128_add = i128_near_max.add_sat(x128.bitcast(Int(128)))
+        i128_sub = i128_near_min.sub_sat(x128.bitcast(Int(128)))
+
+        log("sat: {} {} {} {} {} {} {} {} {}", x8, u8_add, u8_sub, i8_add, i8_sub,
+            u128_add, u128_sub, i128_add, i128_sub)
+
+
+def check(raw):
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'sat:' not in line:
+            continue
+        toks = [int(i) for i in line.split('sat:')[1].split()]
+        x = toks[0]
+        assert toks[1] == min(200 + x, 255), line
+        assert toks[2] == max(x - 50, 0), line
+        assert toks[3] == min(100 + x, 127), line
+        assert toks[4] == max(-100 - x, -128), line
+        assert toks[5] == min(U128_MAX - 127 + 2 * x, U128_MAX), line
+        assert toks[6] == max(x - 50, 0), line
+        assert toks[7] == min(I128_MAX - 127 + 2 * x, I128_MAX), line
+        assert toks[8] == max(I128_MIN + 127 - 2 * x, I128_MIN), line
+        cnt += 1
+    assert cnt == 100, f'cnt: {cnt} != 100'
+
+
+def test_saturate():
+    def top():
+        Driver().build()
+
+    run_test('saturate', top=top, checker=check, sim_threshold=100, idle_threshold=100)
+
+
+if __name__ == '__main__':
+    test_saturate()
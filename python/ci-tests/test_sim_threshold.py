@@ -0,0 +1,50 @@
+import re
+
+from assassyn.frontend import *
+from assassyn.backend import elaborate
+from assassyn import utils
+
+THRESHOLD = 25
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        log('tick: {}', cnt[0])
+
+
+def top():
+    sys = SysBuilder('sim_threshold')
+    with sys:
+        Driver().build()
+    return sys
+
+
+def ticks(raw):
+    '''The (cycle, value) of each tick line'''
+    res = []
+    for line in raw.splitlines():
+        if 'tick:' in line:
+            cycle = re.search(r'Cycle @(\d+)\.', line).group(1)
+            res.append((int(cycle), int(line.split()[-1])))
+    return res
+
+
+def test_sim_threshold():
+    sim, ver = elaborate(top(), verbose=False, sim_threshold=THRESHOLD,
+                         verilog=utils.has_verilator(), enable_cache=False)
+    # `sim_threshold` cycles, numbered from 1, the Driver running in each of them
+    expected = [(i + 1, i) for i in range(THRESHOLD)]
+    assert ticks(utils.run_simulator(sim)) == expected
+    if ver:
+        assert ticks(utils.run_verilator(ver)) == expected
+
+
+if __name__ == '__main__':
+    test_sim_threshold()
@@ -0,0 +1,56 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+from assassyn.codegen.simulator.snapshot import SNAPSHOT_MSG
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        flag = RegArray(Bits(1), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        (flag & self)[0] <= ~flag[0]
+        log("cnt: {} {}", cnt[0], flag[0])
+
+
+def top():
+    Driver().build()
+
+
+def counts(raw):
+    return [[int(i) for i in line.split('cnt:')[1].split()]
+            for line in raw.split('\n') if 'cnt:' in line]
+
+
+# Shared between the two runs, since the checkers only see the simulator output
+snapshots = []
+
+
+def check_snapshot(raw):
+    assert counts(raw) == [[i, i % 2] for i in range(100)]
+    lines = [line for line in raw.split('\n') if SNAPSHOT_MSG in line]
+    assert len(lines) == 1, lines
+    path = lines[0].split(SNAPSHOT_MSG)[1].strip()
+    with open(path, encoding='utf-8') as f:
+        content = f.read()
+    # The writes of cycle 50 are visible at the end of it
+    assert '# cycle 50' in content and ' 0 50\n' in content, content
+    snapshots.append(path)
+
+
+def check_restore(raw):
+    assert counts(raw) == [[i, i % 2] for i in range(50, 60)]
+
+
+def test_snapshot():
+    run_test('snapshot', top=top, checker=check_snapshot, snapshot_at=50, sim_threshold=100)
+    run_test('restore', top=top, checker=check_restore, restore_from=snapshots[0],
+             sim_threshold=10)
+
+
+if __name__ == '__main__':
+    test_snapshot()
@@ -25,7 +25,7 @@ class Driver(Module):
         super().__init__(ports={})
 
     @module.combinational
-    def build(self, width, init_file, user):
+    def build(self, width, init_file, user, init_format='hex'):
         cnt = RegArray(Int(width), 1)
         v = cnt[0]
         we = v[0:0]
@@ -35,7 +35,7 @@ class Driver(Module):
         raddr = v[0:8]
         addr = we.select(waddr, raddr).bitcast(Int(9))
         (cnt & self)[0] <= plused
-        sram = SRAM(width, 512, init_file)
+        sram = SRAM(width, 512, init_file, init_format)
         sram.build(we, re, addr, v.bitcast(Bits(width)))
         user.async_called()
         return sram
@@ -72,6 +72,30 @@ def test_memory_init():
              sim_threshold=200, idle_threshold=200,
              resource_base=f'{utils.repo_path()}/python/ci-tests/resources')
 
+def test_memory_init_bin():
+    outputs = {}
+
+    def top(init_file, fmt):
+        def inner():
+            user = MemUser()
+            driver = Driver()
+            sram = driver.build(32, init_file, user, fmt)
+            user.build(sram.dout)
+        return inner
+
+    def checker(fmt):
+        def inner(raw):
+            check(raw)
+            outputs[fmt] = [i.split(']')[-1] for i in raw.splitlines() if '[MemUser' in i]
+        return inner
+
+    for fmt, init_file in [('hex', 'init_1.hex'), ('bin', 'init_1.bin')]:
+        run_test(f'memory_init_{fmt}', top(init_file, fmt), checker(fmt),
+                 sim_threshold=200, idle_threshold=200,
+                 resource_base=f'{utils.repo_path()}/python/ci-tests/resources')
+    # The binary file holds the same values as the hexadecimal one
+    assert outputs['bin'] and outputs['bin'] == outputs['hex']
+
 def test_memory_wide():
     def top():
         user = MemUser()
@@ -84,4 +108,5 @@ def test_memory_wide():
 if __name__ == "__main__":
     test_memory()
     test_memory_init()
+    test_memory_init_bin()
     test_memory_wide()
@@ -0,0 +1,76 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Flat(Module):
+
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        a = self.pop_all_ports(True)
+        b = a + UInt(32)(1)
+        c = b * UInt(32)(3)
+        d = (c + a).bitcast(UInt(64))
+        log("flat: {}", d)
+
+
+class Piped(Module):
+
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        a = self.pop_all_ports(True)
+        b = a + UInt(32)(1)
+        stage()
+        c = b * UInt(32)(3)
+        stage()
+        d = (c + a).bitcast(UInt(64))
+        stage()
+        log("piped: {}", d)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, flat: Flat, piped: Piped):
+        cnt = RegArray(UInt(32), 1)
+        v = cnt[0]
+        cnt[0] = v + UInt(32)(1)
+        flat.async_called(a=v)
+        piped.async_called(a=v)
+
+
+def top():
+    flat = Flat()
+    flat.build()
+    piped = Piped()
+    piped.build()
+    Driver().build(flat, piped)
+
+
+def check(raw):
+    flat, piped = [], []
+    for line in raw.split('\n'):
+        if 'flat:' in line:
+            flat.append(int(line.split()[-1]))
+        elif 'piped:' in line:
+            piped.append(int(line.split()[-1]))
+    # The pipelined version produces the same sequence, only 3 cycles later.
+    assert len(flat) > 3
+    assert piped == flat[:len(piped)]
+    assert len(piped) >= len(flat) - 3, (len(flat), len(piped))
+
+
+def test_stage():
+    run_test('stage', top, check, sim_threshold=50, idle_threshold=50)
+
+
+if __name__ == '__main__':
+    test_stage()
@@ -0,0 +1,43 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        v = cnt[0]
+        cnt[0] = v + UInt(32)(1)
+        word = RegArray(UInt(32), 1, initializer=[0xaabbccdd])
+        # Only the low byte is written, the upper three keep their initial value
+        word.store_masked(0, v, Bits(4)(0b0001))
+        log("cnt: {} word: {}", v, word[0])
+
+
+def top():
+    Driver().build()
+
+
+def check(raw):
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'cnt:' not in line:
+            continue
+        toks = line.split()
+        cnt = int(toks[-3])
+        word = int(toks[-1])
+        expected = 0xaabbccdd if cnt == 0 else 0xaabbcc00 | ((cnt - 1) & 0xff)
+        assert word == expected, line
+    assert cnt > 8
+
+
+def test_store_masked():
+    run_test('store_masked', top, check, sim_threshold=40, idle_threshold=40)
+
+
+if __name__ == '__main__':
+    test_store_masked()
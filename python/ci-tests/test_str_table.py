@@ -0,0 +1,52 @@
+import pytest
+
+from assassyn.backend import elaborate
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+MNEMONICS = ['add', 'sub', 'and', 'or']
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        opcode = cnt[0][0:1]
+        mnemonics = RegArray(Str(), 4, initializer=MNEMONICS, name='mnemonics')
+        log('opcode: {} {}', opcode, mnemonics[opcode])
+
+
+def check(raw):
+    cnt = 0
+    for i in raw.split('\n'):
+        if 'opcode:' in i:
+            opcode, mnemonic = i.split()[-2:]
+            assert mnemonic == MNEMONICS[int(opcode)]
+            cnt += 1
+    assert cnt > 0
+
+
+def test_str_table():
+    def top():
+        driver = Driver()
+        driver.build()
+
+    # The arrays of strings only exist in the simulator
+    run_test('str_table', top, check, verilog=False)
+
+
+def test_str_table_verilog(tmp_path):
+    sys = SysBuilder('str_table_verilog')
+    with sys:
+        Driver().build()
+    with pytest.raises(ValueError, match='array of strings'):
+        elaborate(sys, path=str(tmp_path), simulator=False, verilog=True, verbose=False)
+
+
+if __name__ == '__main__':
+    test_str_table()
@@ -0,0 +1,76 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+# lui, addi, lw, sw, beq, jal, add, ecall
+INSTS = [0x000122b7, 0x00500093, 0x0000a103, 0x0020a023,
+         0x00208463, 0x008000ef, 0x002081b3, 0x00000073]
+OPCODES = {0b0110111: 'lui', 0b0010011: 'alu', 0b0000011: 'mem', 0b0100011: 'mem',
+           0b1100011: 'branch', 0b1101111: 'jump', 0b1100111: 'jump', 0b0110011: 'alu'}
+
+
+class Decoder(Module):
+
+    def __init__(self):
+        super().__init__(ports={'inst': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        inst = self.inst.pop()
+        opcode = inst[0:6]
+        rd = inst[7:11]
+        with Switch(opcode) as sw:
+            with sw.case(0b0110111):
+                log('decode: {} lui {}', inst, inst[12:31])
+            with sw.case(0b0010011, 0b0110011):
+                log('decode: {} alu {}', inst, rd)
+            with sw.case(0b0000011, 0b0100011):
+                log('decode: {} mem {}', inst, inst[15:19])
+            with sw.case(0b1100011):
+                log('decode: {} branch {}', inst, inst[15:19])
+            with sw.case(0b1101111, 0b1100111):
+                log('decode: {} jump {}', inst, rd)
+            with sw.default():
+                log('decode: {} unknown {}', inst, opcode)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, decoder: Decoder):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        insts = RegArray(UInt(32), len(INSTS), initializer=INSTS)
+        with Condition(cnt[0] < UInt(32)(len(INSTS))):
+            decoder.async_called(inst=insts[cnt[0][0:2]])
+
+
+def check(raw):
+    decoded = []
+    for line in raw.split('\n'):
+        if 'decode:' not in line:
+            continue
+        inst, kind, field = line.split('decode:')[1].split()
+        inst, field = int(inst), int(field)
+        expected = OPCODES.get(inst & 0x7f, 'unknown')
+        assert kind == expected, f'{line}: expected {expected}'
+        fields = {'lui': inst >> 12, 'alu': inst >> 7 & 0x1f, 'mem': inst >> 15 & 0x1f,
+                  'branch': inst >> 15 & 0x1f, 'jump': inst >> 7 & 0x1f, 'unknown': inst & 0x7f}
+        assert field == fields[kind], line
+        decoded.append(inst)
+    assert decoded == INSTS, f'{decoded} != {INSTS}'
+
+
+def test_switch_decode():
+    def top():
+        decoder = Decoder()
+        decoder.build()
+        Driver().build(decoder)
+
+    run_test('switch_decode', top, check)
+
+
+if __name__ == '__main__':
+    test_switch_decode()
@@ -0,0 +1,56 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+from assassyn.ir.expr.intrinsic import current_cycle
+
+
+class Consumer(Module):
+
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(64))})
+
+    @module.combinational
+    def build(self):
+        data = self.pop_all_ports(True)
+        log('consumed {}', data)
+
+
+class Testbench(Module):
+
+    __test__ = False
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, consumer: Consumer):
+        # Every 4th cycle in [4, 44)
+        with Cycle(range(4, 44, 4)):
+            consumer.async_called(data=current_cycle())
+        # Unbounded, and a stride which is not a power of two
+        with CycleRange(7, stride=30):
+            log('tbstride {}', current_cycle())
+
+
+def check(raw):
+    consumed = []
+    strides = []
+    for line in raw.split('\n'):
+        if 'consumed' in line:
+            consumed.append(int(line.split()[-1]))
+        if 'tbstride' in line:
+            strides.append(int(line.split()[-1]))
+    assert consumed == list(range(4, 44, 4)), consumed
+    assert strides == [7, 37, 67, 97], strides
+
+
+def test_testbench_range():
+    def top():
+        consumer = Consumer()
+        consumer.build()
+        Testbench().build(consumer)
+
+    run_test('testbench_range', top, check)
+
+
+if __name__ == '__main__':
+    test_testbench_range()
@@ -0,0 +1,72 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Worker(Module):
+
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(32))})
+
+    @module.combinational
+    def build(self, gate: Array):
+        wait_until(self.data.valid())
+        v = self.data.peek()
+        # A condition block before the second wait_until
+        with Condition(v[2:2] == Bits(1)(1)):
+            log('bit2 {}', v)
+        # A wait_until inside a condition block only holds the values with bit 1 set
+        with Condition(v[1:1] == Bits(1)(1)):
+            wait_until(gate[0])
+        x = self.data.pop()
+        log('worker {} {}', x, gate[0])
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, worker: Worker, gate: Array):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        # The gate is closed in 4 out of every 16 cycles
+        (gate & self)[0] <= cnt[0][2:2] | cnt[0][3:3]
+        with Condition(cnt[0][0:0] == Bits(1)(0)):
+            worker.async_called(data=cnt[0])
+
+
+def check(raw):
+    popped = []
+    passed_closed = 0
+    bit2 = set()
+    for line in raw.split('\n'):
+        if 'bit2' in line:
+            bit2.add(int(line.split()[-1]))
+        if 'worker' in line:
+            value, gate = [int(i) for i in line.split('worker')[1].split()]
+            popped.append(value)
+            # Only the values with bit 1 set wait for the gate
+            assert not value & 2 or gate == 1, line
+            passed_closed += gate == 0
+    # Nothing is lost or reordered by the waits
+    assert popped == list(range(0, 2 * len(popped), 2)), popped
+    assert len(popped) > 40, len(popped)
+    # The condition block before the wait runs in every attempt, so it logs each value
+    assert {v for v in popped if v & 4} <= bit2, bit2
+    # The other values are not held by a closed gate
+    assert passed_closed > 0
+
+
+def test_wait_until_nested():
+    def top():
+        gate = RegArray(Bits(1), 1)
+        worker = Worker()
+        worker.build(gate)
+        Driver().build(worker, gate)
+
+    run_test('wait_until_nested', top, check)
+
+
+if __name__ == '__main__':
+    test_wait_until_nested()
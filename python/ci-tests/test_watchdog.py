@@ -0,0 +1,71 @@
+import subprocess
+
+import pytest
+
+from assassyn.frontend import *
+from assassyn.backend import elaborate
+from assassyn.utils import run_simulator
+
+
+class Sink(Module):
+
+    def __init__(self):
+        super().__init__({
+                "data": Port(UInt(32)),
+        })
+
+    @module.combinational
+    def build(self):
+        watchdog(self.data.valid(), 10)
+        data = self.data.pop()
+        log("sink: {}", data)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__({})
+
+    @module.combinational
+    def build(self, sink: Sink, limit: int):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        # The sink stops being fed after `limit` cycles
+        with Condition(cnt[0] < UInt(32)(limit)):
+            sink.async_called(data=cnt[0])
+
+
+def top(limit):
+    sys = SysBuilder("watchdog")
+    with sys:
+        sink = Sink()
+        driver = Driver()
+
+        sink.build()
+        driver.build(sink, limit)
+
+    return sys
+
+
+def test_watchdog_holds():
+    sys = top(1000)
+    sim, _ = elaborate(sys, verbose=False, simulator=True, verilog=False, sim_threshold=50)
+    raw = run_simulator(sim)
+    assert "sink: 40" in raw
+
+
+def test_watchdog_expires(capfd):
+    sys = top(5)
+    sim, _ = elaborate(sys, verbose=False, simulator=True, verilog=False, sim_threshold=50)
+
+    with pytest.raises(subprocess.CalledProcessError):
+        run_simulator(sim)
+
+    stdout, stderr = capfd.readouterr()
+    combined = stdout + stderr
+    assert "Watchdog expired" in combined
+    assert "sink: 4" in combined
+
+
+if __name__ == "__main__":
+    test_watchdog_holds()
@@ -0,0 +1,47 @@
+from assassyn.frontend import *
+from assassyn.backend import elaborate
+from assassyn import utils
+
+WIDE = 0x123456789ABCDEF012345678
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        # The words of WIDE in registers, so their concat is not folded
+        words = [RegArray(Bits(32), 1, initializer=[(WIDE >> (32 * i)) & 0xFFFFFFFF])
+                 for i in range(3)]
+        built = words[2][0].concat(words[1][0]).concat(words[0][0])
+        literal = Bits(96)(WIDE)
+        mask = Bits(96)((1 << 96) - 1)
+        log('wide: {} {}', built == literal, (built & mask) == literal)
+
+
+def top():
+    sys = SysBuilder('wide_imm')
+    with sys:
+        Driver().build()
+    return sys
+
+
+def check(raw):
+    lines = [i for i in raw.splitlines() if 'wide:' in i]
+    assert lines
+    for line in lines:
+        assert line.split()[-2:] == ['1', '1'], line
+
+
+def test_wide_imm():
+    sim, ver = elaborate(top(), verbose=False, sim_threshold=5,
+                         verilog=utils.has_verilator(), enable_cache=False)
+    check(utils.run_simulator(sim))
+    if ver:
+        check(utils.run_verilator(ver))
+
+
+if __name__ == '__main__':
+    test_wide_imm()
@@ -0,0 +1,56 @@
+from assassyn.frontend import *
+from assassyn.test import run_test
+
+
+class Writer(Module):
+
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(32))})
+
+    @module.combinational
+    def build(self, arr):
+        data = self.data.pop()
+        (arr & self)[1] <= data + UInt(32)(100)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, arr, writer: Writer):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        # Both modules write `arr` in every cycle from the second one, on their own ports
+        (arr & self)[0] <= cnt[0]
+        writer.async_called(data=cnt[0])
+        log('ports: {} {} {}', cnt[0], arr[0], arr[1])
+
+
+def top():
+    arr = RegArray(UInt(32), 2, attr=[WritePorts(2)])
+    writer = Writer()
+    writer.build(arr)
+    Driver().build(arr, writer)
+
+
+def check(raw):
+    cnt = 0
+    for line in raw.split('\n'):
+        if 'ports:' not in line:
+            continue
+        i, first, second = [int(x) for x in line.split('ports:')[1].split()]
+        # Written in the cycle before, and by the writer a cycle after the call
+        assert first == max(i - 1, 0), line
+        assert second == (i - 2 + 100 if i >= 2 else 0), line
+        cnt += 1
+    assert cnt == 50, cnt
+
+
+def test_write_ports():
+    run_test('write_ports', top, check, sim_threshold=50)
+
+
+if __name__ == '__main__':
+    test_write_ports()
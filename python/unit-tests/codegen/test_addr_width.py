@@ -0,0 +1,80 @@
+"""Index widths of the Verilog backend are ceil(log2(size)), with at least one bit."""
+
+import os
+import re
+import sys
+
+import pytest
+
+sys.path.append(os.path.join(os.path.dirname(__file__), '..', '..'))
+
+from assassyn.codegen.verilog.elaborate import elaborate  # noqa: E402
+from assassyn.codegen.verilog.utils import addr_width  # noqa: E402
+from assassyn.frontend import (Bits, Module, RegArray, SRAM, SysBuilder, UInt,  # noqa: E402
+                               module)
+from assassyn.ir.array import index_width  # noqa: E402
+
+
+@pytest.mark.parametrize('size, width', [
+    (1, 1), (2, 1), (3, 2), (4, 2), (1023, 10), (1024, 10), (1025, 11),
+])
+def test_addr_width(size, width):
+    assert addr_width(size) == width
+
+
+def test_addr_width_matches_index_bits():
+    """Apart from the 1-bit floor, the ports are as wide as the frontend array index."""
+    sizes = [1, 2, 3, 4, 5, 7, 8, 9, 1023, 1024, 1025]
+    with SysBuilder('addr_width'):
+        arrays = [RegArray(UInt(8), size) for size in sizes]
+    for array in arrays:
+        assert addr_width(array.size) == max(array.index_bits, 1)
+
+
+def test_addr_width_empty():
+    with pytest.raises(AssertionError, match='Invalid array size'):
+        addr_width(0)
+
+
+def test_index_width():
+    """A power-of-two size needs no extra bit."""
+    widths = {1: 0, 2: 1, 32: 5, 33: 6}
+    with SysBuilder('index_width'):
+        arrays = {size: RegArray(UInt(8), size) for size in widths}
+    for size, width in widths.items():
+        assert index_width(size) == width
+        assert arrays[size].index_bits == width
+        assert arrays[size].index_type() == UInt(width)
+        assert addr_width(size) == max(width, 1)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, depth):
+        cnt = RegArray(UInt(32), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(32)(1)
+        addr = cnt[0][0:15].bitcast(UInt(16))
+        sram = SRAM(32, depth, None)
+        sram.build(cnt[0][0:0], ~cnt[0][0:0], addr, cnt[0].bitcast(Bits(32)))
+        return sram
+
+
+def test_memory_addr_width(tmp_path):
+    """A 65536-word memory has a 16-bit address in the IR and on every bus of the design."""
+    sys = SysBuilder('memory_addr_width')
+    with sys:
+        sram = Driver().build(65536)
+    assert sram.addr_width == 16
+    path = elaborate(sys, path=str(tmp_path), sim_threshold=10)
+    design = (path / 'design.py').read_text(encoding='utf-8')
+    # The address output of the memory, the blackbox wrapper input, and the Top wire
+    assert 'mem_address = Output(Bits(16))' in design
+    assert 'address = Input(Bits(16))' in design
+    assert re.search(r'mem_\w+_address = Wire\(Bits\(16\)\)', design)
+    assert not re.search(r'address = (Output|Input|Wire)\(Bits\((?!16\))', design)
+    blackbox, = path.glob('sram_blackbox_*.sv')
+    assert 'parameter ADDR_WIDTH = 16' in blackbox.read_text(encoding='utf-8')
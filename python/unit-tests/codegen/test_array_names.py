@@ -0,0 +1,86 @@
+"""Test that arrays given the same name keep distinct signals"""
+
+import sys as py_sys
+from pathlib import Path
+
+import pytest
+
+from assassyn.frontend import Module, RegArray, SysBuilder, UInt, module
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.ir.array import verify_array_names
+from assassyn.utils import repo_path
+
+
+class Stage(Module):
+    """Writes its bypass registers, as a pipeline stage does"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, bypass_reg, bypass_data):
+        (bypass_reg & self)[0] <= bypass_reg[0] + UInt(5)(1)
+        (bypass_data & self)[0] <= bypass_data[0] + UInt(32)(1)
+
+
+def test_repeated_names_stay_distinct(tmp_path):
+    sys = SysBuilder('array_names')
+    with sys:
+        arrays = []
+        for _ in range(2):
+            bypass_reg = RegArray(UInt(5), 1, name='bypass_reg')
+            bypass_data = RegArray(UInt(32), 1, name='bypass_data')
+            Stage().build(bypass_reg, bypass_data)
+            arrays += [bypass_reg, bypass_data]
+
+    names = [array.name for array in arrays]
+    assert names == ['bypass_reg', 'bypass_data', 'bypass_reg_1', 'bypass_data_1']
+    verify_array_names(sys)
+
+    path = tmp_path / 'design.py'
+    generate_design(str(path), sys)
+    code = path.read_text(encoding='utf-8')
+    # Four families of write ports, one per array
+    for name in names:
+        assert f'{name}_w_port0 = Output(Bits(1))' in code
+
+
+def test_verify_rejects_shared_name():
+    sys = SysBuilder('array_names_shared')
+    with sys:
+        first = RegArray(UInt(8), 1, name='acc')
+        second = RegArray(UInt(8), 1)
+        # A name set after the array is declared is not made unique
+        second.name = first.name
+    with pytest.raises(ValueError, match='Two arrays are named acc'):
+        verify_array_names(sys)
+
+
+def _build_minor_cpu():
+    src = Path(repo_path()) / 'examples' / 'minor-cpu' / 'src'
+    py_sys.path.insert(0, str(src))
+    import main  # pylint: disable=import-outside-toplevel,import-error
+    main.init_workspace(f'{src}/../../../python/unit-tests', 'rv32ui-p-add')
+    built = []
+
+    def capture(system, **_):
+        built.append(system)
+        raise StopIteration
+
+    main.elaborate = capture
+    try:
+        main.build_cpu(depth_log=16)
+    except StopIteration:
+        pass
+    return built[0]
+
+
+def test_minor_cpu_bypass_signals(tmp_path):
+    system = _build_minor_cpu()
+    verify_array_names(system)
+    path = tmp_path / 'design.py'
+    generate_design(str(path), system)
+    code = path.read_text(encoding='utf-8')
+    # Each stage forwards through its own pair of bypass registers
+    for stage in ['exec', 'mem', 'wb']:
+        for kind in ['reg', 'data']:
+            assert f'{stage}_bypass_{kind}_w_port0 = Output(Bits(1))' in code
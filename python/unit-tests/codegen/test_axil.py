@@ -0,0 +1,84 @@
+"""Test the AXI4-Lite slave mapping the exposed arrays"""
+
+import json
+
+import pytest
+
+from assassyn.frontend import Module, RegArray, SysBuilder, UInt, module
+from assassyn.codegen.verilog.axil import axil_addr_width, axil_regmap
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.codegen.verilog.testbench import _axil_bfm
+
+
+class Driver(Module):
+    """Counts in `cnt`, and reads the control registers"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, cnt, ctrl):
+        cnt[0] = cnt[0] + UInt(32)(1)
+        ctrl[0] = ctrl[1]
+
+
+def _build(name, kinds=('Output', 'Inout')):
+    sys = SysBuilder(name)
+    with sys:
+        cnt = RegArray(UInt(32), 1)
+        ctrl = RegArray(UInt(8), 3)
+        Driver().build(cnt, ctrl)
+        sys.expose_on_top(cnt, kind=kinds[0])
+        sys.expose_on_top(ctrl, kind=kinds[1])
+    return sys
+
+
+def test_regmap():
+    regions = axil_regmap(_build('axil_regmap'))
+    assert [(i.name, i.base, i.writable) for i in regions] == [
+        ('cnt', 0, False), ('ctrl', 16, True)]
+    # 8 words, from 0 to 0x1c
+    assert axil_addr_width(regions) == 5
+
+
+def test_design(tmp_path):
+    path = tmp_path / 'design.py'
+    generate_design(str(path), _build('axil_design'), axi_lite=True)
+    code = path.read_text(encoding='utf-8')
+    regmap = json.loads((tmp_path / 'regmap.json').read_text(encoding='utf-8'))
+    assert regmap['addr_width'] == 5
+    assert regmap['registers'] == [
+        {'name': 'cnt', 'base': 0, 'size': 1, 'bits': 32, 'access': 'ro'},
+        {'name': 'ctrl', 'base': 16, 'size': 3, 'bits': 8, 'access': 'rw'},
+    ]
+    assert 's_axil_awaddr = Input(Bits(5))' in code
+    assert 'axil = AxilRegs(ADDR_WIDTH=5)' in code
+    # The bus reads both arrays, but only writes ctrl, after the port of Driver
+    assert 'aw_cnt_w_port1' not in code
+    assert 'aw_ctrl_w_port1.assign(axil.wr_en & axil_hit_ctrl)' in code
+    assert 'axil_hit_ctrl = axil.addr[2:3] == Bits(1)(1)' in code
+    # ctrl spans 4 words, the last of which is past its 3 elements
+    assert 'axil_hit_ctrl = axil_hit_ctrl & (axil.addr[0:2].as_uint() < UInt(2)(3))' in code
+    assert 'axil_wr_ok.assign(reduce(operator.or_, [axil_hit_ctrl]))' in code
+
+
+def test_no_bus_without_option(tmp_path):
+    path = tmp_path / 'design.py'
+    generate_design(str(path), _build('axil_off'))
+    assert 's_axil_' not in path.read_text(encoding='utf-8')
+    assert not (tmp_path / 'regmap.json').exists()
+
+
+def test_rejects_wide_elements():
+    sys = SysBuilder('axil_wide')
+    with sys:
+        wide = RegArray(UInt(64), 1)
+        sys.expose_on_top(wide, kind='Inout')
+    with pytest.raises(ValueError, match='64-bit elements, wider than the 32-bit'):
+        axil_regmap(sys)
+
+
+def test_bfm_transactions():
+    lines = _axil_bfm([('write', 20, 0x5a), ('wait', 2), ('read', 20)])
+    assert "    for op, *args in [('write', 20, 90), ('wait', 2), ('read', 20)]:" in lines
+    with pytest.raises(ValueError, match='Invalid AXI4-Lite transaction'):
+        _axil_bfm([('read', 20, 1)])
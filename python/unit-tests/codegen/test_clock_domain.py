@@ -0,0 +1,96 @@
+"""Test the checks of the clock domains and the harness of a two-domain design"""
+
+from fractions import Fraction
+
+import pytest
+
+from assassyn.analysis import check_clock_domains
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.codegen.verilog.testbench import generate_testbench
+from assassyn.frontend import Module, Port, RegArray, SysBuilder, UInt, log, module
+
+
+class Consumer(Module):
+    """Logs its data in the slow domain"""
+    def __init__(self, domain='slow'):
+        super().__init__(ports={'data': Port(UInt(8))}, clock_domain=domain)
+
+    @module.combinational
+    def build(self):
+        log('data {}', self.data.pop())
+
+
+class Producer(Module):
+    """Calls the consumer with a counter, and writes `shared` if given"""
+    def __init__(self, domain=None):
+        super().__init__(ports={}, clock_domain=domain)
+
+    @module.combinational
+    def build(self, consumer, shared=None):
+        cnt = RegArray(UInt(8), 1)
+        cnt[0] = cnt[0] + UInt(8)(1)
+        if shared is not None:
+            shared[0] = cnt[0]
+        consumer.async_called(data=cnt[0])
+
+
+def _build(name='clock_domain'):
+    sys = SysBuilder(name)
+    with sys:
+        consumer = Consumer()
+        consumer.build()
+        Producer().build(consumer)
+    return sys
+
+
+def test_periods():
+    sys = _build()
+    assert check_clock_domains(sys, {'slow': '3/2', 'unused': 4}) == {'slow': Fraction(3, 2)}
+    for domains in [None, {'slow': Fraction(1, 2)}, {'slow': 'fast'}]:
+        with pytest.raises(ValueError):
+            check_clock_domains(sys, domains)
+
+
+def test_single_source():
+    sys = SysBuilder('clock_domain_sources')
+    with sys:
+        consumer = Consumer()
+        consumer.build()
+        Producer().build(consumer)
+        Producer('other').build(consumer)
+    with pytest.raises(ValueError, match='driven by a single domain'):
+        check_clock_domains(sys, {'slow': 2, 'other': 3})
+
+
+def test_array_single_domain():
+    sys = SysBuilder('clock_domain_arrays')
+    with sys:
+        shared = RegArray(UInt(8), 1)
+        consumer = Consumer(None)
+        consumer.build()
+        Producer().build(consumer, shared)
+        other = Consumer(None)
+        other.build()
+        Producer('other').build(other, shared)
+    with pytest.raises(ValueError, match='clocked by a single domain'):
+        check_clock_domains(sys, {'other': 2})
+
+
+def test_harness(tmp_path):
+    sys = _build()
+    logs = generate_design(str(tmp_path / 'design.py'), sys, clock_domains={'slow': 2},
+                           cdc_sync_stages=3)
+    code = (tmp_path / 'design.py').read_text(encoding='utf-8')
+    assert 'clk_slow = Clock()' in code
+    fifo = code.split('fifo_ConsumerInstance_data_inst = ')[1].split('\n')[0]
+    assert fifo.startswith('AsyncFIFO(WIDTH=8, ')
+    assert 'SYNC_STAGES=3' in fifo and 'push_clk=self.clk,' in fifo
+    assert 'pop_clk=self.clk_slow' in fifo
+    assert 'ConsumerInstance_trigger_counter_inst = AsyncFIFO(WIDTH=1' in code
+    assert 'inst_ConsumerInstance = ConsumerInstance(clk=self.clk_slow' in code
+    # The log of the slow module is printed once per cycle of its clock
+    assert any('* 1 % 2 < 1' in i for i in logs)
+
+    generate_testbench(tmp_path / 'tb.py', sys, 10, logs, [], clock_domains={'slow': 2})
+    tb = (tmp_path / 'tb.py').read_text(encoding='utf-8')
+    assert "Clock(dut.clk_slow, 2000000, units='ps')" in tb
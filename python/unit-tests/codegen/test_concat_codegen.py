@@ -0,0 +1,65 @@
+"""Verilog codegen flattens chains of binary Concat nodes."""
+
+import os
+import sys
+
+sys.path.append(os.path.join(os.path.dirname(__file__), '..', '..'))
+
+from assassyn.frontend import Module, Port, SysBuilder, UInt, log, module  # type: ignore
+from assassyn.ir.expr.comm import concat
+from assassyn.codegen.verilog.design import CIRCTDumper  # type: ignore
+from assassyn.codegen.verilog.analysis import collect_fifo_metadata  # type: ignore
+
+
+def _render(body):
+    sys_builder = SysBuilder("concat_chain_codegen")
+    with sys_builder:
+
+        class Packer(Module):  # type: ignore[misc]
+
+            def __init__(self, emit):
+                super().__init__(ports={
+                    'a': Port(UInt(2)),
+                    'b': Port(UInt(3)),
+                    'c': Port(UInt(4)),
+                    'd': Port(UInt(5)),
+                })
+                self.emit = emit
+
+            @module.combinational
+            def build(self):
+                self.emit(self.a.pop(), self.b.pop(), self.c.pop(), self.d.pop())
+
+        Packer(body).build()
+
+    mod = sys_builder.modules[0]
+    module_metadata, interactions = collect_fifo_metadata(sys_builder)
+    dumper = CIRCTDumper(module_metadata=module_metadata, interactions=interactions)
+    dumper.sys = sys_builder
+    dumper.array_metadata.collect(sys_builder)
+    dumper.visit_module(mod)
+    return [line.strip() for line in dumper.code if 'BitsSignal.concat' in line]
+
+
+def test_concat_chain_is_flat():
+    """concat(a, b, c, d) emits one four-operand concat."""
+    def build(a, b, c, d):
+        packed = concat(a, b, c, d)
+        log("packed: {}", packed)
+
+    lines = _render(build)
+    assert len(lines) == 1, lines
+    operands = lines[0].split('BitsSignal.concat([')[1].rsplit('])', 1)[0]
+    # The most significant operand comes first
+    assert operands.split(', ') == [f'self.{name}.as_bits()' for name in 'abcd']
+
+
+def test_shared_inner_concat_is_kept():
+    """An inner Concat with another user keeps its own wire."""
+    def build(a, b, c, _):
+        inner = concat(a, b)
+        outer = concat(inner, c)
+        log("{} {}", inner, outer)
+
+    lines = _render(build)
+    assert len(lines) == 2, lines
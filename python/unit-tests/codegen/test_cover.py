@@ -0,0 +1,65 @@
+"""Test the coverage outputs of the condition blocks and their counters in the testbenches"""
+
+from assassyn.analysis import cover_key, cover_points
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.codegen.verilog.testbench import generate_testbench, generate_vcs_script
+from assassyn.frontend import Condition, Else, Module, RegArray, SysBuilder, UInt, log, module
+
+
+class Driver(Module):
+    """Logs whether its counter is even, and when it wraps"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(8), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+        with Condition(~cnt[0][0:0]):
+            log('even {}', cnt[0])
+            with Condition(cnt[0] == UInt(8)(254)):
+                log('wrap')
+        with Else():
+            log('odd {}', cnt[0])
+
+
+def test_cover_points():
+    with SysBuilder('cover_points'):
+        driver = Driver()
+        driver.build()
+    points = cover_points(driver)
+    assert [field for field, _ in points] == ['cover_0', 'cover_1', 'cover_2']
+    assert all(block.loc and 'test_cover.py' in block.loc for _, block in points)
+    assert cover_key(driver, 'cover_2') == 'Driver_cover_2'
+
+
+def test_verilog_cover(tmp_path):
+    sys = SysBuilder('verilog_cover')
+    with sys:
+        Driver().build()
+    logs = generate_design(str(tmp_path / 'design.py'), sys, cover=True)
+    code = (tmp_path / 'design.py').read_text(encoding='utf-8')
+    code = code.split('class Driver')[1].split('\nclass ')[0]
+    for idx in range(3):
+        assert f'cover_{idx} = Output(Bits(1))' in code
+        assert f'self.cover_{idx} = executed_wire & (' in code
+
+    generate_testbench(tmp_path / 'tb.py', sys, 10, logs, [], cover=True)
+    tb = (tmp_path / 'tb.py').read_text(encoding='utf-8')
+    compile(tb, 'tb.py', 'exec')
+    assert "cover_hits['Driver_cover_1'] += int(dut.Driver.cover_1.value)" in tb
+    assert "print(f'[stats] cover {key} {hits}')" in tb
+    assert "print('[cover] untaken Driver_cover_2 at ' + " in tb
+
+    generate_vcs_script(tmp_path, 10, [], sys, cover=True)
+    vcs = (tmp_path / 'tb_vcs.sv').read_text(encoding='utf-8')
+    assert 'int Driver_cover_0 = 0;' in vcs
+    assert 'Driver_cover_0 += tb.dut.Driver.cover_0;' in vcs
+    assert '$display("[stats] cover Driver_cover_0 %0d", Driver_cover_0);' in vcs
+    assert vcs.index('[cover] untaken Driver_cover_0') < vcs.index('$finish;')
+
+    # Without the option, nothing changes
+    logs = generate_design(str(tmp_path / 'design.py'), sys)
+    assert 'cover_' not in (tmp_path / 'design.py').read_text(encoding='utf-8')
+    generate_testbench(tmp_path / 'tb.py', sys, 10, logs, [])
+    assert 'cover_' not in (tmp_path / 'tb.py').read_text(encoding='utf-8')
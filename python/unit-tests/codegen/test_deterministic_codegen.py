@@ -0,0 +1,95 @@
+"""Test that elaborating the same system twice generates the same code"""
+
+import os
+import subprocess
+import sys as py_sys
+from pathlib import Path
+
+from assassyn.backend import config, elaborate
+from assassyn.frontend import (Downstream, Module, Port, RegArray, SysBuilder, UInt, Value,
+                               downstream, log, module)
+from assassyn.utils import repo_path
+
+
+class Sink(Module):
+    """Adds its three inputs"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8)), 'b': Port(UInt(8)), 'c': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        a, b, c = self.pop_all_ports(True)
+        log('sum {}', a + b + c)
+
+
+class Source(Module):
+    """Counts and feeds every input of the sink"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, sink: Sink):
+        cnt = RegArray(UInt(8), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+        sink.async_called(a=cnt[0], b=cnt[0] + UInt(8)(1), c=cnt[0] + UInt(8)(2))
+        return cnt[0]
+
+
+class Merge(Downstream):
+    """Combines the values of two sources"""
+    def __init__(self):
+        super().__init__()
+
+    @downstream.combinational
+    def build(self, x: Value, y: Value):
+        x = x.optional(UInt(8)(0))
+        y = y.optional(UInt(8)(0))
+        log('merge {}', x + y)
+
+
+def _elaborate(path):
+    sys = SysBuilder('deterministic')
+    with sys:
+        sink = Sink()
+        sink.build()
+        x = Source().build(sink)
+        y = Source().build(sink)
+        Merge().build(x, y)
+    cfg = config(path=str(path), verilog=True, verbose=False, enable_cache=False)
+    elaborate(sys, **cfg)
+    return _read_tree(path)
+
+
+def _read_tree(path):
+    return {p.relative_to(path): p.read_bytes() for p in sorted(path.rglob('*')) if p.is_file()}
+
+
+def test_same_process(tmp_path):
+    first = _elaborate(tmp_path / 'first')
+    second = _elaborate(tmp_path / 'second')
+    assert first and first == second
+
+
+# Elaborates the minor CPU without building the simulator binary
+_MINOR_CPU = '''
+import sys
+sys.path.insert(0, sys.argv[1])
+from assassyn import utils
+import main
+utils.build_simulator = lambda path: None
+main.init_workspace(f'{sys.argv[1]}/../unit-tests', 'rv32ui-p-add')
+main.build_cpu(depth_log=16, path=sys.argv[2], verilog=True, enable_cache=False, verbose=False)
+'''
+
+
+def test_across_processes(tmp_path):
+    src = Path(repo_path()) / 'examples' / 'minor-cpu' / 'src'
+    trees = []
+    # Different hash seeds give the sets of strings a different order
+    for seed in ['1', '2']:
+        out = tmp_path / seed
+        env = dict(os.environ, PYTHONHASHSEED=seed)
+        subprocess.run([py_sys.executable, '-c', _MINOR_CPU, str(src), str(out)],
+                       env=env, check=True, capture_output=True)
+        trees.append(_read_tree(out))
+    assert trees[0] and trees[0] == trees[1]
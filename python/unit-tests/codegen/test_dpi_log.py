@@ -0,0 +1,102 @@
+"""Test printing the logs of the VCS testbench through the DPI-C function assassyn_log"""
+
+import re
+import shutil
+import subprocess
+
+import pytest
+
+from assassyn.frontend import Condition, Int, Module, Port, SysBuilder, UInt, log, module
+from assassyn.codegen.verilog.dpi_log import (LOG_DPI_SOURCE, read_log_records, sv_format,
+                                              sv_spec)
+from assassyn.codegen.verilog.elaborate import elaborate
+from assassyn.utils import repo_path
+
+
+class Decoder(Module):
+    """Logs an unsigned, a signed and a hexadecimal field, one under a condition"""
+    def __init__(self):
+        super().__init__(ports={'inst': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        inst = self.pop_all_ports(True)
+        imm = inst[20:31].bitcast(Int(12))
+        log('decode: rd={} imm={} raw=0x{:08x} 100%', inst[7:11], imm, inst)
+        with Condition(inst[0:0]):
+            log('odd: {:b}', inst[0:6])
+
+
+class Driver(Module):
+    """Feeds the decoder"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, decoder):
+        decoder.async_called(inst=UInt(32)(0x12345))
+
+
+def _elaborate(path):
+    sys = SysBuilder('dpi_log')
+    with sys:
+        decoder = Decoder()
+        decoder.build()
+        Driver().build(decoder)
+    return elaborate(sys, path=str(path), sim_threshold=10, verilog='vcs', log_dpi=True)
+
+
+def _literals(fmt, field):
+    '''The literal text of `fmt` around its fields, matched by `field`.'''
+    return re.split(field, fmt)
+
+
+def test_specs():
+    assert sv_spec(None) == sv_spec('') == sv_spec('?') == '%0d'
+    assert sv_spec('x') == '%0x' and sv_spec('08x') == '%08x' and sv_spec('5') == '%5d'
+    assert sv_spec('x', frac=4) == '%.4f'
+    with pytest.raises(ValueError, match='no \\$sformatf equivalent'):
+        sv_spec('>8s')
+    assert sv_format('a {} b {:x} 5%', ['%0d', '%0x']) == 'a %0d b %0x 5%%'
+
+
+def test_same_messages(tmp_path):
+    path = _elaborate(tmp_path)
+    tb = (path / 'tb_vcs.sv').read_text(encoding='utf-8')
+    assert 'import "DPI-C" function void assassyn_log(' in tb
+    assert '`include "dpi_logs.svh"' in tb
+    assert LOG_DPI_SOURCE in (path / 'run_vcs.sh').read_text(encoding='utf-8')
+    assert (path / LOG_DPI_SOURCE).is_file()
+
+    calls = [i for i in (path / 'dpi_logs.svh').read_text(encoding='utf-8').split('\n')
+             if 'assassyn_log(' in i]
+    prints = [i.strip() for i in (path / 'tb.py').read_text(encoding='utf-8').split('\n')
+              if i.strip().startswith('print(f"@line')]
+    assert len(calls) == len(prints) == 2
+    for call, line in zip(calls, prints):
+        fmt = re.search(r'\$sformatf\("((?:[^"\\]|\\.)*)"', call).group(1).replace('%%', '%')
+        printed = line[len('print(f"'):-len('")')]
+        # The same text around the fields, which are printed by $sformatf or by cocotb, the
+        # cycle being printed as a float by the latter
+        assert _literals(fmt, r'%0d\.00|%[0-9.]*[dxbf]') == \
+            _literals(printed, r'\{[^}]*\}'), call
+    assert '$signed(dut.DecoderInstance.expose_' in calls[0]
+    assert '%08x 100%%' in calls[0]
+    assert calls[1].startswith('if (dut.DecoderInstance.valid_') and '%0b' in calls[1]
+
+
+def test_records(tmp_path):
+    if shutil.which('cc') is None:
+        return
+    main = tmp_path / 'main.c'
+    main.write_text('void assassyn_log(int, const char *, const char *);\n'
+                    'int main(void) {\n'
+                    '  assassyn_log(0, "Decoder", "decode: rd=1");\n'
+                    '  assassyn_log(2, "Driver", "");\n'
+                    '  return 0;\n'
+                    '}\n', encoding='utf-8')
+    source = f'{repo_path()}/python/assassyn/codegen/verilog/{LOG_DPI_SOURCE}'
+    subprocess.run(['cc', '-o', str(tmp_path / 'main'), str(main), source], check=True)
+    subprocess.run([str(tmp_path / 'main')], check=True, cwd=tmp_path)
+    assert read_log_records(tmp_path / 'assassyn_log.bin') == [
+        (0, 'Decoder', 'decode: rd=1'), (2, 'Driver', '')]
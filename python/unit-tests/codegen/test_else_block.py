@@ -0,0 +1,77 @@
+"""Test else blocks paired with condition blocks."""
+
+import os
+import sys
+
+import pytest
+
+sys.path.append(os.path.join(os.path.dirname(__file__), '..', '..'))
+
+from assassyn.frontend import (
+    Condition, Else, Module, Port, SysBuilder, UInt, log, module,
+)
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.ir.block import verify_blocks
+from assassyn.ir.expr import UnaryOp
+from assassyn.ir.expr.intrinsic import Intrinsic
+
+
+class Branch(Module):
+    def __init__(self):
+        super().__init__(ports={'c': Port(UInt(1))})
+
+    @module.combinational
+    def build(self):
+        c = self.c.pop()
+        with Condition(c):
+            log('then')
+        with Else():
+            log('else')
+
+
+def test_else_negates_condition(tmp_path):
+    sysb = SysBuilder('else_block')
+    with sysb:
+        branch = Branch()
+        branch.build()
+    verify_blocks(branch)
+    push_else = next(expr for expr in branch.body
+                     if isinstance(expr, Intrinsic) and expr.opcode == Intrinsic.PUSH_ELSE)
+    then = push_else.matching
+    assert then.args[0].value is push_else.args[0].value
+    # The else predicate is the intrinsic itself, not a flip of the condition
+    assert not any(isinstance(expr, UnaryOp) for expr in branch.body)
+    path = tmp_path / 'design.py'
+    generate_design(str(path), sysb)
+    code = path.read_text(encoding='utf-8')
+    assert 'c_1_else = ~(self.c.as_bits())' in code, code
+
+
+def test_else_without_condition():
+    sysb = SysBuilder('else_orphan')
+    with sysb:
+        class Orphan(Module):
+            def __init__(self):
+                super().__init__(ports={'c': Port(UInt(1))})
+
+            @module.combinational
+            def build(self):
+                c = self.c.pop()
+                log('no block {}', c)
+                with Else():
+                    log('else')
+
+        with pytest.raises(ValueError, match='must directly follow'):
+            Orphan().build()
+
+
+def test_verify_unpaired_else():
+    with SysBuilder('unpaired_else'):
+        branch = Branch()
+        branch.build()
+    # Moving the else block away from its condition block breaks the pairing
+    idx = next(i for i, expr in enumerate(branch.body)
+               if isinstance(expr, Intrinsic) and expr.opcode == Intrinsic.PUSH_ELSE)
+    branch.body.insert(idx, branch.body[0])
+    with pytest.raises(ValueError, match='must directly follow'):
+        verify_blocks(branch)
@@ -0,0 +1,59 @@
+"""Test the FIRRTL backend on a single arithmetic module"""
+
+import shutil
+import subprocess
+
+from assassyn.backend import config, elaborate
+from assassyn.codegen.firrtl import generate_firrtl
+from assassyn.frontend import Int, Module, Port, SysBuilder, UInt, log, module
+
+
+class Arith(Module):
+    """Combines its two inputs"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(Int(32)), 'b': Port(Int(32))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        c = a + b
+        d = (a * b)[0:15]
+        e = c.bitcast(UInt(32)) >> UInt(5)(3)
+        f = (a < b).select(d, e[0:15])
+        g = a ^ b
+        log('{} {} {}', c, f, g)
+
+
+def test_structure():
+    sys = SysBuilder('firrtl_structure')
+    with sys:
+        Arith().build()
+    code = generate_firrtl(sys)
+    lines = code.splitlines()
+    assert lines[:3] == ['FIRRTL version 3.3.0', 'circuit Top :', '  module ArithInstance :']
+    for port in ['input a : SInt<32>', 'input a_valid : UInt<1>', 'output a_pop_ready : UInt<1>',
+                 'input trigger_counter_pop_valid : UInt<1>', 'output executed : UInt<1>']:
+        assert f'    {port}' in lines, port
+    # The widened sum is truncated back to 32 bits
+    assert '    node _c = asSInt(bits(pad(add(_a, _b), 32), 31, 0))' in lines
+    assert '    node _d = bits(_a_mul_b, 15, 0)' in lines
+    assert any(i.startswith('    node _f = mux(') for i in lines)
+    # A bitwise operation of signed operands is fitted to the type of the IR
+    assert '    node _g = bits(pad(xor(_a, _b), 32), 31, 0)' in lines
+    # The pops wait for both inputs
+    assert '    connect _executed, and(trigger_counter_pop_valid, _a_valid_and__b_valid)' in lines
+    assert '  public module Top :' in lines
+    assert '    inst ArithInstance of ArithInstance' in lines
+
+
+def test_elaborate(tmp_path):
+    cfg = config(path=str(tmp_path), simulator=False, firrtl=True, verbose=False,
+                 enable_cache=False)
+    sys = SysBuilder('firrtl_arith')
+    with sys:
+        Arith().build()
+    elaborate(sys, **cfg)
+    fir = tmp_path / 'firrtl_arith' / 'firrtl' / 'firrtl_arith.fir'
+    assert fir.read_text(encoding='utf-8').startswith('FIRRTL version')
+    if shutil.which('firtool'):
+        subprocess.run(['firtool', str(fir), '-o', str(tmp_path / 'Top.sv')], check=True)
@@ -0,0 +1,128 @@
+"""Test the SymbiYosys harness of the assertions and wait_until conditions"""
+
+import re
+import shlex
+
+import pytest
+
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.codegen.verilog.formal import (FORMAL_TOP, formal_properties, generate_formal,
+                                             top_formal_port, write_sby)
+from assassyn.frontend import (Module, Port, RegArray, SysBuilder, UInt, assume, module,
+                               wait_until)
+
+
+class Checker(Module):
+    """Waits for its data and checks it is below 100"""
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        wait_until(self.data.valid())
+        x = self.data.pop()
+        assume(x < UInt(8)(100))
+
+
+class Driver(Module):
+    """Counts and sends the count to the checker"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, checker: Checker):
+        cnt = RegArray(UInt(8), 1, name='cnt')
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+        checker.async_called(data=cnt[0])
+        return cnt
+
+
+def _build(expose=False):
+    sys = SysBuilder('formal')
+    with sys:
+        checker = Checker()
+        checker.build()
+        cnt = Driver().build(checker)
+        if expose:
+            sys.expose_on_top(cnt, kind='Input')
+    return sys, checker
+
+
+def _sections(text):
+    '''The lines of each [section] of a SymbiYosys script.'''
+    res = {}
+    for line in text.splitlines():
+        if re.fullmatch(r'\[\w+\]', line):
+            section = res.setdefault(line[1:-1], [])
+        elif line:
+            section.append(line)
+    return res
+
+
+def test_properties(tmp_path):
+    sys, checker = _build()
+    (wait_field, _), (assert_field, _) = formal_properties(checker)
+    assert (wait_field, assert_field) == ('formal_assume_0', 'formal_assert_1')
+    path = tmp_path / 'design.py'
+    generate_design(str(path), sys, formal=True)
+    code = path.read_text(encoding='utf-8')
+    module_code = code.split(f'class {checker.name}')[1].split('\nclass ')[0]
+    for field in (wait_field, assert_field):
+        assert f'{field} = Output(Bits(1))' in module_code
+        assert f'self.{field} = ~(executed_wire & (' in module_code
+        port = top_formal_port(checker, field)
+        assert f'{port} = Output(Bits(1))' in code
+        assert f'self.{port} = inst_{checker.name}.{field}' in code
+    # Without the option, nothing changes
+    generate_design(str(path), sys)
+    assert 'formal_' not in path.read_text(encoding='utf-8')
+
+
+def test_formal_top(tmp_path):
+    sys, checker = _build(expose=True)
+    generate_formal(tmp_path, sys, 12, ['ext.sv'], axi_lite=True)
+    top = (tmp_path / f'{FORMAL_TOP}.sv').read_text(encoding='utf-8')
+    assert f'module {FORMAL_TOP} (' in top and 'Top dut (.clk(clk), .rst(rst)' in top
+    assert f'assume property (rst || {top_formal_port(checker, "formal_assume_0")});' in top
+    assert f'assert property (rst || {top_formal_port(checker, "formal_assert_1")});' in top
+    # The inputs of the AXI4-Lite slave are free, its outputs unconnected
+    assert '(* anyseq *) logic [0:0] s_axil_awvalid;' in top
+    assert '.s_axil_awvalid(s_axil_awvalid)' in top and 's_axil_rdata' not in top
+
+
+def test_sby(tmp_path):
+    sys, _ = _build()
+    generate_formal(tmp_path, sys, 12, ['ext.sv'])
+    script = (tmp_path / 'run_formal.sh').read_text(encoding='utf-8')
+    assert script.rstrip().endswith(f'sby -f {sys.name}.sby')
+    # The command writing the .sby is valid Python
+    command = script.replace('\\\n', '').split('python -c ')[-1].split('\n')[0]
+    compile(shlex.split(command)[0], 'run_formal.sh', 'exec')
+
+    # What run_formal.sh finds once design.py ran
+    (tmp_path / 'sv' / 'hw').mkdir(parents=True)
+    (tmp_path / 'sv' / 'hw' / 'filelist.f').write_text('Checker.sv\nTop.sv\n', encoding='utf-8')
+    (tmp_path / 'sram_blackbox_mem.sv').write_text('', encoding='utf-8')
+    sby = write_sby(tmp_path, sys.name, 12, ['ext.sv'])
+    sections = _sections(sby.read_text(encoding='utf-8'))
+    assert sections['options'] == ['mode bmc', 'depth 12'] and sections['engines'] == ['smtbmc']
+    files = sections['files']
+    assert files == ['sv/hw/Checker.sv', 'sv/hw/Top.sv', 'sram_blackbox_mem.sv', 'fifo.sv',
+                     'trigger_counter.sv', 'ext.sv', f'{FORMAL_TOP}.sv']
+    # Each file is read by its base name, the one SymbiYosys copies it to, before the top
+    reads = [i.split()[-1] for i in sections['script'][:-1]]
+    assert reads == [i.split('/')[-1] for i in files]
+    assert all(i.startswith('read -formal ') for i in sections['script'][:-1])
+    assert sections['script'][-1] == f'prep -top {FORMAL_TOP}'
+
+
+def test_clock_domains(tmp_path):
+    sys, checker = _build()
+    with pytest.raises(ValueError, match='default clock'):
+        generate_formal(tmp_path, sys, 12, [], clock_domains={checker.name: 2})
+
+
+def test_reset_style(tmp_path):
+    sys, _ = _build()
+    with pytest.raises(ValueError, match='async_low'):
+        generate_formal(tmp_path, sys, 12, [], reset_style='sync_high')
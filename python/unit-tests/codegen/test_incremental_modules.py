@@ -0,0 +1,58 @@
+"""Test the incremental dump of the simulator module files"""
+
+from assassyn.codegen.simulator.modules import dump_modules
+from assassyn.codegen.simulator.port_mapper import reset_port_manager
+from assassyn.frontend import Module, Port, RegArray, SysBuilder, UInt, log, module
+
+
+class Offset(Module):
+    """Logs its input plus a constant"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8))})
+
+    @module.combinational
+    def build(self, delta: int):
+        a = self.pop_all_ports(True)
+        log('sum {}', a + UInt(8)(delta))
+
+
+class Driver(Module):
+    """Counts and calls the offset"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, offset: Offset):
+        cnt = RegArray(UInt(8), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+        offset.async_called(a=cnt[0])
+
+
+def _build(delta):
+    sys = SysBuilder('incremental')
+    with sys:
+        offset = Offset()
+        offset.build(delta)
+        Driver().build(offset)
+    return sys
+
+
+def test_fingerprint_is_stable():
+    first, second, changed = _build(1), _build(1), _build(2)
+    fingerprints = [[s.compute_fingerprint(m) for m in s.modules] for s in [first, second]]
+    assert fingerprints[0] == fingerprints[1]
+    offset, driver = changed.modules
+    assert changed.compute_fingerprint(offset) != fingerprints[0][0]
+    assert changed.compute_fingerprint(driver) == fingerprints[0][1]
+
+
+def test_unchanged_modules_are_kept(tmp_path):
+    reset_port_manager()
+    dump_modules(_build(1), tmp_path, incremental=True)
+    stamps = {p.name: p.stat().st_mtime_ns for p in tmp_path.glob('*.rs')}
+    (tmp_path / 'Stale.rs').write_text('', encoding='utf-8')
+    reset_port_manager()
+    dump_modules(_build(2), tmp_path, incremental=True)
+    assert not (tmp_path / 'Stale.rs').exists()
+    changed = [p.name for p in tmp_path.glob('*.rs') if p.stat().st_mtime_ns != stamps[p.name]]
+    assert sorted(changed) == sorted(['mod.rs', f'{_build(2).modules[0].name}.rs'])
@@ -0,0 +1,56 @@
+"""Test that names colliding with keywords give valid generated code"""
+
+import ast
+
+from assassyn.frontend import Module, Port, RegArray, SysBuilder, UInt, module
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.utils import namify
+
+
+class Consumer(Module):
+    """Pops a port named after a keyword of Rust"""
+    def __init__(self):
+        super().__init__(ports={'match': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        self.pop_all_ports(True)
+
+
+class Driver(Module):
+    """Calls the consumer"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, consumer):
+        wire = RegArray(UInt(8), 1)
+        wire[0] = wire[0] + UInt(8)(1)
+        consumer.async_called(match=wire[0])
+
+
+def test_namify_keywords():
+    assert namify('type') == 'type_'
+    assert namify('module') == 'module_'
+    assert namify('None') == 'None_'
+    assert namify('a.b') == 'a_b'
+    assert namify('types') == 'types'
+    # Already renamed names stay as they are
+    assert namify(namify('loop')) == 'loop_'
+
+
+def test_keyword_module_design(tmp_path):
+    sys = SysBuilder('keyword_names')
+    with sys:
+        consumer = Consumer()
+        consumer.build()
+        consumer.name = 'type'
+        Driver().build(consumer)
+
+    path = tmp_path / 'design.py'
+    generate_design(str(path), sys)
+    code = path.read_text(encoding='utf-8')
+    ast.parse(code)
+    assert 'class type_(Module):' in code
+    assert 'match_ = Input(UInt(8))' in code
+    assert "wire_ = build_register_file(\n    'wire_'," in code
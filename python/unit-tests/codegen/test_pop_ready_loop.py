@@ -0,0 +1,53 @@
+"""Test the detection of combinational loops through pop_ready."""
+
+import os
+import sys
+
+import pytest
+
+sys.path.append(os.path.join(os.path.dirname(__file__), '..', '..'))
+
+from assassyn.frontend import Module, SysBuilder, UInt, Port, module
+from assassyn.codegen.verilog.design import generate_design
+
+
+class Consumer(Module):
+    def __init__(self, flow_through):
+        super().__init__(ports={'data': Port(UInt(8))}, flow_through=flow_through)
+
+    @module.combinational
+    def build(self):
+        # Waits for valid, so pop_ready depends on the valid of the FIFO
+        self.pop_all_ports(True)
+
+
+class Driver(Module):
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, consumer):
+        consumer.async_called(data=UInt(8)(1))
+
+
+def _build(name, flow_through):
+    sysb = SysBuilder(name)
+    with sysb:
+        consumer = Consumer(flow_through)
+        consumer.build()
+        Driver().build(consumer)
+    return sysb
+
+
+def test_pop_ready_loop_rejected(tmp_path):
+    sysb = _build('pop_ready_loop', True)
+    with pytest.raises(ValueError, match='Combinational loop on'):
+        generate_design(str(tmp_path / 'design.py'), sysb, default_fifo_depth=0)
+
+
+def test_pop_ready_no_loop(tmp_path):
+    # A registered valid, either from a deeper FIFO or without the bypass, breaks the loop
+    generate_design(str(tmp_path / 'deep.py'), _build('pop_ready_deep', True),
+                    default_fifo_depth=1)
+    generate_design(str(tmp_path / 'registered.py'), _build('pop_ready_reg', False),
+                    default_fifo_depth=0)
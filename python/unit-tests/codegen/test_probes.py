@@ -0,0 +1,85 @@
+"""Test the probes dumped to the waveform of the Verilog simulation"""
+
+import pytest
+
+from assassyn.frontend import Module, RegArray, SysBuilder, UInt, module, probe
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.codegen.verilog.testbench import generate_testbench, generate_vcs_script
+
+
+class Driver(Module):
+    """Probes the next value of its counter"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(8), 1)
+        nxt = cnt[0] + UInt(8)(1)
+        (cnt & self)[0] <= nxt
+        return cnt, nxt
+
+
+def test_add_probe_rejects():
+    sys = SysBuilder('add_probe_rejects')
+    with sys:
+        cnt, nxt = Driver().build()
+        sys.add_probe(nxt, 'next_cnt')
+    with pytest.raises(ValueError, match='already taken'):
+        sys.add_probe(nxt, 'next_cnt')
+    with pytest.raises(ValueError, match='not an identifier'):
+        sys.add_probe(nxt, 'next cnt')
+    with pytest.raises(ValueError, match='Only a value'):
+        sys.add_probe(cnt, 'cnt')
+    assert [label for _, label in sys.probes] == ['next_cnt']
+    assert 'probe next_cnt = Driver.' in repr(sys)
+
+
+def test_default_label():
+    sys = SysBuilder('probe_label')
+    with sys:
+        _, nxt = Driver().build()
+        assert probe(nxt) is nxt
+    assert [label for _, label in sys.probes] == ['Driver_nxt']
+    with pytest.raises(RuntimeError, match='not initialised'):
+        probe(nxt)
+
+
+def test_verilog_probe(tmp_path):
+    sys = SysBuilder('verilog_probe')
+    with sys:
+        _, nxt = Driver().build()
+        sys.add_probe(nxt, 'next_cnt')
+    logs = generate_design(str(tmp_path / 'design.py'), sys)
+    code = (tmp_path / 'design.py').read_text(encoding='utf-8')
+    code = code.split('class Driver')[1].split('\nclass ')[0]
+    assert 'probe_next_cnt = Output(Bits(8))' in code
+    assert 'self.probe_next_cnt = ' in code
+    assert 'probe_next_cnt_valid = Output(Bits(1))' in code
+    assert 'self.probe_next_cnt_valid = executed_wire & (' in code
+
+    generate_testbench(tmp_path / 'tb.py', sys, 10, logs, [])
+    tb = (tmp_path / 'tb.py').read_text(encoding='utf-8')
+    compile(tb, 'tb.py', 'exec')
+    assert "probes.write('$scope module Driver $end\\n')" in tb
+    assert "probes.write('$var wire 8 ! probe_next_cnt $end\\n')" in tb
+    assert "probes.write(f'b{dut.Driver.probe_next_cnt.value} !\\n')" in tb
+    assert 'probes.close()' in tb
+    assert 'if dut.Driver.probe_next_cnt_valid.value:' in tb
+    assert 'print(f"@probe {int(dut.global_cycle_count.value)} next_cnt ' in tb
+
+    generate_vcs_script(tmp_path, 10, [], sys)
+    vcs = (tmp_path / 'tb_vcs.sv').read_text(encoding='utf-8')
+    assert '$fsdbDumpvars(0, tb.dut.Driver.probe_next_cnt);' in vcs
+
+
+def test_no_probes(tmp_path):
+    sys = SysBuilder('no_probes')
+    with sys:
+        Driver().build()
+    generate_testbench(tmp_path / 'tb.py', sys, 10, [], [])
+    tb = (tmp_path / 'tb.py').read_text(encoding='utf-8')
+    compile(tb, 'tb.py', 'exec')
+    assert 'probes' not in tb
+    generate_vcs_script(tmp_path, 10, [], sys)
+    assert 'fsdb' not in (tmp_path / 'tb_vcs.sv').read_text(encoding='utf-8')
@@ -0,0 +1,70 @@
+"""Test the flops of a registered value in its producer and the valid its consumer reads"""
+
+import pytest
+
+from assassyn.analysis import get_upstreams, registered_inputs
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.frontend import (Condition, Downstream, Module, Port, SysBuilder, UInt, Value,
+                               downstream, log, module)
+
+
+class Producer(Module):
+    """Triples its input"""
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        data = self.pop_all_ports(True)
+        return data + data + data
+
+
+class Consumer(Downstream):
+    """Logs the value of the producer when it is valid"""
+    def __init__(self):
+        super().__init__()
+
+    @downstream.combinational
+    def build(self, value: Value):
+        with Condition(value.valid()):
+            log('consumer {}', value)
+
+
+def _build(registered):
+    sys = SysBuilder('registered')
+    with sys:
+        producer = Producer()
+        value = producer.build()
+        if registered:
+            sys.register_value(value)
+        consumer = Consumer()
+        consumer.build(value)
+    return sys, producer, consumer, value
+
+
+def test_register_value():
+    sys, producer, consumer, value = _build(True)
+    assert value.registered and sys.registered_values == [value]
+    assert f'registered {producer.name}.{value.as_operand()}' in repr(sys)
+    assert registered_inputs(consumer) == [value]
+    # The consumer reads the value of the previous cycle, so it need not follow the producer
+    assert producer not in get_upstreams(consumer)
+    with pytest.raises(ValueError):
+        sys.register_value(UInt(8)(1))
+
+
+def test_verilog_registered(tmp_path):
+    sys, _, _, value = _build(True)
+    generate_design(str(tmp_path / 'design.py'), sys)
+    code = (tmp_path / 'design.py').read_text(encoding='utf-8')
+    producer, consumer = code.split('class Consumer')
+    name = value.as_operand()
+    assert f'expose_ProducerInstance_{name}_reg = Reg(UInt(8)' in producer
+    assert f'valid_ProducerInstance_{name}_reg = Reg(Bits(1)' in producer
+    # The consumer executes on the registered valid, and does not delay the value again
+    assert f'[self.ProducerInstance_{name}_valid]' in consumer
+    assert '_reg = Reg(' not in consumer
+
+    sys, _, _, value = _build(False)
+    generate_design(str(tmp_path / 'design.py'), sys)
+    assert '_reg = Reg(' not in (tmp_path / 'design.py').read_text(encoding='utf-8')
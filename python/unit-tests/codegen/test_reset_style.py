@@ -0,0 +1,73 @@
+"""Test the synchronous active-high reset of the FIFOs and trigger counters"""
+
+import re
+
+import pytest
+
+from assassyn.frontend import Module, Port, SysBuilder, UInt, module
+from assassyn.codegen.verilog.elaborate import elaborate
+from assassyn.codegen.verilog.reset import check_reset_style
+
+
+class Sink(Module):
+    """Pops two operands"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(32)), 'b': Port(UInt(32))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        _ = a + b
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, sink: Sink):
+        sink.async_called(a=UInt(32)(1), b=UInt(32)(2))
+
+
+def _elaborate(path, **kwargs):
+    sys = SysBuilder('reset_style')
+    with sys:
+        sink = Sink()
+        sink.build()
+        Driver().build(sink)
+    return elaborate(sys, path=str(path), sim_threshold=10, **kwargs)
+
+
+def test_sync_high(tmp_path):
+    path = _elaborate(tmp_path, reset_style='sync_high')
+    for resource in ['fifo.sv', 'trigger_counter.sv']:
+        code = (path / resource).read_text(encoding='utf-8')
+        assert 'always @(posedge clk) begin\n' in code
+        assert 'if (rst) begin' in code
+        assert re.search(r'input\s+logic\s+rst,', code)
+        assert 'rst_n' not in code and 'negedge' not in code
+    code = (path / 'async_fifo.sv').read_text(encoding='utf-8')
+    assert 'always @(posedge pop_clk) begin\n    if (pop_rst) begin' in code
+    assert 'push_rst_n' not in code
+    # Only the formal bound runs out of reset
+    assert 'always @(*) if (!rst) assert' in (path / 'fifo.sv').read_text(encoding='utf-8')
+
+    design = (path / 'design.py').read_text(encoding='utf-8')
+    assert 'sync_reset=True)(clk=self.clk, rst=self.rst, ' in design
+    assert 'rst_n' not in design.split('class Top')[1]
+
+
+def test_async_low(tmp_path):
+    path = _elaborate(tmp_path)
+    code = (path / 'fifo.sv').read_text(encoding='utf-8')
+    assert 'always @(posedge clk or negedge rst_n) begin' in code
+    assert 'if (!rst_n) begin' in code
+    design = (path / 'design.py').read_text(encoding='utf-8')
+    assert 'sync_reset' not in design and 'rst_n=~self.rst' in design
+
+
+def test_unknown_style():
+    check_reset_style('sync_high')
+    with pytest.raises(ValueError, match="not 'sync_low'"):
+        check_reset_style('sync_low')
@@ -0,0 +1,95 @@
+"""Test that the simulator crate is reused while its IR hash is unchanged"""
+
+from assassyn.codegen import simulator
+from assassyn.frontend import (Bits, Int, Module, Port, RegArray, SRAM, SysBuilder, UInt, log,
+                               module)
+
+
+class Offset(Module):
+    """Logs its input plus a constant"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8))})
+
+    @module.combinational
+    def build(self, delta: int):
+        a = self.pop_all_ports(True)
+        log('sum {}', a + UInt(8)(delta))
+
+
+class Driver(Module):
+    """Counts and calls the offset"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, offset: Offset):
+        cnt = RegArray(UInt(8), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+        offset.async_called(a=cnt[0])
+
+
+class Table(Module):
+    """Reads an initialized table and an initialized memory"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, data, init_file):
+        cnt = RegArray(UInt(2), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(2)(1)
+        table = RegArray(UInt(8), 4, initializer=data)
+        log('entry {}', table[cnt[0]])
+        sram = SRAM(8, 4, init_file)
+        sram.build(Bits(1)(0), Bits(1)(1), cnt[0].bitcast(Int(2)), Bits(8)(0))
+
+
+def _build(delta):
+    sys = SysBuilder('sim_cache')
+    with sys:
+        offset = Offset()
+        offset.build(delta)
+        Driver().build(offset)
+    return sys
+
+
+def test_reuse(tmp_path):
+    cfg = {'path': str(tmp_path), 'sim_threshold': 100, 'idle_threshold': 100}
+    manifest, reused = simulator.elaborate(_build(1), **cfg)
+    assert not reused
+    hash_path = manifest.parent / 'ir_hash.txt'
+    stamp = hash_path.stat().st_mtime_ns
+    # The same system is a no-op
+    again, reused = simulator.elaborate(_build(1), **cfg)
+    assert reused and again == manifest and hash_path.stat().st_mtime_ns == stamp
+    # So is an option that does not change the crate
+    assert simulator.elaborate(_build(1), **cfg, verbose=True)[1]
+    # A different constant changes the IR
+    assert not simulator.elaborate(_build(2), **cfg)[1]
+    assert simulator.elaborate(_build(2), **cfg)[1]
+    # So does an option of the backend
+    assert not simulator.elaborate(_build(2), **dict(cfg, sim_threshold=200))[1]
+    assert not simulator.elaborate(_build(2), **dict(cfg, sim_threshold=200),
+                                   force_regenerate=True)[1]
+
+
+def test_initializers(tmp_path):
+    init = tmp_path / 'init.hex'
+    init.write_text('0\n1\n2\n3\n', encoding='utf-8')
+    cfg = {'path': str(tmp_path), 'resource_base': str(tmp_path), 'sim_threshold': 10,
+           'idle_threshold': 10}
+
+    def build(data):
+        sys = SysBuilder('sim_cache_init')
+        with sys:
+            Table().build(data, 'init.hex')
+        return sys
+
+    manifest, _ = simulator.elaborate(build([0, 1, 2, 3]), **cfg)
+    # The printed IR is the same, but not the initializer
+    assert not simulator.elaborate(build([0, 9, 2, 3]), **cfg)[1]
+    code = (manifest.parent / 'src' / 'simulator.rs').read_text(encoding='utf-8')
+    assert 'vec![0u8, 9u8, 2u8, 3u8]' in code
+    assert simulator.elaborate(build([0, 9, 2, 3]), **cfg)[1]
+    # Nor the contents of the init file of the memory
+    init.write_text('3\n2\n1\n0\n', encoding='utf-8')
+    assert not simulator.elaborate(build([0, 9, 2, 3]), **cfg)[1]
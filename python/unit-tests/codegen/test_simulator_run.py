@@ -0,0 +1,46 @@
+"""Test building and running a simulator crate"""
+
+import pytest
+
+from assassyn.codegen.simulator.run import SimulatorBuildError, first_rustc_error, run_crate
+
+_LOG = '''   Compiling broken v0.1.0
+warning: unused variable: `x`
+error[E0308]: mismatched types
+ --> src/main.rs:2:18
+  |
+2 |     let x: u8 = "a";
+  |                 ^^^ expected `u8`, found `&str`
+
+error: aborting due to 1 previous error
+'''
+
+
+def test_first_rustc_error():
+    error = first_rustc_error(_LOG)
+    assert error.startswith('error[E0308]: mismatched types')
+    assert error.endswith('expected `u8`, found `&str`')
+    assert first_rustc_error('   Compiling broken v0.1.0\n') is None
+
+
+def _crate(path, main):
+    (path / 'src').mkdir(parents=True)
+    (path / 'Cargo.toml').write_text(
+        '[package]\nname = "broken"\nversion = "0.1.0"\nedition = "2021"\n', encoding='utf-8')
+    (path / 'src' / 'main.rs').write_text(main, encoding='utf-8')
+    return str(path / 'Cargo.toml')
+
+
+def test_build_error(tmp_path):
+    manifest = _crate(tmp_path / 'crate', 'fn main() {\n    let _x: u8 = "a";\n}\n')
+    with pytest.raises(SimulatorBuildError) as info:
+        run_crate(manifest, tmp_path / 'target')
+    assert info.value.error.startswith('error[E0308]'), info.value.error
+    assert 'src/main.rs:2' in info.value.error
+
+
+def test_run(tmp_path):
+    manifest = _crate(tmp_path / 'crate', 'fn main() {\n    println!("[stats] cycles 7");\n}\n')
+    out = run_crate(manifest, tmp_path / 'target')
+    assert out.log == '[stats] cycles 7\n'
+    assert out.report.cycles == 7
@@ -0,0 +1,54 @@
+"""Test that synthesis builds generate the Verilog without the logs"""
+
+from assassyn.backend import config, elaborate
+from assassyn.frontend import Module, RegArray, SysBuilder, UInt, log, module
+from assassyn.ir.expr import Log
+from assassyn.xform import strip_logs
+
+
+class Driver(Module):
+    """Counts, and logs the next count"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        cnt = RegArray(UInt(8), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+        log('cnt {}', cnt[0] + UInt(8)(1))
+
+
+def _logs(sys):
+    return [e for m in sys.modules for e in m.body if isinstance(e, Log)]
+
+
+def _verilog(tmp_path, **kwargs):
+    sys = SysBuilder('strip_logs_verilog')
+    with sys:
+        Driver().build()
+    cfg = config(path=str(tmp_path), simulator=False, verilog=True, verbose=False,
+                 enable_cache=False, **kwargs)
+    path = elaborate(sys, **cfg)[1]
+    design = (path / 'design.py').read_text(encoding='utf-8')
+    tb = (path / 'tb.py').read_text(encoding='utf-8')
+    return sys, design, tb
+
+
+def test_strip_logs():
+    sys = SysBuilder('strip_logs')
+    with sys:
+        Driver().build()
+    (log_expr,) = _logs(sys)
+    value = log_expr.operands[1].value
+    assert strip_logs(sys) == 1
+    assert not _logs(sys)
+    assert not value.users
+
+
+def test_synthesis_verilog(tmp_path):
+    _, design, tb = _verilog(tmp_path / 'default')
+    assert 'print(f"' in tb and 'expose_' in design
+    sys, design, tb = _verilog(tmp_path / 'synthesis', synthesis=True)
+    assert 'print(f"' not in tb and 'expose_' not in design
+    # The system itself keeps its log
+    assert len(_logs(sys)) == 1
@@ -0,0 +1,89 @@
+"""Test the synthesis pragmas of the modules and arrays"""
+
+import pytest
+
+from assassyn.frontend import Module, Port, RegArray, SynthPragma, SysBuilder, UInt, module
+from assassyn.codegen.verilog.elaborate import elaborate
+from assassyn.codegen.verilog.pragma import apply_synth_pragmas
+
+
+class Mac(Module):
+    """Accumulates the products of its operands"""
+    def __init__(self, synth_pragma=None):
+        super().__init__(ports={'a': Port(UInt(16)), 'b': Port(UInt(16))},
+                         synth_pragma=synth_pragma)
+
+    @module.combinational
+    def build(self, attr):
+        a, b = self.pop_all_ports(True)
+        acc = RegArray(UInt(32), 4, attr=attr)
+        acc[0] = (acc[0] + a * b).bitcast(UInt(32))
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, mac: Mac):
+        mac.async_called(a=UInt(16)(3), b=UInt(16)(5))
+
+
+def _elaborate(path, synth_pragma=None, attr=None):
+    sys = SysBuilder('synth_pragma')
+    with sys:
+        mac = Mac(synth_pragma)
+        mac.build(attr)
+        Driver().build(mac)
+    design = elaborate(sys, path=str(path), sim_threshold=10) / 'design.py'
+    return sys, mac, design.read_text(encoding='utf-8')
+
+
+def test_design_applies_pragmas(tmp_path):
+    sys, mac, design = _elaborate(tmp_path, 'use_dsp = "yes"',
+                                  [SynthPragma('ram_style = "distributed"')])
+    # Printed in the IR, so that a pragma changes the build cache key
+    assert "synth_pragma: ['use_dsp = \"yes\"']" in repr(sys)
+    assert "#[synth_pragma('ram_style = \"distributed\"')]" in repr(sys)
+    assert design.rstrip().endswith(
+        f"apply_synth_pragmas('sv', {{'{mac.name}': ['use_dsp = \"yes\"']}}, "
+        f"{{'acc': ['ram_style = \"distributed\"']}})")
+
+
+def test_design_without_pragmas(tmp_path):
+    _, _, design = _elaborate(tmp_path)
+    assert 'apply_synth_pragmas' not in design
+
+
+def test_apply_pragmas(tmp_path):
+    hw = tmp_path / 'sv' / 'hw'
+    hw.mkdir(parents=True)
+    # Shaped like the modules CIRCT emits
+    (hw / 'Mac.sv').write_text(
+        'module Mac(\n  input clk\n);\n  reg [31:0] r;\nendmodule\n\n', encoding='utf-8')
+    (hw / 'Mac_array.sv').write_text(
+        'module Mac_array(\n  input clk\n);\n  reg [3:0][31:0] data_reg;\nendmodule\n',
+        encoding='utf-8')
+    top = 'module Top(\n  input clk\n);\n  Mac mac (.clk(clk));\nendmodule\n'
+    (hw / 'Top.sv').write_text(top, encoding='utf-8')
+
+    apply_synth_pragmas(tmp_path / 'sv', {'Mac': ['use_dsp = "yes"', 'keep_hierarchy = "yes"']},
+                        {'Mac_array': ['ram_style = "distributed"']})
+    mac = (hw / 'Mac.sv').read_text(encoding='utf-8')
+    assert mac.startswith('(* use_dsp = "yes" *)\n(* keep_hierarchy = "yes" *)\nmodule Mac(')
+    # The registers of a module are left alone, only those of a register file are annotated
+    assert '  reg [31:0] r;' in mac
+    array = (hw / 'Mac_array.sv').read_text(encoding='utf-8')
+    assert array.startswith('module Mac_array(')
+    assert '  (* ram_style = "distributed" *) reg [3:0][31:0] data_reg;' in array
+    assert (hw / 'Top.sv').read_text(encoding='utf-8') == top
+
+
+def test_invalid_pragma():
+    assert SynthPragma(' keep ').text == 'keep'
+    for text in ['', 'keep *) (* dont_touch', 'keep\n']:
+        with pytest.raises(ValueError, match='synthesis pragma'):
+            SynthPragma(text)
+    with pytest.raises(ValueError, match='synthesis pragma'):
+        Mac(synth_pragma=['keep', '*)'])
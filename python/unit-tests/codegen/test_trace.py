@@ -0,0 +1,74 @@
+"""Test the backend traces and their comparison"""
+
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.cosim import compare_traces
+from assassyn.frontend import Module, Port, RegArray, SysBuilder, UInt, module
+
+_SIM = '''@trace 1 cnt[0] 1
+@trace 1 Sink.a 0
+log line
+@trace 2 cnt[0] 2
+@trace 2 Sink.a 1
+@trace 3 cnt[0] 3
+'''
+
+# Counted from 5, in another order, and one cycle short
+_VERILOG = '''@trace 6 Sink.a 0
+@trace 6 cnt[0] 1
+@trace 7 cnt[0] 2
+@trace 7 Sink.a 1
+'''
+
+
+def test_compare_aligned():
+    report = compare_traces(_SIM, _VERILOG)
+    assert report.ok and report.cycles == 2
+
+
+def test_compare_divergence():
+    report = compare_traces(_SIM, _VERILOG.replace('Sink.a 1', 'Sink.a 3'))
+    assert report.cycles == 1
+    divergence = report.divergence
+    assert (divergence.cycle, divergence.signal) == (2, 'Sink.a'), divergence
+    assert divergence.simulator == ['1'] and divergence.verilog == ['3'], divergence
+    # A push missing from one backend diverges as well
+    report = compare_traces(_SIM, _VERILOG.replace('@trace 7 Sink.a 1\n', ''))
+    assert report.divergence.verilog == [], report.divergence
+
+
+def test_compare_one_sided():
+    assert compare_traces('', '').ok
+    report = compare_traces(_SIM, '')
+    assert report.divergence.cycle == 1 and report.divergence.signal == 'Sink.a'
+
+
+class Sink(Module):
+    """Holds the pushed values"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8))})
+
+
+class Driver(Module):
+    """Counts and pushes its count"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, sink: Sink):
+        cnt = RegArray(UInt(8), 1, name='cnt')
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+        sink.async_called(a=cnt[0])
+
+
+def test_verilog_trace(tmp_path):
+    sys = SysBuilder('trace')
+    with sys:
+        Driver().build(Sink())
+    path = tmp_path / 'design.py'
+    assert not any('@trace' in i for i in generate_design(str(path), sys))
+    logs = generate_design(str(path), sys, trace=True)
+    traces = [i for i in logs if '@trace' in i]
+    assert len(traces) == 2, traces
+    assert any(' cnt[{int(dut.Driver.cnt_widx_port0.value)}] ' in i for i in traces), traces
+    assert any(' SinkInstance.a {int(dut.Driver.SinkInstance_a_push_data.value) & 255:x}' in i
+               for i in traces), traces
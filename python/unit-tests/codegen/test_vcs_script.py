@@ -0,0 +1,24 @@
+"""The VCS flow emits a run script and a testbench bound to the generated top."""
+
+import os
+import sys
+
+sys.path.append(os.path.join(os.path.dirname(__file__), '..', '..'))
+
+from assassyn.codegen.verilog.testbench import generate_vcs_script  # noqa: E402
+
+
+def test_vcs_script_targets_top(tmp_path):
+    """run_vcs.sh compiles sv/hw/Top.sv through tb_vcs.sv and runs simv."""
+    generate_vcs_script(tmp_path, 42, ['ext_adder.sv'])
+
+    script = (tmp_path / 'run_vcs.sh').read_text(encoding='utf-8')
+    assert os.access(tmp_path / 'run_vcs.sh', os.X_OK)
+    assert 'test -f sv/hw/Top.sv' in script
+    assert 'vcs -full64 -sverilog' in script and '-top tb' in script
+    assert 'tb_vcs.sv' in script and 'ext_adder.sv' in script
+    assert script.rstrip().endswith('./simv')
+
+    tb = (tmp_path / 'tb_vcs.sv').read_text(encoding='utf-8')
+    assert 'Top dut (' in tb
+    assert 'cycle < 42' in tb
@@ -0,0 +1,46 @@
+"""The Verilator build of the testbench takes defines, include directories and flags."""
+
+import ast
+
+from assassyn.codegen.verilog.testbench import generate_testbench
+from assassyn.frontend import Module, SysBuilder, module
+
+
+class Empty(Module):
+    """Does nothing"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self):
+        pass
+
+
+def _testbench(tmp_path, **kwargs):
+    sys = SysBuilder('verilator_options')
+    with sys:
+        Empty().build()
+    path = tmp_path / 'tb.py'
+    generate_testbench(path, sys, 10, [], ['ext.sv'], **kwargs)
+    code = path.read_text(encoding='utf-8')
+    ast.parse(code)
+    return code
+
+
+def test_options(tmp_path):
+    inc = tmp_path / 'inc'
+    code = _testbench(tmp_path, defines={'WIDTH': 8}, include_dirs=[inc],
+                      verilator_flags=['-Wno-WIDTH'])
+    assert "defines = {'WIDTH': '8'}" in code
+    assert f"includes = [{str(inc.resolve())!r}]" in code
+    assert "build_args = ['-Wno-WIDTH']" in code
+    assert "'async_fifo.sv', 'ext.sv']" in code
+    assert "open('verilator.vc', 'w')" in code
+    assert "'--trace-fst'" in code
+
+
+def test_no_options(tmp_path):
+    code = _testbench(tmp_path)
+    assert 'defines = {}' in code
+    assert 'includes = []' in code
+    assert 'build_args = []' in code
@@ -0,0 +1,66 @@
+"""Test finding the wait_until of modules with condition blocks"""
+
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.codegen.verilog.utils import find_wait_until, find_wait_until_recursive
+from assassyn.frontend import Condition, Module, Port, SysBuilder, UInt, log, module, wait_until
+
+
+class Worker(Module):
+    """Logs in a condition block before waiting for its data"""
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(8)), 'gate': Port(UInt(1))})
+
+    @module.combinational
+    def build(self):
+        gate = self.gate.pop()
+        with Condition(gate):
+            log('before {}', gate)
+        wait_until(self.data.valid())
+        x = self.data.pop()
+        log('after {}', x)
+
+
+class Gated(Module):
+    """Only waits for its data when the gate is set"""
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(8)), 'gate': Port(UInt(1))})
+
+    @module.combinational
+    def build(self):
+        gate = self.gate.pop()
+        with Condition(gate):
+            log('gated {}', gate)
+            wait_until(self.data.valid())
+        x = self.data.pop()
+        log('after {}', x)
+
+
+def test_find_wait_until():
+    with SysBuilder('find_wait_until'):
+        worker = Worker()
+        worker.build()
+        gated = Gated()
+        gated.build()
+    # The condition block before the wait does not hide it
+    wait = find_wait_until(worker)
+    assert wait is not None and find_wait_until_recursive(worker) is wait
+    # A wait inside a condition block does not always hold the module
+    assert find_wait_until(gated) is None
+    nested = find_wait_until_recursive(gated)
+    assert nested is not None and nested.meta_cond is not None
+
+
+def test_verilog_gates_after_wait(tmp_path):
+    sys = SysBuilder('verilog_gates_after_wait')
+    with sys:
+        Worker().build()
+        Gated().build()
+    path = tmp_path / 'design.py'
+    generate_design(str(path), sys)
+    code = path.read_text(encoding='utf-8')
+    code = code.split('class WorkerInstance')[1].split('\nclass ')[0]
+    # The gate is popped before the wait, so it is not held by the data
+    (gate_ready,) = [i for i in code.splitlines() if 'self.gate_pop_ready =' in i]
+    assert 'data_valid' not in gate_ready, gate_ready
+    (data_ready,) = [i for i in code.splitlines() if 'self.data_pop_ready =' in i]
+    assert 'data_valid' in data_ready, data_ready
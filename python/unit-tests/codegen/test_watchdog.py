@@ -0,0 +1,72 @@
+"""Test the watchdog intrinsic and its generated code"""
+
+import pytest
+
+from assassyn.codegen.simulator.watchdog import collect_watchdogs, watchdog_field
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.frontend import Module, Port, SysBuilder, UInt, log, module, watchdog
+
+
+class Sink(Module):
+    """Expects its data at least every 10 cycles"""
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        watchdog(self.data.valid(), 10)
+        x = self.data.pop()
+        log('data {}', x)
+
+
+class Misused(Module):
+    """Builds a watchdog from the given arguments"""
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(8))})
+
+    @module.combinational
+    def build(self, signal, cycles):
+        watchdog(signal(self), cycles)
+
+
+@pytest.mark.parametrize('signal, cycles', [
+    # A multi-bit signal
+    (lambda m: m.data.peek(), 10),
+    # Cycles not a positive int
+    (lambda m: m.data.valid(), 0),
+    (lambda m: m.data.valid(), True),
+])
+def test_invalid_args(signal, cycles):
+    sys = SysBuilder('watchdog_args')
+    with sys:
+        with pytest.raises(ValueError):
+            Misused().build(signal, cycles)
+
+
+def test_collect():
+    sys = SysBuilder('watchdog')
+    with sys:
+        sink = Sink()
+        sink.build()
+    with sys:
+        # Never built, like the modules a co-simulation drives from outside
+        Sink()
+    ((owner, node),) = collect_watchdogs(sys)
+    assert owner is sink
+    assert watchdog_field(node).startswith(f'watchdog_{sink.name}_')
+
+
+def test_verilog_counter(tmp_path):
+    sys = SysBuilder('verilog_counter')
+    with sys:
+        Sink().build()
+    path = tmp_path / 'design.py'
+    logs = generate_design(str(path), sys)
+    code = path.read_text(encoding='utf-8')
+    code = code.split('class SinkInstance')[1].split('\nclass ')[0]
+    (_, node), = collect_watchdogs(sys)
+    name = watchdog_field(node)
+    assert f'{name} = Output(Bits(1))' in code
+    assert f'{name}_count = Reg(UInt(4)' in code
+    assert f'self.{name} = {name}_expired' in code
+    assert any(f'.{name}.value' in i for i in logs)
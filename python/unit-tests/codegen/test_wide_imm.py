@@ -0,0 +1,30 @@
+"""Test the codegen of immediates wider than 64 bits"""
+
+from assassyn.codegen.simulator.utils import int_imm_dumper_impl
+from assassyn.codegen.verilog.rval import _dump_const
+from assassyn.frontend import Bits, Int, UInt
+
+WIDE = 0x123456789ABCDEF012345678
+
+
+def test_simulator_literals():
+    assert int_imm_dumper_impl(UInt(96), 5) == 'ValueCastTo::<BigUint>::cast(&(5 as u64))'
+    assert int_imm_dumper_impl(UInt(96), WIDE) == \
+        'BigUint::parse_bytes(b"123456789abcdef012345678", 16).unwrap()'
+    assert int_imm_dumper_impl(Int(96), -(1 << 80)) == \
+        'BigInt::parse_bytes(b"-100000000000000000000", 16).unwrap()'
+
+
+def test_verilog_literals():
+    assert _dump_const(None, UInt(96)(5), False) == 'UInt(96)(5)'
+    assert _dump_const(None, Bits(96)(WIDE), False) == \
+        'BitsSignal.concat([Bits(32)(305419896), Bits(32)(2596069104), Bits(32)(305419896)])'
+    # The chunks hold the two's-complement bits, the top one narrower
+    assert _dump_const(None, Int(72)(-(1 << 70)), False) == \
+        'BitsSignal.concat([Bits(8)(192), Bits(32)(0), Bits(32)(0)]).as_sint()'
+
+
+def test_const_slices():
+    value = Bits(96)(WIDE)
+    assert value[0:63].value == WIDE & ((1 << 64) - 1)
+    assert value[32:95].dtype == Bits(64)
@@ -7,6 +7,7 @@ sys.path.append(os.path.join(os.path.dirname(__file__), '..', '..'))
 from assassyn.frontend import (
     Module, SysBuilder, UInt, Port, log, module
 )
+from assassyn.ir.expr import AsyncCall, FIFOPush
 from assassyn.test import dump_ir
 
 
@@ -58,6 +59,32 @@ def test_call_ops_dump():
     dump_ir("call_ops_test", builder, checker)
 
 
+def test_async_called_if():
+    """A guarded async call predicates the call and its pushes."""
+    sys_builder = SysBuilder('async_called_if')
+    with sys_builder:
+        class Caller(Module):
+            def __init__(self):
+                super().__init__(ports={'arg': Port(UInt(8))})
+
+            @module.combinational
+            def build(self, callee):
+                arg = self.arg.pop()
+                guard = arg[0:0]
+                callee.async_called_if(guard, input_port=arg, output_port=arg)
+                return guard
+
+        callee = CalleeModule()
+        caller = Caller()
+        guard = caller.build(callee)
+
+    guarded = [expr for expr in caller.body if isinstance(expr, (AsyncCall, FIFOPush))]
+    assert len(guarded) == 3, guarded
+    assert all(expr.meta_cond is guard for expr in guarded)
+    assert 'async_call' in repr(sys_builder)
+
+
 if __name__ == '__main__':
     test_call_ops_dump()
+    test_async_called_if()
     print("\n=== Call Operations Tests Completed Successfully ===")
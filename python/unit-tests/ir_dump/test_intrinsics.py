@@ -2,11 +2,12 @@
 
 import sys
 import os
+import pytest
 sys.path.append(os.path.join(os.path.dirname(__file__), '..', '..'))
 
 from assassyn.frontend import (
     Module, SysBuilder, UInt, Port, log, module,
-    wait_until, finish, assume
+    wait_until, finish, assume, Downstream, downstream, Value
 )
 from assassyn.test import dump_ir
 
@@ -42,6 +43,30 @@ def test_intrinsics_dump():
     dump_ir("intrinsics_test", builder, checker)
 
 
+
+def test_wait_until_in_downstream():
+    """A downstream module has no event to hold, so wait_until is rejected."""
+    class Waiter(Downstream):
+        @downstream.combinational
+        def build(self, cond: Value):
+            wait_until(cond)
+
+    class Source(Module):
+        def __init__(self):
+            super().__init__(ports={'cond': Port(UInt(1))})
+
+        @module.combinational
+        def build(self):
+            return self.cond.pop()
+
+    sys_builder = SysBuilder('wait_until_downstream')
+    with sys_builder:
+        cond = Source().build()
+        with pytest.raises(ValueError, match='downstream'):
+            Waiter().build(cond)
+
+
 if __name__ == '__main__':
     test_intrinsics_dump()
+    test_wait_until_in_downstream()
     print("\n=== Intrinsics Tests Completed Successfully ===")
@@ -0,0 +1,69 @@
+"""Golden-file tests pinning the IR printer format."""
+
+import os
+import sys
+sys.path.append(os.path.join(os.path.dirname(__file__), '..', '..'))
+
+from assassyn.frontend import (
+    Module, SysBuilder, UInt, Port, RegArray, Condition, log, module,
+)
+
+GOLDEN = os.path.join(os.path.dirname(__file__), 'golden')
+
+
+class Sink(Module):
+
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        data = self.pop_all_ports(True)
+        acc = RegArray(UInt(8), 1, name='acc')
+        total = acc[0] + data
+        (acc & self)[0] <= total
+        log('sink {}', total)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, sink: Sink):
+        cnt = RegArray(UInt(8), 1, name='cnt')
+        nxt = cnt[0] + UInt(8)(1)
+        (cnt & self)[0] <= nxt
+        odd = cnt[0][0:0]
+        with Condition(odd):
+            bound = sink.bind(data=cnt[0])
+            bound.set_fifo_depth(data=8)
+            bound.async_called()
+
+
+def build_system():
+    sys_builder = SysBuilder('printer')
+    with sys_builder:
+        sink = Sink()
+        sink.build()
+        Driver().build(sink)
+    return sys_builder
+
+
+def check_golden(name, text):
+    with open(os.path.join(GOLDEN, name), encoding='utf-8') as f:
+        assert text == f.read(), text
+
+
+def test_printer_golden():
+    check_golden('printer.txt', repr(build_system()) + '\n')
+
+
+def test_printer_filtered():
+    check_golden('printer_sink.txt', build_system().to_string_filtered(['SinkInstance']) + '\n')
+
+
+if __name__ == '__main__':
+    test_printer_golden()
+    test_printer_filtered()
@@ -0,0 +1,52 @@
+"""Test the initialization of register arrays from data and files, and of counters"""
+
+import pytest
+
+from assassyn.frontend import (Int, RegArray, SysBuilder, UInt, create_array_from_file,
+                               create_array_with_data, create_counter)
+
+
+def test_array_with_data():
+    with SysBuilder('array_with_data'):
+        lut = create_array_with_data('lut', UInt(8), [0x3f, 0x06, 0x5b, 0x4f])
+        neg = create_array_with_data('neg', Int(4), (-8, 7))
+    assert lut.name == 'lut' and lut.size == 4
+    assert lut.initializer == [0x3f, 0x06, 0x5b, 0x4f]
+    assert neg.initializer == [-8, 7]
+
+
+def test_array_with_data_out_of_range():
+    with SysBuilder('array_with_data_range'):
+        with pytest.raises(AssertionError, match='Initial value 16 of element 1'):
+            create_array_with_data('lut', UInt(4), [1, 16])
+        with pytest.raises(AssertionError, match='Initial value -9 of element 0'):
+            create_array_with_data('neg', Int(4), [-9])
+
+
+def test_initializer_size_mismatch():
+    with SysBuilder('array_init_size'):
+        with pytest.raises(AssertionError, match='Initializer of 3 values for an array of 4'):
+            RegArray(UInt(8), 4, initializer=[1, 2, 3])
+
+
+def test_array_from_file(tmp_path):
+    path = tmp_path / 'init.hex'
+    path.write_text('// a comment\n1\n2_0\n@5\nff\n', encoding='utf-8')
+    with SysBuilder('array_from_file'):
+        arr = create_array_from_file('rom', UInt(8), 8, str(path))
+        with pytest.raises(AssertionError, match='more than the 4 elements'):
+            create_array_from_file('small', UInt(8), 4, str(path))
+        with pytest.raises(AssertionError, match='Initial value 32 of element 1'):
+            create_array_from_file('narrow', UInt(4), 8, str(path))
+    assert arr.initializer == [1, 0x20, 0, 0, 0, 0xff, 0, 0]
+
+
+def test_counter_bounds():
+    # The bounds are checked before anything is built, so no module is needed
+    with SysBuilder('counter_bounds'):
+        with pytest.raises(AssertionError, match='bound 16 does not fit in 4 bits'):
+            create_counter(4, wrap=16)
+        with pytest.raises(AssertionError, match=r'initial value 10 is not in \[0, 9\]'):
+            create_counter(4, 10, wrap=9)
+        with pytest.raises(AssertionError, match=r'step 0 is not in \[1, 15\]'):
+            create_counter(4, step=0)
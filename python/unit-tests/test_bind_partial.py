@@ -0,0 +1,64 @@
+"""Test incremental binds created by Module.partial"""
+
+import pytest
+
+from assassyn.frontend import Module, Port, SysBuilder, UInt, module
+
+
+class Alu(Module):
+    """Callee with three ports"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8)), 'b': Port(UInt(8)), 'op': Port(UInt(2))})
+
+
+class Caller(Module):
+    """Binds the callee argument by argument"""
+    def __init__(self, bind_op):
+        super().__init__(ports={})
+        self.bind_op = bind_op
+
+    @module.combinational
+    def build(self, alu: Alu):
+        call = alu.partial(a=UInt(8)(1))
+        call.bind(b=UInt(8)(2))
+        assert call.unbound_ports() == ['op']
+        if self.bind_op:
+            call.bind(op=UInt(2)(0))
+        return call.async_called()
+
+
+def test_partial_fully_bound_calls():
+    """A strict bind with every port bound produces an async call"""
+    sys = SysBuilder('test_partial_fully_bound_calls')
+    with sys:
+        alu = Alu()
+        call = Caller(True).build(alu)
+        assert call.bind.is_fully_bound()
+        assert call in alu.users
+
+
+def test_partial_missing_port_errors():
+    """A strict bind missing a port refuses to be called"""
+    sys = SysBuilder('test_partial_missing_port_errors')
+    with sys:
+        alu = Alu()
+        with pytest.raises(ValueError) as exc_info:
+            Caller(False).build(alu)
+        assert "['op']" in str(exc_info.value)
+        assert not alu.users
+
+
+def test_plain_bind_stays_permissive():
+    """Binds created by Module.bind may still be called partially"""
+    class PartialCaller(Module):
+        def __init__(self):
+            super().__init__(ports={})
+
+        @module.combinational
+        def build(self, alu: Alu):
+            return alu.bind(a=UInt(8)(1)).async_called()
+
+    sys = SysBuilder('test_plain_bind_stays_permissive')
+    with sys:
+        call = PartialCaller().build(Alu())
+        assert call.bind.unbound_ports() == ['b', 'op']
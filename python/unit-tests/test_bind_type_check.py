@@ -5,7 +5,7 @@ import pytest
 
 from assassyn.ir.dtype import UInt, Int, Bits, Record
 from assassyn.ir.module import Module, Port
-from assassyn.frontend import SysBuilder
+from assassyn.frontend import SysBuilder, module
 
 
 class ModuleUInt8(Module):
@@ -142,6 +142,38 @@ def test_bind_type_match_record():
             assert True
 
 
+class Memory(Module):
+    """Test module with the ports of a memory write"""
+    def __init__(self):
+        super().__init__(ports={
+            'write': Port(Bits(1)), 'wdata': Port(UInt(32)), 'addr': Port(UInt(10)),
+        })
+
+
+class Writer(Module):
+    """Test module calling the memory with an address of the wrong width"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, memory: Memory):
+        memory.async_called(write=Bits(1)(1), wdata=UInt(32)(7), addr=UInt(8)(3))
+
+
+def test_async_call_wrong_width_addr():
+    """Test that an async call with a wrong-width argument names the port and the call site"""
+    sys = SysBuilder('test_async_call_wrong_width_addr')
+    with sys:
+        memory = Memory()
+        with pytest.raises(ValueError) as exc_info:
+            Writer().build(memory)
+
+    msg = str(exc_info.value)
+    assert "port 'addr' of" in msg and memory.name in msg
+    assert "u10" in msg and "u8" in msg
+    assert "test_bind_type_check.py:" in msg, msg
+
+
 if __name__ == "__main__":
     # Run tests with pytest
     sys.exit(pytest.main([__file__, "-v"]))
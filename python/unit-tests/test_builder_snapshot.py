@@ -0,0 +1,87 @@
+"""Unit tests for SysBuilder snapshot/rollback."""
+
+from assassyn.frontend import (
+    Condition, Int, Module, Port, RegArray, SysBuilder, log, module
+)
+
+
+class Adder(Module):
+
+    def __init__(self):
+        super().__init__(ports={'a': Port(Int(32)), 'b': Port(Int(32))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        c = a + b
+        d = c * a
+        log("Adder: {} {}", c, d)
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, adder: Adder):
+        cnt = RegArray(Int(32), 1)
+        (cnt & self)[0] <= cnt[0] + Int(32)(1)
+        with Condition(cnt[0] < Int(32)(100)):
+            adder.async_called(a=cnt[0], b=cnt[0])
+
+
+class Tail(Module):
+
+    def __init__(self):
+        super().__init__(ports={'c': Port(Int(32))})
+
+
+def test_rollback_restores_cut_rewrite():
+    """A partial CutModules-style rewrite is fully undone by rollback."""
+    sys = SysBuilder("builder_snapshot_cut")
+    with sys:
+        adder = Adder()
+        adder.build()
+        Driver().build(adder)
+
+        before = repr(sys)
+        snap = sys.snapshot()
+
+        # Cut the adder after `c`: move the tail into a fresh module and call it.
+        tail = Tail()
+        tail.body = []
+        cut = next(i for i, e in enumerate(adder.body) if getattr(e, 'name', None) == 'c')
+        moved = adder.body[cut + 1:]
+        del adder.body[cut + 1:]
+        for expr in moved:
+            expr.parent = tail
+            tail.body.append(expr)
+        sys.enter_context_of(adder)
+        tail.async_called(c=adder.body[cut])
+        sys.exit_context_of()
+        sys.expose_on_top(adder.body[cut], kind='Output')
+        sys.register_value(adder.body[cut])
+
+        assert repr(sys) != before
+        sys.rollback(snap)
+
+        assert repr(sys) == before
+        assert tail not in sys.modules
+        assert not sys.exposed_nodes
+        assert not sys.registered_values and not adder.body[cut].registered
+        assert all(expr.parent is adder for expr in adder.body)
+
+
+def test_rollback_is_reusable():
+    """The same snapshot can be rolled back to more than once."""
+    sys = SysBuilder("builder_snapshot_reuse")
+    with sys:
+        adder = Adder()
+        adder.build()
+        before = repr(sys)
+        snap = sys.snapshot()
+        for _ in range(2):
+            RegArray(Int(8), 4)
+            sys.rollback(snap)
+            assert repr(sys) == before
@@ -0,0 +1,66 @@
+"""Test the response FIFOs built by respond and call_and_wait"""
+
+import pytest
+
+from assassyn.frontend import Module, Port, SysBuilder, UInt, module
+from assassyn.ir.expr import FIFOPop, FIFOPush
+from assassyn.ir.module.module import verify_responses
+
+
+class Double(Module):
+    """Responds with twice its input"""
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(8))})
+
+    @module.combinational
+    def build(self, respond=True):
+        x = self.pop_all_ports(True)
+        if respond:
+            self.respond(x + x)
+
+
+class Caller(Module):
+    """Waits for the response of its callee"""
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(8))})
+
+    @module.combinational
+    def build(self, callee):
+        data = self.data.pop()
+        return callee.call_and_wait(x=data)
+
+
+def test_response_port():
+    sys = SysBuilder('call_and_wait')
+    with sys:
+        double = Double()
+        double.build()
+        caller = Caller()
+        result = caller.build(double)
+    port = caller.DoubleInstance_response
+    assert port in caller.ports and port.module is caller
+    assert port.dtype == UInt(8)
+    assert isinstance(result, FIFOPop) and result.fifo is port
+    # Pushed by the callee, popped by the caller
+    assert [i.parent for i in port.users if isinstance(i, FIFOPush)] == [double]
+    verify_responses(sys)
+
+
+def test_errors():
+    sys = SysBuilder('call_and_wait_errors')
+    with sys:
+        silent = Double()
+        silent.build(respond=False)
+        with pytest.raises(ValueError, match='sends no response'):
+            Caller().build(silent)
+
+        double = Double()
+        double.build()
+        Caller().build(double)
+        with pytest.raises(ValueError, match='already waited for'):
+            Caller().build(double)
+
+        unclaimed = Double()
+        unclaimed.build()
+    with pytest.raises(ValueError, match='no module waits'):
+        verify_responses(sys)
@@ -0,0 +1,95 @@
+"""Test cloning a built module with substitutions"""
+
+import pytest
+
+from assassyn.frontend import Condition, Module, Port, RegArray, SysBuilder, UInt, log, module
+from assassyn.codegen.verilog.elaborate import elaborate
+from assassyn.ir.expr import ArrayWrite, Expr, Operand
+
+
+class Writeback(Module):
+    """Writes a register under a condition"""
+    def __init__(self):
+        super().__init__(ports={'rd': Port(UInt(2)), 'data': Port(UInt(8))})
+
+    @module.combinational
+    def build(self, rf):
+        self.rf = rf
+        rd, data = self.pop_all_ports(True)
+        with Condition(data[0:0]):
+            rf[rd] = data
+        log('wb {} {}', rd, rf[rd])
+
+
+class Driver(Module):
+
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, *writebacks):
+        for wb in writebacks:
+            wb.async_called(rd=UInt(2)(1), data=UInt(8)(3))
+
+
+def _refs(expr):
+    '''The nodes the operands of `expr` and its predicate refer to.'''
+    res = [i.value if isinstance(i, Operand) else i for i in expr.operands]
+    return res + [expr.meta_cond]
+
+
+def test_clone_is_independent():
+    sys = SysBuilder('clone_module')
+    with sys:
+        rf, other = RegArray(UInt(8), 4, name='rf'), RegArray(UInt(8), 4, name='other')
+        wb = Writeback()
+        wb.build(rf)
+    copy = sys.clone_module(wb, 'WritebackCopy', {rf: other})
+    assert copy in sys.modules and copy.name == 'WritebackCopy'
+    assert [(p.name, p.dtype) for p in copy.ports] == [(p.name, p.dtype) for p in wb.ports]
+    assert all(a is not b for a, b in zip(copy.ports, wb.ports))
+    # The subclass attributes are substituted too
+    assert copy.rf is other
+
+    assert len(copy.body) == len(wb.body)
+    source = {id(i) for i in wb.body + wb.ports + [rf]}
+    for old, new in zip(wb.body, copy.body):
+        assert type(old) is type(new) and new.parent is copy and old.parent is wb
+        assert not any(id(i) in source for i in _refs(new)), new
+        for operand in new.operands:
+            if isinstance(operand, Operand) and isinstance(operand.value, Expr):
+                assert any(u is operand for u in operand.value.users)
+    # The source is left as it was
+    assert str(wb).count('rf[') == 2 and 'other' not in str(wb)
+    assert str(copy).count('other[') == 2 and 'rf[' not in str(copy)
+
+    writes = [i for i in copy.body if isinstance(i, ArrayWrite)]
+    assert writes and all(i.module is copy for i in writes)
+    assert copy in other.get_write_ports() and copy not in rf.get_write_ports()
+
+
+def test_clone_elaborates(tmp_path):
+    sys = SysBuilder('clone_elaborates')
+    with sys:
+        rf, other = RegArray(UInt(8), 4, name='rf'), RegArray(UInt(8), 4, name='other')
+        wb = Writeback()
+        wb.build(rf)
+        copy = sys.clone_module(wb, 'WritebackCopy', {rf: other})
+        Driver().build(wb, copy)
+    design = elaborate(sys, path=str(tmp_path), sim_threshold=10) / 'design.py'
+    assert 'class WritebackCopy(Module):' in design.read_text(encoding='utf-8')
+
+
+def test_clone_rejects():
+    sys = SysBuilder('clone_rejects')
+    with sys:
+        wb = Writeback()
+        wb.build(RegArray(UInt(8), 4, name='rf'))
+    with pytest.raises(ValueError, match='already has a module'):
+        sys.clone_module(wb, wb.name)
+    with pytest.raises(ValueError, match='identifier'):
+        sys.clone_module(wb, 'not a name')
+    with sys:
+        unbuilt = Writeback()
+    with pytest.raises(ValueError, match='built Module'):
+        sys.clone_module(unbuilt, 'Unbuilt')
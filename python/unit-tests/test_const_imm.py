@@ -0,0 +1,58 @@
+"""Boundary tests for integer immediates."""
+
+import pytest
+
+from assassyn.frontend import Bits, Int, SysBuilder, UInt, bool_imm, imm, zero
+
+
+@pytest.mark.parametrize('dtype, lo, hi', [
+    (UInt(8), 0, 255),
+    (Bits(8), 0, 255),
+    (Int(8), -128, 127),
+    (UInt(1), 0, 1),
+    (UInt(64), 0, (1 << 64) - 1),
+])
+def test_imm_boundaries(dtype, lo, hi):
+    """The extreme values fit, one past them does not."""
+    with SysBuilder('test_imm_boundaries'):
+        assert imm(lo, dtype).value == lo
+        assert imm(hi, dtype).value == hi
+        for bad in (lo - 1, hi + 1):
+            with pytest.raises(ValueError, match='does not fit'):
+                imm(bad, dtype)
+
+
+def test_dtype_call_is_imm():
+    """`UInt(8)(5)` and `imm(5, UInt(8))` are the same cached constant."""
+    with SysBuilder('test_dtype_call_is_imm'):
+        assert UInt(8)(5) is imm(5, UInt(8))
+        with pytest.raises(ValueError):
+            Bits(8)(256)
+
+
+def test_bool_imm_and_zero():
+    """Shortcuts produce b1 booleans and typed zeros."""
+    with SysBuilder('test_bool_imm_and_zero'):
+        t, f = bool_imm(True), bool_imm(False)
+        assert (t.value, f.value) == (1, 0)
+        assert t.dtype.type_eq(Bits(1))
+        z = zero(Int(16))
+        assert z.value == 0 and z.dtype.type_eq(Int(16))
+        with pytest.raises(TypeError):
+            bool_imm(1)
+        # A bool is an integer immediate too
+        assert Bits(1)(True) is Bits(1)(1) and imm(False, UInt(4)).value == 0
+
+
+def test_negative_imm_bits():
+    """Negative immediates keep their value, and fold on their two's-complement bits."""
+    with SysBuilder('test_negative_imm_bits'):
+        minus_one = Int(8)(-1)
+        assert minus_one.value == -1
+        assert minus_one.raw_bits == 0xFF
+        assert Int(32)(-8).raw_bits == 0xFFFFFFF8
+        assert minus_one[4:7].value == 0xF
+        assert minus_one.concat(UInt(8)(0)).value == 0xFF00
+        assert UInt(4)(1).concat(Int(4)(-2)).value == 0x1E
+        with pytest.raises(ValueError, match='does not fit'):
+            Int(8)(-129)
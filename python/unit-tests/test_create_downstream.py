@@ -0,0 +1,90 @@
+"""Test building downstreams from closures with create_downstream"""
+
+import pytest
+
+from assassyn.analysis.topo import get_upstreams
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.frontend import (Module, Port, SysBuilder, UInt, create_downstream,
+                               create_downstream_with_condition, log, module)
+
+
+class Source(Module):
+    """Forwards its data"""
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        return self.data.pop()
+
+
+def test_sum():
+    sys = SysBuilder('create_downstream')
+    with sys:
+        lhs, rhs = Source(), Source()
+        a, b = lhs.build(), rhs.build()
+
+        def body(ctx):
+            c = ctx.use_value(a).optional(UInt(8)(0)) + ctx.use_value(b).optional(UInt(8)(0))
+            log('sum {}', c)
+
+        adder = create_downstream('Adder', body)
+    assert adder.name == 'Adder'
+    assert adder in sys.downstreams
+    assert get_upstreams(adder) == {lhs, rhs}
+
+
+def test_cycle():
+    sys = SysBuilder('create_downstream_cycle')
+    with sys:
+        source = Source()
+        a = source.build()
+        values = {}
+
+        def inner(ctx):
+            values['inner'] = ctx.use_value(values['outer']) + UInt(8)(1)
+
+        def outer(ctx):
+            values['outer'] = ctx.use_value(a) + UInt(8)(1)
+            create_downstream('Inner', inner)
+            with pytest.raises(ValueError, match='Outer -> Inner -> Outer'):
+                ctx.use_value(values['inner'])
+
+        create_downstream('Outer', outer)
+
+
+def test_not_a_value():
+    sys = SysBuilder('create_downstream_const')
+    with sys:
+        with pytest.raises(ValueError):
+            create_downstream('Const', lambda ctx: ctx.use_value(UInt(8)(1)))
+
+
+class Parity(Module):
+    """Forwards its data and whether it is odd"""
+    def __init__(self):
+        super().__init__(ports={'data': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        data = self.data.pop()
+        return data, data[0:0]
+
+
+def test_condition(tmp_path):
+    sys = SysBuilder('create_downstream_condition')
+    with sys:
+        source = Parity()
+        a, odd = source.build()
+        guarded = create_downstream_with_condition('Guarded', odd, lambda ctx: log('odd'))
+        with pytest.raises(ValueError, match='one bit'):
+            create_downstream_with_condition('Wide', a, lambda ctx: None)
+    assert guarded.condition is odd
+    assert get_upstreams(guarded) == {source}
+    assert f'#[downstream, condition: {source.name}.{odd.as_operand()}]' in repr(sys)
+
+    generate_design(str(tmp_path / 'design.py'), sys)
+    code = (tmp_path / 'design.py').read_text(encoding='utf-8').split('class Guarded')[1]
+    port = f'{source.name}_{odd.as_operand()}'
+    assert f'executed_wire = (reduce(operator.or_, [self.{source.name}_executed], ' \
+           f'Bits(1)(0))) & self.{port}_valid & self.{port}.as_bits()' in code
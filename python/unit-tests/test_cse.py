@@ -0,0 +1,136 @@
+"""Test merging equal one-hot selects, concatenations and slices"""
+
+import sys as py_sys
+from pathlib import Path
+
+from assassyn.frontend import Bits, Condition, Module, Port, SysBuilder, concat, log, module
+from assassyn.ir.array import Slice
+from assassyn.ir.const import Const
+from assassyn.ir.expr import Concat, Log, Select1Hot
+from assassyn.utils import repo_path, unwrap_operand
+from assassyn.xform import cse
+from assassyn.xform.cse import _expr_key
+
+
+class Mux(Module):
+    """Computes the same values several times"""
+    def __init__(self):
+        super().__init__(ports={'sel': Port(Bits(3)), 'x': Port(Bits(8))})
+
+    @module.combinational
+    def build(self):
+        sel, x = self.pop_all_ports(True)
+        first = sel.select1hot(x, x[0:7], Bits(8)(1))
+        second = sel.select1hot(x, x[0:7], Bits(8)(1))
+        # A different arity is a different select
+        third = sel.select1hot(x, x[0:7])
+        log('{} {} {}', first, second, third)
+        log('{}', concat(x[0:3], x[4:7]))
+        with Condition(sel[0:0]):
+            # Reuses the concat of the enclosing block
+            log('{}', concat(x[0:3], x[4:7]))
+            log('{}', x[1:2])
+        with Condition(sel[1:1]):
+            # Not the slice of the sibling block
+            log('{}', x[1:2])
+
+
+def _merged(body):
+    return [expr for expr in body if isinstance(expr, (Select1Hot, Concat, Slice))]
+
+
+def test_cse():
+    sys = SysBuilder('cse')
+    with sys:
+        mux = Mux()
+        mux.build()
+
+    removed = cse(sys)
+    selects = [expr for expr in mux.body if isinstance(expr, Select1Hot)]
+    assert len(selects) == 2
+    assert [len(expr.values) for expr in selects] == [3, 2]
+    # The log prints the first select twice
+    (printed,) = [expr for expr in mux.body if isinstance(expr, Log) and len(expr.values) == 3]
+    first, second, _ = [unwrap_operand(v) for v in printed.values]
+    assert first is selects[0] and second is selects[0]
+    concats = [expr for expr in mux.body if isinstance(expr, Concat)]
+    assert len(concats) == 1
+    # x[1:2] is kept in both sibling blocks
+    slices = [expr for expr in mux.body if isinstance(expr, Slice) and expr.dtype.bits == 2]
+    assert len(slices) == 2
+    # x[0:7] three times, the second select, and x[0:3], x[4:7] and their concat
+    assert len(removed) == 2 + 1 + 3, removed
+    assert not cse(sys)
+
+
+def test_nested_concats():
+
+    class Pack(Module):
+        """Joins the same four slices with three nestings"""
+        def __init__(self):
+            super().__init__(ports={'x': Port(Bits(8))})
+
+        @module.combinational
+        def build(self):
+            x = self.x.pop()
+            a, b, c, d = x[0:1], x[2:3], x[4:5], x[6:7]
+            left = a.concat(b).concat(c).concat(d)
+            right = a.concat(b.concat(c.concat(d)))
+            pairs = a.concat(b).concat(c.concat(d))
+            # The same leaves in another order differ
+            swapped = b.concat(a).concat(c).concat(d)
+            log('{} {} {} {}', left, right, pairs, swapped)
+
+    sys = SysBuilder('cse_nested_concats')
+    with sys:
+        pack = Pack()
+        pack.build()
+
+    removed = cse(sys)
+    (printed,) = [expr for expr in pack.body if isinstance(expr, Log)]
+    left, right, pairs, swapped = [unwrap_operand(v) for v in printed.values]
+    assert right is left and pairs is left and swapped is not left
+    # The inner concats of the merged chains go with them: three of right, and the three of
+    # pairs, its a.concat(b) merged first
+    assert len(removed) == 3 + 3
+    assert all(expr.parent is None for expr in removed)
+    concats = [expr for expr in pack.body if isinstance(expr, Concat)]
+    assert len(concats) == 3 + 3
+    assert all(expr.users for expr in concats)
+    assert not cse(sys)
+
+
+def _is_top_level(expr):
+    meta = unwrap_operand(expr.meta_cond)
+    return isinstance(meta, Const) and meta.value == 1
+
+
+def _build_minor_cpu():
+    src = Path(repo_path()) / 'examples' / 'minor-cpu' / 'src'
+    py_sys.path.insert(0, str(src))
+    import main  # pylint: disable=import-outside-toplevel,import-error
+    main.init_workspace(f'{src}/../../../python/unit-tests', 'rv32ui-p-add')
+    built = []
+
+    def capture(system, **_):
+        built.append(system)
+        raise StopIteration
+
+    main.elaborate = capture
+    try:
+        main.build_cpu(depth_log=16)
+    except StopIteration:
+        pass
+    return built[0]
+
+
+def test_minor_cpu_execution():
+    system = _build_minor_cpu()
+    (execution,) = [m for m in system.modules if type(m).__name__ == 'Execution']
+    before = len(_merged(execution.body))
+    cse(system)
+    after = _merged(execution.body)
+    assert len(after) < before
+    # No two top-level selects or slices of the execution module are left equal
+    keys = [_expr_key(expr) for expr in after if _is_top_level(expr)]
+    assert len(keys) == len(set(keys))
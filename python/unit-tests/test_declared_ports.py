@@ -0,0 +1,55 @@
+"""Test the ports declared as annotations of a Module subclass"""
+
+from assassyn.frontend import Int, Module, Port, SysBuilder, UInt, module
+
+
+class Adder(Module):
+    """Declares its ports as annotations only"""
+    a: Port[Int(32)]
+    b: Port[Int(32)]
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        return a + b
+
+
+class WideAdder(Adder):
+    """Inherits the ports of Adder, retyping one and adding another"""
+    b: Port[UInt(8)]
+    c: 'Port[UInt(16)]'
+    note: 'SomethingUndefined'
+    count: int
+
+
+class Explicit(Module):
+    """Passes its ports, which wins over the annotations"""
+    a: Port[Int(32)]
+
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(1))})
+
+
+def test_declared_ports():
+    with SysBuilder('declared_ports'):
+        adder = Adder()
+        adder.build()
+        other = Adder()
+    assert [i.name for i in adder.ports] == ['a', 'b']
+    assert all(i.dtype == Int(32) and i.module is adder for i in adder.ports)
+    assert adder.a is adder.ports[0]
+    # Each instance gets its own ports
+    assert all(i is not j for i, j in zip(adder.ports, other.ports))
+
+
+def test_inherited_and_string_ports():
+    with SysBuilder('inherited_ports'):
+        wide = WideAdder()
+    assert [(i.name, i.dtype) for i in wide.ports] == \
+        [('a', Int(32)), ('b', UInt(8)), ('c', UInt(16))]
+
+
+def test_explicit_ports():
+    with SysBuilder('explicit_ports'):
+        explicit = Explicit()
+    assert [i.name for i in explicit.ports] == ['x']
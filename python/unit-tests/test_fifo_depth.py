@@ -0,0 +1,136 @@
+"""Test the static FIFO depth inference"""
+
+import io
+from contextlib import redirect_stdout
+
+import pytest
+
+from assassyn.analysis import infer_fifo_depths
+from assassyn.codegen.verilog.design import generate_design
+from assassyn.frontend import Module, Port, SysBuilder, UInt, log, module
+from assassyn.ir.expr import FIFOPush
+from assassyn.xform import apply_fifo_depths
+
+
+class Sink(Module):
+    """Joins a value of the short path and a value of the long path"""
+    def __init__(self):
+        super().__init__(ports={'short': Port(UInt(8)), 'long': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        short, long_ = self.pop_all_ports(True)
+        log('sink {} {}', short, long_)
+
+
+class Stage(Module):
+    """Forwards its input to the next stage"""
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(8))})
+
+    @module.combinational
+    def build(self, succ, port):
+        x = self.pop_all_ports(True)
+        succ.async_called(**{port: x + UInt(8)(1)})
+
+
+class Driver(Module):
+    """Feeds the sink directly and through the stages"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, sink: Sink, head: Stage):
+        sink.async_called(short=UInt(8)(1))
+        head.async_called(x=UInt(8)(2))
+
+
+def _diamond():
+    sys = SysBuilder('fifo_depth')
+    with sys:
+        sink = Sink()
+        sink.build()
+        mid2 = Stage()
+        mid2.build(sink, 'long')
+        mid1 = Stage()
+        mid1.build(mid2, 'x')
+        Driver().build(sink, mid1)
+    return sys, sink, mid1, mid2
+
+
+def test_diamond_depths():
+    sys, sink, mid1, mid2 = _diamond()
+    depths = infer_fifo_depths(sys)
+    # The sink fires at cycle 3, so the value of the direct call waits for 2 cycles
+    assert depths[sink.short] == 2
+    assert depths[sink.long] == 1
+    assert depths[mid1.x] == 1 and depths[mid2.x] == 1
+
+
+def test_apply_depths():
+    sys, sink, mid1, mid2 = _diamond()
+    assert set(apply_fifo_depths(sys)) == {sink, mid1, mid2}
+    # Two entries, the minimum, hold the waiting values of the sink
+    assert sink.short.fifo_depth == 1 and sink.long.fifo_depth == 1
+    assert mid1.x.fifo_depth == 1
+
+
+def test_apply_keeps_user_depth():
+    sys, sink, _, mid2 = _diamond()
+    push = next(u for u in sink.long.users if isinstance(u, FIFOPush))
+    push.bind.set_fifo_depth(long=3)
+    updated = apply_fifo_depths(sys, {sink.short: 8, sink.long: 8, mid2.x: 8})
+    assert sink not in updated and mid2 in updated
+    assert sink.short.fifo_depth is None and sink.long.fifo_depth == 3
+    assert mid2.x.fifo_depth == 3
+
+
+def test_cycle_falls_back():
+    sys = SysBuilder('fifo_depth_cycle')
+    with sys:
+        ping = Stage()
+        pong = Stage()
+        ping.build(pong, 'x')
+        pong.build(ping, 'x')
+
+        class Kick(Module):
+            """Starts the ping-pong"""
+            def __init__(self):
+                super().__init__(ports={})
+
+            @module.combinational
+            def build(self, ping):
+                ping.async_called(x=UInt(8)(0))
+
+        Kick().build(ping)
+    out = io.StringIO()
+    with redirect_stdout(out):
+        assert not infer_fifo_depths(sys)
+        assert not apply_fifo_depths(sys)
+    assert f'cycle: {ping.name} -> {pong.name} -> {ping.name}' in out.getvalue()
+
+
+def test_sweep_depths(tmp_path):
+    sys, sink, mid1, mid2 = _diamond()
+    path = tmp_path / 'design.py'
+    for depth in (2, 4):
+        names = [f'{sink.name}.short', f'{sink.name}.long', f'{mid2.name}.x']
+        ports = sys.set_all_fifo_depths(dict.fromkeys(names, depth))
+        assert ports == [sink.short, sink.long, mid2.x]
+        assert f'short: Port<u8> // depth {depth}' in repr(sys)
+        generate_design(str(path), sys)
+        code = path.read_text(encoding='utf-8')
+        for port in ('short', 'long'):
+            assert f'fifo_{sink.name}_{port}_inst = FIFO(WIDTH=8, DEPTH_LOG2={depth})' in code
+        assert f'fifo_{mid1.name}_x_inst = FIFO(WIDTH=8, DEPTH_LOG2=1)' in code
+
+
+def test_sweep_rejects():
+    sys, sink, _, _ = _diamond()
+    with pytest.raises(ValueError, match='No FIFO named Sink.wide'):
+        sys.set_all_fifo_depths({f'{sink.name}.short': 2, 'Sink.wide': 2})
+    for depth in (0, -1, 1.5, True):
+        with pytest.raises(ValueError, match='positive integer'):
+            sys.set_all_fifo_depths({f'{sink.name}.short': depth})
+    # Nothing is set when one of the depths is rejected
+    assert sink.short.fifo_depth is None
@@ -0,0 +1,76 @@
+"""The formats inferred by fixed-point arithmetic and casts"""
+
+import pytest
+
+from assassyn.frontend import Fixed, Int, Module, SysBuilder, UInt, from_fixed, log, module, \
+    to_fixed
+from assassyn.ir.expr import Log
+
+Q44 = Fixed(4, 4)
+Q26 = Fixed(2, 6)
+
+
+class Arith(Module):
+    """Builds the arithmetic of a test"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, body):
+        body()
+
+
+def _build(body):
+    sys = SysBuilder('fixed_format')
+    with sys:
+        Arith().build(body)
+    return sys
+
+
+def test_aligned_formats():
+    res = {}
+
+    def body():
+        a, b = Q44(1.5), Q26(-0.25)
+        res['sum'] = a + b
+        res['diff'] = a - b
+        res['full'] = a.fmul_full(b)
+
+    _build(body)
+    assert res['sum'].dtype == Fixed(4, 6)
+    assert res['diff'].dtype == Fixed(4, 6)
+    assert res['full'].dtype == Fixed(6, 10)
+    assert res['full'].value().dtype == Int(16)
+
+
+def test_casts():
+    res = {}
+
+    def body():
+        res['wide'] = Q44(-1.5).cast(Fixed(8, 8))
+        res['narrow'] = Q44(-1.5).cast(Fixed(4, 2))
+        res['unsigned'] = to_fixed(UInt(4)(15), Q44)
+        res['floor'] = from_fixed(Q44(-1.5), Int(4))
+
+    _build(body)
+    assert res['wide'].dtype == Fixed(8, 8)
+    assert res['narrow'].dtype == Fixed(4, 2)
+    assert res['unsigned'].dtype == Q44
+    assert res['floor'].dtype == Int(4)
+
+
+def test_mixed_signedness():
+    def body():
+        Q44(1) + Fixed(4, 4, signed=False)(1)
+
+    with pytest.raises(AssertionError, match='signedness'):
+        _build(body)
+
+
+def test_log_fixed():
+    def body():
+        log('{} {}', Q44(1.5), UInt(8)(3))
+
+    sys = _build(body)
+    node = next(i for i in sys.modules[0].body if isinstance(i, Log))
+    assert node.fixed == {0: Q44}
@@ -0,0 +1,82 @@
+"""Test the Gray code encoder, decoder and counter"""
+
+import pytest
+
+from assassyn.frontend import (Bits, Int, Module, RegArray, SysBuilder, UInt, binary_to_gray,
+                               create_gray_counter, gray_to_binary, module)
+from assassyn.ir.expr import BinaryOp
+from assassyn.utils import unwrap_operand
+
+
+class Codes(Module):
+    """Builds the codes of the test on its registers"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, check):
+        values = RegArray(UInt(5), 1)
+        check(values[0])
+
+
+def _build(check):
+    sys = SysBuilder('gray_code')
+    with sys:
+        Codes().build(check)
+    return sys
+
+
+def _xors(value):
+    '''The XORs building `value`, through its casts and shifts.'''
+    res = set()
+    todo = [value]
+    while todo:
+        expr = todo.pop()
+        if isinstance(expr, BinaryOp) and expr.opcode == BinaryOp.BITWISE_XOR:
+            res.add(expr)
+        todo.extend(unwrap_operand(i) for i in getattr(expr, 'operands', []))
+    return res
+
+
+def test_codes_keep_type():
+    def check(value):
+        assert binary_to_gray(value).dtype == UInt(5)
+        assert gray_to_binary(value).dtype == UInt(5)
+        raw = value.bitcast(Bits(5))
+        assert gray_to_binary(raw).dtype == Bits(5)
+
+    _build(check)
+
+
+def test_decoder_folds_xors():
+    def check(value):
+        # 5 bits take shifts by 1, 2 and 4
+        assert len(_xors(gray_to_binary(value))) == 3
+        assert len(_xors(binary_to_gray(value))) == 1
+
+    _build(check)
+
+
+def test_codes_reject_signed():
+    def check(value):
+        signed = value.bitcast(Int(5))
+        with pytest.raises(ValueError, match='UInt or Bits'):
+            binary_to_gray(signed)
+        with pytest.raises(ValueError, match='UInt or Bits'):
+            gray_to_binary(signed)
+        with pytest.raises(TypeError):
+            gray_to_binary(3)
+
+    _build(check)
+
+
+def test_gray_counter():
+    res = []
+
+    def check(_):
+        res.append(create_gray_counter(3, 5, name='ptr'))
+
+    _build(check)
+    array, gray = res[0]
+    assert array.name == 'ptr' and array.initializer == [5]
+    assert array.scalar_ty == UInt(3) and gray.dtype == UInt(3)
@@ -0,0 +1,97 @@
+"""Test the heuristic inference of downstream modules"""
+
+from assassyn.frontend import (
+    Array, Condition, Module, Port, RegArray, SysBuilder, UInt, module,
+)
+from assassyn.analysis import infer_downstream
+from assassyn.backend import config
+
+
+class Extension(Module):
+    """Never waits and only writes the array of its caller"""
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(8))})
+
+    @module.combinational
+    def build(self, acc: Array):
+        x = self.pop_all_ports(False)
+        (acc & self)[0] <= x
+
+
+class Waiting(Module):
+    """Waits for its port"""
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        self.pop_all_ports(True)
+
+
+class Private(Module):
+    """Writes an array nobody else writes"""
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        x = self.pop_all_ports(False)
+        own = RegArray(UInt(8), 1)
+        (own & self)[0] <= x
+
+
+class Guarded(Module):
+    """Called only under a condition"""
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        self.pop_all_ports(False)
+
+
+class Driver(Module):
+    """Writes `acc` and calls all the others"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, acc: Array, ext: Extension, waiting: Waiting, private: Private,
+              guarded: Guarded):
+        (acc & self)[0] <= acc[0] + UInt(8)(1)
+        ext.async_called(x=acc[0])
+        waiting.async_called(x=acc[0])
+        private.async_called(x=acc[0])
+        with Condition(acc[0] == UInt(8)(0)):
+            guarded.async_called(x=acc[0])
+
+
+def test_infer_downstream():
+    """Only the module meeting all three conditions is marked, once"""
+    sys = SysBuilder('test_infer_downstream')
+    with sys:
+        acc = RegArray(UInt(8), 1)
+        ext = Extension()
+        ext.build(acc)
+        waiting = Waiting()
+        waiting.build()
+        private = Private()
+        private.build()
+        guarded = Guarded()
+        guarded.build()
+        Driver().build(acc, ext, waiting, private, guarded)
+
+    marked = infer_downstream(sys)
+    assert len(marked) == 1 and marked[0] is ext
+    assert ext.inferred_downstream
+    assert not any(m.inferred_downstream for m in (waiting, private, guarded))
+    assert 'downstream: True' in repr(sys)
+    # Inference is idempotent
+    again = infer_downstream(sys)
+    assert len(again) == 1 and again[0] is ext
+
+
+def test_off_by_default():
+    """The marks change the IR hash, so elaborate only infers when asked to"""
+    assert config()['infer_downstream'] is False
+    assert config(infer_downstream=True)['infer_downstream'] is True
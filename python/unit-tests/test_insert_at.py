@@ -0,0 +1,91 @@
+"""Test building expressions in front of or after an existing one"""
+
+import pytest
+
+from assassyn.frontend import Condition, Module, Port, SysBuilder, UInt, log, module
+from assassyn.ir.expr import BinaryOp, Log
+from assassyn.utils import unwrap_operand
+
+
+class OddLog(Module):
+    """Pops two operands, and logs their sum under a condition"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8)), 'b': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        c = a + b
+        with Condition(c[0:0]):
+            log('odd {}', c)
+        log('c {}', c)
+
+
+def _index(body, expr):
+    return next(i for i, elem in enumerate(body) if elem is expr)
+
+
+def test_insert_before_start():
+    sys = SysBuilder('insert_before_start')
+    with sys:
+        logger = OddLog()
+        logger.build()
+    # The valid check of the ports opens the body
+    first = logger.body[0]
+    size = len(logger.body)
+    one = sys.insert_before(first, lambda: UInt(8)(1) + UInt(8)(2))
+    assert logger.body[0] is one and logger.body[1] is first
+    assert len(logger.body) == size + 1
+    assert one.parent is logger
+
+
+def test_insert_after_middle():
+    sys = SysBuilder('insert_after_middle')
+    with sys:
+        logger = OddLog()
+        logger.build()
+    add = next(expr for expr in logger.body if isinstance(expr, BinaryOp))
+    a, b = unwrap_operand(add.lhs), unwrap_operand(add.rhs)
+
+    def build():
+        # Several expressions, the later ones using the earlier ones
+        diff = a - b
+        return diff, diff ^ add
+
+    diff, xor = sys.insert_after(add, build)
+    pos = _index(logger.body, add)
+    assert logger.body[pos + 1] is diff and logger.body[pos + 2] is xor
+
+
+def test_insert_after_end():
+    sys = SysBuilder('insert_after_end')
+    with sys:
+        logger = OddLog()
+        logger.build()
+    last = logger.body[-1]
+    assert isinstance(last, Log)
+    done = sys.insert_after(last, lambda: log('done'))
+    assert logger.body[-1] is done and logger.body[-2] is last
+
+
+def test_insert_in_condition():
+    sys = SysBuilder('insert_in_condition')
+    with sys:
+        logger = OddLog()
+        logger.build()
+    odd = next(expr for expr in logger.body if isinstance(expr, Log))
+    c = odd.args[1]
+    with sys:
+        # The builder may already be active
+        twice = sys.insert_before(odd, lambda: c + c)
+    # Built in the block of the anchor, under its condition
+    assert _index(logger.body, twice) == _index(logger.body, odd) - 1
+    assert twice.meta_cond is odd.meta_cond
+
+
+def test_insert_rejects_detached():
+    sys = SysBuilder('insert_rejects_detached')
+    with sys:
+        OddLog().build()
+    with pytest.raises(ValueError, match='not in the body of a module'):
+        sys.insert_before(UInt(8)(1), lambda: None)
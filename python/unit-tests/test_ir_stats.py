@@ -0,0 +1,62 @@
+"""Test the IR statistics of a system"""
+
+import io
+import json
+from contextlib import redirect_stderr
+
+from assassyn.frontend import Module, Port, RegArray, SysBuilder, UInt, log, module
+
+
+class Sum(Module):
+    """Logs the sum of its operands"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8)), 'b': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        log('sum {}', a + b)
+
+
+class Driver(Module):
+    """Counts and calls the sum, with a deeper FIFO for the second operand"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, callee: Sum):
+        cnt = RegArray(UInt(8), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+        call = callee.async_called(a=cnt[0], b=cnt[0])
+        call.bind.set_fifo_depth(b=4)
+
+
+def test_counts():
+    sys = SysBuilder('ir_stats')
+    with sys:
+        callee = Sum()
+        callee.build()
+        Driver().build(callee)
+    stats = sys.stats()
+    assert (stats.modules, stats.downstreams, stats.arrays, stats.fifos) == (2, 0, 1, 2)
+    callee, driver = stats.per_module
+    assert (callee.ports, driver.ports) == (2, 0)
+    assert stats.expressions == callee.body_length + driver.body_length
+    assert stats.avg_body_length == stats.expressions / 2
+    assert (callee.externals, driver.externals) == (0, 0)
+    assert stats.fifo_depths == {f'{callee.name}.a': None, f'{callee.name}.b': 4}
+    assert json.loads(stats.to_json())['per_module'][1]['name'] == driver.name
+
+
+def test_print_stats():
+    sys = SysBuilder('ir_stats')
+    with sys:
+        callee = Sum()
+        callee.build()
+        Driver().build(callee)
+    err = io.StringIO()
+    with redirect_stderr(err):
+        sys.print_stats()
+    lines = err.getvalue().splitlines()
+    assert lines[0].startswith('[ir-stats] system ir_stats: 2 modules')
+    assert lines[-1].endswith('depth 4')
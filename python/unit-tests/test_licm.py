@@ -0,0 +1,76 @@
+"""Test hoisting invariant array reads out of condition blocks"""
+
+from assassyn.frontend import (
+    Array, Bits, Condition, Module, Port, RegArray, SysBuilder, UInt, log, module,
+)
+from assassyn.ir.expr import ArrayRead, Log
+from assassyn.ir.expr.intrinsic import Intrinsic
+from assassyn.utils import unwrap_operand
+from assassyn.xform import licm
+
+
+class Reader(Module):
+    """Reads `rf` in several condition blocks"""
+    def __init__(self):
+        super().__init__(ports={'sel': Port(Bits(1)), 'x': Port(UInt(8))})
+
+    @module.combinational
+    def build(self, rf: Array):
+        sel, x = self.pop_all_ports(True)
+        with Condition(sel):
+            log('taken {}', rf[0])
+        with Condition(~sel):
+            log('not taken {}', rf[0])
+        with Condition(sel):
+            (rf & self)[1] <= x
+            # A store to rf earlier in the block keeps this read in place
+            log('after store {}', rf[1])
+        with Condition(sel):
+            # The index is computed in the block
+            log('dynamic {}', rf[x[0:1].bitcast(UInt(2))])
+
+
+def _reads(body, index):
+    return [expr for expr in body if isinstance(expr, ArrayRead)
+            and getattr(unwrap_operand(expr.idx), 'value', None) == index]
+
+
+def _index(body, node):
+    # Values overload ==, so nodes are found by identity
+    return next(i for i, expr in enumerate(body) if expr is node)
+
+
+def test_licm():
+    sys = SysBuilder('licm')
+    with sys:
+        rf = RegArray(UInt(8), 4, name='rf')
+        reader = Reader()
+        reader.build(rf)
+
+    hoisted = licm(sys)
+    body = reader.body
+    first_block = next(i for i, expr in enumerate(body)
+                       if isinstance(expr, Intrinsic) and expr.opcode == Intrinsic.PUSH_CONDITION)
+
+    # rf[0] is read once, in front of all the blocks, and unconditionally
+    reads = _reads(body, 0)
+    assert len(reads) == 1, reads
+    assert len(hoisted) == 1 and hoisted[0] is reads[0]
+    assert _index(body, reads[0]) < first_block
+    assert unwrap_operand(reads[0].meta_cond).value == 1
+    # Both logs use the hoisted read
+    logs = [expr for expr in body if isinstance(expr, Log)]
+    for log_expr in logs[:2]:
+        assert log_expr.values[0] is reads[0]
+    assert len(reads[0].users) == 2
+    assert sum(1 for user in rf.users if user is reads[0]) == 1
+    assert len(rf.users) == len([e for e in body if isinstance(e, ArrayRead)]) + 1
+
+    # The read after the store and the dynamic read stay in their blocks
+    assert all(_index(body, read) > first_block for read in _reads(body, 1))
+    dynamic = [expr for expr in body if isinstance(expr, ArrayRead)
+               and not _reads([expr], 0) and not _reads([expr], 1)]
+    assert len(dynamic) == 1 and _index(body, dynamic[0]) > first_block
+
+    # Running the pass again moves nothing
+    assert not licm(sys)
@@ -0,0 +1,55 @@
+"""Test the check on the ports of modules merging the calls of a cycle"""
+
+import io
+from contextlib import redirect_stdout
+
+import pytest
+
+from assassyn.analysis import check_merged_calls, shared_merged_ports
+from assassyn.frontend import Module, Port, SysBuilder, UInt, log, module
+
+
+class Counter(Module):
+    """Sums the deltas of its callers"""
+    def __init__(self):
+        super().__init__(ports={'d0': Port(UInt(8)), 'd1': Port(UInt(8))}, merge_calls=True)
+
+    @module.combinational
+    def build(self):
+        log('counter {} {}', *self.pop_all_ports(True))
+
+
+class Caller(Module):
+    """Pushes a delta into a port of the counter"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, counter: Counter, port: str):
+        counter.async_called(**{port: UInt(8)(1)})
+
+
+def _build(ports):
+    sys = SysBuilder('merge_calls')
+    with sys:
+        counter = Counter()
+        counter.build()
+        for port in ports:
+            Caller().build(counter, port)
+    return sys, counter
+
+
+def test_distinct_ports():
+    sys, _ = _build(['d0', 'd1'])
+    assert not shared_merged_ports(sys)
+
+
+def test_shared_port():
+    sys, counter = _build(['d0', 'd0'])
+    assert shared_merged_ports(sys) == [counter.d0]
+    out = io.StringIO()
+    with redirect_stdout(out):
+        check_merged_calls(sys)
+    assert f'Port {counter.name}.d0 is pushed by several calls' in out.getvalue()
+    with pytest.raises(ValueError, match='d0'):
+        check_merged_calls(sys, strict=True)
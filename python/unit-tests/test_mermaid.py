@@ -0,0 +1,70 @@
+"""Test the Mermaid flowcharts of the call graph and of the data flow"""
+
+from assassyn.frontend import (Module, Port, RegArray, SysBuilder, UInt, create_downstream, log,
+                               module)
+
+
+class Producer(Module):
+    """Logs the sum of its inputs, and returns it to a downstream"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8)), 'b': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        c = a + b
+        log('sum {}', c)
+        return c
+
+
+class Driver(Module):
+    """Counts and calls the producer"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, producer: Producer):
+        cnt = RegArray(UInt(8), 1)
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+        producer.async_called(a=cnt[0], b=cnt[0])
+
+
+def test_call_graph():
+    sys = SysBuilder('mermaid_call_graph')
+    with sys:
+        producer = Producer()
+        c = producer.build()
+        Driver().build(producer)
+
+        def body(ctx):
+            log('twice {}', ctx.use_value(c).optional(UInt(8)(0)) * UInt(8)(2))
+
+        create_downstream('Twice', body)
+    assert sys.mermaid_call_graph().split('\n') == [
+        'flowchart TD',
+        f'    {producer.name}["{producer.name}"]',
+        '    Driver["Driver"]',
+        '    Twice["Twice"]',
+        f'    Driver -->|a, b| {producer.name}',
+        f'    {producer.name} -.-> Twice',
+    ]
+
+
+def test_dataflow():
+    sys = SysBuilder('mermaid_dataflow')
+    with sys:
+        producer = Producer()
+        c = producer.build()
+        Driver().build(producer)
+
+        def body(ctx):
+            log('twice {}', ctx.use_value(c).optional(UInt(8)(0)) * UInt(8)(2))
+
+        create_downstream('Twice', body)
+    lines = sys.mermaid_dataflow(producer).split('\n')
+    assert lines[0] == 'flowchart TD'
+    assert '    a --> c' in lines and '    b --> c' in lines
+    twice = sys.mermaid_dataflow(sys.downstreams[0]).split('\n')
+    # The value of the producer is a node of its own, named after its module
+    assert f'    {producer.name}_c["{producer.name}.c"]' in twice
+    assert any(i.startswith(f'    {producer.name}_c --> ') for i in twice)
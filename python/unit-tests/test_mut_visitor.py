@@ -0,0 +1,108 @@
+"""Test rewriting the IR in place with the MutVisitor"""
+
+from assassyn.frontend import (
+    Condition, Module, Port, RegArray, SysBuilder, UInt, log, module,
+)
+from assassyn.ir.expr import BinaryOp, Log
+from assassyn.ir.visitor import MutVisitor, replace_operand
+from assassyn.utils import unwrap_operand
+
+
+class NestedLogs(Module):
+    """Logs in and out of condition blocks"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8)), 'b': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        c = a + b
+        log('c {}', c)
+        with Condition(c[0:0]):
+            log('odd {}', c)
+            d = c + a
+            with Condition(d[0:0]):
+                log('nested {}', d)
+        acc = RegArray(UInt(8), 1)
+        (acc & self)[0] <= d
+        log('acc {}', acc[0])
+
+
+class StripLogs(MutVisitor):
+    """Deletes every log"""
+    def visit_expr_mut(self, node):
+        if isinstance(node, Log):
+            self.erase(node)
+
+
+class SwapAdd(MutVisitor):
+    """Replaces `c + a` by `a + c`, built in front of it"""
+    def __init__(self, sys):
+        super().__init__()
+        self.sys = sys
+
+    def visit_expr_mut(self, node):
+        if isinstance(node, BinaryOp) and node.opcode == BinaryOp.ADD \
+                and isinstance(unwrap_operand(node.lhs), BinaryOp):
+            lhs, rhs = unwrap_operand(node.lhs), unwrap_operand(node.rhs)
+            swapped = self.sys.insert_before(node, lambda: rhs + lhs)
+            self.replace_expr(node, swapped)
+
+
+def test_strip_logs():
+    sys = SysBuilder('strip_logs')
+    with sys:
+        nested = NestedLogs()
+        nested.build()
+    kept = [expr for expr in nested.body if not isinstance(expr, Log)]
+    StripLogs().visit_system(sys)
+    # The other expressions stay in place, including the nested blocks
+    assert len(nested.body) == len(kept)
+    assert all(x is y for x, y in zip(nested.body, kept))
+    # The logged values are no longer used by the logs
+    for expr in nested.body:
+        assert not any(isinstance(user.user, Log) for user in getattr(expr, 'users', []))
+
+
+def test_replace_expr():
+    sys = SysBuilder('replace_expr')
+    with sys:
+        nested = NestedLogs()
+        nested.build()
+    old = next(expr for expr in nested.body
+               if isinstance(expr, BinaryOp) and isinstance(unwrap_operand(expr.lhs), BinaryOp))
+    SwapAdd(sys).visit_system(sys)
+    assert not any(expr is old for expr in nested.body)
+    new = next(expr for expr in nested.body
+               if isinstance(expr, BinaryOp) and isinstance(unwrap_operand(expr.rhs), BinaryOp))
+    # The users of the old sum, the nested condition included, use the new one
+    assert new.users and all(user.value is new for user in new.users)
+    pos = next(i for i, expr in enumerate(nested.body) if expr is new)
+    assert all(next(i for i, expr in enumerate(nested.body) if expr is user.user) > pos
+               for user in new.users)
+    assert new.meta_cond is old.meta_cond
+
+
+def test_replace_operand():
+    sys = SysBuilder('replace_operand')
+    with sys:
+        nested = NestedLogs()
+        nested.build()
+    add = next(expr for expr in nested.body
+               if isinstance(expr, BinaryOp) and isinstance(unwrap_operand(expr.lhs), BinaryOp))
+    old, new = unwrap_operand(add.lhs), unwrap_operand(add.rhs)
+    old_uses = len(old.users)
+    new_uses = len(new.users)
+    MutVisitor().replace_operand(add, 0, new)
+    # `c + a` is now `a + a`, and only the use lists of the two values changed
+    assert unwrap_operand(add.lhs) is new and unwrap_operand(add.rhs) is new
+    assert add.get_operand(0).user is add
+    assert len(old.users) == old_uses - 1
+    assert not any(user.user is add for user in old.users)
+    assert len(new.users) == new_uses + 1
+    assert sum(user is add.get_operand(0) for user in new.users) == 1
+    # Constants are wrapped, and not recorded as users of anything
+    one = UInt(8)(1)
+    replace_operand(add, 1, one)
+    assert unwrap_operand(add.rhs) is one
+    assert len(new.users) == new_uses
@@ -0,0 +1,70 @@
+"""Test the checked one_hot_mux and binary_mux builders"""
+
+import pytest
+
+from assassyn.frontend import (Bits, Int, Module, RegArray, SysBuilder, UInt, binary_mux,
+                               module, one_hot_mux)
+from assassyn.ir.expr import Select, Select1Hot
+from assassyn.utils import unwrap_operand
+
+
+class Muxes(Module):
+    """Builds the muxes of the test on its registers"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, check):
+        sel = RegArray(Bits(3), 1)
+        values = RegArray(UInt(8), 3)
+        check(sel[0], [values[0], values[1], values[2]])
+
+
+def _build(check):
+    sys = SysBuilder('mux_builders')
+    with sys:
+        Muxes().build(check)
+
+
+def test_one_hot_mux():
+    res = []
+
+    def check(sel, values):
+        mux = one_hot_mux(sel, values)
+        assert isinstance(mux, Select1Hot)
+        assert unwrap_operand(mux.cond) is sel
+        assert all(unwrap_operand(a) is b for a, b in zip(mux.values, values))
+        assert mux.dtype == UInt(8)
+        res.append(mux)
+
+    _build(check)
+    assert res
+
+
+def test_one_hot_mux_rejects():
+    def check(sel, values):
+        with pytest.raises(ValueError, match='one bit for each'):
+            one_hot_mux(sel, values[:2])
+        with pytest.raises(ValueError, match='at least one'):
+            one_hot_mux(sel, [])
+        with pytest.raises(ValueError, match='one type'):
+            one_hot_mux(sel, values[:2] + [Int(8)(1)])
+        with pytest.raises(TypeError):
+            one_hot_mux(sel, values[:2] + [1])
+
+    _build(check)
+
+
+def test_binary_mux():
+    def check(sel, values):
+        cond = sel[0:0]
+        mux = binary_mux(cond, values[0], values[1])
+        assert isinstance(mux, Select)
+        operands = (mux.cond, mux.true_value, mux.false_value)
+        assert all(unwrap_operand(a) is b for a, b in zip(operands, (cond, values[0], values[1])))
+        with pytest.raises(ValueError, match='1 bit'):
+            binary_mux(sel, values[0], values[1])
+        with pytest.raises(ValueError, match='one type'):
+            binary_mux(cond, values[0], Int(8)(1))
+
+    _build(check)
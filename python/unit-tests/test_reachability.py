@@ -0,0 +1,56 @@
+"""Test the check that every module is reachable from the driver"""
+
+import io
+from contextlib import redirect_stdout
+
+import pytest
+
+from assassyn.analysis import check_reachability, unreachable_modules
+from assassyn.frontend import Module, Port, SysBuilder, UInt, log, module
+
+
+class Worker(Module):
+    """Logs its input"""
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        log('worker {}', self.pop_all_ports(True))
+
+
+class Driver(Module):
+    """Calls the first worker only"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, worker: Worker):
+        worker.async_called(x=UInt(8)(1))
+
+
+def test_dangling_module_warns():
+    sys = SysBuilder('dangling_module_warns')
+    with sys:
+        called = Worker()
+        called.build()
+        dangling = Worker()
+        dangling.build()
+        Driver().build(called)
+    assert [m.name for m in unreachable_modules(sys)] == [dangling.name]
+    out = io.StringIO()
+    with redirect_stdout(out):
+        assert check_reachability(sys) == [dangling]
+    assert f'Module {dangling.name} is never async-called' in out.getvalue()
+
+
+def test_dangling_module_strict():
+    sys = SysBuilder('dangling_module_strict')
+    with sys:
+        called = Worker()
+        called.build()
+        dangling = Worker()
+        dangling.build()
+        Driver().build(called)
+    with pytest.raises(ValueError, match=dangling.name):
+        check_reachability(sys, strict=True)
@@ -0,0 +1,64 @@
+"""Test renaming the ports of a built module"""
+
+import pytest
+
+from assassyn.frontend import Module, Port, SysBuilder, UInt, log, module
+
+
+class Callee(Module):
+    """Logs the sum of its operands"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8)), 'b': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        log('sum {}', a + b)
+
+
+def test_rename_port():
+
+    class Driver(Module):
+        """Binds the first operand, then calls it with the second one"""
+        def __init__(self):
+            super().__init__(ports={})
+
+        @module.combinational
+        def build(self, callee: Callee):
+            bound = callee.bind(a=UInt(8)(1))
+            bound.async_called(b=UInt(8)(2))
+            return bound
+
+    sys = SysBuilder('rename_port')
+    with sys:
+        callee = Callee()
+        callee.build()
+        bound = Driver().build(callee)
+
+    port = callee.a
+    assert sys.rename_port(callee, 'a', 'lhs') is port
+    assert callee.lhs is port and not hasattr(callee, 'a')
+    assert [p.name for p in callee.ports] == ['lhs', 'b']
+    # The bind still pushes the renamed port, and resolves it by its new name
+    assert [push.fifo for push in bound.pushes] == [port, callee.b]
+    assert not bound.unbound_ports()
+    bound.set_fifo_depth(lhs=2)
+    assert port.fifo_depth == 2
+    dump = repr(sys)
+    assert f'{callee.name}.lhs' in dump and f'{callee.name}.a' not in dump
+
+
+def test_rename_port_invalid():
+    sys = SysBuilder('rename_port_invalid')
+    with sys:
+        callee = Callee()
+        callee.build()
+    with pytest.raises(ValueError, match='has no port named c'):
+        sys.rename_port(callee, 'c', 'x')
+    with pytest.raises(ValueError, match='the name is taken'):
+        sys.rename_port(callee, 'a', 'b')
+    with pytest.raises(ValueError, match='the name is taken'):
+        sys.rename_port(callee, 'a', 'build')
+    with pytest.raises(ValueError, match='Invalid port name'):
+        sys.rename_port(callee, 'a', 'a b')
+    assert [p.name for p in callee.ports] == ['a', 'b']
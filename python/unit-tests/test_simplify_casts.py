@@ -0,0 +1,90 @@
+"""Test removing the casts that cancel out"""
+
+import sys as py_sys
+from pathlib import Path
+
+from assassyn.frontend import Bits, Int, Module, Port, SysBuilder, UInt, log, module
+from assassyn.ir.expr import BinaryOp, Cast, FIFOPop, Log
+from assassyn.utils import repo_path, unwrap_operand
+from assassyn.xform import simplify_casts
+
+
+class Alu(Module):
+    """Computes on the bits of its inputs as signed integers"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(Bits(32)), 'b': Port(Bits(32)), 'c': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        a, b, c = self.pop_all_ports(True)
+        add = (a.bitcast(Int(32)) + b.bitcast(Int(32))).bitcast(Bits(32))
+        inc = (a.bitcast(Int(32)) + Int(32)(-4)).bitcast(Bits(32))
+        # The compare needs the signed operands, which the subtraction shares
+        sa = a.bitcast(Int(32))
+        less = sa < b.bitcast(Int(32))
+        diff = (sa - b.bitcast(Int(32))).bitcast(Bits(32))
+        back = c.bitcast(Bits(8)).bitcast(UInt(8))
+        wide = c.zext(UInt(16)).zext(UInt(32))
+        log('{} {} {} {} {} {}', add, inc, less, diff, back, wide)
+
+
+def _printed(alu):
+    (printed,) = [expr for expr in alu.body if isinstance(expr, Log)]
+    return [unwrap_operand(v) for v in printed.values]
+
+
+def test_simplify_casts():
+    sys = SysBuilder('simplify_casts')
+    with sys:
+        alu = Alu()
+        alu.build()
+
+    removed = simplify_casts(sys)
+    add, inc, less, diff, back, wide = _printed(alu)
+    # A single addition of the bits, with the same type as the round-trip
+    assert isinstance(add, BinaryOp) and add.dtype == Bits(32)
+    assert [unwrap_operand(i).name for i in (add.lhs, add.rhs)] == ['a', 'b']
+    assert isinstance(inc, BinaryOp) and inc.dtype == Bits(32)
+    assert unwrap_operand(inc.rhs).value == 0xFFFFFFFC
+    # The signed compare keeps its casts, even the one the subtraction no longer reads
+    assert all(isinstance(unwrap_operand(i), Cast) for i in (less.lhs, less.rhs))
+    assert isinstance(diff, BinaryOp) and diff.dtype == Bits(32)
+    assert isinstance(back, FIFOPop) and back.dtype == UInt(8)
+    assert isinstance(wide, Cast) and unwrap_operand(wide.x) is back
+    casts = [expr for expr in alu.body if isinstance(expr, Cast)]
+    # The two casts of the compare, and the last zext
+    assert len(casts) == 3, casts
+    assert len(removed) == 3 + 2 + 2 + 2 + 1, removed
+    assert not simplify_casts(sys)
+
+
+def _build_minor_cpu():
+    src = Path(repo_path()) / 'examples' / 'minor-cpu' / 'src'
+    py_sys.path.insert(0, str(src))
+    import main  # pylint: disable=import-outside-toplevel,import-error
+    main.init_workspace(f'{src}/../../../python/unit-tests', 'rv32ui-p-add')
+    built = []
+
+    def capture(system, **_):
+        built.append(system)
+        raise StopIteration
+
+    main.elaborate = capture
+    try:
+        main.build_cpu(depth_log=16)
+    except StopIteration:
+        pass
+    return built[0]
+
+
+def test_minor_cpu():
+    system = _build_minor_cpu()
+
+    def count():
+        return sum(isinstance(expr, Cast) for m in system.modules + system.downstreams
+                   for expr in m.body or [])
+
+    before = count()
+    assert simplify_casts(system)
+    assert count() < before
+    assert not system.validate_types()
@@ -0,0 +1,41 @@
+"""Test the validation of Array.store_masked"""
+
+import pytest
+
+from assassyn.frontend import Bits, Module, RegArray, SysBuilder, UInt, module
+from assassyn.ir.expr import ArrayWrite
+
+
+class Writer(Module):
+    """Stores `value` into a word of `dtype` under `mask`"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, dtype, mask):
+        word = RegArray(dtype, 1)
+        word.store_masked(0, dtype(1), mask)
+
+
+def test_one_write():
+    sys = SysBuilder('store_masked')
+    with sys:
+        writer = Writer()
+        writer.build(UInt(32), Bits(4)(1))
+    writes = [i for i in writer.body if isinstance(i, ArrayWrite)]
+    assert len(writes) == 1
+    assert writes[0].val.dtype == UInt(32)
+
+
+@pytest.mark.parametrize('dtype, mask', [
+    # One bit per byte, not per bit
+    (UInt(32), Bits(32)(1)),
+    (UInt(32), Bits(2)(1)),
+    # Elements of a partial byte
+    (UInt(12), Bits(1)(1)),
+])
+def test_invalid(dtype, mask):
+    sys = SysBuilder('store_masked_invalid')
+    with sys:
+        with pytest.raises(ValueError):
+            Writer().build(dtype, mask)
@@ -0,0 +1,89 @@
+"""Test the reachability of the modules from a root, and the subgraph it spans"""
+
+from assassyn.frontend import (Module, Port, RegArray, SysBuilder, UInt, create_downstream, log,
+                               module)
+
+
+class Sink(Module):
+    """Logs its input"""
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        x = self.pop_all_ports(True)
+        acc = RegArray(UInt(8), 1, name='acc')
+        (acc & self)[0] <= acc[0] + x
+        log('sink {}', x)
+
+
+class Stage(Module):
+    """Doubles its input for the sink"""
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(8))})
+
+    @module.combinational
+    def build(self, sink: Sink):
+        x = self.pop_all_ports(True)
+        y = x + x
+        sink.async_called(x=y)
+        return y
+
+
+class Driver(Module):
+    """Counts and calls the stage"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, stage: Stage):
+        cnt = RegArray(UInt(8), 1, name='cnt')
+        (cnt & self)[0] <= cnt[0] + UInt(8)(1)
+        stage.async_called(x=cnt[0])
+        return cnt
+
+
+def test_reachable_from():
+    sys = SysBuilder('reachable_from')
+    with sys:
+        sink = Sink()
+        sink.build()
+        stage = Stage()
+        y = stage.build(sink)
+        cnt = Driver().build(stage)
+
+        def body(ctx):
+            log('y {}', ctx.use_value(y).optional(UInt(8)(0)))
+
+        create_downstream('Watch', body)
+        sys.expose_on_top(cnt, kind='Output')
+    watch, = sys.downstreams
+    assert sys.reachable_from(stage) == [stage, sink, watch]
+    assert sys.reachable_from(sink) == [sink]
+    driver = sys.has_module('Driver')
+    assert sys.reachable_from(driver) == [driver, stage, sink, watch]
+
+
+def test_callee_subgraph():
+    sys = SysBuilder('subgraph')
+    with sys:
+        sink = Sink()
+        sink.build()
+        stage = Stage()
+        y = stage.build(sink)
+        cnt = Driver().build(stage)
+
+        def body(ctx):
+            log('y {}', ctx.use_value(y).optional(UInt(8)(0)))
+
+        create_downstream('Watch', body)
+        sys.expose_on_top(cnt, kind='Output')
+    sub = sys.callee_subgraph(stage)
+    assert sub.name == f'subgraph_{stage.name}'
+    assert sub.modules == [m for m in sys.modules if m in (stage, sink)]
+    assert sub.downstreams == sys.downstreams
+    # The counter of the driver is left out, with its exposure
+    assert [a.name for a in sub.arrays] == ['acc']
+    assert not sub.exposed_nodes
+    # The original system is untouched
+    assert len(sys.modules) == 3 and len(sys.arrays) == 2
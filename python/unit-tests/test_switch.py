@@ -0,0 +1,85 @@
+"""Test multi-way Switch blocks"""
+
+import pytest
+
+from assassyn.frontend import Module, Port, Switch, SysBuilder, UInt, log, module
+from assassyn.ir.block import verify_blocks
+from assassyn.ir.expr import BinaryOp, UnaryOp
+from assassyn.ir.expr.intrinsic import Intrinsic
+from assassyn.utils import unwrap_operand
+
+
+class Decoder(Module):
+    """Switches on its opcode"""
+    def __init__(self):
+        super().__init__(ports={'op': Port(UInt(4))})
+
+    @module.combinational
+    def build(self):
+        op = self.op.pop()
+        with Switch(op) as sw:
+            with sw.case(1):
+                log('one')
+            with sw.case(2, 3):
+                log('two or three')
+            with sw.default():
+                log('other {}', op)
+
+
+def _pushes(module_):
+    return [expr for expr in module_.body
+            if isinstance(expr, Intrinsic) and expr.opcode == Intrinsic.PUSH_CONDITION]
+
+
+def test_switch_blocks():
+    sysb = SysBuilder('switch')
+    with sysb:
+        decoder = Decoder()
+        decoder.build()
+    verify_blocks(decoder)
+    pushes = _pushes(decoder)
+    assert len(pushes) == 3
+    one, two_three, default = [unwrap_operand(push.args[0]) for push in pushes]
+    # A single key is compared directly, several keys are OR-ed
+    assert isinstance(one, BinaryOp) and one.opcode == BinaryOp.EQ
+    assert unwrap_operand(one.rhs).value == 1
+    assert isinstance(two_three, BinaryOp) and two_three.opcode == BinaryOp.BITWISE_OR
+    keys = [unwrap_operand(unwrap_operand(eq).rhs).value for eq in (two_three.lhs, two_three.rhs)]
+    assert keys == [2, 3]
+    # The default block is taken when no case matches
+    assert isinstance(default, UnaryOp) and default.opcode == UnaryOp.FLIP
+    matched = unwrap_operand(default.x)
+    assert unwrap_operand(matched.lhs) is one and unwrap_operand(matched.rhs) is two_three
+
+
+def _build_invalid(arms):
+    sysb = SysBuilder('switch_invalid')
+    with sysb:
+        class Invalid(Module):
+            """Builds the given arms"""
+            def __init__(self):
+                super().__init__(ports={'op': Port(UInt(4))})
+
+            @module.combinational
+            def build(self, arms):
+                with Switch(self.op.pop()) as sw:
+                    for arm in arms:
+                        with arm(sw):
+                            log('arm')
+
+        Invalid().build(arms)
+
+
+def test_switch_duplicated_key():
+    with pytest.raises(AssertionError, match='Duplicated case key'):
+        _build_invalid([lambda sw: sw.case(1), lambda sw: sw.case(2, 1)])
+
+
+def test_switch_case_after_default():
+    with pytest.raises(AssertionError, match='never taken'):
+        _build_invalid([lambda sw: sw.case(1), lambda sw: sw.default(), lambda sw: sw.case(2)])
+
+
+def test_switch_default_only():
+    with pytest.raises(AssertionError, match='at least one case'):
+        _build_invalid([lambda sw: sw.default()])
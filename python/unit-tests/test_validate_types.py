@@ -0,0 +1,59 @@
+"""Test the pre-elaboration type checker"""
+
+import pytest
+
+from assassyn.frontend import Int, Module, Port, SysBuilder, UInt, module
+from assassyn.analysis import check_types
+
+
+class Sink(Module):
+    """Callee with an 8-bit port"""
+    def __init__(self):
+        super().__init__(ports={'x': Port(UInt(8))})
+
+
+class Broken(Module):
+    """Builds one ill-typed expression per checked rule"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8)), 'b': Port(UInt(4))})
+
+    @module.combinational
+    def build(self, sink: Sink):
+        a, b = self.pop_all_ports(True)
+        _ = a == b
+        _ = a[4:8]
+        _ = a.zext(UInt(4))
+        sink.x.push(b)
+        _ = a.add_sat(a.bitcast(Int(8)))
+        # Well-typed ones are not reported
+        _ = a + b
+        _ = a << b
+        _ = a.bitcast(UInt(4))
+        _ = a.sub_sat(a)
+
+
+def test_all_errors_are_reported():
+    """Every ill-typed expression is reported in one pass"""
+    sys = SysBuilder('test_all_errors_are_reported')
+    with sys:
+        Broken().build(Sink())
+    errors = sys.validate_types()
+    messages = [error.message for error in errors]
+    assert len(errors) == 5, messages
+    assert 'Operands of "==" differ in width' in messages
+    assert 'Slice [4:8] is out of 8 bits' in messages
+    assert 'Zext narrows the value' in messages
+    assert 'Push to FIFO x mismatches' in messages
+    assert 'Operands of "+|" differ in width or signedness' in messages
+    push = errors[messages.index('Push to FIFO x mismatches')]
+    assert push.expected == UInt(8) and push.actual == UInt(4)
+
+
+def test_check_types_raises():
+    """Backends refuse an ill-typed system, listing all errors"""
+    sys = SysBuilder('test_check_types_raises')
+    with sys:
+        Broken().build(Sink())
+    with pytest.raises(ValueError) as exc_info:
+        check_types(sys)
+    assert '5 type error(s)' in str(exc_info.value)
@@ -0,0 +1,71 @@
+"""Test collecting the results of a Visitor with its default walk"""
+
+from assassyn.frontend import Condition, Module, Port, SysBuilder, UInt, log, module
+from assassyn.ir.expr import BinaryOp, Log
+from assassyn.ir.visitor import Visitor
+
+
+class CondAdd(Module):
+    """Adds and logs, in and out of a condition block"""
+    def __init__(self):
+        super().__init__(ports={'a': Port(UInt(8)), 'b': Port(UInt(8))})
+
+    @module.combinational
+    def build(self):
+        a, b = self.pop_all_ports(True)
+        c = a + b
+        log('c {}', c)
+        with Condition(c[0:0]):
+            d = c + a
+            log('d {}', d)
+
+
+class Exprs(Visitor):
+    """Collects every expression with its module"""
+    def visit_expr(self, node):
+        return node, self.current_module
+
+
+class Adds(Visitor):
+    """Collects the additions, skipping the other expressions"""
+    def visit_expr(self, node):
+        return node if isinstance(node, BinaryOp) and node.opcode == BinaryOp.ADD else None
+
+
+def test_walk_module():
+    sys = SysBuilder('walk_module')
+    with sys:
+        adder = CondAdd()
+        adder.build()
+    exprs = Exprs().walk(adder)
+    assert len(exprs) == len(adder.body)
+    assert all(i is j and module is adder for (i, module), j in zip(exprs, adder.body))
+
+
+def test_walk_skips_none():
+    sys = SysBuilder('walk_skips_none')
+    with sys:
+        adder = CondAdd()
+        adder.build()
+    adds = Adds().walk(sys)
+    assert len(adds) == 2
+    order = [next(i for i, e in enumerate(adder.body) if e is add) for add in adds]
+    assert order == sorted(order)
+    # Visiting without walking collects nothing, and leaves no list behind
+    visitor = Adds()
+    visitor.visit_system(sys)
+    assert visitor.walk(adder) == adds
+
+
+def test_walk_logs():
+    sys = SysBuilder('walk_logs')
+    with sys:
+        adder = CondAdd()
+        adder.build()
+
+    class Logs(Visitor):
+        """Collects the formats of the logs"""
+        def visit_expr(self, node):
+            return node.fmt if isinstance(node, Log) else None
+
+    assert Logs().walk(adder) == ['c {}', 'd {}']
@@ -0,0 +1,42 @@
+"""The WritePorts attribute bounds the modules writing an array"""
+
+import pytest
+
+from assassyn.frontend import Module, RegArray, SysBuilder, UInt, WritePorts, module
+from assassyn.ir.array import verify_write_ports
+
+
+class Writer(Module):
+    """Writes its index to the array"""
+    def __init__(self):
+        super().__init__(ports={})
+
+    @module.combinational
+    def build(self, arr, idx):
+        (arr & self)[idx] <= UInt(8)(idx)
+
+
+def _build(ports, writers):
+    sys = SysBuilder('write_ports')
+    with sys:
+        arr = RegArray(UInt(8), 4, attr=[WritePorts(ports)])
+        for i in range(writers):
+            Writer().build(arr, i)
+    return sys, arr
+
+
+def test_within_limit():
+    sys, arr = _build(2, 2)
+    assert arr.write_port_limit == 2
+    verify_write_ports(sys)
+
+
+def test_too_many_writers():
+    sys, _ = _build(2, 3)
+    with pytest.raises(ValueError, match='has 2 write ports, but 3 modules write it'):
+        verify_write_ports(sys)
+
+
+def test_invalid_count():
+    with pytest.raises(AssertionError, match='Invalid number of write ports'):
+        WritePorts(0)
@@ -1,9 +1,52 @@
-use num_bigint::{BigInt, BigUint, ToBigInt, ToBigUint};
+use num_bigint::{BigInt, BigUint, Sign, ToBigInt, ToBigUint};
+use num_traits::{One, Signed};
 
 pub trait ValueCastTo<T> {
   fn cast(&self) -> T;
 }
 
+/// Casts reading the source as a `bits`-wide two's complement value: when bit `bits - 1` is
+/// set, the value is negative.
+pub trait SignedValueCastTo<T> {
+  fn signed_cast(&self, bits: usize) -> T;
+}
+
+/// Casts reading the source as a `bits`-wide unsigned value: a negative source is wrapped to
+/// its two's complement bits.
+pub trait UnsignedValueCastTo<T> {
+  fn unsigned_cast(&self, bits: usize) -> T;
+}
+
+impl UnsignedValueCastTo<BigUint> for BigUint {
+  fn unsigned_cast(&self, bits: usize) -> BigUint {
+    self % (BigUint::one() << bits)
+  }
+}
+impl UnsignedValueCastTo<BigUint> for BigInt {
+  fn unsigned_cast(&self, bits: usize) -> BigUint {
+    let modulus = BigInt::one() << bits;
+    let wrapped = (self % &modulus + &modulus) % &modulus;
+    wrapped.magnitude().clone()
+  }
+}
+impl SignedValueCastTo<BigInt> for BigUint {
+  fn signed_cast(&self, bits: usize) -> BigInt {
+    let value = self.unsigned_cast(bits);
+    let negative = bits > 0 && value.bit(bits as u64 - 1);
+    let value = BigInt::from_biguint(Sign::Plus, value);
+    if negative {
+      value - (BigInt::one() << bits)
+    } else {
+      value
+    }
+  }
+}
+impl SignedValueCastTo<BigInt> for BigInt {
+  fn signed_cast(&self, bits: usize) -> BigInt {
+    self.unsigned_cast(bits).signed_cast(bits)
+  }
+}
+
 impl ValueCastTo<bool> for bool {
   fn cast(&self) -> bool {
     *self
@@ -16,7 +59,12 @@ impl ValueCastTo<BigInt> for BigInt {
 }
 impl ValueCastTo<BigUint> for BigInt {
   fn cast(&self) -> BigUint {
-    self.to_biguint().unwrap()
+    if !self.is_negative() {
+      return self.magnitude().clone();
+    }
+    // A negative value keeps its two's complement bits in whole 64-bit words, as `u64` does
+    let bits = ((-self - 1u8).bits() as usize / 64 + 1) * 64;
+    self.unsigned_cast(bits)
   }
 }
 impl ValueCastTo<BigInt> for bool {
@@ -40,7 +88,7 @@ impl ValueCastTo<BigUint> for BigUint {
 }
 impl ValueCastTo<BigInt> for BigUint {
   fn cast(&self) -> BigInt {
-    self.to_bigint().unwrap()
+    BigInt::from_biguint(Sign::Plus, self.clone())
   }
 }
 impl ValueCastTo<BigUint> for bool {
@@ -350,7 +398,7 @@ impl ValueCastTo<u64> for BigInt {
     }
     match sign {
       num_bigint::Sign::Plus => data[0],
-      num_bigint::Sign::Minus => (!data[0] + 1) & u64::MAX,
+      num_bigint::Sign::Minus => data[0].wrapping_neg(),
       num_bigint::Sign::NoSign => data[0],
     }
   }
@@ -0,0 +1,67 @@
+// The number of cycles a FIFO spent at each occupancy.
+pub struct OccupancyHistogram {
+  // `buckets[d]` counts the cycles ending with `d` entries; the last bucket also counts
+  // all the deeper ones.
+  pub buckets: Vec<usize>,
+}
+
+impl Default for OccupancyHistogram {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl OccupancyHistogram {
+  pub const BUCKETS: usize = 65;
+
+  pub fn new() -> Self {
+    OccupancyHistogram {
+      buckets: vec![0; Self::BUCKETS],
+    }
+  }
+
+  pub fn record(&mut self, depth: usize) {
+    self.buckets[depth.min(Self::BUCKETS - 1)] += 1;
+  }
+
+  pub fn cycles(&self) -> usize {
+    self.buckets.iter().sum()
+  }
+
+  // The deepest occupancy observed
+  pub fn max(&self) -> usize {
+    self.buckets.iter().rposition(|&n| n != 0).unwrap_or(0)
+  }
+
+  pub fn mean(&self) -> f64 {
+    let cycles = self.cycles();
+    if cycles == 0 {
+      return 0.0;
+    }
+    let total: usize = self.buckets.iter().enumerate().map(|(d, &n)| d * n).sum();
+    total as f64 / cycles as f64
+  }
+
+  // The smallest occupancy that at least `p` percent of the cycles do not exceed
+  pub fn percentile(&self, p: f64) -> usize {
+    let bound = self.cycles() as f64 * p / 100.0;
+    let mut seen = 0;
+    for (depth, &n) in self.buckets.iter().enumerate() {
+      seen += n;
+      if seen > 0 && seen as f64 >= bound {
+        return depth;
+      }
+    }
+    0
+  }
+
+  // The non-empty prefix of the buckets, separated by spaces
+  pub fn dump(&self) -> String {
+    let used = &self.buckets[..=self.max()];
+    used
+      .iter()
+      .map(|n| n.to_string())
+      .collect::<Vec<_>>()
+      .join(" ")
+  }
+}
@@ -1,7 +1,9 @@
 pub mod cast;
+pub mod histogram;
 pub mod utils;
 pub mod xeq;
 
 pub use cast::*;
+pub use histogram::*;
 pub use utils::*;
 pub use xeq::*;
@@ -6,9 +6,18 @@ pub fn cyclize(stamp: usize) -> String {
 }
 
 pub fn load_hex_file<T: Num>(array: &mut [T], init_file: &str) {
+  load_mem_file(array, init_file, 16);
+}
+
+/// Loads a `$readmemb` file, whose `@` addresses are still hexadecimal.
+pub fn load_bin_file<T: Num>(array: &mut [T], init_file: &str) {
+  load_mem_file(array, init_file, 2);
+}
+
+fn load_mem_file<T: Num>(array: &mut [T], init_file: &str, radix: u32) {
   let mut idx = 0;
   for line in read_to_string(init_file)
-    .expect("can not open hex file")
+    .expect("can not open memory file")
     .lines()
   {
     let line = if let Some(to_strip) = line.find("//") {
@@ -25,7 +34,7 @@ pub fn load_hex_file<T: Num>(array: &mut [T], init_file: &str) {
       idx = addr;
       continue;
     }
-    array[idx] = T::from_str_radix(line.as_str(), 16).ok().unwrap();
+    array[idx] = T::from_str_radix(line.as_str(), radix).ok().unwrap();
     idx += 1;
   }
 }
@@ -176,12 +176,26 @@ impl<T: Sized> FIFO<T> {
     self.payload.front()
   }
 
+  // The front of a flow-through FIFO: when it is empty, a value pushed to be committed
+  // at `cycle` is already visible.
+  pub fn flow_front(&self, cycle: usize) -> Option<&T> {
+    self
+      .payload
+      .front()
+      .or_else(|| self.push.peek(cycle).map(|event| &event.data))
+  }
+
   pub fn tick(&mut self, cycle: usize) {
-    if self.pop.pop(cycle).is_some() && !self.payload.is_empty() {
+    let popped = self.pop.pop(cycle).is_some();
+    let flowed = popped && self.payload.is_empty();
+    if popped && !flowed {
       self.payload.pop_front().unwrap();
     }
     if let Some(event) = self.push.pop(cycle) {
-      self.payload.push_back(event.data);
+      // A value popped in the cycle it was pushed flowed through, and is never stored
+      if !flowed {
+        self.payload.push_back(event.data);
+      }
     }
   }
 }
@@ -215,6 +229,14 @@ impl<T: Sized + Cycled> XEQ<T> {
     }
   }
 
+  pub fn peek(&self, current: usize) -> Option<&T> {
+    self
+      .q
+      .first_key_value()
+      .filter(|(cycle, _)| **cycle <= current)
+      .map(|(_, event)| event)
+  }
+
   pub fn pop(&mut self, current: usize) -> Option<T> {
     if self
       .q
@@ -0,0 +1,61 @@
+use sim_runtime::num_bigint::{BigInt, BigUint};
+use sim_runtime::{SignedValueCastTo, UnsignedValueCastTo, ValueCastTo};
+
+fn big(value: &str) -> BigInt {
+  value.parse().unwrap()
+}
+
+fn pow2(bits: usize) -> BigUint {
+  BigUint::from(1u8) << bits
+}
+
+#[test]
+fn test_signed_cast_msb_set() {
+  // The 96-bit pattern with only the MSB set is the most negative `int<96>`
+  let msb = pow2(95);
+  assert_eq!(msb.signed_cast(96), -BigInt::from(pow2(95)));
+  // All ones is -1 at any width
+  let ones = pow2(96) - 1u8;
+  assert_eq!(ones.signed_cast(96), big("-1"));
+  assert_eq!(ones.signed_cast(65), big("-1"));
+  // Without the MSB, the value is kept
+  let small = pow2(94) + 5u8;
+  assert_eq!(small.signed_cast(96), BigInt::from(small.clone()));
+  // Bits above the width are dropped before the sign is read
+  let wide = pow2(100) | BigUint::from(0b101u8);
+  assert_eq!(wide.signed_cast(3), big("-3"));
+}
+
+#[test]
+fn test_unsigned_cast_negative() {
+  let minus_one = big("-1");
+  assert_eq!(minus_one.unsigned_cast(96), pow2(96) - 1u8);
+  let min = -BigInt::from(pow2(95));
+  assert_eq!(min.unsigned_cast(96), pow2(95));
+  assert_eq!(big("5").unsigned_cast(96), BigUint::from(5u8));
+  // Round trips keep the value of the signed type
+  for value in [
+    "-170141183460469231731687303715884105728",
+    "-42",
+    "0",
+    "123456789012345678901",
+  ] {
+    let value = big(value);
+    assert_eq!(value.unsigned_cast(128).signed_cast(128), value);
+  }
+}
+
+#[test]
+fn test_value_cast_does_not_panic() {
+  // A value with its MSB set keeps its magnitude as a BigInt
+  let msb = pow2(127);
+  let signed: BigInt = msb.cast();
+  assert_eq!(signed, BigInt::from(pow2(127)));
+  // A negative BigInt wraps to whole 64-bit words, as it does when cast to u64
+  let unsigned: BigUint = big("-1").cast();
+  assert_eq!(unsigned, BigUint::from(u64::MAX));
+  let unsigned: BigUint = (-BigInt::from(pow2(64))).cast();
+  assert_eq!(unsigned, BigUint::from(u64::MAX) * pow2(64));
+  let low: u64 = (-BigInt::from(pow2(64))).cast();
+  assert_eq!(low, 0);
+}
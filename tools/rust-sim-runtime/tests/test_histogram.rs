@@ -0,0 +1,32 @@
+use sim_runtime::OccupancyHistogram;
+
+#[test]
+fn test_histogram_summary() {
+  let mut hist = OccupancyHistogram::new();
+  // 90 cycles empty, 9 cycles with one entry, 1 cycle with three entries
+  for _ in 0..90 {
+    hist.record(0);
+  }
+  for _ in 0..9 {
+    hist.record(1);
+  }
+  hist.record(3);
+  assert_eq!(hist.cycles(), 100);
+  assert_eq!(hist.max(), 3);
+  assert!((hist.mean() - 0.12).abs() < 1e-9);
+  assert_eq!(hist.percentile(50.0), 0);
+  assert_eq!(hist.percentile(95.0), 1);
+  assert_eq!(hist.percentile(99.0), 1);
+  assert_eq!(hist.percentile(100.0), 3);
+  assert_eq!(hist.dump(), "90 9 0 1");
+}
+
+#[test]
+fn test_histogram_caps_deep_fifos() {
+  let mut hist = OccupancyHistogram::new();
+  hist.record(1000);
+  assert_eq!(hist.max(), OccupancyHistogram::BUCKETS - 1);
+  let empty = OccupancyHistogram::new();
+  assert_eq!((empty.max(), empty.mean(), empty.percentile(99.0)), (0, 0.0, 0));
+  assert_eq!(empty.dump(), "0");
+}
@@ -0,0 +1,19 @@
+use sim_runtime::{load_bin_file, load_hex_file};
+
+fn write_temp(name: &str, content: &str) -> String {
+  let path = std::env::temp_dir().join(name);
+  std::fs::write(&path, content).unwrap();
+  path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_bin_matches_hex() {
+  let hex = write_temp("sim_runtime_mem.hex", "// values\n0a\nff\n@10\n3_c\n");
+  let bin = write_temp("sim_runtime_mem.bin", "// values\n1010\n1111_1111\n@10\n111100\n");
+  let mut from_hex = vec![0u32; 32];
+  let mut from_bin = vec![0u32; 32];
+  load_hex_file(&mut from_hex, &hex);
+  load_bin_file(&mut from_bin, &bin);
+  assert_eq!(from_bin, from_hex);
+  assert_eq!((from_bin[0], from_bin[1], from_bin[16]), (10, 255, 60));
+}